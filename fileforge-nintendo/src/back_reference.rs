@@ -0,0 +1,21 @@
+use fileforge::error::render::{buffer::canvas::RenderBufferCanvas, builtin::number::formatted_unsigned::FormattedUnsigned, r#trait::renderable::Renderable};
+
+/// A copy of `length` bytes starting `distance` bytes before the end of the decompressed data, as
+/// found in every LZ-style Nintendo format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BackReference {
+  pub distance: u32,
+  pub length: u32,
+}
+
+impl<'t> Renderable<'t> for BackReference {
+  fn render_into<'r, 'c>(&self, canvas: &mut RenderBufferCanvas<'r, 'c, 't>) -> Result<(), ()> {
+    canvas.set_str("BackReference { distance: ");
+    canvas.write(&FormattedUnsigned::from(self.distance))?;
+    canvas.set_str(", length: ");
+    canvas.write(&FormattedUnsigned::from(self.length))?;
+    canvas.set_str(" }");
+
+    Ok(())
+  }
+}
@@ -0,0 +1,9 @@
+use fileforge::error::render::builtin::number::formatted_unsigned::FormattedUnsigned;
+use fileforge_macros::FileforgeError;
+
+#[derive(FileforgeError)]
+pub enum BiosEncodeError {
+  #[report(&"Input is too large for BIOS compression")]
+  #[flag("{length} bytes don't fit in a 32-bit decompressed size", length = FormattedUnsigned::new(*length as u128))]
+  TooLarge { length: u64 },
+}
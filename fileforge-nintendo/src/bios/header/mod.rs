@@ -0,0 +1,96 @@
+pub mod readable;
+
+use fileforge::error::render::{buffer::canvas::RenderBufferCanvas, r#trait::renderable::Renderable};
+
+/// Largest size the compact header can hold. Anything bigger, or empty, uses the extended header.
+pub const COMPACT_SIZE_LIMIT: u32 = 0xFF_FFFF;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BiosCompression {
+  Lz10,
+  Lz11,
+  /// Huffman coding over 4-bit symbols, low nibble first.
+  Huffman4,
+  /// Huffman coding over 8-bit symbols.
+  Huffman8,
+  Rle,
+}
+
+impl BiosCompression {
+  pub fn tag(self) -> u8 {
+    match self {
+      Self::Lz10 => 0x10,
+      Self::Lz11 => 0x11,
+      Self::Huffman4 => 0x24,
+      Self::Huffman8 => 0x28,
+      Self::Rle => 0x30,
+    }
+  }
+
+  pub fn from_tag(tag: u8) -> Option<Self> {
+    Some(match tag {
+      0x10 => Self::Lz10,
+      0x11 => Self::Lz11,
+      0x24 => Self::Huffman4,
+      0x28 => Self::Huffman8,
+      0x30 => Self::Rle,
+      _ => return None,
+    })
+  }
+}
+
+impl<'t> Renderable<'t> for BiosCompression {
+  fn render_into<'r, 'c>(&self, canvas: &mut RenderBufferCanvas<'r, 'c, 't>) -> Result<(), ()> {
+    canvas.set_str(match self {
+      Self::Lz10 => "LZ10",
+      Self::Lz11 => "LZ11",
+      Self::Huffman4 => "Huffman (4-bit)",
+      Self::Huffman8 => "Huffman (8-bit)",
+      Self::Rle => "RLE",
+    });
+
+    Ok(())
+  }
+}
+
+/// The header shared by every BIOS compression format.
+///
+/// A size of zero in the compact header means a second word holds the real size, which DS tools
+/// use for files of 16MiB or more.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BiosHeader {
+  compression: BiosCompression,
+  decompressed_size: u32,
+}
+
+impl BiosHeader {
+  pub fn new(compression: BiosCompression, decompressed_size: u32) -> Self {
+    Self { compression, decompressed_size }
+  }
+
+  pub fn compression(&self) -> BiosCompression {
+    self.compression
+  }
+
+  pub fn decompressed_size(&self) -> u32 {
+    self.decompressed_size
+  }
+
+  pub fn is_extended(&self) -> bool {
+    self.decompressed_size == 0 || self.decompressed_size > COMPACT_SIZE_LIMIT
+  }
+
+  /// The header's bytes as they appear at the start of a compressed file.
+  pub fn encode(&self) -> heapless::Vec<u8, 8> {
+    let mut bytes = heapless::Vec::new();
+
+    if self.is_extended() {
+      bytes.extend(u32::from(self.compression.tag()).to_le_bytes());
+      bytes.extend(self.decompressed_size.to_le_bytes());
+    } else {
+      bytes.extend((self.decompressed_size << 8 | u32::from(self.compression.tag())).to_le_bytes());
+    }
+
+    bytes
+  }
+}
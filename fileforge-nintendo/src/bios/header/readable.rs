@@ -0,0 +1,59 @@
+use fileforge::{
+  binary_reader::{
+    error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError},
+    readable::Readable,
+    BinaryReader, PrimitiveReader,
+  },
+  diagnostic::value::{DiagnosticSaturation, DiagnosticValue},
+  error::{ext::annotations::annotated::Annotated, render::builtin::number::formatted_unsigned::FormattedUnsigned},
+  stream::{error::user_read::UserReadError, ReadableStream},
+};
+use fileforge_macros::FileforgeError;
+
+use super::{BiosCompression, BiosHeader};
+
+impl<'pool, S: ReadableStream<Type = u8>> Readable<'pool, S> for BiosHeader {
+  type Error = BiosHeaderReadError<'pool, S::ReadError>;
+  /// The formats the caller can decode.
+  type Argument = &'static [BiosCompression];
+
+  async fn read(reader: &mut BinaryReader<'pool, S>, accepted: Self::Argument) -> Result<Self, Self::Error> {
+    // always little-endian, whatever the reader is set to
+    let word = u32::from_le_bytes(reader.get().await.map_err(BiosHeaderReadError::Type)?);
+    let tag = word as u8;
+
+    let compression = BiosCompression::from_tag(tag).ok_or(BiosHeaderReadError::UnknownCompression { tag })?;
+
+    if !accepted.contains(&compression) {
+      return Err(BiosHeaderReadError::UnexpectedCompression {
+        found: reader.create_physical_diagnostic(-4, Some(1), "Compression Type").saturate(compression),
+      });
+    }
+
+    let decompressed_size = match word >> 8 {
+      0 => u32::from_le_bytes(reader.get().await.map_err(BiosHeaderReadError::ExtendedSize)?),
+      size => size,
+    };
+
+    Ok(BiosHeader { compression, decompressed_size })
+  }
+}
+
+#[derive(FileforgeError)]
+#[report(&"Failed to read BIOS compression header")]
+pub enum BiosHeaderReadError<'pool, U: UserReadError> {
+  Type(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  ExtendedSize(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+
+  #[report(&"Unknown BIOS compression type")]
+  #[flag("Type {tag} isn't LZ10, LZ11, Huffman or RLE", tag = FormattedUnsigned::new(*tag as u128))]
+  UnknownCompression {
+    tag: u8,
+  },
+
+  #[report(&"Unexpected BIOS compression type")]
+  UnexpectedCompression {
+    #[error("Found {found.value()}, which this stream can't decode")]
+    found: DiagnosticValue<'pool, BiosCompression>,
+  },
+}
@@ -0,0 +1,279 @@
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+use core::cmp::Reverse;
+
+use crate::bios::{
+  error::BiosEncodeError,
+  header::BiosHeader,
+  huffman::{HuffmanWidth, ROOT_INDEX},
+  pad_to_word,
+};
+
+/// Furthest a node's children may sit from it, in pairs.
+const MAX_OFFSET: usize = 0x3F;
+
+enum Node {
+  Leaf(u8),
+  Internal([usize; 2]),
+}
+
+/// A code tree whose nodes are always created after their children.
+struct Tree {
+  nodes: Vec<Node>,
+  root: usize,
+}
+
+impl Tree {
+  /// The used symbols and their frequencies, padded with unused ones so the root is never a leaf.
+  fn symbols(frequencies: &[u64]) -> Vec<(u64, u8)> {
+    let (mut symbols, unused): (Vec<_>, Vec<_>) = frequencies
+      .iter()
+      .enumerate()
+      .map(|(symbol, &frequency)| (frequency, symbol as u8))
+      .partition(|&(frequency, _)| frequency > 0);
+    let missing = 2usize.saturating_sub(symbols.len());
+
+    symbols.extend(unused.into_iter().take(missing));
+    symbols
+  }
+
+  /// An optimal code tree.
+  fn huffman(frequencies: &[u64]) -> Self {
+    let mut nodes = Vec::new();
+    let mut heap = BinaryHeap::new();
+
+    for (frequency, symbol) in Self::symbols(frequencies) {
+      heap.push(Reverse((frequency, nodes.len())));
+      nodes.push(Node::Leaf(symbol));
+    }
+
+    while let (Some(Reverse((a_frequency, a))), Some(Reverse((b_frequency, b)))) = (heap.pop(), heap.pop()) {
+      heap.push(Reverse((a_frequency + b_frequency, nodes.len())));
+      nodes.push(Node::Internal([a, b]));
+    }
+
+    // the loop above ends by popping the root on its own
+    let root = nodes.len() - 1;
+
+    Self { nodes, root }
+  }
+
+  /// A tree with one leaf on every level, which always fits in a tree table.
+  fn chain(frequencies: &[u64]) -> Self {
+    let mut symbols = Self::symbols(frequencies);
+    symbols.sort_by_key(|&(frequency, symbol)| (Reverse(frequency), symbol));
+
+    let mut nodes: Vec<Node> = symbols.iter().map(|&(_, symbol)| Node::Leaf(symbol)).collect();
+    let mut root = nodes.len() - 1;
+
+    for leaf in (0..symbols.len() - 1).rev() {
+      nodes.push(Node::Internal([leaf, root]));
+      root = nodes.len() - 1;
+    }
+
+    Self { nodes, root }
+  }
+
+  fn children(&self, node: usize) -> Option<[usize; 2]> {
+    match self.nodes[node] {
+      Node::Leaf(_) => None,
+      Node::Internal(children) => Some(children),
+    }
+  }
+
+  /// Lays the tree out as a tree table, or returns `None` if some node's children can't be placed
+  /// within [`MAX_OFFSET`] pairs of it.
+  ///
+  /// Pairs are placed one at a time. Nodes with small subtrees go first, since they close more
+  /// pending nodes than they open, unless doing so would leave another node unplaceable.
+  fn layout(&self) -> Option<Vec<u8>> {
+    let mut sizes = vec![0usize; self.nodes.len()];
+
+    for node in 0..self.nodes.len() {
+      if let Some(children) = self.children(node) {
+        sizes[node] = 1 + sizes[children[0]] + sizes[children[1]];
+      }
+    }
+
+    let pairs = sizes[self.root];
+    let mut table = vec![0u8; 2 + 2 * pairs];
+    // (node, index in the table, last pair its children may use)
+    let mut pending = vec![(self.root, ROOT_INDEX, MAX_OFFSET)];
+
+    for pair in 0..pairs {
+      let mut candidates: Vec<usize> = (0..pending.len()).collect();
+      candidates.sort_by_key(|&candidate| (sizes[pending[candidate].0], pending[candidate].2));
+
+      let chosen = candidates.into_iter().find(|&candidate| self.can_place(&pending, candidate, pair))?;
+      let (node, index, _) = pending.swap_remove(chosen);
+      let children = self.children(node).unwrap();
+      let child_index = 2 + 2 * pair;
+      let mut byte = ((child_index - (index & !1) - 2) / 2) as u8;
+
+      for (bit, child) in children.into_iter().enumerate() {
+        match self.nodes[child] {
+          Node::Leaf(symbol) => {
+            byte |= 0x80 >> bit;
+            table[child_index + bit] = symbol;
+          }
+          Node::Internal(_) => pending.push((child, child_index + bit, pair + MAX_OFFSET + 1)),
+        }
+      }
+
+      table[index] = byte;
+    }
+
+    pad_to_word(&mut table);
+    table[0] = (table.len() / 2 - 1) as u8;
+
+    Some(table)
+  }
+
+  /// Whether placing `candidate`'s children at `pair` still lets every pending node be placed
+  /// before its deadline, taking them earliest deadline first.
+  fn can_place(&self, pending: &[(usize, usize, usize)], candidate: usize, pair: usize) -> bool {
+    let (node, _, deadline) = pending[candidate];
+
+    if deadline < pair {
+      return false;
+    }
+
+    let mut deadlines: Vec<usize> = pending.iter().enumerate().filter(|&(other, _)| other != candidate).map(|(_, &(_, _, deadline))| deadline).collect();
+
+    for child in self.children(node).unwrap() {
+      if self.children(child).is_some() {
+        deadlines.push(pair + MAX_OFFSET + 1);
+      }
+    }
+
+    deadlines.sort_unstable();
+    deadlines.iter().enumerate().all(|(position, &deadline)| deadline > pair + position)
+  }
+
+  /// The bits leading to each symbol, with a clear bit taking a node's first child.
+  fn codes(&self, symbol_count: usize) -> Vec<Vec<bool>> {
+    let mut codes = vec![Vec::new(); symbol_count];
+    let mut pending = vec![(self.root, Vec::new())];
+
+    while let Some((node, path)) = pending.pop() {
+      match self.nodes[node] {
+        Node::Leaf(symbol) => codes[symbol as usize] = path,
+        Node::Internal(children) => {
+          for (bit, child) in children.into_iter().enumerate() {
+            let mut path = path.clone();
+            path.push(bit == 1);
+            pending.push((child, path));
+          }
+        }
+      }
+    }
+
+    codes
+  }
+}
+
+/// Packs bits into little-endian words, most significant bit first.
+struct BitWriter<'a> {
+  out: &'a mut Vec<u8>,
+  word: u32,
+  bits: u8,
+}
+
+impl<'a> BitWriter<'a> {
+  fn push(&mut self, bit: bool) {
+    self.word |= (bit as u32) << (31 - self.bits);
+    self.bits += 1;
+
+    if self.bits == 32 {
+      self.flush();
+    }
+  }
+
+  fn flush(&mut self) {
+    if self.bits > 0 {
+      self.out.extend(self.word.to_le_bytes());
+      self.word = 0;
+      self.bits = 0;
+    }
+  }
+}
+
+/// Splits `data` into the symbols a Huffman file of `width` encodes.
+fn symbols(data: &[u8], width: HuffmanWidth) -> impl Iterator<Item = u8> + '_ {
+  data
+    .iter()
+    .flat_map(move |&byte| match width {
+      HuffmanWidth::Four => [Some(byte & 0xF), Some(byte >> 4)],
+      HuffmanWidth::Eight => [Some(byte), None],
+    })
+    .flatten()
+}
+
+/// Compresses `data` into a complete 4-bit or 8-bit Huffman file.
+///
+/// 8-bit trees with many rare symbols can be too deep to fit the tree table's 6-bit offsets. Those
+/// fall back to a less efficient tree with one symbol per level.
+pub fn encode(data: &[u8], width: HuffmanWidth) -> Result<Vec<u8>, BiosEncodeError> {
+  let decompressed_size = u32::try_from(data.len()).map_err(|_| BiosEncodeError::TooLarge { length: data.len() as u64 })?;
+  let header = BiosHeader::new(width.compression(), decompressed_size);
+
+  let mut frequencies = vec![0u64; if width == HuffmanWidth::Four { 0x10 } else { 0x100 }];
+
+  for symbol in symbols(data, width) {
+    frequencies[symbol as usize] += 1;
+  }
+
+  let (tree, table) = {
+    let tree = Tree::huffman(&frequencies);
+
+    match tree.layout() {
+      Some(table) => (tree, table),
+      None => {
+        let tree = Tree::chain(&frequencies);
+        let table = tree.layout().expect("chain trees never need offsets past their own pair");
+
+        (tree, table)
+      }
+    }
+  };
+
+  let codes = tree.codes(frequencies.len());
+  let mut out = Vec::from(header.encode().as_slice());
+
+  out.extend(table);
+
+  let mut writer = BitWriter { out: &mut out, word: 0, bits: 0 };
+
+  for symbol in symbols(data, width) {
+    for &bit in &codes[symbol as usize] {
+      writer.push(bit);
+    }
+  }
+
+  writer.flush();
+  Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn lays_out_balanced_trees() {
+    assert!(Tree::huffman(&[1; 0x100]).layout().is_some());
+  }
+
+  #[test]
+  fn lays_out_skewed_trees() {
+    // frequencies growing slowly enough to give a deep but not fully degenerate tree
+    let frequencies: Vec<u64> = (0..0x100u64).map(|symbol| 1 + symbol * symbol).collect();
+
+    assert!(Tree::huffman(&frequencies).layout().is_some());
+  }
+
+  #[test]
+  fn lays_out_chains() {
+    let frequencies: Vec<u64> = (0..0x100u32).map(|symbol| 1u64 << (symbol / 4)).collect();
+
+    assert!(Tree::chain(&frequencies).layout().is_some());
+  }
+}
@@ -0,0 +1,36 @@
+use fileforge::{
+  binary_reader::error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError},
+  diagnostic::value::DiagnosticValue,
+  error::{ext::annotations::annotated::Annotated, render::builtin::number::formatted_unsigned::FormattedUnsigned},
+  stream::error::{user_read::UserReadError, user_skip::UserSkipError},
+};
+use fileforge_macros::FileforgeError;
+
+use crate::bios::{header::readable::BiosHeaderReadError, huffman::TreeNode};
+
+#[derive(FileforgeError)]
+pub enum HuffmanReadError<'pool, U: UserReadError> {
+  Header(#[from] BiosHeaderReadError<'pool, U>),
+  TreeSize(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  Tree(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+
+  #[report(&"Huffman tree node points outside the tree")]
+  InvalidNode {
+    #[error(
+      "Its children would be at {child}, but the tree is only {length} bytes long",
+      child = FormattedUnsigned::new(*child as u128),
+      length = FormattedUnsigned::new(*length as u128)
+    )]
+    node: DiagnosticValue<'pool, TreeNode>,
+    child: u16,
+    length: u16,
+  },
+}
+
+#[derive(FileforgeError)]
+pub enum HuffmanError<'pool, U: UserReadError> {
+  Data(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+}
+
+impl<'pool, U: UserReadError> UserReadError for HuffmanError<'pool, U> {}
+impl<'pool, U: UserReadError> UserSkipError for HuffmanError<'pool, U> {}
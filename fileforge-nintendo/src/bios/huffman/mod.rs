@@ -0,0 +1,263 @@
+#[cfg(feature = "alloc")]
+pub mod encoder;
+pub mod error;
+pub mod readable;
+
+use fileforge::{
+  binary_reader::{BinaryReader, PrimitiveReader},
+  error::render::{buffer::canvas::RenderBufferCanvas, builtin::number::formatted_unsigned::FormattedUnsigned, r#trait::renderable::Renderable},
+  stream::{
+    error::{stream_exhausted::StreamExhaustedError, stream_read::StreamReadError, stream_skip::StreamSkipError},
+    ReadableStream,
+  },
+};
+
+use crate::bios::{
+  header::{BiosCompression, BiosHeader},
+  huffman::error::HuffmanError,
+};
+
+/// Largest tree table, including its size byte: 255 pairs of children plus the root.
+pub const MAX_TREE_LENGTH: usize = 0x200;
+
+/// Where the root node sits in the tree table, right after the size byte.
+const ROOT_INDEX: usize = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HuffmanWidth {
+  /// Each byte is two 4-bit symbols, low nibble first.
+  Four,
+  /// Each byte is one symbol.
+  Eight,
+}
+
+impl HuffmanWidth {
+  pub fn compression(self) -> BiosCompression {
+    match self {
+      Self::Four => BiosCompression::Huffman4,
+      Self::Eight => BiosCompression::Huffman8,
+    }
+  }
+}
+
+/// A node in a Huffman tree table.
+///
+/// Its children are a pair of entries `offset * 2 + 2` bytes past the start of the node's own
+/// pair. The top two bits mark which of them are leaves holding a symbol rather than nodes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TreeNode(pub u8);
+
+impl TreeNode {
+  pub fn offset(self) -> u8 {
+    self.0 & 0x3F
+  }
+
+  pub fn is_leaf(self, bit: bool) -> bool {
+    self.0 & (0x80 >> bit as u8) != 0
+  }
+
+  /// Index of the child taken on `bit`, for a node at `index` in the tree table.
+  pub fn child(self, index: usize, bit: bool) -> usize {
+    (index & !1) + self.offset() as usize * 2 + 2 + bit as usize
+  }
+}
+
+impl<'t> Renderable<'t> for TreeNode {
+  fn render_into<'r, 'c>(&self, canvas: &mut RenderBufferCanvas<'r, 'c, 't>) -> Result<(), ()> {
+    canvas.set_str("TreeNode { offset: ");
+    canvas.write(&FormattedUnsigned::from(self.offset()))?;
+    canvas.set_str(" }");
+
+    Ok(())
+  }
+}
+
+/// A decompressed view of a 4-bit or 8-bit Huffman file.
+///
+/// The tree table is kept in memory. Codes are read from little-endian words, most significant
+/// bit first, with a clear bit taking a node's first child.
+pub struct HuffmanStream<'pool, S: ReadableStream<Type = u8>> {
+  header: BiosHeader,
+  reader: BinaryReader<'pool, S>,
+  tree: heapless::Vec<u8, MAX_TREE_LENGTH>,
+  word: u32,
+  bits: u8,
+  offset: u64,
+}
+
+impl<'pool, S: ReadableStream<Type = u8>> HuffmanStream<'pool, S> {
+  pub fn header(&self) -> &BiosHeader {
+    &self.header
+  }
+
+  pub fn width(&self) -> HuffmanWidth {
+    match self.header.compression() {
+      BiosCompression::Huffman4 => HuffmanWidth::Four,
+      _ => HuffmanWidth::Eight,
+    }
+  }
+
+  async fn next_bit(&mut self) -> Result<bool, HuffmanError<'pool, S::ReadError>> {
+    if self.bits == 0 {
+      self.word = u32::from_le_bytes(self.reader.get().await.map_err(HuffmanError::Data)?);
+      self.bits = 32;
+    }
+
+    let bit = self.word & 0x8000_0000 != 0;
+
+    self.word <<= 1;
+    self.bits -= 1;
+    Ok(bit)
+  }
+
+  async fn next_symbol(&mut self) -> Result<u8, HuffmanError<'pool, S::ReadError>> {
+    let mut index = ROOT_INDEX;
+
+    loop {
+      let node = TreeNode(self.tree[index]);
+      let bit = self.next_bit().await?;
+
+      index = node.child(index, bit);
+
+      if node.is_leaf(bit) {
+        return Ok(self.tree[index]);
+      }
+    }
+  }
+
+  async fn next_byte(&mut self) -> Result<u8, HuffmanError<'pool, S::ReadError>> {
+    match self.width() {
+      HuffmanWidth::Eight => self.next_symbol().await,
+      HuffmanWidth::Four => {
+        let low = self.next_symbol().await? & 0xF;
+        let high = self.next_symbol().await? & 0xF;
+
+        Ok(high << 4 | low)
+      }
+    }
+  }
+}
+
+impl<'pool, S: ReadableStream<Type = u8>> ReadableStream for HuffmanStream<'pool, S> {
+  type Type = u8;
+
+  type ReadError = HuffmanError<'pool, S::ReadError>;
+  type SkipError = HuffmanError<'pool, S::ReadError>;
+
+  fn len(&self) -> Option<u64> {
+    Some(self.header.decompressed_size().into())
+  }
+
+  fn offset(&self) -> u64 {
+    self.offset
+  }
+
+  async fn read<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[Self::Type; SIZE]) -> V) -> Result<V, StreamReadError<Self::ReadError>> {
+    StreamExhaustedError::assert(self.header.decompressed_size() as u64, self.offset, SIZE as u64)?;
+
+    let mut buffer = [0; SIZE];
+
+    for byte in buffer.iter_mut() {
+      *byte = self.next_byte().await?;
+    }
+
+    self.offset += SIZE as u64;
+    Ok(reader(&buffer).await)
+  }
+
+  async fn skip(&mut self, size: u64) -> Result<(), StreamSkipError<Self::SkipError>> {
+    StreamSkipError::assert_relative_forwards(self.header.decompressed_size() as u64, self.offset, size)?;
+
+    for _ in 0..size {
+      self.next_byte().await?;
+    }
+
+    self.offset += size;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use fileforge::{
+    binary_reader::{endianness::Endianness, BinaryReader},
+    provider::hint::ReadHint,
+    stream::{ReadableStream, SINGLE},
+  };
+
+  use super::*;
+  use crate::bios::huffman::{encoder::encode, error::HuffmanReadError};
+
+  const REAL: &[u8] = include_bytes!("../../../../fileforge-test/binaries/real.byml");
+
+  async fn stream(file: Vec<u8>) -> HuffmanStream<'static, impl ReadableStream<Type = u8>> {
+    let reader = BinaryReader::new_from_provider(file, Endianness::LittleEndian, ReadHint::new());
+    reader.into::<HuffmanStream<_>>().await.map_err(|_| {}).unwrap()
+  }
+
+  async fn decompress(file: Vec<u8>) -> Vec<u8> {
+    let mut stream = stream(file).await;
+    let mut out = Vec::new();
+
+    while let Ok(byte) = stream.read(SINGLE).await {
+      out.push(byte);
+    }
+
+    assert_eq!(stream.len(), Some(out.len() as u64));
+    out
+  }
+
+  fn samples() -> Vec<Vec<u8>> {
+    let mut noise = Vec::new();
+    let mut seed = 0x0BAD_F00Du32;
+    for _ in 0..10_000 {
+      seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+      noise.push((seed >> 24) as u8);
+    }
+
+    vec![Vec::new(), b"a".to_vec(), vec![0; 10_000], noise, REAL.to_vec()]
+  }
+
+  #[tokio::test]
+  async fn decodes_handmade_tree() {
+    // a root with the leaves 'a' and 'b', then the bits 0110
+    let file = vec![0x28, 4, 0, 0, 1, 0xC0, b'a', b'b', 0, 0, 0, 0x60];
+
+    assert_eq!(decompress(file).await, b"abba");
+  }
+
+  #[tokio::test]
+  async fn round_trips() {
+    for width in [HuffmanWidth::Four, HuffmanWidth::Eight] {
+      for sample in samples() {
+        let file = encode(&sample, width).map_err(|_| {}).unwrap();
+
+        assert_eq!(file.len() % 4, 0);
+        assert_eq!(decompress(file).await, sample, "{width:?}");
+      }
+    }
+  }
+
+  #[tokio::test]
+  async fn reads_and_skips_in_chunks() {
+    let mut stream = stream(encode(REAL, HuffmanWidth::Four).map_err(|_| {}).unwrap()).await;
+
+    let head = stream.read(async |bytes: &[u8; 300]| *bytes).await.map_err(|_| {}).unwrap();
+    stream.skip(1000).await.map_err(|_| {}).unwrap();
+    let tail = stream.read(async |bytes: &[u8; 64]| *bytes).await.map_err(|_| {}).unwrap();
+
+    assert_eq!(head[..], REAL[..300]);
+    assert_eq!(tail[..], REAL[1300..1364]);
+    assert!(stream.skip(REAL.len() as u64).await.is_err());
+  }
+
+  #[tokio::test]
+  async fn rejects_nodes_outside_the_tree() {
+    let reader = BinaryReader::new_from_provider(vec![0x28, 4, 0, 0, 1, 0x01, 0, 0], Endianness::LittleEndian, ReadHint::new());
+
+    assert!(matches!(
+      reader.into::<HuffmanStream<_>>().await,
+      Err(HuffmanReadError::InvalidNode { node, child: 4, length: 4 }) if *node.value() == TreeNode(0x01)
+    ));
+  }
+}
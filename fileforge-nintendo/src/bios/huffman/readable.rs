@@ -0,0 +1,66 @@
+use fileforge::{
+  binary_reader::{readable::IntoReadable, BinaryReader, PrimitiveReader},
+  diagnostic::value::DiagnosticSaturation,
+  stream::ReadableStream,
+};
+
+use crate::bios::{
+  header::{BiosCompression, BiosHeader},
+  huffman::{error::HuffmanReadError, HuffmanStream, TreeNode, MAX_TREE_LENGTH, ROOT_INDEX},
+};
+
+impl<'pool, S: ReadableStream<Type = u8>> IntoReadable<'pool, S> for HuffmanStream<'pool, S> {
+  type Argument = ();
+  type Error = HuffmanReadError<'pool, S::ReadError>;
+
+  async fn read(mut reader: BinaryReader<'pool, S>, _: Self::Argument) -> Result<Self, Self::Error> {
+    let header = reader.read_with::<BiosHeader>(&[BiosCompression::Huffman4, BiosCompression::Huffman8]).await?;
+
+    let tree_start = reader.offset();
+    let size: u8 = reader.get().await.map_err(HuffmanReadError::TreeSize)?;
+    let mut tree = heapless::Vec::<u8, MAX_TREE_LENGTH>::new();
+
+    tree.push(size).unwrap();
+
+    while tree.len() < (size as usize + 1) * 2 {
+      tree.push(reader.get().await.map_err(HuffmanReadError::Tree)?).unwrap();
+    }
+
+    // every reachable node is checked up front, so decoding can follow the tree blindly
+    let mut visited = [false; MAX_TREE_LENGTH];
+    let mut pending = heapless::Vec::<usize, MAX_TREE_LENGTH>::new();
+
+    pending.push(ROOT_INDEX).unwrap();
+
+    while let Some(index) = pending.pop() {
+      let node = TreeNode(tree[index]);
+      let child = node.child(index, false);
+
+      if child + 1 >= tree.len() {
+        let relative = (tree_start + index as u64) as i128 - reader.offset() as i128;
+
+        return Err(HuffmanReadError::InvalidNode {
+          node: reader.create_physical_diagnostic(relative, Some(1), "Huffman Node").saturate(node),
+          child: child as u16,
+          length: tree.len() as u16,
+        });
+      }
+
+      for bit in [false, true] {
+        if !node.is_leaf(bit) && !visited[child + bit as usize] {
+          visited[child + bit as usize] = true;
+          pending.push(child + bit as usize).unwrap();
+        }
+      }
+    }
+
+    Ok(HuffmanStream {
+      header,
+      reader,
+      tree,
+      word: 0,
+      bits: 0,
+      offset: 0,
+    })
+  }
+}
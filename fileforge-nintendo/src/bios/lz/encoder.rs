@@ -0,0 +1,97 @@
+use alloc::vec::Vec;
+
+use crate::{
+  bios::{error::BiosEncodeError, header::BiosHeader, lz::LzVersion, pad_to_word},
+  sead::yaz0::compress::matcher::{HashChain, Match},
+};
+
+/// Candidates a single search may visit, as for Yaz0's lazy level.
+const MAX_CHAIN: usize = 256;
+
+/// Inserts positions up to `position` into `chain`, then searches it.
+fn find(chain: &mut HashChain, inserted: &mut usize, data: &[u8], position: usize, version: LzVersion) -> Option<Match> {
+  while *inserted < position {
+    chain.insert(data, *inserted);
+    *inserted += 1;
+  }
+
+  chain.find_up_to(data, position, version.max_match())
+}
+
+fn push_reference(out: &mut Vec<u8>, version: LzVersion, Match { offset, length }: Match) {
+  let distance = offset as u32 - 1;
+  let length = length as u32;
+
+  match version {
+    LzVersion::Lz10 => out.extend([((length - 3) << 4 | distance >> 8) as u8, distance as u8]),
+    LzVersion::Lz11 if length <= 0x10 => out.extend([((length - 1) << 4 | distance >> 8) as u8, distance as u8]),
+    LzVersion::Lz11 if length <= 0x110 => {
+      let length = length - 0x11;
+
+      out.extend([(length >> 4) as u8, ((length & 0xF) << 4 | distance >> 8) as u8, distance as u8]);
+    }
+    LzVersion::Lz11 => {
+      let length = length - 0x111;
+
+      out.extend([(0x10 | length >> 12) as u8, (length >> 4) as u8, ((length & 0xF) << 4 | distance >> 8) as u8, distance as u8]);
+    }
+  }
+}
+
+/// Compresses `data` into a complete LZ10 or LZ11 file.
+///
+/// Matches are chosen with one byte of lookahead, like [`Yaz0CompressionLevel::Lazy`]. LZ11 could
+/// encode back-references of up to `0x10110` bytes, but matches stop at `0xFFFF`.
+///
+/// [`Yaz0CompressionLevel::Lazy`]: crate::sead::yaz0::compress::Yaz0CompressionLevel::Lazy
+pub fn encode(data: &[u8], version: LzVersion) -> Result<Vec<u8>, BiosEncodeError> {
+  let decompressed_size = u32::try_from(data.len()).map_err(|_| BiosEncodeError::TooLarge { length: data.len() as u64 })?;
+  let header = BiosHeader::new(version.compression(), decompressed_size);
+
+  let mut out = Vec::from(header.encode().as_slice());
+  let mut chain = HashChain::new(MAX_CHAIN);
+  let mut inserted = 0;
+  let mut pending = None;
+  let mut position = 0;
+  let mut flags_index = 0;
+  let mut flag = 0;
+
+  while position < data.len() {
+    if flag == 0 {
+      flags_index = out.len();
+      out.push(0);
+      flag = 0x80;
+    }
+
+    let found = match pending.take() {
+      Some(found) => Some(found),
+      None => match find(&mut chain, &mut inserted, data, position, version) {
+        Some(found) => match find(&mut chain, &mut inserted, data, position + 1, version) {
+          Some(next) if next.length as usize >= found.length as usize + 2 => {
+            pending = Some(next);
+            None
+          }
+          _ => Some(found),
+        },
+        None => None,
+      },
+    };
+
+    match found {
+      Some(found) => {
+        out[flags_index] |= flag;
+        push_reference(&mut out, version, found);
+        position += found.length as usize;
+      }
+      None => {
+        out.push(data[position]);
+        position += 1;
+      }
+    }
+
+    flag >>= 1;
+  }
+
+  pad_to_word(&mut out);
+  Ok(out)
+}
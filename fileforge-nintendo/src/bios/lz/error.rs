@@ -0,0 +1,30 @@
+use fileforge::{
+  binary_reader::error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError},
+  diagnostic::value::DiagnosticValue,
+  error::{ext::annotations::annotated::Annotated, render::builtin::number::formatted_unsigned::FormattedUnsigned},
+  stream::error::{user_read::UserReadError, user_skip::UserSkipError},
+};
+use fileforge_macros::FileforgeError;
+
+use crate::back_reference::BackReference;
+
+#[derive(FileforgeError)]
+pub enum LzError<'pool, U: UserReadError> {
+  Flags(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  Literal(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  Reference(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+
+  #[report(&"LZ back-reference reaches before the start of the data")]
+  InvalidBackReference {
+    #[error(
+      "This block reaches back {distance} bytes, but only {available} have been decompressed",
+      distance = FormattedUnsigned::new(reference.value().distance as u128),
+      available = FormattedUnsigned::new(*available as u128)
+    )]
+    reference: DiagnosticValue<'pool, BackReference>,
+    available: u16,
+  },
+}
+
+impl<'pool, U: UserReadError> UserReadError for LzError<'pool, U> {}
+impl<'pool, U: UserReadError> UserSkipError for LzError<'pool, U> {}
@@ -0,0 +1,324 @@
+#[cfg(feature = "alloc")]
+pub mod encoder;
+pub mod error;
+pub mod readable;
+
+use fileforge::{
+  binary_reader::{BinaryReader, PrimitiveReader},
+  diagnostic::value::DiagnosticSaturation,
+  stream::{
+    error::{stream_exhausted::StreamExhaustedError, stream_read::StreamReadError, stream_skip::StreamSkipError},
+    ReadableStream,
+  },
+};
+
+use crate::{
+  back_reference::BackReference,
+  bios::{
+    header::{BiosCompression, BiosHeader},
+    lz::error::LzError,
+  },
+  sead::yaz0::parser::data::BlockHeader,
+};
+
+/// Furthest a back-reference may reach in either version (12-bit distance, stored minus one).
+pub const LZ_WINDOW_SIZE: usize = 0x1000;
+/// Shortest back-reference either version can encode.
+pub const LZ_MIN_MATCH: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LzVersion {
+  /// The GBA format: back-references of up to 18 bytes.
+  Lz10,
+  /// The DS format: back-references of up to `0x10110` bytes, in 2, 3 or 4 byte tokens.
+  Lz11,
+}
+
+impl LzVersion {
+  pub fn compression(self) -> BiosCompression {
+    match self {
+      Self::Lz10 => BiosCompression::Lz10,
+      Self::Lz11 => BiosCompression::Lz11,
+    }
+  }
+
+  /// Longest back-reference a single token can encode.
+  pub fn max_match(self) -> usize {
+    match self {
+      Self::Lz10 => 0x12,
+      Self::Lz11 => 0x10110,
+    }
+  }
+}
+
+/// A decompressed view of an LZ10 or LZ11 file.
+///
+/// Blocks are a flag byte followed by eight tokens, most significant flag first. A set flag is a
+/// back-reference, a clear one a literal byte.
+pub struct LzStream<'pool, S: ReadableStream<Type = u8>> {
+  header: BiosHeader,
+  reader: BinaryReader<'pool, S>,
+  history: heapless::Deque<u8, LZ_WINDOW_SIZE>,
+  flags: BlockHeader,
+  block_offset: u64,
+  copy: Option<BackReference>,
+  offset: u64,
+}
+
+impl<'pool, S: ReadableStream<Type = u8>> LzStream<'pool, S> {
+  pub fn header(&self) -> &BiosHeader {
+    &self.header
+  }
+
+  pub fn version(&self) -> LzVersion {
+    match self.header.compression() {
+      BiosCompression::Lz11 => LzVersion::Lz11,
+      _ => LzVersion::Lz10,
+    }
+  }
+
+  fn push(&mut self, byte: u8) {
+    if self.history.is_full() {
+      self.history.pop_front();
+    }
+
+    let _ = self.history.push_back(byte);
+  }
+
+  fn back(&self, distance: u32) -> u8 {
+    let (front, back) = self.history.as_slices();
+    let index = self.history.len() - distance as usize;
+
+    match front.get(index) {
+      Some(byte) => *byte,
+      None => back[index - front.len()],
+    }
+  }
+
+  async fn read_reference(&mut self) -> Result<BackReference, LzError<'pool, S::ReadError>> {
+    let [a, b]: [u8; 2] = self.reader.get().await.map_err(LzError::Reference)?;
+    let a = a as u32;
+    let b = b as u32;
+
+    Ok(match (self.version(), a >> 4) {
+      (LzVersion::Lz11, 0) => {
+        let c: u8 = self.reader.get().await.map_err(LzError::Reference)?;
+
+        BackReference {
+          distance: ((b & 0xF) << 8 | c as u32) + 1,
+          length: ((a & 0xF) << 4 | b >> 4) + 0x11,
+        }
+      }
+      (LzVersion::Lz11, 1) => {
+        let [c, d]: [u8; 2] = self.reader.get().await.map_err(LzError::Reference)?;
+
+        BackReference {
+          distance: ((c as u32 & 0xF) << 8 | d as u32) + 1,
+          length: ((a & 0xF) << 12 | b << 4 | c as u32 >> 4) + 0x111,
+        }
+      }
+      (LzVersion::Lz11, n) => BackReference {
+        distance: ((a & 0xF) << 8 | b) + 1,
+        length: n + 1,
+      },
+      (LzVersion::Lz10, n) => BackReference {
+        distance: ((a & 0xF) << 8 | b) + 1,
+        length: n + 3,
+      },
+    })
+  }
+
+  async fn next_byte(&mut self) -> Result<u8, LzError<'pool, S::ReadError>> {
+    loop {
+      if let Some(copy) = self.copy {
+        let byte = self.back(copy.distance);
+
+        self.copy = (copy.length > 1).then_some(BackReference { length: copy.length - 1, ..copy });
+        self.push(byte);
+        return Ok(byte);
+      }
+
+      let compressed = match self.flags.take() {
+        Some(compressed) => compressed,
+        None => {
+          self.block_offset = self.reader.offset();
+          self.flags = BlockHeader::from_byte(self.reader.get().await.map_err(LzError::Flags)?);
+          self.flags.take().unwrap()
+        }
+      };
+
+      if !compressed {
+        let byte = self.reader.get().await.map_err(LzError::Literal)?;
+
+        self.push(byte);
+        return Ok(byte);
+      }
+
+      let reference = self.read_reference().await?;
+      let available = self.history.len() as u16;
+
+      if reference.distance > available as u32 {
+        let block_length = self.reader.offset() - self.block_offset;
+
+        return Err(LzError::InvalidBackReference {
+          reference: self.reader.create_physical_diagnostic(-(block_length as i128), Some(block_length), "LZ Block").saturate(reference),
+          available,
+        });
+      }
+
+      self.copy = Some(reference);
+    }
+  }
+}
+
+impl<'pool, S: ReadableStream<Type = u8>> ReadableStream for LzStream<'pool, S> {
+  type Type = u8;
+
+  type ReadError = LzError<'pool, S::ReadError>;
+  type SkipError = LzError<'pool, S::ReadError>;
+
+  fn len(&self) -> Option<u64> {
+    Some(self.header.decompressed_size().into())
+  }
+
+  fn offset(&self) -> u64 {
+    self.offset
+  }
+
+  async fn read<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[Self::Type; SIZE]) -> V) -> Result<V, StreamReadError<Self::ReadError>> {
+    StreamExhaustedError::assert(self.header.decompressed_size() as u64, self.offset, SIZE as u64)?;
+
+    let mut buffer = [0; SIZE];
+
+    for byte in buffer.iter_mut() {
+      *byte = self.next_byte().await?;
+    }
+
+    self.offset += SIZE as u64;
+    Ok(reader(&buffer).await)
+  }
+
+  async fn skip(&mut self, size: u64) -> Result<(), StreamSkipError<Self::SkipError>> {
+    StreamSkipError::assert_relative_forwards(self.header.decompressed_size() as u64, self.offset, size)?;
+
+    for _ in 0..size {
+      self.next_byte().await?;
+    }
+
+    self.offset += size;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use fileforge::{
+    binary_reader::{endianness::Endianness, BinaryReader},
+    provider::hint::ReadHint,
+    stream::{ReadableStream, StreamReadError, SINGLE},
+  };
+
+  use super::*;
+  use crate::bios::{header::readable::BiosHeaderReadError, lz::encoder::encode};
+
+  const REAL: &[u8] = include_bytes!("../../../../fileforge-test/binaries/real.byml");
+
+  async fn stream(file: Vec<u8>) -> LzStream<'static, impl ReadableStream<Type = u8>> {
+    let reader = BinaryReader::new_from_provider(file, Endianness::LittleEndian, ReadHint::new());
+    reader.into::<LzStream<_>>().await.map_err(|_| {}).unwrap()
+  }
+
+  async fn decompress(file: Vec<u8>) -> Vec<u8> {
+    let mut stream = stream(file).await;
+    let mut out = Vec::new();
+
+    while let Ok(byte) = stream.read(SINGLE).await {
+      out.push(byte);
+    }
+
+    assert_eq!(stream.len(), Some(out.len() as u64));
+    out
+  }
+
+  fn samples() -> Vec<Vec<u8>> {
+    let mut noise = Vec::new();
+    let mut seed = 0x0BAD_F00Du32;
+    for _ in 0..10_000 {
+      seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+      noise.push((seed >> 24) as u8 & 0x1F);
+    }
+
+    vec![Vec::new(), b"a".to_vec(), vec![0; 100_000], noise, REAL.to_vec()]
+  }
+
+  #[tokio::test]
+  async fn decodes_lz10() {
+    // "ab", then a four byte back-reference two bytes back
+    let file = vec![0x10, 6, 0, 0, 0b0010_0000, b'a', b'b', 0x10, 0x01];
+
+    assert_eq!(decompress(file).await, b"ababab");
+  }
+
+  #[tokio::test]
+  async fn decodes_every_lz11_token() {
+    let mut file = vec![0x11, 0, 0, 0];
+    file.extend((1u32 + 0x10 + 0x100 + 0x1000).to_le_bytes());
+    file.extend([0b0111_0000, b'x', 0xF0, 0x00, 0x0E, 0xF0, 0x00, 0x10, 0xEE, 0xF0, 0x00]);
+
+    assert_eq!(decompress(file).await, [b'x'; 1 + 0x10 + 0x100 + 0x1000]);
+  }
+
+  #[tokio::test]
+  async fn round_trips() {
+    for version in [LzVersion::Lz10, LzVersion::Lz11] {
+      for sample in samples() {
+        let file = encode(&sample, version).map_err(|_| {}).unwrap();
+
+        assert_eq!(file.len() % 4, 0);
+        assert!(file.len() <= 8 + sample.len() + sample.len() / 8 + 4);
+        assert_eq!(decompress(file).await, sample, "{version:?}");
+      }
+    }
+  }
+
+  #[tokio::test]
+  async fn lz11_beats_lz10_on_long_runs() {
+    let run = [7u8; 100_000];
+
+    assert!(encode(&run, LzVersion::Lz11).map_err(|_| {}).unwrap().len() * 4 < encode(&run, LzVersion::Lz10).map_err(|_| {}).unwrap().len());
+  }
+
+  #[tokio::test]
+  async fn reads_and_skips_in_chunks() {
+    let mut stream = stream(encode(REAL, LzVersion::Lz11).map_err(|_| {}).unwrap()).await;
+
+    let head = stream.read(async |bytes: &[u8; 300]| *bytes).await.map_err(|_| {}).unwrap();
+    stream.skip(1000).await.map_err(|_| {}).unwrap();
+    let tail = stream.read(async |bytes: &[u8; 64]| *bytes).await.map_err(|_| {}).unwrap();
+
+    assert_eq!(head[..], REAL[..300]);
+    assert_eq!(tail[..], REAL[1300..1364]);
+    assert!(stream.skip(REAL.len() as u64).await.is_err());
+  }
+
+  #[tokio::test]
+  async fn rejects_readback_before_start() {
+    let file = vec![0x10, 6, 0, 0, 0b0010_0000, b'a', b'b', 0x10, 0x02];
+    let mut stream = stream(file).await;
+
+    assert!(matches!(
+      stream.read(async |bytes: &[u8; 6]| *bytes).await,
+      Err(StreamReadError::User(LzError::InvalidBackReference { reference, available: 2 })) if *reference.value() == BackReference { distance: 3, length: 4 }
+    ));
+  }
+
+  #[tokio::test]
+  async fn rejects_other_formats() {
+    let reader = BinaryReader::new_from_provider(vec![0x30, 1, 0, 0, 0, 0], Endianness::LittleEndian, ReadHint::new());
+
+    assert!(matches!(
+      reader.into::<LzStream<_>>().await,
+      Err(BiosHeaderReadError::UnexpectedCompression { found }) if *found.value() == BiosCompression::Rle
+    ));
+  }
+}
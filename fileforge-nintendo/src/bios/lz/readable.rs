@@ -0,0 +1,31 @@
+use fileforge::{
+  binary_reader::{readable::IntoReadable, BinaryReader},
+  stream::ReadableStream,
+};
+
+use crate::{
+  bios::{
+    header::{readable::BiosHeaderReadError, BiosCompression, BiosHeader},
+    lz::LzStream,
+  },
+  sead::yaz0::parser::data::BlockHeader,
+};
+
+impl<'pool, S: ReadableStream<Type = u8>> IntoReadable<'pool, S> for LzStream<'pool, S> {
+  type Argument = ();
+  type Error = BiosHeaderReadError<'pool, S::ReadError>;
+
+  async fn read(mut reader: BinaryReader<'pool, S>, _: Self::Argument) -> Result<Self, Self::Error> {
+    let header = reader.read_with::<BiosHeader>(&[BiosCompression::Lz10, BiosCompression::Lz11]).await?;
+
+    Ok(LzStream {
+      header,
+      block_offset: reader.offset(),
+      reader,
+      history: heapless::Deque::new(),
+      flags: BlockHeader::empty(),
+      copy: None,
+      offset: 0,
+    })
+  }
+}
@@ -0,0 +1,17 @@
+//! Compression formats understood by the GBA and DS BIOS decompression calls.
+//!
+//! Every format starts with the same little-endian [`header::BiosHeader`]: a type byte and the
+//! decompressed size. Nintendo pads compressed files to a multiple of four bytes, and so do the
+//! encoders here.
+
+#[cfg(feature = "alloc")]
+pub mod error;
+pub mod header;
+pub mod huffman;
+pub mod lz;
+pub mod rle;
+
+#[cfg(feature = "alloc")]
+fn pad_to_word(data: &mut alloc::vec::Vec<u8>) {
+  data.resize(data.len().next_multiple_of(4), 0);
+}
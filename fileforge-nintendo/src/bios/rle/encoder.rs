@@ -0,0 +1,48 @@
+use alloc::vec::Vec;
+
+use crate::bios::{
+  error::BiosEncodeError,
+  header::{BiosCompression, BiosHeader},
+  pad_to_word,
+  rle::{MAX_LITERALS, MAX_RUN, MIN_RUN},
+};
+
+fn flush_literals(out: &mut Vec<u8>, literals: &[u8]) {
+  if !literals.is_empty() {
+    out.push(literals.len() as u8 - 1);
+    out.extend_from_slice(literals);
+  }
+}
+
+/// Compresses `data` into a complete RLE file. Runs shorter than three bytes are kept as literals.
+pub fn encode(data: &[u8]) -> Result<Vec<u8>, BiosEncodeError> {
+  let decompressed_size = u32::try_from(data.len()).map_err(|_| BiosEncodeError::TooLarge { length: data.len() as u64 })?;
+  let header = BiosHeader::new(BiosCompression::Rle, decompressed_size);
+
+  let mut out = Vec::from(header.encode().as_slice());
+  let mut literals_start = 0;
+  let mut position = 0;
+
+  while position < data.len() {
+    let byte = data[position];
+    let run = data[position..].iter().take(MAX_RUN).take_while(|&&b| b == byte).count();
+
+    if run >= MIN_RUN {
+      flush_literals(&mut out, &data[literals_start..position]);
+      out.extend([0x80 | (run - MIN_RUN) as u8, byte]);
+      position += run;
+      literals_start = position;
+    } else {
+      position += 1;
+
+      if position - literals_start == MAX_LITERALS {
+        flush_literals(&mut out, &data[literals_start..position]);
+        literals_start = position;
+      }
+    }
+  }
+
+  flush_literals(&mut out, &data[literals_start..]);
+  pad_to_word(&mut out);
+  Ok(out)
+}
@@ -0,0 +1,16 @@
+use fileforge::{
+  binary_reader::error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError},
+  error::ext::annotations::annotated::Annotated,
+  stream::error::{user_read::UserReadError, user_skip::UserSkipError},
+};
+use fileforge_macros::FileforgeError;
+
+#[derive(FileforgeError)]
+pub enum RleError<'pool, U: UserReadError> {
+  Flag(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  Literal(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  Run(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+}
+
+impl<'pool, U: UserReadError> UserReadError for RleError<'pool, U> {}
+impl<'pool, U: UserReadError> UserSkipError for RleError<'pool, U> {}
@@ -0,0 +1,163 @@
+#[cfg(feature = "alloc")]
+pub mod encoder;
+pub mod error;
+pub mod readable;
+
+use fileforge::{
+  binary_reader::{BinaryReader, PrimitiveReader},
+  stream::{
+    error::{stream_exhausted::StreamExhaustedError, stream_read::StreamReadError, stream_skip::StreamSkipError},
+    ReadableStream,
+  },
+};
+
+use crate::bios::{header::BiosHeader, rle::error::RleError};
+
+/// Shortest run a flag byte can encode.
+pub const MIN_RUN: usize = 3;
+/// Longest run a flag byte can encode.
+pub const MAX_RUN: usize = 0x7F + MIN_RUN;
+/// Most literal bytes a flag byte can be followed by.
+pub const MAX_LITERALS: usize = 0x80;
+
+/// What's left of the flag byte being decoded.
+#[derive(Clone, Copy, Default)]
+struct Run {
+  /// The repeated byte, or `None` while copying literals.
+  byte: Option<u8>,
+  remaining: u8,
+}
+
+/// A decompressed view of an RLE file.
+///
+/// Each flag byte either repeats the byte after it `(flag & 0x7F) + 3` times, when its top bit is
+/// set, or is followed by `(flag & 0x7F) + 1` literal bytes.
+pub struct RleStream<'pool, S: ReadableStream<Type = u8>> {
+  header: BiosHeader,
+  reader: BinaryReader<'pool, S>,
+  run: Run,
+  offset: u64,
+}
+
+impl<'pool, S: ReadableStream<Type = u8>> RleStream<'pool, S> {
+  pub fn header(&self) -> &BiosHeader {
+    &self.header
+  }
+
+  async fn next_byte(&mut self) -> Result<u8, RleError<'pool, S::ReadError>> {
+    if self.run.remaining == 0 {
+      let flag: u8 = self.reader.get().await.map_err(RleError::Flag)?;
+
+      self.run = if flag & 0x80 != 0 {
+        Run {
+          byte: Some(self.reader.get().await.map_err(RleError::Run)?),
+          remaining: (flag & 0x7F) + MIN_RUN as u8,
+        }
+      } else {
+        Run { byte: None, remaining: flag + 1 }
+      };
+    }
+
+    self.run.remaining -= 1;
+
+    match self.run.byte {
+      Some(byte) => Ok(byte),
+      None => self.reader.get().await.map_err(RleError::Literal),
+    }
+  }
+}
+
+impl<'pool, S: ReadableStream<Type = u8>> ReadableStream for RleStream<'pool, S> {
+  type Type = u8;
+
+  type ReadError = RleError<'pool, S::ReadError>;
+  type SkipError = RleError<'pool, S::ReadError>;
+
+  fn len(&self) -> Option<u64> {
+    Some(self.header.decompressed_size().into())
+  }
+
+  fn offset(&self) -> u64 {
+    self.offset
+  }
+
+  async fn read<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[Self::Type; SIZE]) -> V) -> Result<V, StreamReadError<Self::ReadError>> {
+    StreamExhaustedError::assert(self.header.decompressed_size() as u64, self.offset, SIZE as u64)?;
+
+    let mut buffer = [0; SIZE];
+
+    for byte in buffer.iter_mut() {
+      *byte = self.next_byte().await?;
+    }
+
+    self.offset += SIZE as u64;
+    Ok(reader(&buffer).await)
+  }
+
+  async fn skip(&mut self, size: u64) -> Result<(), StreamSkipError<Self::SkipError>> {
+    StreamSkipError::assert_relative_forwards(self.header.decompressed_size() as u64, self.offset, size)?;
+
+    for _ in 0..size {
+      self.next_byte().await?;
+    }
+
+    self.offset += size;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use fileforge::{
+    binary_reader::{endianness::Endianness, BinaryReader},
+    provider::hint::ReadHint,
+    stream::{ReadableStream, SINGLE},
+  };
+
+  use super::*;
+  use crate::bios::rle::encoder::encode;
+
+  const REAL: &[u8] = include_bytes!("../../../../fileforge-test/binaries/real.byml");
+
+  async fn decompress(file: Vec<u8>) -> Vec<u8> {
+    let reader = BinaryReader::new_from_provider(file, Endianness::LittleEndian, ReadHint::new());
+    let mut stream = reader.into::<RleStream<_>>().await.map_err(|_| {}).unwrap();
+    let mut out = Vec::new();
+
+    while let Ok(byte) = stream.read(SINGLE).await {
+      out.push(byte);
+    }
+
+    assert_eq!(stream.len(), Some(out.len() as u64));
+    out
+  }
+
+  #[tokio::test]
+  async fn decodes_runs_and_literals() {
+    let file = vec![0x30, 7, 0, 0, 0x81, b'z', 0x02, b'a', b'b', b'c'];
+
+    assert_eq!(decompress(file).await, b"zzzzabc");
+  }
+
+  #[tokio::test]
+  async fn round_trips() {
+    let mut mixed = Vec::new();
+    for length in 0..300 {
+      mixed.extend((0..length % 140).map(|i| if length % 3 == 0 { length as u8 } else { (i * 7) as u8 }));
+    }
+
+    for sample in [Vec::new(), b"ab".to_vec(), vec![5; 1000], (0..=255).collect(), mixed, REAL.to_vec()] {
+      let file = encode(&sample).map_err(|_| {}).unwrap();
+
+      assert_eq!(file.len() % 4, 0);
+      assert_eq!(decompress(file).await, sample);
+    }
+  }
+
+  #[test]
+  fn uses_longest_runs() {
+    let file = encode(&[9; MAX_RUN * 2]).map_err(|_| {}).unwrap();
+
+    assert_eq!(file[4..8], [0xFF, 9, 0xFF, 9]);
+  }
+}
@@ -0,0 +1,25 @@
+use fileforge::{
+  binary_reader::{readable::IntoReadable, BinaryReader},
+  stream::ReadableStream,
+};
+
+use crate::bios::{
+  header::{readable::BiosHeaderReadError, BiosCompression, BiosHeader},
+  rle::{RleStream, Run},
+};
+
+impl<'pool, S: ReadableStream<Type = u8>> IntoReadable<'pool, S> for RleStream<'pool, S> {
+  type Argument = ();
+  type Error = BiosHeaderReadError<'pool, S::ReadError>;
+
+  async fn read(mut reader: BinaryReader<'pool, S>, _: Self::Argument) -> Result<Self, Self::Error> {
+    let header = reader.read_with::<BiosHeader>(&[BiosCompression::Rle]).await?;
+
+    Ok(RleStream {
+      header,
+      reader,
+      run: Run::default(),
+      offset: 0,
+    })
+  }
+}
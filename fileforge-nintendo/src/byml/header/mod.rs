@@ -6,7 +6,7 @@ pub mod readable;
 
 pub static BYML_HEADER_SIZE: usize = 0x10;
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct BymlHeader {
   config: BymlHeaderConfig,
   endianness: Endianness,
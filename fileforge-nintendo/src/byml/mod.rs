@@ -1,6 +1,6 @@
 use fileforge::{
-  binary_reader::{error::SkipError, BinaryReader},
-  stream::ReadableStream,
+  binary_reader::{error::SkipError, snapshot::BinaryReaderSnapshot, BinaryReader},
+  stream::{error::stream_restore::StreamRestoreError, ReadableStream, RestorableStream},
 };
 use fileforge_macros::FileforgeError;
 
@@ -12,6 +12,10 @@ use crate::byml::{
 pub mod header;
 pub mod node;
 pub mod readable;
+#[cfg(feature = "alloc")]
+pub mod value;
+#[cfg(feature = "alloc")]
+pub mod writer;
 
 pub struct Byml<'pool, S: ReadableStream<Type = u8>> {
   header: BymlHeader,
@@ -25,6 +29,14 @@ pub enum IntoDataAtError<'pool, S: ReadableStream> {
   SeekError(#[from] SkipError<'pool, S::SkipError>),
 }
 
+/// Moves `reader` forwards to the absolute `position`. Nodes can only be reached by skipping forwards, so anything behind the reader is out of bounds.
+pub(crate) async fn reader_into_data_at<'pool, S: ReadableStream<Type = u8>>(mut reader: BinaryReader<'pool, S>, position: u64) -> Result<BinaryReader<'pool, S>, IntoDataAtError<'pool, S>> {
+  let offset = position.checked_sub(reader.offset()).ok_or(IntoDataAtError::OutOfBounds)?;
+  reader.skip(offset).await?;
+
+  Ok(reader)
+}
+
 impl<'pool, S: ReadableStream<Type = u8>> Byml<'pool, S> {
   fn version(&self) -> BymlNodeDiscriminantVersionConfig {
     BymlNodeDiscriminantVersionConfig {
@@ -33,11 +45,8 @@ impl<'pool, S: ReadableStream<Type = u8>> Byml<'pool, S> {
     }
   }
 
-  async fn into_data_at(mut self, position: u64) -> Result<BinaryReader<'pool, S>, IntoDataAtError<'pool, S>> {
-    let offset = position.checked_sub(self.header.size()).ok_or(IntoDataAtError::OutOfBounds)?;
-    self.reader.skip(offset).await?;
-
-    Ok(self.reader)
+  async fn into_data_at(self, position: u64) -> Result<BinaryReader<'pool, S>, IntoDataAtError<'pool, S>> {
+    reader_into_data_at(self.reader, position).await
   }
 
   async fn into_node(self, discriminant: BymlNodeDiscriminants, value: u32) -> Result<BymlNode<'pool, S>, BymlConstructionError<'pool, IntoDataAtError<'pool, S>, S>> {
@@ -61,4 +70,166 @@ impl<'pool, S: ReadableStream<Type = u8>> Byml<'pool, S> {
       None => Ok(None),
     }
   }
+
+  pub async fn into_root(self) -> Result<Option<BymlNode<'pool, S>>, BymlConstructionError<'pool, IntoDataAtError<'pool, S>, S>> {
+    match self.header.root_data_offset() {
+      Some(v) => Ok(Some(self.into_node_dyn(v.get()).await?)),
+      None => Ok(None),
+    }
+  }
+
+  /// Remembers the start of the document, so that its tables and root can be borrowed any number of times, in any order.
+  pub fn into_restorable(self) -> RestorableByml<'pool, S>
+  where
+    S: RestorableStream,
+  {
+    RestorableByml {
+      start: self.reader.snapshot(),
+      byml: self,
+    }
+  }
+
+  fn by_ref(&mut self) -> Byml<'pool, &mut S> {
+    Byml {
+      header: self.header,
+      reader: self.reader.borrow_fork(),
+    }
+  }
+}
+
+/// A document that nodes can be borrowed from without giving up the reader.
+///
+/// Nodes are reached by skipping forwards, so the document goes back to its start before each access.
+pub struct RestorableByml<'pool, S: RestorableStream<Type = u8>> {
+  byml: Byml<'pool, S>,
+  start: BinaryReaderSnapshot<'pool, S>,
+}
+
+#[derive(FileforgeError)]
+pub enum BymlBorrowError<'pool, S: RestorableStream<Type = u8>> {
+  #[report(&"Failed to restore to the start of the document")]
+  Restore(StreamRestoreError<S::RestoreError>),
+
+  Construction(#[from] BymlConstructionError<'pool, IntoDataAtError<'pool, S>, S>),
+}
+
+impl<'pool, S: RestorableStream<Type = u8>> RestorableByml<'pool, S> {
+  /// Goes back to the start of the document and borrows it.
+  async fn by_ref(&mut self) -> Result<Byml<'pool, &mut S>, StreamRestoreError<S::RestoreError>> {
+    self.byml.reader.restore(self.start.clone()).await?;
+
+    Ok(self.byml.by_ref())
+  }
+
+  pub async fn root(&mut self) -> Result<Option<BymlNode<'pool, &mut S>>, BymlBorrowError<'pool, &mut S>> {
+    Ok(self.by_ref().await.map_err(BymlBorrowError::Restore)?.into_root().await?)
+  }
+
+  pub async fn key_table(&mut self) -> Result<Option<BymlNode<'pool, &mut S>>, BymlBorrowError<'pool, &mut S>> {
+    Ok(self.by_ref().await.map_err(BymlBorrowError::Restore)?.into_key_table().await?)
+  }
+
+  pub async fn literal_table(&mut self) -> Result<Option<BymlNode<'pool, &mut S>>, BymlBorrowError<'pool, &mut S>> {
+    Ok(self.by_ref().await.map_err(BymlBorrowError::Restore)?.into_literal_table().await?)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use fileforge::{
+    binary_reader::{endianness::Endianness, BinaryReader},
+    provider::hint::ReadHint,
+    stream::builtin::provider::ProviderStream,
+  };
+
+  use crate::byml::{header::BymlHeaderConfig, Byml, RestorableByml};
+
+  async fn bed() -> RestorableByml<'static, ProviderStream<Vec<u8>>> {
+    let reader = BinaryReader::new_from_provider(Vec::from(*include_bytes!("../../../fileforge-test/binaries/Bed.byml")), Endianness::BigEndian, ReadHint::new());
+
+    reader
+      .into_with::<Byml<_>>(BymlHeaderConfig::build().without_binary_data_table().build())
+      .await
+      .map_err(|_| {})
+      .unwrap()
+      .into_restorable()
+  }
+
+  async fn strings(byml: &mut RestorableByml<'static, ProviderStream<Vec<u8>>>) -> Vec<Vec<u8>> {
+    byml
+      .literal_table()
+      .await
+      .map_err(|_| {})
+      .unwrap()
+      .unwrap()
+      .into_string_table()
+      .map_err(|_| {})
+      .unwrap()
+      .into_strings()
+      .await
+      .map_err(|_| {})
+      .unwrap()
+  }
+
+  #[tokio::test]
+  async fn root_is_a_dictionary() {
+    let mut byml = bed().await;
+    let root = byml.root().await.map_err(|_| {}).unwrap().unwrap();
+
+    assert_eq!(root.into_dictionary().map_err(|_| {}).unwrap().len(), 12);
+  }
+
+  #[tokio::test]
+  async fn get_path_resolves_keys() {
+    let mut byml = bed().await;
+    let index = byml
+      .get_path("UnitConfig/GenerateCategory")
+      .await
+      .map_err(|_| {})
+      .unwrap()
+      .unwrap()
+      .into_string()
+      .map_err(|_| {})
+      .unwrap()
+      .string_table_index();
+
+    assert_eq!(strings(&mut byml).await[index as usize], b"ObjectList");
+  }
+
+  #[tokio::test]
+  async fn borrows_repeatedly() {
+    let mut byml = bed().await;
+
+    for _ in 0..2 {
+      let node = byml.get_path("Scale/X").await.map_err(|_| {}).unwrap().unwrap();
+
+      assert_eq!(node.into_float32().map_err(|_| {}).unwrap().value(), 1.0);
+    }
+
+    let root = byml.root().await.map_err(|_| {}).unwrap().unwrap();
+
+    assert_eq!(root.into_dictionary().map_err(|_| {}).unwrap().len(), 12);
+  }
+
+  #[tokio::test]
+  async fn get_path_reads_scalars() {
+    let mut byml = bed().await;
+    let node = byml.get_path("Scale/Y").await.map_err(|_| {}).unwrap().unwrap();
+
+    assert_eq!(node.into_float32().map_err(|_| {}).unwrap().value(), 1.0);
+  }
+
+  #[tokio::test]
+  async fn get_path_missing_key() {
+    let mut byml = bed().await;
+
+    assert!(byml.get_path("UnitConfig/Missing").await.map_err(|_| {}).unwrap().is_none());
+  }
+
+  #[tokio::test]
+  async fn get_path_rejects_scalars() {
+    let mut byml = bed().await;
+
+    assert!(byml.get_path("Id/0").await.is_err());
+  }
 }
@@ -0,0 +1,116 @@
+use fileforge::{
+  binary_reader::{
+    error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError, SkipError},
+    BinaryReader, PrimitiveReader,
+  },
+  error::ext::annotations::annotated::Annotated,
+  stream::{
+    error::{
+      stream_exhausted::StreamExhaustedError, stream_read::StreamReadError, stream_restore::StreamRestoreError, stream_seek_out_of_bounds::StreamSeekOutOfBoundsError, stream_skip::StreamSkipError,
+      user_read::UserReadError,
+    },
+    ReadableStream, RestorableStream,
+  },
+};
+use fileforge_macros::FileforgeError;
+
+use crate::byml::node::{
+  array::BymlArrayNode,
+  discriminant::{BymlNodeDiscriminantVersionConfig, BymlNodeDiscriminantsReadError},
+  reference::BymlNodeReference,
+  BymlNodeDiscriminants,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BymlArrayEntry {
+  pub discriminant: BymlNodeDiscriminants,
+  pub value: u32,
+}
+
+/// A stream over the elements of a [`BymlArrayNode`].
+pub struct BymlArrayEntries<'pool, S: RestorableStream<Type = u8>> {
+  pub(super) index: u32,
+  pub(super) count: u32,
+  pub(super) version: BymlNodeDiscriminantVersionConfig,
+  pub(super) reader: BinaryReader<'pool, S>,
+}
+
+#[derive(FileforgeError)]
+pub enum BymlArrayEntriesReadError<'pool, S: RestorableStream<Type = u8>> {
+  FailedToReadType(BymlNodeDiscriminantsReadError<'pool, S>),
+  FailedToSkipToValue(SkipError<'pool, S::SkipError>),
+  FailedToReadValue(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>),
+
+  #[report(&"Failed to restore to the type table")]
+  FailedToRestore(StreamRestoreError<S::RestoreError>),
+}
+
+impl<'pool, S: RestorableStream<Type = u8>> UserReadError for BymlArrayEntriesReadError<'pool, S> {}
+
+impl<'pool, S: RestorableStream<Type = u8>> BymlArrayEntries<'pool, S> {
+  /// Descends into `entry`, which must have been read from this stream.
+  pub fn into_reference(self, entry: BymlArrayEntry) -> BymlNodeReference<'pool, S> {
+    BymlNodeReference::new(entry.discriminant, entry.value, self.version, self.reader)
+  }
+
+  async fn read_entry(&mut self) -> Result<BymlArrayEntry, BymlArrayEntriesReadError<'pool, S>> {
+    let discriminant = self.reader.read_with(self.version).await.map_err(BymlArrayEntriesReadError::FailedToReadType)?;
+    let snapshot = self.reader.snapshot();
+
+    self
+      .reader
+      .skip(BymlArrayNode::<S>::type_to_value_distance(self.count, self.index))
+      .await
+      .map_err(BymlArrayEntriesReadError::FailedToSkipToValue)?;
+
+    let value = self.reader.get().await.map_err(BymlArrayEntriesReadError::FailedToReadValue)?;
+
+    self.reader.restore(snapshot).await.map_err(BymlArrayEntriesReadError::FailedToRestore)?;
+    self.index += 1;
+
+    Ok(BymlArrayEntry { discriminant, value })
+  }
+}
+
+impl<'pool, S: RestorableStream<Type = u8>> ReadableStream for BymlArrayEntries<'pool, S> {
+  type Type = BymlArrayEntry;
+
+  type ReadError = BymlArrayEntriesReadError<'pool, S>;
+  type SkipError = S::SkipError;
+
+  fn len(&self) -> Option<u64> {
+    Some(self.count as u64)
+  }
+
+  fn offset(&self) -> u64 {
+    self.index as u64
+  }
+
+  async fn skip(&mut self, size: u64) -> Result<(), StreamSkipError<Self::SkipError>> {
+    let seek_point = StreamSkipError::assert_relative_forwards(self.count as u64, self.index as u64, size)?;
+
+    self.reader.skip(size).await.map_err(|e| match e {
+      SkipError::User(u) => StreamSkipError::User(u),
+      SkipError::OutOfBounds(_) => StreamSkipError::OutOfBounds(StreamSeekOutOfBoundsError {
+        stream_length: self.count as u64,
+        seek_point,
+      }),
+    })?;
+
+    self.index = seek_point as u32;
+
+    Ok(())
+  }
+
+  async fn read<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[Self::Type; SIZE]) -> V) -> Result<V, StreamReadError<Self::ReadError>> {
+    StreamExhaustedError::assert(self.count as u64, self.index as u64, SIZE as u64)?;
+
+    let mut dest = heapless::Vec::<BymlArrayEntry, SIZE>::new();
+
+    while !dest.is_full() {
+      dest.push(self.read_entry().await?).map_err(|_| {}).unwrap();
+    }
+
+    Ok(reader(&dest.into_array::<SIZE>().map_err(|_| {}).unwrap()).await)
+  }
+}
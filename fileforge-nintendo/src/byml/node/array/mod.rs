@@ -0,0 +1,208 @@
+pub mod entries;
+
+use fileforge::{
+  binary_reader::{
+    error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError, SkipError},
+    primitive::numeric::u24,
+    BinaryReader, PrimitiveReader,
+  },
+  error::{ext::annotations::annotated::Annotated, render::builtin::number::formatted_unsigned::FormattedUnsigned},
+  stream::{ReadableStream, RestorableStream},
+};
+use fileforge_macros::FileforgeError;
+
+use crate::byml::node::{
+  array::entries::BymlArrayEntries,
+  discriminant::{BymlNodeDiscriminantVersionConfig, BymlNodeDiscriminantsReadError},
+  reference::BymlNodeReference,
+  BymlDynConstructable,
+};
+
+/// An array node (`0xC0`).
+///
+/// Laid out as a `u24` element count, one type byte per element (padded to 4 bytes), then one `u32` value per element.
+pub struct BymlArrayNode<'pool, S: ReadableStream<Type = u8>> {
+  count: u32,
+  version: BymlNodeDiscriminantVersionConfig,
+  reader: BinaryReader<'pool, S>,
+}
+
+#[derive(FileforgeError)]
+pub enum BymlArrayNodeConstructableError<'pool, S: ReadableStream<Type = u8>> {
+  ReadCountError(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>),
+}
+
+impl<'pool, S: ReadableStream<Type = u8>> BymlDynConstructable<'pool, S> for BymlArrayNode<'pool, S> {
+  type Error = BymlArrayNodeConstructableError<'pool, S>;
+
+  async fn construct_dyn(mut reader: BinaryReader<'pool, S>, version: BymlNodeDiscriminantVersionConfig) -> Result<Self, Self::Error> {
+    Ok(BymlArrayNode {
+      count: reader.get::<u24>().await.map_err(BymlArrayNodeConstructableError::ReadCountError)?.into(),
+      version,
+      reader,
+    })
+  }
+}
+
+#[derive(FileforgeError)]
+pub enum BymlArrayNodeIntoEntryError<'pool, S: ReadableStream<Type = u8>> {
+  #[report(&"Array index out of bounds")]
+  #[flag("Index {index} is out of bounds for {count} elements", index = FormattedUnsigned::new(*index as u128), count = FormattedUnsigned::new(*count as u128))]
+  IndexOutOfBounds {
+    index: u32,
+    count: u32,
+  },
+
+  FailedToSkipToType(SkipError<'pool, S::SkipError>),
+  FailedToReadType(BymlNodeDiscriminantsReadError<'pool, S>),
+  FailedToSkipToValue(SkipError<'pool, S::SkipError>),
+  FailedToReadValue(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>),
+}
+
+impl<'pool, S: ReadableStream<Type = u8>> BymlArrayNode<'pool, S> {
+  pub fn len(&self) -> u32 {
+    self.count
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.count == 0
+  }
+
+  /// The length of the type table, including the padding up to the value table.
+  fn type_table_length(count: u32) -> u64 {
+    (count as u64 + 3) & !3
+  }
+
+  /// The distance from just after the type of `index` to its value.
+  fn type_to_value_distance(count: u32, index: u32) -> u64 {
+    Self::type_table_length(count) - (index as u64 + 1) + (index as u64 * 4)
+  }
+
+  pub async fn into_entry(mut self, index: u32) -> Result<BymlNodeReference<'pool, S>, BymlArrayNodeIntoEntryError<'pool, S>> {
+    if index >= self.count {
+      return Err(BymlArrayNodeIntoEntryError::IndexOutOfBounds { index, count: self.count });
+    }
+
+    self.reader.skip(index as u64).await.map_err(BymlArrayNodeIntoEntryError::FailedToSkipToType)?;
+
+    let discriminant = self.reader.read_with(self.version).await.map_err(BymlArrayNodeIntoEntryError::FailedToReadType)?;
+
+    self
+      .reader
+      .skip(Self::type_to_value_distance(self.count, index))
+      .await
+      .map_err(BymlArrayNodeIntoEntryError::FailedToSkipToValue)?;
+
+    let value = self.reader.get().await.map_err(BymlArrayNodeIntoEntryError::FailedToReadValue)?;
+
+    Ok(BymlNodeReference::new(discriminant, value, self.version, self.reader))
+  }
+}
+
+impl<'pool, S: RestorableStream<Type = u8>> BymlArrayNode<'pool, S> {
+  /// Iterates the elements of the array.
+  ///
+  /// Types and values live in separate tables, so each element restores back into the type table after reading its value.
+  pub fn into_entries(self) -> BymlArrayEntries<'pool, S> {
+    BymlArrayEntries {
+      index: 0,
+      count: self.count,
+      version: self.version,
+      reader: self.reader,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use fileforge::{
+    binary_reader::{endianness::Endianness, BinaryReader},
+    provider::hint::ReadHint,
+    stream::{builtin::provider::ProviderStream, extensions::readable::ReadableStreamExt},
+  };
+
+  use crate::byml::node::{discriminant::BymlNodeDiscriminantVersionConfig, BymlAnyConstructable, BymlNode, BymlNodeDiscriminants};
+
+  const VERSION: BymlNodeDiscriminantVersionConfig = BymlNodeDiscriminantVersionConfig {
+    version_number: 3,
+    feat_binary_data_table: false,
+  };
+
+  // [true, 7, [1.5]] with the nested array stored at 0x14
+  fn fixture() -> Vec<u8> {
+    let mut data = vec![0xC0, 0x00, 0x00, 0x03, 0xD0, 0xD1, 0xC0, 0x00];
+    data.extend(1u32.to_be_bytes());
+    data.extend(7u32.to_be_bytes());
+    data.extend(0x14u32.to_be_bytes());
+    data.extend([0xC0, 0x00, 0x00, 0x01, 0xD2, 0x00, 0x00, 0x00]);
+    data.extend(1.5f32.to_bits().to_be_bytes());
+    data
+  }
+
+  async fn root(data: Vec<u8>) -> BymlNode<'static, ProviderStream<Vec<u8>>> {
+    let reader = BinaryReader::new_from_provider(data, Endianness::BigEndian, ReadHint::new());
+
+    BymlNode::construct_dyn(0, VERSION, async |_| Ok::<_, ()>(reader)).await.map_err(|_| {}).unwrap()
+  }
+
+  #[tokio::test]
+  async fn array_len() {
+    let array = root(fixture()).await.into_array().map_err(|_| {}).unwrap();
+
+    assert_eq!(array.len(), 3);
+  }
+
+  #[tokio::test]
+  async fn array_into_entry() {
+    let array = root(fixture()).await.into_array().map_err(|_| {}).unwrap();
+    let entry = array.into_entry(1).await.map_err(|_| {}).unwrap();
+
+    assert_eq!(entry.discriminant(), BymlNodeDiscriminants::Integer32);
+    assert_eq!(entry.value(), 7);
+  }
+
+  #[tokio::test]
+  async fn array_into_entry_out_of_bounds() {
+    let array = root(fixture()).await.into_array().map_err(|_| {}).unwrap();
+
+    assert!(array.into_entry(3).await.is_err());
+  }
+
+  #[tokio::test]
+  async fn array_entries() {
+    let mut entries = root(fixture()).await.into_array().map_err(|_| {}).unwrap().into_entries();
+
+    let first = entries.next().await.map_err(|_| {}).unwrap();
+    let second = entries.next().await.map_err(|_| {}).unwrap();
+    let third = entries.next().await.map_err(|_| {}).unwrap();
+
+    assert_eq!((first.discriminant, first.value), (BymlNodeDiscriminants::Bool, 1));
+    assert_eq!((second.discriminant, second.value), (BymlNodeDiscriminants::Integer32, 7));
+    assert_eq!((third.discriminant, third.value), (BymlNodeDiscriminants::Array, 0x14));
+    assert!(entries.next().await.is_err());
+  }
+
+  #[tokio::test]
+  async fn array_descends_into_child() {
+    let array = root(fixture()).await.into_array().map_err(|_| {}).unwrap();
+    let child = array
+      .into_entry(2)
+      .await
+      .map_err(|_| {})
+      .unwrap()
+      .into_node()
+      .await
+      .map_err(|_| {})
+      .unwrap()
+      .into_array()
+      .map_err(|_| {})
+      .unwrap();
+
+    assert_eq!(child.len(), 1);
+
+    let entry = child.into_entry(0).await.map_err(|_| {}).unwrap();
+
+    assert_eq!(entry.discriminant(), BymlNodeDiscriminants::Float32);
+    assert_eq!(entry.value(), 1.5f32.to_bits());
+  }
+}
@@ -0,0 +1,141 @@
+use fileforge::{
+  binary_reader::{
+    error::{common::Read, primitive_name_annotation::PrimitiveName, DynamicSubforkError, GetPrimitiveError},
+    BinaryReader, PrimitiveReader,
+  },
+  error::ext::annotations::annotated::Annotated,
+  stream::{DynamicPartitionableStream, ReadableStream},
+};
+use fileforge_macros::FileforgeError;
+
+use crate::byml::node::{BymlConstructable, BymlOutOfLineError};
+
+/// A binary data node (`0xA1`).
+///
+/// Usually stored out of line as a `u32` length followed by the payload. Documents with a binary data table instead store an index into that table.
+pub struct BymlBinaryDataNode<'pool, S: ReadableStream<Type = u8>> {
+  storage: BymlBinaryDataStorage<'pool, S>,
+}
+
+enum BymlBinaryDataStorage<'pool, S: ReadableStream<Type = u8>> {
+  Inline { length: u32, reader: BinaryReader<'pool, S> },
+  Table { index: u32 },
+}
+
+#[derive(FileforgeError)]
+pub enum BymlBinaryDataNodeConstructableError<'pool, S: ReadableStream<Type = u8>> {
+  ReadLengthError(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>),
+}
+
+#[derive(FileforgeError)]
+pub enum BymlBinaryDataNodeIntoDataError<'pool, S: DynamicPartitionableStream<Type = u8>> {
+  #[report(&"Binary data is stored in the binary data table")]
+  StoredInTable(u32),
+
+  Partition(#[from] DynamicSubforkError<'pool, S::PartitionError>),
+}
+
+impl<'pool, S: ReadableStream<Type = u8>, E> BymlConstructable<'pool, S, E> for BymlBinaryDataNode<'pool, S> {
+  type Error = BymlOutOfLineError<E, BymlBinaryDataNodeConstructableError<'pool, S>>;
+
+  async fn construct<F: AsyncFnOnce(u64) -> Result<BinaryReader<'pool, S>, E>>(value: u32, get_reader: F) -> Result<Self, Self::Error> {
+    let mut reader = get_reader(value as u64).await.map_err(BymlOutOfLineError::ReaderAcquire)?;
+    let length = reader.get().await.map_err(|e| BymlOutOfLineError::Item(BymlBinaryDataNodeConstructableError::ReadLengthError(e)))?;
+
+    Ok(Self {
+      storage: BymlBinaryDataStorage::Inline { length, reader },
+    })
+  }
+}
+
+impl<'pool, S: ReadableStream<Type = u8>> BymlBinaryDataNode<'pool, S> {
+  pub(in crate::byml::node) fn table(index: u32) -> Self {
+    Self {
+      storage: BymlBinaryDataStorage::Table { index },
+    }
+  }
+
+  /// The length of the payload, if it is stored inline.
+  pub fn inline_len(&self) -> Option<u32> {
+    match self.storage {
+      BymlBinaryDataStorage::Inline { length, .. } => Some(length),
+      BymlBinaryDataStorage::Table { .. } => None,
+    }
+  }
+
+  /// The index of the payload in the binary data table, if it is stored there.
+  pub fn table_index(&self) -> Option<u32> {
+    match self.storage {
+      BymlBinaryDataStorage::Inline { .. } => None,
+      BymlBinaryDataStorage::Table { index } => Some(index),
+    }
+  }
+}
+
+impl<'pool, S: DynamicPartitionableStream<Type = u8>> BymlBinaryDataNode<'pool, S> {
+  /// Bounds the reader to the payload.
+  pub async fn into_data(self) -> Result<BinaryReader<'pool, S::PartitionDynamicLeft>, BymlBinaryDataNodeIntoDataError<'pool, S>> {
+    match self.storage {
+      BymlBinaryDataStorage::Inline { length, reader } => Ok(reader.partition_dynamic(length as u64, Some("Binary Data")).await?.0),
+      BymlBinaryDataStorage::Table { index } => Err(BymlBinaryDataNodeIntoDataError::StoredInTable(index)),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use fileforge::{
+    binary_reader::{endianness::Endianness, BinaryReader, PrimitiveReader},
+    provider::hint::ReadHint,
+  };
+
+  use crate::byml::node::{discriminant::BymlNodeDiscriminantVersionConfig, BymlAnyConstructable, BymlNode, BymlNodeDiscriminants};
+
+  const VERSION: BymlNodeDiscriminantVersionConfig = BymlNodeDiscriminantVersionConfig {
+    version_number: 4,
+    feat_binary_data_table: false,
+  };
+
+  #[tokio::test]
+  async fn binary_data_is_length_bounded() {
+    let mut data = vec![0x00, 0x00, 0x00, 0x03, 0xAA, 0xBB, 0xCC, 0xDD];
+    let reader = BinaryReader::new_from_provider(&mut data, Endianness::BigEndian, ReadHint::new());
+
+    let node = BymlNode::construct(BymlNodeDiscriminants::BinaryData, 0, VERSION, async |_| Ok::<_, ()>(reader))
+      .await
+      .map_err(|_| {})
+      .unwrap()
+      .into_binary_data()
+      .map_err(|_| {})
+      .unwrap();
+
+    assert_eq!(node.inline_len(), Some(3));
+    assert_eq!(node.table_index(), None);
+
+    let mut payload = node.into_data().await.map_err(|_| {}).unwrap();
+
+    assert_eq!(payload.get::<[u8; 3]>().await.map_err(|_| {}).unwrap(), [0xAA, 0xBB, 0xCC]);
+    assert!(payload.get::<u8>().await.is_err());
+  }
+
+  #[tokio::test]
+  async fn binary_data_in_table_is_an_index() {
+    let version = BymlNodeDiscriminantVersionConfig {
+      version_number: 1,
+      feat_binary_data_table: true,
+    };
+
+    let node = BymlNode::construct(BymlNodeDiscriminants::BinaryData, 2, version, async |_| {
+      Err::<BinaryReader<'static, fileforge::stream::builtin::provider::ProviderStream<Vec<u8>>>, _>(())
+    })
+    .await
+    .map_err(|_| {})
+    .unwrap()
+    .into_binary_data()
+    .map_err(|_| {})
+    .unwrap();
+
+    assert_eq!(node.table_index(), Some(2));
+    assert_eq!(node.inline_len(), None);
+  }
+}
@@ -0,0 +1,161 @@
+use fileforge::{
+  binary_reader::{
+    error::{common::Read, primitive_name_annotation::PrimitiveName, DynamicSubforkError, GetPrimitiveError, SkipError},
+    primitive::numeric::u24,
+    BinaryReader, PrimitiveReader,
+  },
+  error::{ext::annotations::annotated::Annotated, render::builtin::number::formatted_unsigned::FormattedUnsigned},
+  stream::{DynamicPartitionableStream, ReadableStream},
+};
+use fileforge_macros::FileforgeError;
+
+use crate::byml::node::{discriminant::BymlNodeDiscriminantVersionConfig, BymlDynConstructable};
+
+/// A binary data table node (`0xC3`).
+///
+/// Shaped like a string table: a `u24` count, `count + 1` node-relative offsets, then the payloads. Each payload runs up to the next offset.
+pub struct BymlBinaryDataTableNode<'pool, S: ReadableStream<Type = u8>> {
+  count: u32,
+  reader: BinaryReader<'pool, S>,
+}
+
+#[derive(FileforgeError)]
+pub enum BymlBinaryDataTableNodeConstructableError<'pool, S: ReadableStream<Type = u8>> {
+  ReadCountError(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>),
+}
+
+impl<'pool, S: ReadableStream<Type = u8>> BymlDynConstructable<'pool, S> for BymlBinaryDataTableNode<'pool, S> {
+  type Error = BymlBinaryDataTableNodeConstructableError<'pool, S>;
+
+  async fn construct_dyn(mut reader: BinaryReader<'pool, S>, _: BymlNodeDiscriminantVersionConfig) -> Result<Self, Self::Error> {
+    Ok(BymlBinaryDataTableNode {
+      count: reader.get::<u24>().await.map_err(BymlBinaryDataTableNodeConstructableError::ReadCountError)?.into(),
+      reader,
+    })
+  }
+}
+
+#[derive(FileforgeError)]
+pub enum BymlBinaryDataTableNodeIntoDataError<'pool, S: DynamicPartitionableStream<Type = u8>> {
+  #[report(&"Binary data table index out of bounds")]
+  #[flag("Index {index} is out of bounds for {count} entries", index = FormattedUnsigned::new(*index as u128), count = FormattedUnsigned::new(*count as u128))]
+  IndexOutOfBounds {
+    index: u32,
+    count: u32,
+  },
+
+  FailedToSkipToOffset(SkipError<'pool, S::SkipError>),
+  FailedToReadStartOffset(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>),
+  FailedToReadEndOffset(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>),
+  FailedToSkipToData(SkipError<'pool, S::SkipError>),
+
+  #[report(&"Binary data offsets are out of order or point into the offset table")]
+  InvalidRange,
+
+  Partition(#[from] DynamicSubforkError<'pool, S::PartitionError>),
+}
+
+impl<'pool, S: ReadableStream<Type = u8>> BymlBinaryDataTableNode<'pool, S> {
+  pub fn len(&self) -> u32 {
+    self.count
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.count == 0
+  }
+
+  /// The node-relative offset of the first byte after the offset table.
+  fn header_length(&self) -> u64 {
+    4 + (self.count as u64 + 1) * 4
+  }
+}
+
+impl<'pool, S: DynamicPartitionableStream<Type = u8>> BymlBinaryDataTableNode<'pool, S> {
+  /// Bounds the reader to the payload at `index`.
+  pub async fn into_data(mut self, index: u32) -> Result<BinaryReader<'pool, S::PartitionDynamicLeft>, BymlBinaryDataTableNodeIntoDataError<'pool, S>> {
+    if index >= self.count {
+      return Err(BymlBinaryDataTableNodeIntoDataError::IndexOutOfBounds { index, count: self.count });
+    }
+
+    let header_length = self.header_length();
+
+    self.reader.skip(index as u64 * 4).await.map_err(BymlBinaryDataTableNodeIntoDataError::FailedToSkipToOffset)?;
+
+    let start: u32 = self.reader.get().await.map_err(BymlBinaryDataTableNodeIntoDataError::FailedToReadStartOffset)?;
+    let end: u32 = self.reader.get().await.map_err(BymlBinaryDataTableNodeIntoDataError::FailedToReadEndOffset)?;
+
+    // the reader is now just past the end offset, relative to the node's discriminant
+    let position = 4 + (index as u64 + 2) * 4;
+    let length = end.checked_sub(start).ok_or(BymlBinaryDataTableNodeIntoDataError::InvalidRange)?;
+
+    if (start as u64) < header_length {
+      return Err(BymlBinaryDataTableNodeIntoDataError::InvalidRange);
+    }
+
+    self.reader.skip(start as u64 - position).await.map_err(BymlBinaryDataTableNodeIntoDataError::FailedToSkipToData)?;
+
+    Ok(self.reader.partition_dynamic(length as u64, Some("Binary Data")).await?.0)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use fileforge::{
+    binary_reader::{endianness::Endianness, BinaryReader, PrimitiveReader},
+    provider::hint::ReadHint,
+  };
+
+  use crate::byml::node::{discriminant::BymlNodeDiscriminantVersionConfig, BymlAnyConstructable, BymlNode};
+
+  const VERSION: BymlNodeDiscriminantVersionConfig = BymlNodeDiscriminantVersionConfig {
+    version_number: 1,
+    feat_binary_data_table: true,
+  };
+
+  // Two payloads, [0x11, 0x22] and [0x33]
+  fn fixture() -> Vec<u8> {
+    let mut data = vec![0xC3, 0x00, 0x00, 0x02];
+    data.extend(0x10u32.to_be_bytes());
+    data.extend(0x12u32.to_be_bytes());
+    data.extend(0x13u32.to_be_bytes());
+    data.extend([0x11, 0x22, 0x33]);
+    data
+  }
+
+  #[tokio::test]
+  async fn binary_data_table_into_data() {
+    let mut data = fixture();
+    let reader = BinaryReader::new_from_provider(&mut data, Endianness::BigEndian, ReadHint::new());
+
+    let table = BymlNode::construct_dyn(0, VERSION, async |_| Ok::<_, ()>(reader))
+      .await
+      .map_err(|_| {})
+      .unwrap()
+      .into_binary_data_table()
+      .map_err(|_| {})
+      .unwrap();
+
+    assert_eq!(table.len(), 2);
+
+    let mut payload = table.into_data(1).await.map_err(|_| {}).unwrap();
+
+    assert_eq!(payload.get::<u8>().await.map_err(|_| {}).unwrap(), 0x33);
+    assert!(payload.get::<u8>().await.is_err());
+  }
+
+  #[tokio::test]
+  async fn binary_data_table_rejects_out_of_bounds_index() {
+    let mut data = fixture();
+    let reader = BinaryReader::new_from_provider(&mut data, Endianness::BigEndian, ReadHint::new());
+
+    let table = BymlNode::construct_dyn(0, VERSION, async |_| Ok::<_, ()>(reader))
+      .await
+      .map_err(|_| {})
+      .unwrap()
+      .into_binary_data_table()
+      .map_err(|_| {})
+      .unwrap();
+
+    assert!(table.into_data(2).await.is_err());
+  }
+}
@@ -0,0 +1,67 @@
+use fileforge::{
+  binary_reader::{
+    error::{common::Read, primitive_name_annotation::PrimitiveName, DynamicSubforkError, GetPrimitiveError},
+    BinaryReader, PrimitiveReader,
+  },
+  error::ext::annotations::annotated::Annotated,
+  stream::{DynamicPartitionableStream, ReadableStream},
+};
+use fileforge_macros::FileforgeError;
+
+use crate::byml::node::{BymlConstructable, BymlOutOfLineError};
+
+/// A binary data node with an extra parameter (`0xA2`).
+///
+/// Stored out of line as a `u32` length and a `u32` parameter (typically the payload's alignment), followed by the payload.
+pub struct BymlBinaryDataWithParameterNode<'pool, S: ReadableStream<Type = u8>> {
+  length: u32,
+  parameter: u32,
+  reader: BinaryReader<'pool, S>,
+}
+
+#[derive(FileforgeError)]
+pub enum BymlBinaryDataWithParameterNodeConstructableError<'pool, S: ReadableStream<Type = u8>> {
+  ReadLengthError(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>),
+  ReadParameterError(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>),
+}
+
+impl<'pool, S: ReadableStream<Type = u8>, E> BymlConstructable<'pool, S, E> for BymlBinaryDataWithParameterNode<'pool, S> {
+  type Error = BymlOutOfLineError<E, BymlBinaryDataWithParameterNodeConstructableError<'pool, S>>;
+
+  async fn construct<F: AsyncFnOnce(u64) -> Result<BinaryReader<'pool, S>, E>>(value: u32, get_reader: F) -> Result<Self, Self::Error> {
+    let mut reader = get_reader(value as u64).await.map_err(BymlOutOfLineError::ReaderAcquire)?;
+
+    Ok(Self {
+      length: reader
+        .get()
+        .await
+        .map_err(|e| BymlOutOfLineError::Item(BymlBinaryDataWithParameterNodeConstructableError::ReadLengthError(e)))?,
+      parameter: reader
+        .get()
+        .await
+        .map_err(|e| BymlOutOfLineError::Item(BymlBinaryDataWithParameterNodeConstructableError::ReadParameterError(e)))?,
+      reader,
+    })
+  }
+}
+
+impl<'pool, S: ReadableStream<Type = u8>> BymlBinaryDataWithParameterNode<'pool, S> {
+  pub fn len(&self) -> u32 {
+    self.length
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.length == 0
+  }
+
+  pub fn parameter(&self) -> u32 {
+    self.parameter
+  }
+}
+
+impl<'pool, S: DynamicPartitionableStream<Type = u8>> BymlBinaryDataWithParameterNode<'pool, S> {
+  /// Bounds the reader to the payload.
+  pub async fn into_data(self) -> Result<BinaryReader<'pool, S::PartitionDynamicLeft>, DynamicSubforkError<'pool, S::PartitionError>> {
+    Ok(self.reader.partition_dynamic(self.length as u64, Some("Binary Data")).await?.0)
+  }
+}
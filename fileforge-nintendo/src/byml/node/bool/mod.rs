@@ -8,6 +8,12 @@ pub struct BymlBoolNode {
   value: bool,
 }
 
+impl BymlBoolNode {
+  pub fn value(&self) -> bool {
+    self.value
+  }
+}
+
 impl<'pool, S: ReadableStream<Type = u8>, E> BymlConstructable<'pool, S, E> for BymlBoolNode {
   type Error = Infallible;
 
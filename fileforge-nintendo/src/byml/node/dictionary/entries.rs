@@ -0,0 +1,72 @@
+use fileforge::{
+  binary_reader::{error::SkipError, BinaryReader},
+  stream::{
+    error::{stream_exhausted::StreamExhaustedError, stream_read::StreamReadError, stream_seek_out_of_bounds::StreamSeekOutOfBoundsError, stream_skip::StreamSkipError},
+    ReadableStream,
+  },
+};
+
+use crate::byml::node::{
+  dictionary::entry::{BymlDictionaryEntry, BymlDictionaryEntryReadError, BYML_DICTIONARY_ENTRY_SIZE},
+  discriminant::BymlNodeDiscriminantVersionConfig,
+  reference::BymlNodeReference,
+};
+
+/// A stream over the entries of a [`BymlDictionaryNode`](super::BymlDictionaryNode), in key table order.
+pub struct BymlDictionaryEntries<'pool, S: ReadableStream<Type = u8>> {
+  pub(super) index: u32,
+  pub(super) count: u32,
+  pub(super) version: BymlNodeDiscriminantVersionConfig,
+  pub(super) reader: BinaryReader<'pool, S>,
+}
+
+impl<'pool, S: ReadableStream<Type = u8>> BymlDictionaryEntries<'pool, S> {
+  /// Descends into `entry`, which must have been read from this stream.
+  pub fn into_reference(self, entry: BymlDictionaryEntry) -> BymlNodeReference<'pool, S> {
+    BymlNodeReference::new(entry.discriminant, entry.value, self.version, self.reader)
+  }
+}
+
+impl<'pool, S: ReadableStream<Type = u8>> ReadableStream for BymlDictionaryEntries<'pool, S> {
+  type Type = BymlDictionaryEntry;
+
+  type ReadError = BymlDictionaryEntryReadError<'pool, S>;
+  type SkipError = S::SkipError;
+
+  fn len(&self) -> Option<u64> {
+    Some(self.count as u64)
+  }
+
+  fn offset(&self) -> u64 {
+    self.index as u64
+  }
+
+  async fn skip(&mut self, size: u64) -> Result<(), StreamSkipError<Self::SkipError>> {
+    let seek_point = StreamSkipError::assert_relative_forwards(self.count as u64, self.offset(), size)?;
+
+    self.reader.skip(size * BYML_DICTIONARY_ENTRY_SIZE).await.map_err(|e| match e {
+      SkipError::User(u) => StreamSkipError::User(u),
+      SkipError::OutOfBounds(_) => StreamSkipError::OutOfBounds(StreamSeekOutOfBoundsError {
+        stream_length: self.count as u64,
+        seek_point,
+      }),
+    })?;
+
+    self.index = seek_point as u32;
+
+    Ok(())
+  }
+
+  async fn read<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[Self::Type; SIZE]) -> V) -> Result<V, StreamReadError<Self::ReadError>> {
+    StreamExhaustedError::assert(self.count as u64, self.offset(), SIZE as u64)?;
+
+    let mut dest = heapless::Vec::<BymlDictionaryEntry, SIZE>::new();
+
+    while !dest.is_full() {
+      dest.push(self.reader.read_with(self.version).await?).map_err(|_| {}).unwrap();
+      self.index += 1;
+    }
+
+    Ok(reader(&dest.into_array::<SIZE>().map_err(|_| {}).unwrap()).await)
+  }
+}
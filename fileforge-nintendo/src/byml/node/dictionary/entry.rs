@@ -0,0 +1,47 @@
+use fileforge::{
+  binary_reader::{
+    error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError},
+    primitive::numeric::u24,
+    readable::Readable,
+    BinaryReader, PrimitiveReader,
+  },
+  error::ext::annotations::annotated::Annotated,
+  stream::{error::user_read::UserReadError, ReadableStream},
+};
+use fileforge_macros::FileforgeError;
+
+use crate::byml::node::{
+  discriminant::{BymlNodeDiscriminantVersionConfig, BymlNodeDiscriminantsReadError},
+  BymlNodeDiscriminants,
+};
+
+pub const BYML_DICTIONARY_ENTRY_SIZE: u64 = 0x8;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BymlDictionaryEntry {
+  pub key_index: u32,
+  pub discriminant: BymlNodeDiscriminants,
+  pub value: u32,
+}
+
+#[derive(FileforgeError)]
+pub enum BymlDictionaryEntryReadError<'pool, S: ReadableStream<Type = u8>> {
+  KeyIndexReadError(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>),
+  TypeReadError(#[from] BymlNodeDiscriminantsReadError<'pool, S>),
+  ValueReadError(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>),
+}
+
+impl<'pool, S: ReadableStream<Type = u8>> UserReadError for BymlDictionaryEntryReadError<'pool, S> {}
+
+impl<'pool, S: ReadableStream<Type = u8>> Readable<'pool, S> for BymlDictionaryEntry {
+  type Argument = BymlNodeDiscriminantVersionConfig;
+  type Error = BymlDictionaryEntryReadError<'pool, S>;
+
+  async fn read(reader: &mut BinaryReader<'pool, S>, version: Self::Argument) -> Result<Self, Self::Error> {
+    Ok(BymlDictionaryEntry {
+      key_index: reader.get::<u24>().await.map_err(BymlDictionaryEntryReadError::KeyIndexReadError)?.into(),
+      discriminant: reader.read_with(version).await?,
+      value: reader.get().await.map_err(BymlDictionaryEntryReadError::ValueReadError)?,
+    })
+  }
+}
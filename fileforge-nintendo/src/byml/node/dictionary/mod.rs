@@ -0,0 +1,240 @@
+pub mod entries;
+pub mod entry;
+
+use fileforge::{
+  binary_reader::{
+    error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError, SkipError},
+    primitive::numeric::u24,
+    BinaryReader, PrimitiveReader,
+  },
+  error::{ext::annotations::annotated::Annotated, render::builtin::number::formatted_unsigned::FormattedUnsigned},
+  stream::ReadableStream,
+};
+use fileforge_macros::FileforgeError;
+
+use crate::byml::node::{
+  dictionary::{
+    entries::BymlDictionaryEntries,
+    entry::{BymlDictionaryEntry, BymlDictionaryEntryReadError, BYML_DICTIONARY_ENTRY_SIZE},
+  },
+  discriminant::BymlNodeDiscriminantVersionConfig,
+  reference::BymlNodeReference,
+  string_table::{BymlStringTableNode, BymlStringTableNodeScanError},
+  BymlDynConstructable,
+};
+
+/// A dictionary node (`0xC1`).
+///
+/// Laid out as a `u24` entry count, followed by entries sorted by their key's index in the key table.
+pub struct BymlDictionaryNode<'pool, S: ReadableStream<Type = u8>> {
+  count: u32,
+  version: BymlNodeDiscriminantVersionConfig,
+  reader: BinaryReader<'pool, S>,
+}
+
+#[derive(FileforgeError)]
+pub enum BymlDictionaryNodeConstructableError<'pool, S: ReadableStream<Type = u8>> {
+  ReadCountError(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>),
+}
+
+impl<'pool, S: ReadableStream<Type = u8>> BymlDynConstructable<'pool, S> for BymlDictionaryNode<'pool, S> {
+  type Error = BymlDictionaryNodeConstructableError<'pool, S>;
+
+  async fn construct_dyn(mut reader: BinaryReader<'pool, S>, version: BymlNodeDiscriminantVersionConfig) -> Result<Self, Self::Error> {
+    Ok(BymlDictionaryNode {
+      count: reader.get::<u24>().await.map_err(BymlDictionaryNodeConstructableError::ReadCountError)?.into(),
+      version,
+      reader,
+    })
+  }
+}
+
+#[derive(FileforgeError)]
+pub enum BymlDictionaryNodeIntoEntryError<'pool, S: ReadableStream<Type = u8>> {
+  #[report(&"Dictionary index out of bounds")]
+  #[flag("Index {index} is out of bounds for {count} entries", index = FormattedUnsigned::new(*index as u128), count = FormattedUnsigned::new(*count as u128))]
+  IndexOutOfBounds {
+    index: u32,
+    count: u32,
+  },
+
+  FailedToSkipToEntry(#[from] SkipError<'pool, S::SkipError>),
+  FailedToReadEntry(#[from] BymlDictionaryEntryReadError<'pool, S>),
+}
+
+#[derive(FileforgeError)]
+pub enum BymlDictionaryNodeIntoValueError<'pool, S: ReadableStream<Type = u8>, K: ReadableStream<Type = u8>> {
+  FailedToResolveKey(#[from] BymlStringTableNodeScanError<'pool, K>),
+  FailedToReadEntry(#[from] BymlDictionaryEntryReadError<'pool, S>),
+}
+
+impl<'pool, S: ReadableStream<Type = u8>> BymlDictionaryNode<'pool, S> {
+  pub fn len(&self) -> u32 {
+    self.count
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.count == 0
+  }
+
+  pub fn into_entries(self) -> BymlDictionaryEntries<'pool, S> {
+    BymlDictionaryEntries {
+      index: 0,
+      count: self.count,
+      version: self.version,
+      reader: self.reader,
+    }
+  }
+
+  /// Descends into the entry at `index`, in key table order.
+  pub async fn into_entry(mut self, index: u32) -> Result<(u32, BymlNodeReference<'pool, S>), BymlDictionaryNodeIntoEntryError<'pool, S>> {
+    if index >= self.count {
+      return Err(BymlDictionaryNodeIntoEntryError::IndexOutOfBounds { index, count: self.count });
+    }
+
+    self.reader.skip(index as u64 * BYML_DICTIONARY_ENTRY_SIZE).await?;
+
+    let entry: BymlDictionaryEntry = self.reader.read_with(self.version).await?;
+
+    Ok((entry.key_index, BymlNodeReference::new(entry.discriminant, entry.value, self.version, self.reader)))
+  }
+
+  /// Descends into the value stored under the key at `key_index` in the key table, if there is one.
+  pub async fn into_value_at_key_index(mut self, key_index: u32) -> Result<Option<BymlNodeReference<'pool, S>>, BymlDictionaryEntryReadError<'pool, S>> {
+    for _ in 0..self.count {
+      let entry: BymlDictionaryEntry = self.reader.read_with(self.version).await?;
+
+      // Entries are sorted by key index, so we can stop as soon as we've passed it.
+      if entry.key_index > key_index {
+        break;
+      }
+
+      if entry.key_index == key_index {
+        return Ok(Some(BymlNodeReference::new(entry.discriminant, entry.value, self.version, self.reader)));
+      }
+    }
+
+    Ok(None)
+  }
+
+  /// Descends into the value stored under `key`, resolving it through the document's key table.
+  pub async fn into_value<K: ReadableStream<Type = u8>>(
+    self,
+    key: &[u8],
+    key_table: BymlStringTableNode<'pool, K>,
+  ) -> Result<Option<BymlNodeReference<'pool, S>>, BymlDictionaryNodeIntoValueError<'pool, S, K>> {
+    match key_table.into_index_of(key).await? {
+      Some(key_index) => Ok(self.into_value_at_key_index(key_index).await?),
+      None => Ok(None),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use fileforge::{
+    binary_reader::{endianness::Endianness, BinaryReader},
+    provider::hint::ReadHint,
+    stream::{builtin::provider::ProviderStream, extensions::readable::ReadableStreamExt},
+  };
+
+  use crate::byml::node::{discriminant::BymlNodeDiscriminantVersionConfig, BymlAnyConstructable, BymlNode, BymlNodeDiscriminants};
+
+  const VERSION: BymlNodeDiscriminantVersionConfig = BymlNodeDiscriminantVersionConfig {
+    version_number: 3,
+    feat_binary_data_table: false,
+  };
+
+  // Key table ["Name", "Scale", "Translate"]
+  fn key_table() -> Vec<u8> {
+    let mut data = vec![0xC2, 0x00, 0x00, 0x03];
+    data.extend(0x14u32.to_be_bytes());
+    data.extend(0x19u32.to_be_bytes());
+    data.extend(0x1Fu32.to_be_bytes());
+    data.extend(0x29u32.to_be_bytes());
+    data.extend(b"Name\0Scale\0Translate\0");
+    data
+  }
+
+  // { Name: 1, Translate: [] } with the nested array stored at 0x14
+  fn dictionary(endianness: Endianness) -> Vec<u8> {
+    let u32_bytes = |v: u32| match endianness {
+      Endianness::BigEndian => v.to_be_bytes(),
+      Endianness::LittleEndian => v.to_le_bytes(),
+    };
+
+    let mut data = match endianness {
+      Endianness::BigEndian => vec![0xC1, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0xA0],
+      Endianness::LittleEndian => vec![0xC1, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xA0],
+    };
+    data.extend(u32_bytes(1));
+    data.extend(match endianness {
+      Endianness::BigEndian => [0x00, 0x00, 0x02, 0xC0],
+      Endianness::LittleEndian => [0x02, 0x00, 0x00, 0xC0],
+    });
+    data.extend(u32_bytes(0x14));
+    data.extend([0xC0, 0x00, 0x00, 0x00]);
+    data
+  }
+
+  async fn node(data: Vec<u8>, endianness: Endianness) -> BymlNode<'static, ProviderStream<Vec<u8>>> {
+    let reader = BinaryReader::new_from_provider(data, endianness, ReadHint::new());
+
+    BymlNode::construct_dyn(0, VERSION, async |_| Ok::<_, ()>(reader)).await.map_err(|_| {}).unwrap()
+  }
+
+  #[tokio::test]
+  async fn dictionary_entries() {
+    let dictionary = node(dictionary(Endianness::BigEndian), Endianness::BigEndian).await.into_dictionary().map_err(|_| {}).unwrap();
+
+    assert_eq!(dictionary.len(), 2);
+
+    let mut entries = dictionary.into_entries();
+    let first = entries.next().await.map_err(|_| {}).unwrap();
+    let second = entries.next().await.map_err(|_| {}).unwrap();
+
+    assert_eq!((first.key_index, first.discriminant, first.value), (0, BymlNodeDiscriminants::String, 1));
+    assert_eq!((second.key_index, second.discriminant, second.value), (2, BymlNodeDiscriminants::Array, 0x14));
+    assert!(entries.next().await.is_err());
+  }
+
+  #[tokio::test]
+  async fn dictionary_little_endian_entries() {
+    let dictionary = node(dictionary(Endianness::LittleEndian), Endianness::LittleEndian).await.into_dictionary().map_err(|_| {}).unwrap();
+    let (key_index, reference) = dictionary.into_entry(1).await.map_err(|_| {}).unwrap();
+
+    assert_eq!(key_index, 2);
+    assert_eq!(reference.discriminant(), BymlNodeDiscriminants::Array);
+    assert_eq!(reference.value(), 0x14);
+  }
+
+  #[tokio::test]
+  async fn dictionary_into_value() {
+    let key_table = node(key_table(), Endianness::BigEndian).await.into_string_table().map_err(|_| {}).unwrap();
+    let dictionary = node(dictionary(Endianness::BigEndian), Endianness::BigEndian).await.into_dictionary().map_err(|_| {}).unwrap();
+
+    let child = dictionary
+      .into_value(b"Translate", key_table)
+      .await
+      .map_err(|_| {})
+      .unwrap()
+      .expect("Translate is present")
+      .into_node()
+      .await
+      .map_err(|_| {})
+      .unwrap()
+      .into_array()
+      .map_err(|_| {})
+      .unwrap();
+
+    assert!(child.is_empty());
+  }
+
+  #[tokio::test]
+  async fn dictionary_into_value_missing() {
+    let key_table = node(key_table(), Endianness::BigEndian).await.into_string_table().map_err(|_| {}).unwrap();
+    let dictionary = node(dictionary(Endianness::BigEndian), Endianness::BigEndian).await.into_dictionary().map_err(|_| {}).unwrap();
+
+    assert!(dictionary.into_value(b"Scale", key_table).await.map_err(|_| {}).unwrap().is_none());
+  }
+}
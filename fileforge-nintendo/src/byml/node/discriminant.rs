@@ -33,6 +33,26 @@ impl BymlNodeDiscriminants {
     })
   }
 
+  pub(crate) fn id(self) -> u8 {
+    match self {
+      BymlNodeDiscriminants::String => 0xA0,
+      BymlNodeDiscriminants::BinaryData => 0xA1,
+      BymlNodeDiscriminants::BinaryDataWithParameter => 0xA2,
+      BymlNodeDiscriminants::Array => 0xC0,
+      BymlNodeDiscriminants::Dictionary => 0xC1,
+      BymlNodeDiscriminants::StringTable => 0xC2,
+      BymlNodeDiscriminants::BinaryDataTable => 0xC3,
+      BymlNodeDiscriminants::Bool => 0xD0,
+      BymlNodeDiscriminants::Integer32 => 0xD1,
+      BymlNodeDiscriminants::Float32 => 0xD2,
+      BymlNodeDiscriminants::UnsignedInteger32 => 0xD3,
+      BymlNodeDiscriminants::Integer64 => 0xD4,
+      BymlNodeDiscriminants::UnsignedInteger64 => 0xD5,
+      BymlNodeDiscriminants::Float64 => 0xD6,
+      BymlNodeDiscriminants::Null => 0xFF,
+    }
+  }
+
   fn min_version(&self) -> Option<u16> {
     Some(match self {
       Self::BinaryDataTable => return None,
@@ -58,7 +78,7 @@ impl BymlNodeDiscriminants {
     })
   }
 
-  fn filter_version(self, version: BymlNodeDiscriminantVersionConfig) -> Option<Self> {
+  pub(crate) fn filter_version(self, version: BymlNodeDiscriminantVersionConfig) -> Option<Self> {
     if version.feat_binary_data_table && matches!(self, Self::BinaryData | Self::BinaryDataTable) {
       return Some(self);
     }
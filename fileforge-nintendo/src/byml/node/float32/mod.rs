@@ -8,6 +8,12 @@ pub struct BymlFloat32Node {
   value: f32,
 }
 
+impl BymlFloat32Node {
+  pub fn value(&self) -> f32 {
+    self.value
+  }
+}
+
 impl<'pool, S: ReadableStream<Type = u8>, E> BymlConstructable<'pool, S, E> for BymlFloat32Node {
   type Error = Infallible;
 
@@ -8,6 +8,12 @@ pub struct BymlInteger32Node {
   value: i32,
 }
 
+impl BymlInteger32Node {
+  pub fn value(&self) -> i32 {
+    self.value
+  }
+}
+
 impl<'pool, S: ReadableStream<Type = u8>, E> BymlConstructable<'pool, S, E> for BymlInteger32Node {
   type Error = Infallible;
 
@@ -0,0 +1,83 @@
+use fileforge::{
+  binary_reader::{
+    error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError},
+    BinaryReader, PrimitiveReader,
+  },
+  error::ext::annotations::annotated::Annotated,
+  stream::ReadableStream,
+};
+
+use crate::byml::node::{BymlConstructable, BymlOutOfLineError};
+
+/// A signed 64-bit integer node (`0xD4`). The container holds the offset of the value rather than the value itself.
+pub struct BymlInteger64Node {
+  value: i64,
+}
+
+impl BymlInteger64Node {
+  pub fn value(&self) -> i64 {
+    self.value
+  }
+}
+
+impl<'pool, S: ReadableStream<Type = u8>, E> BymlConstructable<'pool, S, E> for BymlInteger64Node {
+  type Error = BymlOutOfLineError<E, Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>>;
+
+  async fn construct<F: AsyncFnOnce(u64) -> Result<BinaryReader<'pool, S>, E>>(value: u32, get_reader: F) -> Result<Self, Self::Error> {
+    let mut reader = get_reader(value as u64).await.map_err(BymlOutOfLineError::ReaderAcquire)?;
+
+    Ok(Self {
+      value: reader.get().await.map_err(BymlOutOfLineError::Item)?,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use fileforge::{
+    binary_reader::{endianness::Endianness, BinaryReader},
+    provider::hint::ReadHint,
+  };
+
+  use crate::byml::node::{discriminant::BymlNodeDiscriminantVersionConfig, BymlAnyConstructable, BymlNode, BymlNodeDiscriminants};
+
+  const VERSION: BymlNodeDiscriminantVersionConfig = BymlNodeDiscriminantVersionConfig {
+    version_number: 3,
+    feat_binary_data_table: false,
+  };
+
+  async fn construct(discriminant: BymlNodeDiscriminants, value: [u8; 8]) -> BymlNode<'static, fileforge::stream::builtin::provider::ProviderStream<Vec<u8>>> {
+    let mut data = vec![0xFF; 4];
+    data.extend(value);
+
+    BymlNode::construct(discriminant, 4, VERSION, async |offset| {
+      let mut reader = BinaryReader::new_from_provider(data, Endianness::BigEndian, ReadHint::new());
+      reader.skip(offset).await.map_err(|_| {})?;
+      Ok::<_, ()>(reader)
+    })
+    .await
+    .map_err(|_| {})
+    .unwrap()
+  }
+
+  #[tokio::test]
+  async fn integer64_reads_out_of_line() {
+    let node = construct(BymlNodeDiscriminants::Integer64, (-2i64).to_be_bytes()).await;
+
+    assert_eq!(node.into_integer64().map_err(|_| {}).unwrap().value(), -2);
+  }
+
+  #[tokio::test]
+  async fn unsigned_integer64_reads_out_of_line() {
+    let node = construct(BymlNodeDiscriminants::UnsignedInteger64, u64::MAX.to_be_bytes()).await;
+
+    assert_eq!(node.into_unsigned_integer64().map_err(|_| {}).unwrap().value(), u64::MAX);
+  }
+
+  #[tokio::test]
+  async fn float64_reads_out_of_line() {
+    let node = construct(BymlNodeDiscriminants::Float64, 0.25f64.to_be_bytes()).await;
+
+    assert_eq!(node.into_float64().map_err(|_| {}).unwrap().value(), 0.25);
+  }
+}
@@ -1,46 +1,72 @@
+pub mod array;
+pub mod binary_data;
+pub mod binary_data_table;
+pub mod binary_data_with_parameter;
 pub mod bool;
+pub mod dictionary;
 pub mod discriminant;
 pub mod float32;
+pub mod float64;
 pub mod integer32;
+pub mod integer64;
 pub mod null;
+pub mod path;
+pub mod reference;
 pub mod string;
 pub mod string_table;
 pub mod unsigned_integer32;
+pub mod unsigned_integer64;
 
 use core::future::Future;
 
 use enum_as_inner::EnumAsInner;
-use fileforge::{binary_reader::BinaryReader, stream::ReadableStream, ResultIgnoreExt};
+use fileforge::{
+  binary_reader::{
+    error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError},
+    BinaryReader,
+  },
+  error::ext::annotations::annotated::Annotated,
+  stream::ReadableStream,
+  ResultIgnoreExt,
+};
 use fileforge_macros::FileforgeError;
 use strum::EnumDiscriminants;
 
 use crate::byml::node::{
+  array::{BymlArrayNode, BymlArrayNodeConstructableError},
+  binary_data::{BymlBinaryDataNode, BymlBinaryDataNodeConstructableError},
+  binary_data_table::{BymlBinaryDataTableNode, BymlBinaryDataTableNodeConstructableError},
+  binary_data_with_parameter::{BymlBinaryDataWithParameterNode, BymlBinaryDataWithParameterNodeConstructableError},
   bool::BymlBoolNode,
+  dictionary::{BymlDictionaryNode, BymlDictionaryNodeConstructableError},
   discriminant::{BymlNodeDiscriminantVersionConfig, BymlNodeDiscriminantsReadError},
   float32::BymlFloat32Node,
+  float64::BymlFloat64Node,
   integer32::BymlInteger32Node,
+  integer64::BymlInteger64Node,
   null::BymlNullNode,
   string::BymlStringNode,
   string_table::{BymlStringTableNode, BymlStringTableNodeConstructableError},
   unsigned_integer32::BymlUnsignedInteger32Node,
+  unsigned_integer64::BymlUnsignedInteger64Node,
 };
 
 #[derive(EnumAsInner, EnumDiscriminants)]
 pub enum BymlNode<'pool, S: ReadableStream<Type = u8>> {
   String(BymlStringNode),
-  BinaryData(()),
-  BinaryDataWithParameter(()),
-  Array(()),
-  Dictionary(()),
+  BinaryData(BymlBinaryDataNode<'pool, S>),
+  BinaryDataWithParameter(BymlBinaryDataWithParameterNode<'pool, S>),
+  Array(BymlArrayNode<'pool, S>),
+  Dictionary(BymlDictionaryNode<'pool, S>),
   StringTable(BymlStringTableNode<'pool, S>),
-  BinaryDataTable(()),
+  BinaryDataTable(BymlBinaryDataTableNode<'pool, S>),
   Bool(BymlBoolNode),
   Integer32(BymlInteger32Node),
   Float32(BymlFloat32Node),
   UnsignedInteger32(BymlUnsignedInteger32Node),
-  Integer64(()),
-  UnsignedInteger64(()),
-  Float64(()),
+  Integer64(BymlInteger64Node),
+  UnsignedInteger64(BymlUnsignedInteger64Node),
+  Float64(BymlFloat64Node),
   Null(BymlNullNode),
 }
 
@@ -53,7 +79,7 @@ pub(super) trait BymlConstructable<'pool, S: ReadableStream<Type = u8>, E>: Size
 pub(super) trait BymlDynConstructable<'pool, S: ReadableStream<Type = u8>>: Sized {
   type Error;
 
-  fn construct_dyn(reader: BinaryReader<'pool, S>) -> impl Future<Output = Result<Self, Self::Error>>;
+  fn construct_dyn(reader: BinaryReader<'pool, S>, version: BymlNodeDiscriminantVersionConfig) -> impl Future<Output = Result<Self, Self::Error>>;
 }
 
 async fn construct_as_dyn<'pool, T: BymlDynConstructable<'pool, S>, S: ReadableStream<Type = u8>, E, F: AsyncFnOnce(u64) -> Result<BinaryReader<'pool, S>, E>>(
@@ -66,7 +92,7 @@ async fn construct_as_dyn<'pool, T: BymlDynConstructable<'pool, S>, S: ReadableS
   let in_place_discriminant = reader.read_with(version).await.map_err(BymlDynConstructableError::ReadType)?;
 
   (discriminant == in_place_discriminant)
-    .then(|| T::construct_dyn(reader))
+    .then(|| T::construct_dyn(reader, version))
     .ok_or(BymlDynConstructableError::InvalidDynConstructable(in_place_discriminant))?
     .await
     .map_err(BymlDynConstructableError::Item)
@@ -90,6 +116,20 @@ impl<'pool, S: ReadableStream<Type = u8>, E, I> BymlDynConstructableError<'pool,
   }
 }
 
+pub(super) enum BymlOutOfLineError<E, I> {
+  ReaderAcquire(E),
+  Item(I),
+}
+
+impl<E, I> BymlOutOfLineError<E, I> {
+  pub fn map_item<'pool, S: ReadableStream<Type = u8>>(self, into: impl FnOnce(I) -> BymlConstructionError<'pool, E, S>) -> BymlConstructionError<'pool, E, S> {
+    match self {
+      Self::ReaderAcquire(e) => BymlConstructionError::ReaderAcquire(e),
+      Self::Item(i) => into(i),
+    }
+  }
+}
+
 pub trait BymlAnyConstructable<'pool, S: ReadableStream<Type = u8>, E>: Sized {
   type Error;
   type DynError;
@@ -116,6 +156,12 @@ pub enum BymlConstructionError<'pool, E, S: ReadableStream<Type = u8>> {
   #[report(&"Invalid Dyn Constructable :(")]
   InvalidDynConstructable(BymlNodeDiscriminants),
 
+  ReadOutOfLineValue(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>),
+  BinaryData(#[from] BymlBinaryDataNodeConstructableError<'pool, S>),
+  BinaryDataWithParameter(#[from] BymlBinaryDataWithParameterNodeConstructableError<'pool, S>),
+  BinaryDataTable(#[from] BymlBinaryDataTableNodeConstructableError<'pool, S>),
+  Array(#[from] BymlArrayNodeConstructableError<'pool, S>),
+  Dictionary(#[from] BymlDictionaryNodeConstructableError<'pool, S>),
   StringTable(#[from] BymlStringTableNodeConstructableError<'pool, S>),
 }
 
@@ -138,20 +184,43 @@ impl<'pool, S: ReadableStream<Type = u8>, E> BymlAnyConstructable<'pool, S, E> f
       BymlNodeDiscriminants::UnsignedInteger32 => BymlNode::UnsignedInteger32(BymlUnsignedInteger32Node::construct(value, get_reader).await.ignore()),
       BymlNodeDiscriminants::Null => BymlNode::Null(BymlNullNode::construct(value, get_reader).await.ignore()),
 
+      // Out Of Line
+      BymlNodeDiscriminants::Integer64 => BymlNode::Integer64(
+        BymlInteger64Node::construct(value, get_reader)
+          .await
+          .map_err(|e| e.map_item(BymlConstructionError::ReadOutOfLineValue))?,
+      ),
+      BymlNodeDiscriminants::UnsignedInteger64 => BymlNode::UnsignedInteger64(
+        BymlUnsignedInteger64Node::construct(value, get_reader)
+          .await
+          .map_err(|e| e.map_item(BymlConstructionError::ReadOutOfLineValue))?,
+      ),
+      BymlNodeDiscriminants::Float64 => BymlNode::Float64(BymlFloat64Node::construct(value, get_reader).await.map_err(|e| e.map_item(BymlConstructionError::ReadOutOfLineValue))?),
+      BymlNodeDiscriminants::BinaryData if version.feat_binary_data_table => BymlNode::BinaryData(BymlBinaryDataNode::table(value)),
+      BymlNodeDiscriminants::BinaryData => BymlNode::BinaryData(BymlBinaryDataNode::construct(value, get_reader).await.map_err(|e| e.map_item(BymlConstructionError::BinaryData))?),
+      BymlNodeDiscriminants::BinaryDataWithParameter => BymlNode::BinaryDataWithParameter(
+        BymlBinaryDataWithParameterNode::construct(value, get_reader)
+          .await
+          .map_err(|e| e.map_item(BymlConstructionError::BinaryDataWithParameter))?,
+      ),
+
       // Non-Trivial
-      BymlNodeDiscriminants::BinaryData => BymlNode::BinaryData(todo!()),
-      BymlNodeDiscriminants::BinaryDataWithParameter => BymlNode::BinaryDataWithParameter(todo!()),
-      BymlNodeDiscriminants::Array => BymlNode::Array(todo!()),
-      BymlNodeDiscriminants::Dictionary => BymlNode::Dictionary(todo!()),
+      BymlNodeDiscriminants::Array => BymlNode::Array(construct_as_dyn(discriminant, value, version, get_reader).await.map_err(|e| e.map_item(BymlConstructionError::Array))?),
+      BymlNodeDiscriminants::Dictionary => BymlNode::Dictionary(
+        construct_as_dyn(discriminant, value, version, get_reader)
+          .await
+          .map_err(|e| e.map_item(BymlConstructionError::Dictionary))?,
+      ),
       BymlNodeDiscriminants::StringTable => BymlNode::StringTable(
         construct_as_dyn(discriminant, value, version, get_reader)
           .await
           .map_err(|e| e.map_item(BymlConstructionError::StringTable))?,
       ),
-      BymlNodeDiscriminants::BinaryDataTable => BymlNode::BinaryDataTable(todo!()),
-      BymlNodeDiscriminants::Integer64 => BymlNode::Integer64(todo!()),
-      BymlNodeDiscriminants::UnsignedInteger64 => BymlNode::UnsignedInteger64(todo!()),
-      BymlNodeDiscriminants::Float64 => BymlNode::Float64(todo!()),
+      BymlNodeDiscriminants::BinaryDataTable => BymlNode::BinaryDataTable(
+        construct_as_dyn(discriminant, value, version, get_reader)
+          .await
+          .map_err(|e| e.map_item(BymlConstructionError::BinaryDataTable))?,
+      ),
     })
   }
 
@@ -167,16 +236,18 @@ impl<'pool, S: ReadableStream<Type = u8>, E> BymlAnyConstructable<'pool, S, E> f
       BymlNodeDiscriminants::UnsignedInteger32 => return Err(BymlConstructionError::InvalidDynConstructable(BymlNodeDiscriminants::UnsignedInteger32)),
       BymlNodeDiscriminants::Null => return Err(BymlConstructionError::InvalidDynConstructable(BymlNodeDiscriminants::Null)),
 
+      // Out Of Line
+      BymlNodeDiscriminants::Integer64 => return Err(BymlConstructionError::InvalidDynConstructable(BymlNodeDiscriminants::Integer64)),
+      BymlNodeDiscriminants::UnsignedInteger64 => return Err(BymlConstructionError::InvalidDynConstructable(BymlNodeDiscriminants::UnsignedInteger64)),
+      BymlNodeDiscriminants::Float64 => return Err(BymlConstructionError::InvalidDynConstructable(BymlNodeDiscriminants::Float64)),
+      BymlNodeDiscriminants::BinaryData => return Err(BymlConstructionError::InvalidDynConstructable(BymlNodeDiscriminants::BinaryData)),
+      BymlNodeDiscriminants::BinaryDataWithParameter => return Err(BymlConstructionError::InvalidDynConstructable(BymlNodeDiscriminants::BinaryDataWithParameter)),
+
       // Non-Trivial
-      BymlNodeDiscriminants::BinaryData => BymlNode::BinaryData(todo!()),
-      BymlNodeDiscriminants::BinaryDataWithParameter => BymlNode::BinaryDataWithParameter(todo!()),
-      BymlNodeDiscriminants::Array => BymlNode::Array(todo!()),
-      BymlNodeDiscriminants::Dictionary => BymlNode::Dictionary(todo!()),
-      BymlNodeDiscriminants::StringTable => BymlNode::StringTable(BymlStringTableNode::construct_dyn(reader).await.map_err(BymlConstructionError::StringTable)?),
-      BymlNodeDiscriminants::BinaryDataTable => BymlNode::BinaryDataTable(todo!()),
-      BymlNodeDiscriminants::Integer64 => BymlNode::Integer64(todo!()),
-      BymlNodeDiscriminants::UnsignedInteger64 => BymlNode::UnsignedInteger64(todo!()),
-      BymlNodeDiscriminants::Float64 => BymlNode::Float64(todo!()),
+      BymlNodeDiscriminants::Array => BymlNode::Array(BymlArrayNode::construct_dyn(reader, version).await.map_err(BymlConstructionError::Array)?),
+      BymlNodeDiscriminants::Dictionary => BymlNode::Dictionary(BymlDictionaryNode::construct_dyn(reader, version).await.map_err(BymlConstructionError::Dictionary)?),
+      BymlNodeDiscriminants::StringTable => BymlNode::StringTable(BymlStringTableNode::construct_dyn(reader, version).await.map_err(BymlConstructionError::StringTable)?),
+      BymlNodeDiscriminants::BinaryDataTable => BymlNode::BinaryDataTable(BymlBinaryDataTableNode::construct_dyn(reader, version).await.map_err(BymlConstructionError::BinaryDataTable)?),
     })
   }
 }
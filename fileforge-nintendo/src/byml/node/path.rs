@@ -0,0 +1,142 @@
+use fileforge::stream::ReadableStream;
+#[cfg(feature = "alloc")]
+use fileforge::stream::{error::stream_restore::StreamRestoreError, RestorableStream};
+use fileforge_macros::FileforgeError;
+
+#[cfg(feature = "alloc")]
+use crate::byml::{node::string_table::BymlStringTableNodeScanError, Byml, RestorableByml};
+use crate::byml::{
+  node::{array::BymlArrayNodeIntoEntryError, dictionary::entry::BymlDictionaryEntryReadError, BymlConstructionError, BymlNode, BymlNodeDiscriminants},
+  IntoDataAtError,
+};
+
+/// Resolves dictionary keys to their index in the document's key table.
+pub trait BymlKeyLookup {
+  fn key_index(&self, key: &[u8]) -> Option<u32>;
+}
+
+/// Key tables are sorted, so a slice of the table's strings (e.g. from [`into_strings`](super::string_table::BymlStringTableNode::into_strings)) can be binary searched.
+impl<T: AsRef<[u8]>> BymlKeyLookup for [T] {
+  fn key_index(&self, key: &[u8]) -> Option<u32> {
+    self.binary_search_by(|candidate| candidate.as_ref().cmp(key)).ok().map(|index| index as u32)
+  }
+}
+
+/// The keys of a path, resolved against the key table up front by [`Byml::get_path`].
+#[cfg(feature = "alloc")]
+struct BymlResolvedKeys<'a> {
+  keys: &'a [&'a [u8]],
+  indices: &'a [Option<u32>],
+}
+
+#[cfg(feature = "alloc")]
+impl BymlKeyLookup for BymlResolvedKeys<'_> {
+  fn key_index(&self, key: &[u8]) -> Option<u32> {
+    self.keys.iter().position(|candidate| *candidate == key).and_then(|position| self.indices[position])
+  }
+}
+
+#[derive(FileforgeError)]
+pub enum BymlPathError<'pool, S: ReadableStream<Type = u8>> {
+  #[report(&"Path descends into a node that isn't a container")]
+  NotAContainer(BymlNodeDiscriminants),
+
+  #[report(&"Array index is not a number")]
+  InvalidArrayIndex,
+
+  Array(#[from] BymlArrayNodeIntoEntryError<'pool, S>),
+  Dictionary(#[from] BymlDictionaryEntryReadError<'pool, S>),
+  Construction(#[from] BymlConstructionError<'pool, IntoDataAtError<'pool, S>, S>),
+}
+
+#[cfg(feature = "alloc")]
+#[derive(FileforgeError)]
+pub enum BymlGetPathError<'pool, S: RestorableStream<Type = u8>> {
+  #[report(&"Failed to restore to the start of the document")]
+  Restore(StreamRestoreError<S::RestoreError>),
+
+  #[report(&"Key table isn't a string table")]
+  KeyTableNotAStringTable(BymlNodeDiscriminants),
+
+  KeyTable(#[from] BymlConstructionError<'pool, IntoDataAtError<'pool, S>, S>),
+  KeyScan(#[from] BymlStringTableNodeScanError<'pool, S>),
+  Path(#[from] BymlPathError<'pool, S>),
+}
+
+impl<'pool, S: ReadableStream<Type = u8>> BymlNode<'pool, S> {
+  /// Walks a `/` separated path of dictionary keys and array indices, e.g. `Actors/0/Name`.
+  ///
+  /// Returns `None` if a key or index along the way doesn't exist. [`Byml::get_path`] resolves the keys from the document itself.
+  pub async fn get_path<K: BymlKeyLookup + ?Sized>(self, path: &str, keys: &K) -> Result<Option<BymlNode<'pool, S>>, BymlPathError<'pool, S>> {
+    let mut node = self;
+
+    for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+      let reference = match node {
+        BymlNode::Array(array) => {
+          let index: u32 = segment.parse().map_err(|_| BymlPathError::InvalidArrayIndex)?;
+
+          if index >= array.len() {
+            return Ok(None);
+          }
+
+          array.into_entry(index).await?
+        }
+
+        BymlNode::Dictionary(dictionary) => {
+          let Some(key_index) = keys.key_index(segment.as_bytes()) else {
+            return Ok(None);
+          };
+
+          match dictionary.into_value_at_key_index(key_index).await? {
+            Some(reference) => reference,
+            None => return Ok(None),
+          }
+        }
+
+        other => return Err(BymlPathError::NotAContainer(BymlNodeDiscriminants::from(&other))),
+      };
+
+      node = reference.into_node().await?;
+    }
+
+    Ok(Some(node))
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<'pool, S: RestorableStream<Type = u8>> Byml<'pool, S> {
+  /// Looks up the index of each of `keys` in the key table, giving the document back as it was.
+  async fn into_key_indices(self, keys: &[&[u8]]) -> Result<(alloc::vec::Vec<Option<u32>>, Self), BymlGetPathError<'pool, S>> {
+    let (header, Some(offset)) = (self.header, self.header.key_table_offset()) else {
+      return Ok((alloc::vec![None; keys.len()], self));
+    };
+
+    let start = self.reader.snapshot();
+    let table = self.into_node_dyn(offset.get()).await?;
+    let table = table
+      .into_string_table()
+      .map_err(|other| BymlGetPathError::KeyTableNotAStringTable(BymlNodeDiscriminants::from(&other)))?;
+    let (indices, mut reader) = table.into_indices_of_and_reader(keys).await?;
+
+    reader.restore(start).await.map_err(BymlGetPathError::Restore)?;
+
+    Ok((indices, Byml { header, reader }))
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<'pool, S: RestorableStream<Type = u8>> RestorableByml<'pool, S> {
+  /// Walks a `/` separated path from the root, like [`BymlNode::get_path`], resolving its keys from the document's key table.
+  ///
+  /// Only the path's keys are looked up, in a single walk over the key table.
+  pub async fn get_path(&mut self, path: &str) -> Result<Option<BymlNode<'pool, &mut S>>, BymlGetPathError<'pool, &mut S>> {
+    let keys: alloc::vec::Vec<&[u8]> = path.split('/').filter(|segment| !segment.is_empty()).map(str::as_bytes).collect();
+    let (indices, document) = self.by_ref().await.map_err(BymlGetPathError::Restore)?.into_key_indices(&keys).await?;
+
+    let Some(root) = document.into_root().await? else {
+      return Ok(None);
+    };
+
+    Ok(root.get_path(path, &BymlResolvedKeys { keys: &keys, indices: &indices }).await?)
+  }
+}
@@ -0,0 +1,35 @@
+use fileforge::{binary_reader::BinaryReader, stream::ReadableStream};
+
+use crate::byml::{
+  node::{discriminant::BymlNodeDiscriminantVersionConfig, BymlAnyConstructable, BymlConstructionError, BymlNode, BymlNodeDiscriminants},
+  reader_into_data_at, IntoDataAtError,
+};
+
+/// A child of a container node that has been located, but not yet constructed.
+pub struct BymlNodeReference<'pool, S: ReadableStream<Type = u8>> {
+  discriminant: BymlNodeDiscriminants,
+  value: u32,
+  version: BymlNodeDiscriminantVersionConfig,
+  reader: BinaryReader<'pool, S>,
+}
+
+impl<'pool, S: ReadableStream<Type = u8>> BymlNodeReference<'pool, S> {
+  pub(crate) fn new(discriminant: BymlNodeDiscriminants, value: u32, version: BymlNodeDiscriminantVersionConfig, reader: BinaryReader<'pool, S>) -> Self {
+    Self { discriminant, value, version, reader }
+  }
+
+  pub fn discriminant(&self) -> BymlNodeDiscriminants {
+    self.discriminant
+  }
+
+  /// The raw value stored in the container. For scalar nodes this is the value itself, otherwise it is the absolute offset of the node.
+  pub fn value(&self) -> u32 {
+    self.value
+  }
+
+  pub async fn into_node(self) -> Result<BymlNode<'pool, S>, BymlConstructionError<'pool, IntoDataAtError<'pool, S>, S>> {
+    let reader = self.reader;
+
+    BymlNode::construct(self.discriminant, self.value, self.version, async move |offset| reader_into_data_at(reader, offset).await).await
+  }
+}
@@ -8,6 +8,13 @@ pub struct BymlStringNode {
   string_table_index: u32,
 }
 
+impl BymlStringNode {
+  /// The index of the string in the document's string table.
+  pub fn string_table_index(&self) -> u32 {
+    self.string_table_index
+  }
+}
+
 impl<'pool, S: ReadableStream<Type = u8>, E> BymlConstructable<'pool, S, E> for BymlStringNode {
   type Error = Infallible;
 
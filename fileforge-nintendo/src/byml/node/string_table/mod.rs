@@ -1,3 +1,5 @@
+use core::cmp::Ordering;
+
 use fileforge::binary_reader::primitive::numeric::u24;
 use fileforge::binary_reader::PrimitiveReader;
 use fileforge::stream::builtin::read_until::ReadUntil;
@@ -17,7 +19,7 @@ use fileforge::{
 };
 use fileforge_macros::FileforgeError;
 
-use crate::byml::node::BymlDynConstructable;
+use crate::byml::node::{discriminant::BymlNodeDiscriminantVersionConfig, BymlDynConstructable};
 
 pub struct BymlStringTableNode<'pool, S: ReadableStream<Type = u8>> {
   count: u32,
@@ -32,7 +34,7 @@ pub enum BymlStringTableNodeConstructableError<'pool, S: ReadableStream<Type = u
 impl<'pool, S: ReadableStream<Type = u8>> BymlDynConstructable<'pool, S> for BymlStringTableNode<'pool, S> {
   type Error = BymlStringTableNodeConstructableError<'pool, S>;
 
-  async fn construct_dyn(mut reader: BinaryReader<'pool, S>) -> Result<Self, Self::Error> {
+  async fn construct_dyn(mut reader: BinaryReader<'pool, S>, _: BymlNodeDiscriminantVersionConfig) -> Result<Self, Self::Error> {
     Ok(BymlStringTableNode {
       count: reader.get::<u24>().await.map_err(BymlStringTableNodeConstructableError::ReadCountError)?.into(),
       reader,
@@ -57,7 +59,21 @@ pub enum BymlStringTableNodeIntoStringError<'pool, S: ReadableStream<Type = u8>>
   FailedToSkipToString(#[from] SkipError<'pool, S::SkipError>),
 }
 
+#[derive(FileforgeError)]
+pub enum BymlStringTableNodeScanError<'pool, S: ReadableStream<Type = u8>> {
+  FailedToSkipAddressTable(#[from] SkipError<'pool, S::SkipError>),
+  FailedToReadString(#[from] Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>),
+}
+
 impl<'pool, S: ReadableStream<Type = u8>> BymlStringTableNode<'pool, S> {
+  pub fn len(&self) -> u32 {
+    self.count
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.count == 0
+  }
+
   fn address_table_length(&self) -> u64 {
     self.count as u64 + 1
   }
@@ -84,4 +100,111 @@ impl<'pool, S: ReadableStream<Type = u8>> BymlStringTableNode<'pool, S> {
 
     Ok(self.reader.into_stream().read_until(0))
   }
+
+  /// Finds the index of `string` in the table.
+  ///
+  /// String tables are sorted and their strings are stored back to back in index order, which lets this walk the strings without going back to the address table.
+  pub async fn into_index_of(mut self, string: &[u8]) -> Result<Option<u32>, BymlStringTableNodeScanError<'pool, S>> {
+    self.reader.skip(self.address_table_length() * 4).await?;
+
+    for index in 0..self.count {
+      let mut ordering = Ordering::Equal;
+      let mut length = 0;
+
+      loop {
+        let byte: u8 = self.reader.get().await?;
+
+        if byte == 0 {
+          break;
+        }
+
+        if ordering == Ordering::Equal {
+          ordering = string.get(length).map_or(Ordering::Greater, |expected| byte.cmp(expected));
+        }
+
+        length += 1;
+      }
+
+      match ordering.then(length.cmp(&string.len())) {
+        Ordering::Equal => return Ok(Some(index)),
+        Ordering::Greater => return Ok(None),
+        Ordering::Less => {}
+      }
+    }
+
+    Ok(None)
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<'pool, S: ReadableStream<Type = u8>> BymlStringTableNode<'pool, S> {
+  /// Reads every string in the table, in index order. Like [`Self::into_index_of`], this walks the strings back to back.
+  pub async fn into_strings(mut self) -> Result<alloc::vec::Vec<alloc::vec::Vec<u8>>, BymlStringTableNodeScanError<'pool, S>> {
+    self.reader.skip(self.address_table_length() * 4).await?;
+
+    let mut strings = alloc::vec::Vec::with_capacity(self.count as usize);
+
+    for _ in 0..self.count {
+      let mut string = alloc::vec::Vec::new();
+
+      loop {
+        let byte: u8 = self.reader.get().await?;
+
+        if byte == 0 {
+          break;
+        }
+
+        string.push(byte);
+      }
+
+      strings.push(string);
+    }
+
+    Ok(strings)
+  }
+
+  /// Finds the index of each of `strings` in the table, in a single walk over it like [`Self::into_index_of`].
+  ///
+  /// The walk stops as soon as every string has been found.
+  pub async fn into_indices_of(mut self, strings: &[&[u8]]) -> Result<alloc::vec::Vec<Option<u32>>, BymlStringTableNodeScanError<'pool, S>> {
+    self.indices_of(strings).await
+  }
+
+  /// Like [`Self::into_indices_of`], but gives back the reader, left wherever the walk stopped.
+  pub(crate) async fn into_indices_of_and_reader(mut self, strings: &[&[u8]]) -> Result<(alloc::vec::Vec<Option<u32>>, BinaryReader<'pool, S>), BymlStringTableNodeScanError<'pool, S>> {
+    Ok((self.indices_of(strings).await?, self.reader))
+  }
+
+  async fn indices_of(&mut self, strings: &[&[u8]]) -> Result<alloc::vec::Vec<Option<u32>>, BymlStringTableNodeScanError<'pool, S>> {
+    self.reader.skip(self.address_table_length() * 4).await?;
+
+    let mut indices = alloc::vec![None; strings.len()];
+    let mut string = alloc::vec::Vec::new();
+
+    for index in 0..self.count {
+      if indices.iter().all(Option::is_some) {
+        break;
+      }
+
+      string.clear();
+
+      loop {
+        let byte: u8 = self.reader.get().await?;
+
+        if byte == 0 {
+          break;
+        }
+
+        string.push(byte);
+      }
+
+      for (found, candidate) in indices.iter_mut().zip(strings) {
+        if *candidate == string.as_slice() {
+          *found = Some(index);
+        }
+      }
+    }
+
+    Ok(indices)
+  }
 }
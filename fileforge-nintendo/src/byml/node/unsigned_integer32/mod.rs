@@ -8,6 +8,12 @@ pub struct BymlUnsignedInteger32Node {
   value: u32,
 }
 
+impl BymlUnsignedInteger32Node {
+  pub fn value(&self) -> u32 {
+    self.value
+  }
+}
+
 impl<'pool, S: ReadableStream<Type = u8>, E> BymlConstructable<'pool, S, E> for BymlUnsignedInteger32Node {
   type Error = Infallible;
 
@@ -0,0 +1,33 @@
+use fileforge::{
+  binary_reader::{
+    error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError},
+    BinaryReader, PrimitiveReader,
+  },
+  error::ext::annotations::annotated::Annotated,
+  stream::ReadableStream,
+};
+
+use crate::byml::node::{BymlConstructable, BymlOutOfLineError};
+
+/// An unsigned 64-bit integer node (`0xD5`), stored out of line like [`BymlInteger64Node`](super::integer64::BymlInteger64Node).
+pub struct BymlUnsignedInteger64Node {
+  value: u64,
+}
+
+impl BymlUnsignedInteger64Node {
+  pub fn value(&self) -> u64 {
+    self.value
+  }
+}
+
+impl<'pool, S: ReadableStream<Type = u8>, E> BymlConstructable<'pool, S, E> for BymlUnsignedInteger64Node {
+  type Error = BymlOutOfLineError<E, Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>>;
+
+  async fn construct<F: AsyncFnOnce(u64) -> Result<BinaryReader<'pool, S>, E>>(value: u32, get_reader: F) -> Result<Self, Self::Error> {
+    let mut reader = get_reader(value as u64).await.map_err(BymlOutOfLineError::ReaderAcquire)?;
+
+    Ok(Self {
+      value: reader.get().await.map_err(BymlOutOfLineError::Item)?,
+    })
+  }
+}
@@ -0,0 +1,49 @@
+pub mod read;
+#[cfg(feature = "serde")]
+mod serialization;
+#[cfg(feature = "yaml")]
+pub mod yaml;
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use crate::byml::node::BymlNodeDiscriminants;
+
+/// An owned BYML document tree.
+///
+/// Dictionaries are keyed by [`BTreeMap`], which keeps keys in the same byte order BYML sorts its key table by.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BymlValue {
+  Null,
+  Bool(bool),
+  Integer32(i32),
+  UnsignedInteger32(u32),
+  Float32(f32),
+  Integer64(i64),
+  UnsignedInteger64(u64),
+  Float64(f64),
+  String(String),
+  BinaryData(Vec<u8>),
+  BinaryDataWithParameter { data: Vec<u8>, parameter: u32 },
+  Array(Vec<BymlValue>),
+  Dictionary(BTreeMap<String, BymlValue>),
+}
+
+impl BymlValue {
+  pub fn discriminant(&self) -> BymlNodeDiscriminants {
+    match self {
+      BymlValue::Null => BymlNodeDiscriminants::Null,
+      BymlValue::Bool(_) => BymlNodeDiscriminants::Bool,
+      BymlValue::Integer32(_) => BymlNodeDiscriminants::Integer32,
+      BymlValue::UnsignedInteger32(_) => BymlNodeDiscriminants::UnsignedInteger32,
+      BymlValue::Float32(_) => BymlNodeDiscriminants::Float32,
+      BymlValue::Integer64(_) => BymlNodeDiscriminants::Integer64,
+      BymlValue::UnsignedInteger64(_) => BymlNodeDiscriminants::UnsignedInteger64,
+      BymlValue::Float64(_) => BymlNodeDiscriminants::Float64,
+      BymlValue::String(_) => BymlNodeDiscriminants::String,
+      BymlValue::BinaryData(_) => BymlNodeDiscriminants::BinaryData,
+      BymlValue::BinaryDataWithParameter { .. } => BymlNodeDiscriminants::BinaryDataWithParameter,
+      BymlValue::Array(_) => BymlNodeDiscriminants::Array,
+      BymlValue::Dictionary(_) => BymlNodeDiscriminants::Dictionary,
+    }
+  }
+}
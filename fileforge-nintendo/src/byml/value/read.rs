@@ -0,0 +1,376 @@
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+
+use fileforge::{
+  binary_reader::{
+    error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError, SkipError},
+    primitive::numeric::u24,
+    snapshot::BinaryReaderSnapshot,
+    BinaryReader, PrimitiveReader,
+  },
+  error::{ext::annotations::annotated::Annotated, render::builtin::number::formatted_unsigned::FormattedUnsigned},
+  stream::{error::stream_restore::StreamRestoreError, RestorableStream},
+};
+use fileforge_macros::FileforgeError;
+
+use crate::byml::{
+  node::{
+    dictionary::entry::{BymlDictionaryEntry, BymlDictionaryEntryReadError},
+    discriminant::{BymlNodeDiscriminantVersionConfig, BymlNodeDiscriminantsReadError},
+    BymlNodeDiscriminants,
+  },
+  value::BymlValue,
+  Byml,
+};
+
+/// Deeper documents are almost certainly cyclic.
+const MAX_DEPTH: u32 = 256;
+
+#[derive(FileforgeError)]
+pub enum BymlValueReadError<'pool, S: RestorableStream<Type = u8>> {
+  #[report(&"Failed to restore to the start of the document")]
+  Restore(StreamRestoreError<S::RestoreError>),
+
+  #[report(&"Node offset points into the header")]
+  OutOfBounds,
+
+  Skip(#[from] SkipError<'pool, S::SkipError>),
+  ReadPrimitive(#[from] Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>),
+  ReadType(#[from] BymlNodeDiscriminantsReadError<'pool, S>),
+  ReadEntry(#[from] BymlDictionaryEntryReadError<'pool, S>),
+
+  #[report(&"Node doesn't have the type its parent gave it")]
+  MismatchedType(BymlNodeDiscriminants),
+
+  #[report(&"Tables can only be referenced from the header")]
+  UnexpectedTable(BymlNodeDiscriminants),
+
+  #[report(&"Table index out of bounds")]
+  #[flag("Index {index} is out of bounds for {count} entries", index = FormattedUnsigned::new(*index as u128), count = FormattedUnsigned::new(*count as u128))]
+  IndexOutOfBounds {
+    index: u32,
+    count: u32,
+  },
+
+  #[report(&"Table offsets are out of order")]
+  InvalidRange,
+
+  #[report(&"Key or string is not valid UTF-8")]
+  InvalidUtf8,
+
+  #[report(&"Nodes are nested too deeply")]
+  TooDeep,
+}
+
+struct BymlValueReader<'pool, S: RestorableStream<Type = u8>> {
+  reader: BinaryReader<'pool, S>,
+  start: BinaryReaderSnapshot<'pool, S>,
+  version: BymlNodeDiscriminantVersionConfig,
+  keys: Vec<String>,
+  strings: Vec<String>,
+  binary_data: Vec<Vec<u8>>,
+}
+
+fn table_entry<T: Clone>(table: &[T], index: u32) -> Result<T, (u32, u32)> {
+  table.get(index as usize).cloned().ok_or((index, table.len() as u32))
+}
+
+impl<'pool, S: RestorableStream<Type = u8>> BymlValueReader<'pool, S> {
+  /// Moves to the absolute `position`, going back to the start of the document first so that nodes can be visited in any order.
+  async fn seek(&mut self, position: u64) -> Result<(), BymlValueReadError<'pool, S>> {
+    self.reader.restore(self.start.clone()).await.map_err(BymlValueReadError::Restore)?;
+
+    let offset = position.checked_sub(self.reader.offset()).ok_or(BymlValueReadError::OutOfBounds)?;
+
+    Ok(self.reader.skip(offset).await?)
+  }
+
+  /// Lengths and counts come from the file, so nothing is reserved for them up front, only for what has already been read.
+  async fn read_bytes(&mut self, length: u32) -> Result<Vec<u8>, BymlValueReadError<'pool, S>> {
+    let mut bytes = Vec::new();
+
+    for _ in 0..length {
+      bytes.push(self.reader.get().await?);
+    }
+
+    Ok(bytes)
+  }
+
+  async fn read_type(&mut self, expected: BymlNodeDiscriminants) -> Result<(), BymlValueReadError<'pool, S>> {
+    let discriminant: BymlNodeDiscriminants = self.reader.read_with(self.version).await?;
+
+    if discriminant != expected {
+      return Err(BymlValueReadError::MismatchedType(discriminant));
+    }
+
+    Ok(())
+  }
+
+  /// Reads every item of a string or binary data table. Items run up to the next offset, including any terminator.
+  async fn read_table(&mut self, position: u64, expected: BymlNodeDiscriminants) -> Result<Vec<Vec<u8>>, BymlValueReadError<'pool, S>> {
+    self.seek(position).await?;
+    self.read_type(expected).await?;
+
+    let count: u32 = self.reader.get::<u24>().await?.into();
+    let mut offsets = Vec::new();
+
+    for _ in 0..=count {
+      offsets.push(self.reader.get::<u32>().await?);
+    }
+
+    let mut items = Vec::with_capacity(offsets.len() - 1);
+
+    for range in offsets.windows(2) {
+      let length = range[1].checked_sub(range[0]).ok_or(BymlValueReadError::InvalidRange)?;
+
+      self.seek(position + range[0] as u64).await?;
+      items.push(self.read_bytes(length).await?);
+    }
+
+    Ok(items)
+  }
+
+  async fn read_string_table(&mut self, position: u64) -> Result<Vec<String>, BymlValueReadError<'pool, S>> {
+    let mut strings = Vec::new();
+
+    for mut string in self.read_table(position, BymlNodeDiscriminants::StringTable).await? {
+      string.truncate(string.iter().position(|byte| *byte == 0).unwrap_or(string.len()));
+      strings.push(String::from_utf8(string).map_err(|_| BymlValueReadError::InvalidUtf8)?);
+    }
+
+    Ok(strings)
+  }
+
+  async fn read_value(&mut self, discriminant: BymlNodeDiscriminants, value: u32, depth: u32) -> Result<BymlValue, BymlValueReadError<'pool, S>> {
+    let index_error = |(index, count)| BymlValueReadError::IndexOutOfBounds { index, count };
+
+    Ok(match discriminant {
+      BymlNodeDiscriminants::Null => BymlValue::Null,
+      BymlNodeDiscriminants::Bool => BymlValue::Bool(value != 0),
+      BymlNodeDiscriminants::Integer32 => BymlValue::Integer32(value as i32),
+      BymlNodeDiscriminants::UnsignedInteger32 => BymlValue::UnsignedInteger32(value),
+      BymlNodeDiscriminants::Float32 => BymlValue::Float32(f32::from_bits(value)),
+      BymlNodeDiscriminants::String => BymlValue::String(table_entry(&self.strings, value).map_err(index_error)?),
+
+      BymlNodeDiscriminants::Integer64 => {
+        self.seek(value as u64).await?;
+        BymlValue::Integer64(self.reader.get().await?)
+      }
+
+      BymlNodeDiscriminants::UnsignedInteger64 => {
+        self.seek(value as u64).await?;
+        BymlValue::UnsignedInteger64(self.reader.get().await?)
+      }
+
+      BymlNodeDiscriminants::Float64 => {
+        self.seek(value as u64).await?;
+        BymlValue::Float64(self.reader.get().await?)
+      }
+
+      BymlNodeDiscriminants::BinaryData if self.version.feat_binary_data_table => BymlValue::BinaryData(table_entry(&self.binary_data, value).map_err(index_error)?),
+
+      BymlNodeDiscriminants::BinaryData => {
+        self.seek(value as u64).await?;
+
+        let length = self.reader.get().await?;
+
+        BymlValue::BinaryData(self.read_bytes(length).await?)
+      }
+
+      BymlNodeDiscriminants::BinaryDataWithParameter => {
+        self.seek(value as u64).await?;
+
+        let length = self.reader.get().await?;
+        let parameter = self.reader.get().await?;
+
+        BymlValue::BinaryDataWithParameter {
+          data: self.read_bytes(length).await?,
+          parameter,
+        }
+      }
+
+      BymlNodeDiscriminants::Array | BymlNodeDiscriminants::Dictionary if depth >= MAX_DEPTH => return Err(BymlValueReadError::TooDeep),
+
+      BymlNodeDiscriminants::Array => {
+        self.seek(value as u64).await?;
+        self.read_type(discriminant).await?;
+
+        let count: u32 = self.reader.get::<u24>().await?.into();
+        let mut types = Vec::new();
+
+        for _ in 0..count {
+          types.push(self.reader.read_with(self.version).await?);
+        }
+
+        self.reader.skip(((count as u64 + 3) & !3) - count as u64).await?;
+
+        let mut values = Vec::with_capacity(types.len());
+
+        for _ in 0..count {
+          values.push(self.reader.get::<u32>().await?);
+        }
+
+        let mut array = Vec::with_capacity(types.len());
+
+        for (discriminant, value) in types.into_iter().zip(values) {
+          array.push(Box::pin(self.read_value(discriminant, value, depth + 1)).await?);
+        }
+
+        BymlValue::Array(array)
+      }
+
+      BymlNodeDiscriminants::Dictionary => {
+        self.seek(value as u64).await?;
+        self.read_type(discriminant).await?;
+
+        let count: u32 = self.reader.get::<u24>().await?.into();
+        let mut entries = Vec::new();
+
+        for _ in 0..count {
+          entries.push(self.reader.read_with::<BymlDictionaryEntry>(self.version).await?);
+        }
+
+        let mut dictionary = BTreeMap::new();
+
+        for entry in entries {
+          let key = table_entry(&self.keys, entry.key_index).map_err(index_error)?;
+
+          dictionary.insert(key, Box::pin(self.read_value(entry.discriminant, entry.value, depth + 1)).await?);
+        }
+
+        BymlValue::Dictionary(dictionary)
+      }
+
+      BymlNodeDiscriminants::StringTable | BymlNodeDiscriminants::BinaryDataTable => return Err(BymlValueReadError::UnexpectedTable(discriminant)),
+    })
+  }
+}
+
+impl<'pool, S: RestorableStream<Type = u8>> Byml<'pool, S> {
+  /// Reads the whole document into an owned tree, or `None` if the document has no root.
+  ///
+  /// Containers may be shared between parents, so every node is reached by restoring to the start of the document and skipping forwards.
+  pub async fn into_value(self) -> Result<Option<BymlValue>, BymlValueReadError<'pool, S>> {
+    let header = self.header;
+    let version = self.version();
+
+    let mut reader = BymlValueReader {
+      start: self.reader.snapshot(),
+      reader: self.reader,
+      version,
+      keys: Vec::new(),
+      strings: Vec::new(),
+      binary_data: Vec::new(),
+    };
+
+    if let Some(position) = header.key_table_offset() {
+      reader.keys = reader.read_string_table(position.get() as u64).await?;
+    }
+
+    if let Some(position) = header.string_table_offset() {
+      reader.strings = reader.read_string_table(position.get() as u64).await?;
+    }
+
+    if let Some(position) = header.binary_data_table_offset() {
+      reader.binary_data = reader.read_table(position.get() as u64, BymlNodeDiscriminants::BinaryDataTable).await?;
+    }
+
+    let Some(position) = header.root_data_offset() else {
+      return Ok(None);
+    };
+
+    reader.seek(position.get() as u64).await?;
+
+    let discriminant = reader.reader.read_with(version).await?;
+
+    Ok(Some(reader.read_value(discriminant, position.get(), 0).await?))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::{collections::BTreeMap, vec};
+
+  use fileforge::{
+    binary_reader::{endianness::Endianness, BinaryReader},
+    provider::hint::ReadHint,
+  };
+
+  use crate::byml::{header::BymlHeaderConfig, value::BymlValue, writer::BymlWriter, Byml};
+
+  async fn read(data: Vec<u8>, config: BymlHeaderConfig) -> BymlValue {
+    let byml = BinaryReader::new_from_provider(data, Endianness::BigEndian, ReadHint::new())
+      .into_with::<Byml<_>>(config)
+      .await
+      .map_err(|_| {})
+      .unwrap();
+
+    byml.into_value().await.map_err(|_| {}).unwrap().unwrap()
+  }
+
+  async fn round_trip(value: &BymlValue, endianness: Endianness, version: u16, config: BymlHeaderConfig) -> BymlValue {
+    let mut reader = BinaryReader::new_from_provider(Vec::new(), Endianness::BigEndian, ReadHint::new());
+
+    reader.overwrite(&BymlWriter::new(value, endianness, version).with_header_config(config)).await.map_err(|_| {}).unwrap();
+
+    read(reader.into_stream().into_provider(), config).await
+  }
+
+  #[tokio::test]
+  async fn reads_bed() {
+    let value = read(Vec::from(*include_bytes!("../../../../fileforge-test/binaries/Bed.byml")), BymlHeaderConfig::default()).await;
+    let BymlValue::Dictionary(root) = value else { panic!("root is a dictionary") };
+
+    assert_eq!(root["Id"], BymlValue::String("obj742".into()));
+    assert_eq!(root["IsLinkDest"], BymlValue::Bool(false));
+    assert_eq!(root["Links"], BymlValue::Dictionary(BTreeMap::new()));
+
+    let BymlValue::Dictionary(scale) = &root["Scale"] else { panic!("Scale is a dictionary") };
+
+    assert_eq!(scale["X"], BymlValue::Float32(1.0));
+  }
+
+  #[tokio::test]
+  async fn round_trips_through_writer() {
+    let array = BymlValue::Array(vec![BymlValue::Integer32(-1), BymlValue::Null, BymlValue::Float64(0.1)]);
+    let value = BymlValue::Dictionary(BTreeMap::from([
+      ("Name".into(), BymlValue::String("Bed".into())),
+      ("Flags".into(), BymlValue::UnsignedInteger32(0xFFFF_FFFF)),
+      ("Big".into(), BymlValue::Integer64(i64::MIN)),
+      ("First".into(), array.clone()),
+      ("Second".into(), array),
+      ("Data".into(), BymlValue::BinaryData(vec![1, 2, 3])),
+      ("Parameterised".into(), BymlValue::BinaryDataWithParameter { data: vec![4, 5], parameter: 16 }),
+    ]));
+
+    assert_eq!(round_trip(&value, Endianness::LittleEndian, 5, BymlHeaderConfig::default()).await, value);
+  }
+
+  #[tokio::test]
+  async fn rejects_lengths_past_the_end() {
+    let value = BymlValue::Array(vec![BymlValue::BinaryData(vec![1, 2, 3])]);
+    let mut reader = BinaryReader::new_from_provider(Vec::new(), Endianness::BigEndian, ReadHint::new());
+
+    reader.overwrite(&BymlWriter::new(&value, Endianness::BigEndian, 4)).await.map_err(|_| {}).unwrap();
+
+    let mut data = reader.into_stream().into_provider();
+    let length = data.windows(7).position(|window| window == [0, 0, 0, 3, 1, 2, 3]).unwrap();
+
+    data[length..length + 4].copy_from_slice(&u32::MAX.to_be_bytes());
+
+    let byml = BinaryReader::new_from_provider(data, Endianness::BigEndian, ReadHint::new())
+      .into_with::<Byml<_>>(BymlHeaderConfig::default())
+      .await
+      .map_err(|_| {})
+      .unwrap();
+
+    assert!(byml.into_value().await.is_err());
+  }
+
+  #[tokio::test]
+  async fn round_trips_binary_data_table() {
+    let config = BymlHeaderConfig::build().with_binary_data_table().build();
+    let value = BymlValue::Array(vec![BymlValue::BinaryData(vec![9, 8]), BymlValue::BinaryData(vec![]), BymlValue::BinaryData(vec![9, 8])]);
+
+    assert_eq!(round_trip(&value, Endianness::BigEndian, 1, config).await, value);
+  }
+}
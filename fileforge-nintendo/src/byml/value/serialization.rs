@@ -0,0 +1,196 @@
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
+use core::fmt;
+
+use serde::{
+  de::{MapAccess, SeqAccess, Visitor},
+  ser::SerializeStruct,
+  Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::byml::value::BymlValue;
+
+struct Bytes<'a>(&'a [u8]);
+
+impl Serialize for Bytes<'_> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bytes(self.0)
+  }
+}
+
+/// Serializes into the closest serde type. Binary data with a parameter becomes a `{ data, parameter }` struct.
+impl Serialize for BymlValue {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    match self {
+      BymlValue::Null => serializer.serialize_unit(),
+      BymlValue::Bool(value) => serializer.serialize_bool(*value),
+      BymlValue::Integer32(value) => serializer.serialize_i32(*value),
+      BymlValue::UnsignedInteger32(value) => serializer.serialize_u32(*value),
+      BymlValue::Float32(value) => serializer.serialize_f32(*value),
+      BymlValue::Integer64(value) => serializer.serialize_i64(*value),
+      BymlValue::UnsignedInteger64(value) => serializer.serialize_u64(*value),
+      BymlValue::Float64(value) => serializer.serialize_f64(*value),
+      BymlValue::String(value) => serializer.serialize_str(value),
+      BymlValue::BinaryData(data) => serializer.serialize_bytes(data),
+
+      BymlValue::BinaryDataWithParameter { data, parameter } => {
+        let mut binary = serializer.serialize_struct("BinaryDataWithParameter", 2)?;
+        binary.serialize_field("data", &Bytes(data))?;
+        binary.serialize_field("parameter", parameter)?;
+        binary.end()
+      }
+
+      BymlValue::Array(values) => serializer.collect_seq(values),
+      BymlValue::Dictionary(values) => serializer.collect_map(values),
+    }
+  }
+}
+
+struct BymlValueVisitor;
+
+impl<'de> Visitor<'de> for BymlValueVisitor {
+  type Value = BymlValue;
+
+  fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+    formatter.write_str("a BYML value")
+  }
+
+  fn visit_unit<E>(self) -> Result<BymlValue, E> {
+    Ok(BymlValue::Null)
+  }
+
+  fn visit_none<E>(self) -> Result<BymlValue, E> {
+    Ok(BymlValue::Null)
+  }
+
+  fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<BymlValue, D::Error> {
+    BymlValue::deserialize(deserializer)
+  }
+
+  fn visit_bool<E>(self, value: bool) -> Result<BymlValue, E> {
+    Ok(BymlValue::Bool(value))
+  }
+
+  fn visit_i32<E>(self, value: i32) -> Result<BymlValue, E> {
+    Ok(BymlValue::Integer32(value))
+  }
+
+  fn visit_i64<E>(self, value: i64) -> Result<BymlValue, E> {
+    Ok(i32::try_from(value).map_or(BymlValue::Integer64(value), BymlValue::Integer32))
+  }
+
+  fn visit_u32<E>(self, value: u32) -> Result<BymlValue, E> {
+    Ok(BymlValue::UnsignedInteger32(value))
+  }
+
+  /// Self-describing formats like JSON report every non-negative integer as a `u64`, so this picks the smallest type that holds it.
+  fn visit_u64<E>(self, value: u64) -> Result<BymlValue, E> {
+    if let Ok(value) = i32::try_from(value) {
+      return Ok(BymlValue::Integer32(value));
+    }
+
+    Ok(u32::try_from(value).map_or(BymlValue::UnsignedInteger64(value), BymlValue::UnsignedInteger32))
+  }
+
+  fn visit_f32<E>(self, value: f32) -> Result<BymlValue, E> {
+    Ok(BymlValue::Float32(value))
+  }
+
+  /// Text formats print a `Float32` as its shortest decimal form, so this keeps any value that is exactly that form as a `Float32`.
+  fn visit_f64<E>(self, value: f64) -> Result<BymlValue, E> {
+    if !value.is_finite() || format!("{}", value as f32).parse::<f64>() == Ok(value) {
+      return Ok(BymlValue::Float32(value as f32));
+    }
+
+    Ok(BymlValue::Float64(value))
+  }
+
+  fn visit_str<E>(self, value: &str) -> Result<BymlValue, E> {
+    Ok(BymlValue::String(value.into()))
+  }
+
+  fn visit_string<E>(self, value: String) -> Result<BymlValue, E> {
+    Ok(BymlValue::String(value))
+  }
+
+  fn visit_bytes<E>(self, value: &[u8]) -> Result<BymlValue, E> {
+    Ok(BymlValue::BinaryData(value.into()))
+  }
+
+  fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<BymlValue, E> {
+    Ok(BymlValue::BinaryData(value))
+  }
+
+  fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<BymlValue, A::Error> {
+    let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+    while let Some(value) = seq.next_element()? {
+      values.push(value);
+    }
+
+    Ok(BymlValue::Array(values))
+  }
+
+  fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<BymlValue, A::Error> {
+    let mut values = BTreeMap::new();
+
+    while let Some((key, value)) = map.next_entry()? {
+      values.insert(key, value);
+    }
+
+    Ok(BymlValue::Dictionary(values))
+  }
+}
+
+/// Deserializes from any self-describing format.
+///
+/// Formats that don't distinguish integer and float widths lose that information. Use the YAML conversion to round trip every type.
+impl<'de> Deserialize<'de> for BymlValue {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    deserializer.deserialize_any(BymlValueVisitor)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::{collections::BTreeMap, vec};
+
+  use serde_test::{assert_de_tokens, assert_tokens, Token};
+
+  use crate::byml::value::BymlValue;
+
+  #[test]
+  fn tokens_round_trip() {
+    let value = BymlValue::Dictionary(BTreeMap::from([
+      ("Count".into(), BymlValue::Integer32(-3)),
+      ("Items".into(), BymlValue::Array(vec![BymlValue::Null, BymlValue::Bool(true)])),
+      ("Name".into(), BymlValue::String("Bed".into())),
+    ]));
+
+    assert_tokens(
+      &value,
+      &[
+        Token::Map { len: Some(3) },
+        Token::Str("Count"),
+        Token::I32(-3),
+        Token::Str("Items"),
+        Token::Seq { len: Some(2) },
+        Token::Unit,
+        Token::Bool(true),
+        Token::SeqEnd,
+        Token::Str("Name"),
+        Token::Str("Bed"),
+        Token::MapEnd,
+      ],
+    );
+  }
+
+  #[test]
+  fn picks_smallest_type() {
+    assert_de_tokens(&BymlValue::Integer32(1), &[Token::U64(1)]);
+    assert_de_tokens(&BymlValue::UnsignedInteger32(u32::MAX), &[Token::U64(u32::MAX as u64)]);
+    assert_de_tokens(&BymlValue::UnsignedInteger64(1 << 32), &[Token::U64(1 << 32)]);
+    assert_de_tokens(&BymlValue::Integer64(-(1 << 32)), &[Token::I64(-(1 << 32))]);
+    assert_de_tokens(&BymlValue::Float32(0.1), &[Token::F64(0.1)]);
+    assert_de_tokens(&BymlValue::Float64(0.123456789), &[Token::F64(0.123456789)]);
+  }
+}
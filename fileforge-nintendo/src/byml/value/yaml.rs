@@ -0,0 +1,184 @@
+use alloc::{
+  boxed::Box,
+  collections::BTreeMap,
+  format,
+  string::{String, ToString},
+  vec::Vec,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use fileforge_macros::FileforgeError;
+use serde_norway::{
+  value::{Tag, TaggedValue},
+  Mapping, Value,
+};
+
+use crate::byml::value::BymlValue;
+
+const TAG_UNSIGNED_INTEGER32: &str = "u";
+const TAG_INTEGER64: &str = "l";
+const TAG_UNSIGNED_INTEGER64: &str = "ul";
+const TAG_FLOAT64: &str = "f64";
+const TAG_BINARY_DATA: &str = "binary";
+const TAG_BINARY_DATA_WITH_PARAMETER: &str = "binary_with_parameter";
+
+#[derive(FileforgeError)]
+pub enum BymlYamlError {
+  #[report(&"Invalid YAML")]
+  Syntax(serde_norway::Error),
+
+  #[report(&"Unknown tag")]
+  UnknownTag(String),
+
+  #[report(&"Value doesn't fit its tag")]
+  InvalidTaggedValue(String),
+
+  #[report(&"Untagged integer doesn't fit in a 32-bit signed integer")]
+  IntegerOutOfRange,
+
+  #[report(&"Dictionary keys must be strings")]
+  InvalidKey,
+
+  #[report(&"Binary data is not valid base64")]
+  InvalidBase64,
+}
+
+fn tagged(tag: &str, value: impl Into<Value>) -> Value {
+  Value::Tagged(Box::new(TaggedValue {
+    tag: Tag::new(tag),
+    value: value.into(),
+  }))
+}
+
+fn to_yaml_value(value: &BymlValue) -> Value {
+  match value {
+    BymlValue::Null => Value::Null,
+    BymlValue::Bool(value) => Value::Bool(*value),
+    BymlValue::Integer32(value) => Value::Number((*value).into()),
+    BymlValue::UnsignedInteger32(value) => tagged(TAG_UNSIGNED_INTEGER32, *value),
+    // go through the shortest decimal form, so `0.1` isn't printed as `0.10000000149011612`
+    BymlValue::Float32(value) => Value::Number(format!("{value}").parse::<f64>().unwrap_or(*value as f64).into()),
+    BymlValue::Integer64(value) => tagged(TAG_INTEGER64, *value),
+    BymlValue::UnsignedInteger64(value) => tagged(TAG_UNSIGNED_INTEGER64, *value),
+    BymlValue::Float64(value) => tagged(TAG_FLOAT64, *value),
+    BymlValue::String(value) => Value::String(value.clone()),
+    BymlValue::BinaryData(data) => tagged(TAG_BINARY_DATA, STANDARD.encode(data)),
+
+    BymlValue::BinaryDataWithParameter { data, parameter } => {
+      let mut mapping = Mapping::new();
+      mapping.insert("data".into(), STANDARD.encode(data).into());
+      mapping.insert("parameter".into(), (*parameter).into());
+
+      tagged(TAG_BINARY_DATA_WITH_PARAMETER, mapping)
+    }
+
+    BymlValue::Array(values) => Value::Sequence(values.iter().map(to_yaml_value).collect()),
+    BymlValue::Dictionary(values) => Value::Mapping(values.iter().map(|(key, value)| (Value::String(key.clone()), to_yaml_value(value))).collect()),
+  }
+}
+
+fn from_base64(value: &Value) -> Result<Vec<u8>, BymlYamlError> {
+  let encoded = value.as_str().ok_or(BymlYamlError::InvalidBase64)?;
+
+  STANDARD.decode(encoded).map_err(|_| BymlYamlError::InvalidBase64)
+}
+
+fn from_tagged_value(tagged: &TaggedValue) -> Result<BymlValue, BymlYamlError> {
+  let tag = tagged.tag.to_string();
+  let value = &tagged.value;
+  let invalid = || BymlYamlError::InvalidTaggedValue(tag.clone());
+
+  Ok(match tag.trim_start_matches('!') {
+    TAG_UNSIGNED_INTEGER32 => BymlValue::UnsignedInteger32(value.as_u64().and_then(|value| u32::try_from(value).ok()).ok_or_else(invalid)?),
+    TAG_INTEGER64 => BymlValue::Integer64(value.as_i64().ok_or_else(invalid)?),
+    TAG_UNSIGNED_INTEGER64 => BymlValue::UnsignedInteger64(value.as_u64().ok_or_else(invalid)?),
+    TAG_FLOAT64 => BymlValue::Float64(value.as_f64().ok_or_else(invalid)?),
+    TAG_BINARY_DATA => BymlValue::BinaryData(from_base64(value)?),
+
+    TAG_BINARY_DATA_WITH_PARAMETER => BymlValue::BinaryDataWithParameter {
+      data: from_base64(value.get("data").ok_or_else(invalid)?)?,
+      parameter: value.get("parameter").and_then(Value::as_u64).and_then(|value| u32::try_from(value).ok()).ok_or_else(invalid)?,
+    },
+
+    _ => return Err(BymlYamlError::UnknownTag(tag)),
+  })
+}
+
+fn from_yaml_value(value: &Value) -> Result<BymlValue, BymlYamlError> {
+  Ok(match value {
+    Value::Null => BymlValue::Null,
+    Value::Bool(value) => BymlValue::Bool(*value),
+
+    Value::Number(number) if number.is_f64() => BymlValue::Float32(number.as_f64().unwrap_or_default() as f32),
+    Value::Number(number) => BymlValue::Integer32(number.as_i64().and_then(|value| i32::try_from(value).ok()).ok_or(BymlYamlError::IntegerOutOfRange)?),
+
+    Value::String(value) => BymlValue::String(value.clone()),
+    Value::Sequence(values) => BymlValue::Array(values.iter().map(from_yaml_value).collect::<Result<_, _>>()?),
+
+    Value::Mapping(values) => {
+      let mut dictionary = BTreeMap::new();
+
+      for (key, value) in values {
+        dictionary.insert(key.as_str().ok_or(BymlYamlError::InvalidKey)?.into(), from_yaml_value(value)?);
+      }
+
+      BymlValue::Dictionary(dictionary)
+    }
+
+    Value::Tagged(tagged) => from_tagged_value(tagged)?,
+  })
+}
+
+impl BymlValue {
+  /// Converts to YAML, tagging the types YAML can't tell apart on its own.
+  ///
+  /// Untagged integers are `Integer32` and untagged floats are `Float32`. `!u`, `!l`, `!ul` and `!f64` mark `UnsignedInteger32`, `Integer64`,
+  /// `UnsignedInteger64` and `Float64`, `!binary` marks base64 binary data and `!binary_with_parameter` marks a `{ data, parameter }` mapping.
+  pub fn to_yaml(&self) -> Result<String, BymlYamlError> {
+    serde_norway::to_string(&to_yaml_value(self)).map_err(BymlYamlError::Syntax)
+  }
+
+  /// Parses YAML produced by [`Self::to_yaml`], or written by hand with the same tags.
+  pub fn from_yaml(yaml: &str) -> Result<BymlValue, BymlYamlError> {
+    from_yaml_value(&serde_norway::from_str(yaml).map_err(BymlYamlError::Syntax)?)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::{collections::BTreeMap, vec};
+
+  use crate::byml::value::{yaml::BymlYamlError, BymlValue};
+
+  #[test]
+  fn yaml_round_trip() {
+    let value = BymlValue::Dictionary(BTreeMap::from([
+      ("Name".into(), BymlValue::String("Bed".into())),
+      ("Scale".into(), BymlValue::Float32(0.1)),
+      ("Flags".into(), BymlValue::UnsignedInteger32(7)),
+      ("Id".into(), BymlValue::Integer64(-1)),
+      ("Seed".into(), BymlValue::UnsignedInteger64(u64::MAX)),
+      ("Precise".into(), BymlValue::Float64(0.1)),
+      ("Items".into(), BymlValue::Array(vec![BymlValue::Null, BymlValue::Integer32(-5), BymlValue::Float32(2.0)])),
+      ("Data".into(), BymlValue::BinaryData(vec![1, 2, 3])),
+      ("Parameterised".into(), BymlValue::BinaryDataWithParameter { data: vec![4], parameter: 16 }),
+    ]));
+
+    let yaml = value.to_yaml().map_err(|_| {}).unwrap();
+
+    assert!(yaml.contains("Scale: 0.1\n"));
+    assert!(yaml.contains("Flags: !u 7\n"));
+    assert!(yaml.contains("Data: !binary AQID\n"));
+    assert_eq!(BymlValue::from_yaml(&yaml).map_err(|_| {}).unwrap(), value);
+  }
+
+  #[test]
+  fn yaml_rejects_unknown_tags() {
+    assert!(matches!(BymlValue::from_yaml("Value: !unknown 1"), Err(BymlYamlError::UnknownTag(_))));
+  }
+
+  #[test]
+  fn yaml_rejects_wide_untagged_integers() {
+    assert!(matches!(BymlValue::from_yaml("Value: 4294967295"), Err(BymlYamlError::IntegerOutOfRange)));
+  }
+}
@@ -0,0 +1,471 @@
+use alloc::{
+  collections::{BTreeMap, BTreeSet},
+  vec,
+  vec::Vec,
+};
+
+use fileforge::binary_reader::{endianness::Endianness, primitive::Primitive};
+
+use crate::byml::{
+  header::BymlHeaderConfig,
+  node::{discriminant::BymlNodeDiscriminantVersionConfig, BymlNodeDiscriminants},
+  value::BymlValue,
+  writer::BymlEncodeError,
+};
+
+const MAX_ENTRIES: usize = 0xFF_FFFF;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Slot {
+  Inline(u32),
+  Container(usize),
+  OutOfLine(usize),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Entry {
+  key_index: u32,
+  discriminant: u8,
+  slot: Slot,
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Container {
+  discriminant: u8,
+  entries: Vec<Entry>,
+}
+
+impl Container {
+  fn size(&self) -> u64 {
+    let count = self.entries.len() as u64;
+
+    if self.discriminant == BymlNodeDiscriminants::Array.id() {
+      4 + align(count) + count * 4
+    } else {
+      4 + count * 8
+    }
+  }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum OutOfLine<'a> {
+  Bits64(u64),
+  BinaryData(&'a [u8]),
+  BinaryDataWithParameter(&'a [u8], u32),
+}
+
+impl OutOfLine<'_> {
+  fn size(&self) -> u64 {
+    match self {
+      OutOfLine::Bits64(_) => 8,
+      OutOfLine::BinaryData(data) => align(4 + data.len() as u64),
+      OutOfLine::BinaryDataWithParameter(data, _) => align(8 + data.len() as u64),
+    }
+  }
+}
+
+fn align(position: u64) -> u64 {
+  (position + 3) & !3
+}
+
+/// The position of `item` in a table built from a sorted set it was collected into.
+fn table_index<T: Ord + ?Sized>(table: &[&T], item: &T) -> u32 {
+  table.binary_search(&item).expect("every table entry is collected before interning") as u32
+}
+
+struct Buffer {
+  data: Vec<u8>,
+  endianness: Endianness,
+}
+
+impl Buffer {
+  fn position(&self) -> u64 {
+    self.data.len() as u64
+  }
+
+  fn put<const SIZE: usize, P: Primitive<SIZE>>(&mut self, value: P) {
+    let mut bytes = [0; SIZE];
+    value.write(&mut bytes, self.endianness);
+    self.data.extend_from_slice(&bytes);
+  }
+
+  fn put_u24(&mut self, value: u32) {
+    match self.endianness {
+      Endianness::LittleEndian => self.data.extend_from_slice(&value.to_le_bytes()[..3]),
+      Endianness::BigEndian => self.data.extend_from_slice(&value.to_be_bytes()[1..]),
+    }
+  }
+
+  fn put_node_header(&mut self, discriminant: BymlNodeDiscriminants, count: u32) {
+    self.put(discriminant.id());
+    self.put_u24(count);
+  }
+
+  fn set_u32(&mut self, position: usize, value: u32) {
+    let mut bytes = [0; 4];
+    value.write(&mut bytes, self.endianness);
+    self.data[position..position + 4].copy_from_slice(&bytes);
+  }
+
+  fn align(&mut self) {
+    self.data.resize(align(self.position()) as usize, 0);
+  }
+
+  /// Writes a string or binary data table: a `u24` count, `count + 1` node-relative offsets, then the items.
+  fn put_offset_table(&mut self, discriminant: BymlNodeDiscriminants, items: &[&[u8]], terminator: bool) {
+    let terminator_length = terminator as u32;
+    let mut offset = 4 + (items.len() as u32 + 1) * 4;
+
+    self.put_node_header(discriminant, items.len() as u32);
+
+    for item in items {
+      self.put(offset);
+      offset += item.len() as u32 + terminator_length;
+    }
+
+    self.put(offset);
+
+    for item in items {
+      self.data.extend_from_slice(item);
+
+      if terminator {
+        self.put(0u8);
+      }
+    }
+
+    self.align();
+  }
+}
+
+struct Collector<'a> {
+  keys: BTreeSet<&'a str>,
+  strings: BTreeSet<&'a str>,
+  binary_data: BTreeSet<&'a [u8]>,
+}
+
+impl<'a> Collector<'a> {
+  fn collect(&mut self, value: &'a BymlValue, config: BymlHeaderConfig) -> Result<(), BymlEncodeError> {
+    match value {
+      BymlValue::String(string) => {
+        if string.contains('\0') {
+          return Err(BymlEncodeError::ContainsNul);
+        }
+
+        self.strings.insert(string);
+      }
+
+      BymlValue::BinaryData(data) if config.feat_binary_data_table => {
+        self.binary_data.insert(data);
+      }
+
+      BymlValue::Array(values) => {
+        for value in values {
+          self.collect(value, config)?;
+        }
+      }
+
+      BymlValue::Dictionary(values) => {
+        for (key, value) in values {
+          if key.contains('\0') {
+            return Err(BymlEncodeError::ContainsNul);
+          }
+
+          self.keys.insert(key);
+          self.collect(value, config)?;
+        }
+      }
+
+      _ => {}
+    }
+
+    Ok(())
+  }
+}
+
+pub(super) struct Encoder<'a> {
+  version: BymlNodeDiscriminantVersionConfig,
+  keys: Vec<&'a str>,
+  strings: Vec<&'a str>,
+  binary_data: Vec<&'a [u8]>,
+  containers: Vec<Container>,
+  container_ids: BTreeMap<Container, usize>,
+  out_of_line: Vec<OutOfLine<'a>>,
+  out_of_line_ids: BTreeMap<OutOfLine<'a>, usize>,
+}
+
+impl<'a> Encoder<'a> {
+  fn intern_container(&mut self, container: Container) -> Slot {
+    if let Some(id) = self.container_ids.get(&container) {
+      return Slot::Container(*id);
+    }
+
+    let id = self.containers.len();
+    self.containers.push(container.clone());
+    self.container_ids.insert(container, id);
+
+    Slot::Container(id)
+  }
+
+  fn intern_out_of_line(&mut self, value: OutOfLine<'a>) -> Slot {
+    let id = *self.out_of_line_ids.entry(value).or_insert(self.out_of_line.len());
+
+    if id == self.out_of_line.len() {
+      self.out_of_line.push(value);
+    }
+
+    Slot::OutOfLine(id)
+  }
+
+  /// Resolves `value` to what its parent stores for it. Identical containers and out of line values share a single slot.
+  fn intern(&mut self, value: &'a BymlValue) -> Result<(u8, Slot), BymlEncodeError> {
+    let discriminant = value.discriminant();
+
+    discriminant.filter_version(self.version).ok_or(BymlEncodeError::UnsupportedNode(discriminant))?;
+
+    let slot = match value {
+      BymlValue::Null => Slot::Inline(0),
+      BymlValue::Bool(value) => Slot::Inline(*value as u32),
+      BymlValue::Integer32(value) => Slot::Inline(*value as u32),
+      BymlValue::UnsignedInteger32(value) => Slot::Inline(*value),
+      BymlValue::Float32(value) => Slot::Inline(value.to_bits()),
+      BymlValue::Integer64(value) => self.intern_out_of_line(OutOfLine::Bits64(*value as u64)),
+      BymlValue::UnsignedInteger64(value) => self.intern_out_of_line(OutOfLine::Bits64(*value)),
+      BymlValue::Float64(value) => self.intern_out_of_line(OutOfLine::Bits64(value.to_bits())),
+      BymlValue::String(value) => Slot::Inline(table_index(&self.strings, value.as_str())),
+      BymlValue::BinaryData(data) if self.version.feat_binary_data_table => Slot::Inline(table_index(&self.binary_data, data.as_slice())),
+      BymlValue::BinaryData(data) => self.intern_out_of_line(OutOfLine::BinaryData(data)),
+      BymlValue::BinaryDataWithParameter { data, parameter } => self.intern_out_of_line(OutOfLine::BinaryDataWithParameter(data, *parameter)),
+
+      BymlValue::Array(values) => {
+        if values.len() > MAX_ENTRIES {
+          return Err(BymlEncodeError::TooManyEntries { count: values.len() });
+        }
+
+        let mut entries = Vec::with_capacity(values.len());
+
+        for value in values {
+          let (discriminant, slot) = self.intern(value)?;
+          entries.push(Entry { key_index: 0, discriminant, slot });
+        }
+
+        self.intern_container(Container {
+          discriminant: discriminant.id(),
+          entries,
+        })
+      }
+
+      BymlValue::Dictionary(values) => {
+        if values.len() > MAX_ENTRIES {
+          return Err(BymlEncodeError::TooManyEntries { count: values.len() });
+        }
+
+        let mut entries = Vec::with_capacity(values.len());
+
+        for (key, value) in values {
+          let (discriminant, slot) = self.intern(value)?;
+          entries.push(Entry {
+            key_index: table_index(&self.keys, key.as_str()),
+            discriminant,
+            slot,
+          });
+        }
+
+        self.intern_container(Container {
+          discriminant: discriminant.id(),
+          entries,
+        })
+      }
+    };
+
+    Ok((discriminant.id(), slot))
+  }
+
+  /// Orders containers so that every container comes after all of its parents, letting forward-only readers reach any child.
+  fn order(&self, root: usize) -> Vec<usize> {
+    let mut parents = vec![0usize; self.containers.len()];
+
+    for container in &self.containers {
+      for entry in &container.entries {
+        if let Slot::Container(child) = entry.slot {
+          parents[child] += 1;
+        }
+      }
+    }
+
+    let mut order = Vec::with_capacity(self.containers.len());
+    let mut next = 0;
+
+    order.push(root);
+
+    while let Some(&id) = order.get(next) {
+      next += 1;
+
+      for entry in &self.containers[id].entries {
+        if let Slot::Container(child) = entry.slot {
+          parents[child] -= 1;
+
+          if parents[child] == 0 {
+            order.push(child);
+          }
+        }
+      }
+    }
+
+    order
+  }
+}
+
+pub(super) fn encode(root: &BymlValue, endianness: Endianness, version: u16, config: BymlHeaderConfig) -> Result<Vec<u8>, BymlEncodeError> {
+  if !matches!(root, BymlValue::Array(_) | BymlValue::Dictionary(_)) {
+    return Err(BymlEncodeError::InvalidRoot(root.discriminant()));
+  }
+
+  let mut collector = Collector {
+    keys: BTreeSet::new(),
+    strings: BTreeSet::new(),
+    binary_data: BTreeSet::new(),
+  };
+
+  collector.collect(root, config)?;
+
+  for count in [collector.keys.len(), collector.strings.len(), collector.binary_data.len()] {
+    if count > MAX_ENTRIES {
+      return Err(BymlEncodeError::TooManyEntries { count });
+    }
+  }
+
+  let mut encoder = Encoder {
+    version: BymlNodeDiscriminantVersionConfig {
+      version_number: version,
+      feat_binary_data_table: config.feat_binary_data_table,
+    },
+    keys: collector.keys.into_iter().collect(),
+    strings: collector.strings.into_iter().collect(),
+    binary_data: collector.binary_data.into_iter().collect(),
+    containers: Vec::new(),
+    container_ids: BTreeMap::new(),
+    out_of_line: Vec::new(),
+    out_of_line_ids: BTreeMap::new(),
+  };
+
+  let root = match encoder.intern(root)?.1 {
+    Slot::Container(id) => id,
+    _ => unreachable!("the root was checked to be a container"),
+  };
+
+  let mut buffer = Buffer { data: Vec::new(), endianness };
+
+  buffer.data.extend_from_slice(match endianness {
+    Endianness::BigEndian => b"BY",
+    Endianness::LittleEndian => b"YB",
+  });
+  buffer.put(version);
+
+  let key_table_position = buffer.data.len();
+  let string_table_position = key_table_position + 4;
+  let binary_data_table_position = string_table_position + 4;
+  let root_position = if config.feat_binary_data_table {
+    binary_data_table_position + 4
+  } else {
+    binary_data_table_position
+  };
+
+  buffer.data.resize(root_position + 4, 0);
+
+  if !encoder.keys.is_empty() {
+    let keys: Vec<&[u8]> = encoder.keys.iter().map(|key| key.as_bytes()).collect();
+    let position = buffer.position() as u32;
+
+    buffer.put_offset_table(BymlNodeDiscriminants::StringTable, &keys, true);
+    buffer.set_u32(key_table_position, position);
+  }
+
+  if !encoder.strings.is_empty() {
+    let strings: Vec<&[u8]> = encoder.strings.iter().map(|string| string.as_bytes()).collect();
+    let position = buffer.position() as u32;
+
+    buffer.put_offset_table(BymlNodeDiscriminants::StringTable, &strings, true);
+    buffer.set_u32(string_table_position, position);
+  }
+
+  if !encoder.binary_data.is_empty() {
+    let position = buffer.position() as u32;
+
+    buffer.put_offset_table(BymlNodeDiscriminants::BinaryDataTable, &encoder.binary_data, false);
+    buffer.set_u32(binary_data_table_position, position);
+  }
+
+  let order = encoder.order(root);
+  let mut container_offsets = vec![0u32; encoder.containers.len()];
+  let mut out_of_line_offsets = vec![0u32; encoder.out_of_line.len()];
+  let mut position = buffer.position();
+
+  for &id in &order {
+    container_offsets[id] = u32::try_from(position).map_err(|_| BymlEncodeError::TooLarge)?;
+    position += encoder.containers[id].size();
+  }
+
+  for (id, value) in encoder.out_of_line.iter().enumerate() {
+    out_of_line_offsets[id] = u32::try_from(position).map_err(|_| BymlEncodeError::TooLarge)?;
+    position += value.size();
+  }
+
+  if position > u32::MAX as u64 {
+    return Err(BymlEncodeError::TooLarge);
+  }
+
+  buffer.set_u32(root_position, container_offsets[root]);
+  buffer.data.reserve((position - buffer.position()) as usize);
+
+  let value_of = |slot: Slot| match slot {
+    Slot::Inline(value) => value,
+    Slot::Container(id) => container_offsets[id],
+    Slot::OutOfLine(id) => out_of_line_offsets[id],
+  };
+
+  for &id in &order {
+    let container = &encoder.containers[id];
+
+    buffer.put(container.discriminant);
+    buffer.put_u24(container.entries.len() as u32);
+
+    if container.discriminant == BymlNodeDiscriminants::Array.id() {
+      for entry in &container.entries {
+        buffer.put(entry.discriminant);
+      }
+
+      buffer.align();
+
+      for entry in &container.entries {
+        buffer.put(value_of(entry.slot));
+      }
+    } else {
+      for entry in &container.entries {
+        buffer.put_u24(entry.key_index);
+        buffer.put(entry.discriminant);
+        buffer.put(value_of(entry.slot));
+      }
+    }
+  }
+
+  for value in &encoder.out_of_line {
+    match value {
+      OutOfLine::Bits64(bits) => buffer.put(*bits),
+
+      OutOfLine::BinaryData(data) => {
+        buffer.put(data.len() as u32);
+        buffer.data.extend_from_slice(data);
+      }
+
+      OutOfLine::BinaryDataWithParameter(data, parameter) => {
+        buffer.put(data.len() as u32);
+        buffer.put(*parameter);
+        buffer.data.extend_from_slice(data);
+      }
+    }
+
+    buffer.align();
+  }
+
+  Ok(buffer.data)
+}
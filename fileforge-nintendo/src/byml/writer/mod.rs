@@ -0,0 +1,232 @@
+mod encoder;
+
+use fileforge::{
+  binary_reader::{endianness::Endianness, writable::Writable, BinaryReader},
+  error::render::builtin::number::formatted_unsigned::FormattedUnsigned,
+  stream::{error::user_overwrite::UserOverwriteError, ResizableStream, StreamOverwriteError},
+};
+use fileforge_macros::FileforgeError;
+
+use crate::byml::{header::BymlHeaderConfig, node::BymlNodeDiscriminants, value::BymlValue};
+
+const WRITE_CHUNK_SIZE: usize = 64;
+
+/// Encodes a [`BymlValue`] tree as a BYML document.
+///
+/// Keys and strings are sorted and de-duplicated into their tables, and identical containers and out of line values are only stored once.
+/// Every container is placed after all of its parents, so the result can be read with forward-only streams.
+pub struct BymlWriter<'a> {
+  root: &'a BymlValue,
+  endianness: Endianness,
+  version: u16,
+  config: BymlHeaderConfig,
+}
+
+impl<'a> BymlWriter<'a> {
+  pub fn new(root: &'a BymlValue, endianness: Endianness, version: u16) -> Self {
+    Self {
+      root,
+      endianness,
+      version,
+      config: BymlHeaderConfig::default(),
+    }
+  }
+
+  /// Sets the header layout, including whether binary data is stored in a binary data table.
+  pub fn with_header_config(mut self, config: BymlHeaderConfig) -> Self {
+    self.config = config;
+    self
+  }
+}
+
+#[derive(FileforgeError)]
+pub enum BymlEncodeError {
+  #[report(&"Node is not supported by this BYML version")]
+  UnsupportedNode(BymlNodeDiscriminants),
+
+  #[report(&"The root node must be an array or a dictionary")]
+  InvalidRoot(BymlNodeDiscriminants),
+
+  #[report(&"Too many entries")]
+  #[flag("{count} entries don't fit in a 24-bit count", count = FormattedUnsigned::new(*count as u128))]
+  TooManyEntries { count: usize },
+
+  #[report(&"Keys and strings can't contain NUL bytes")]
+  ContainsNul,
+
+  #[report(&"Document is too large to be addressed by 32-bit offsets")]
+  TooLarge,
+}
+
+#[derive(FileforgeError)]
+pub enum BymlWriteError<U: UserOverwriteError> {
+  Encode(#[from] BymlEncodeError),
+
+  #[report(&"Failed to write the document")]
+  Overwrite(StreamOverwriteError<U>),
+}
+
+impl<'pool: 'l, 'l, S: ResizableStream<Type = u8> + 'l> Writable<'pool, 'l, S> for BymlWriter<'_> {
+  type Error = BymlWriteError<S::OverwriteError>;
+
+  /// Inserts the encoded document at the reader's position.
+  async fn overwrite_into(&self, reader: &'l mut BinaryReader<'pool, S>) -> Result<(), Self::Error> {
+    let data = encoder::encode(self.root, self.endianness, self.version, self.config)?;
+    let stream = reader.stream_mut();
+    let mut chunks = data.chunks_exact(WRITE_CHUNK_SIZE);
+
+    for chunk in &mut chunks {
+      stream
+        .overwrite(0, <[u8; WRITE_CHUNK_SIZE]>::try_from(chunk).expect("chunks are exact"))
+        .await
+        .map_err(BymlWriteError::Overwrite)?;
+    }
+
+    for byte in chunks.remainder() {
+      stream.overwrite(0, [*byte]).await.map_err(BymlWriteError::Overwrite)?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::BTreeMap;
+
+  use fileforge::{
+    binary_reader::{endianness::Endianness, BinaryReader},
+    provider::hint::ReadHint,
+    stream::{builtin::provider::ProviderStream, extensions::readable::ReadableStreamExt},
+  };
+
+  use crate::byml::{
+    header::BymlHeaderConfig,
+    node::BymlNode,
+    value::BymlValue,
+    writer::{BymlWriteError, BymlWriter},
+    Byml,
+  };
+
+  fn dictionary(entries: impl IntoIterator<Item = (&'static str, BymlValue)>) -> BymlValue {
+    BymlValue::Dictionary(entries.into_iter().map(|(key, value)| (key.into(), value)).collect::<BTreeMap<_, _>>())
+  }
+
+  async fn write(writer: BymlWriter<'_>) -> Result<Vec<u8>, BymlWriteError<<ProviderStream<Vec<u8>> as fileforge::stream::ResizableStream>::OverwriteError>> {
+    let mut reader = BinaryReader::new_from_provider(Vec::new(), Endianness::BigEndian, ReadHint::new());
+
+    reader.overwrite(&writer).await?;
+
+    Ok(reader.into_stream().into_provider())
+  }
+
+  async fn open(data: Vec<u8>, config: BymlHeaderConfig) -> Byml<'static, ProviderStream<Vec<u8>>> {
+    BinaryReader::new_from_provider(data, Endianness::BigEndian, ReadHint::new())
+      .into_with::<Byml<_>>(config)
+      .await
+      .map_err(|_| {})
+      .unwrap()
+  }
+
+  async fn read(data: Vec<u8>, config: BymlHeaderConfig) -> (Vec<Vec<u8>>, Vec<Vec<u8>>, BymlNode<'static, ProviderStream<Vec<u8>>>) {
+    let mut byml = open(data.clone(), config).await.into_restorable();
+
+    let keys = byml
+      .key_table()
+      .await
+      .map_err(|_| {})
+      .unwrap()
+      .unwrap()
+      .into_string_table()
+      .map_err(|_| {})
+      .unwrap()
+      .into_strings()
+      .await
+      .map_err(|_| {})
+      .unwrap();
+    let strings = match byml.literal_table().await.map_err(|_| {}).unwrap() {
+      Some(table) => table.into_string_table().map_err(|_| {}).unwrap().into_strings().await.map_err(|_| {}).unwrap(),
+      None => Vec::new(),
+    };
+
+    (keys, strings, open(data, config).await.into_root().await.map_err(|_| {}).unwrap().unwrap())
+  }
+
+  #[tokio::test]
+  async fn writes_readable_document() {
+    let root = dictionary([
+      ("Name", BymlValue::String("Bed".into())),
+      ("Scale", BymlValue::Float32(1.5)),
+      ("Items", BymlValue::Array(vec![BymlValue::Integer32(7), BymlValue::String("Pillow".into())])),
+    ]);
+
+    let data = write(BymlWriter::new(&root, Endianness::BigEndian, 2)).await.map_err(|_| {}).unwrap();
+
+    assert_eq!(&data[..4], b"BY\x00\x02");
+
+    let (keys, strings, root) = read(data, BymlHeaderConfig::default()).await;
+
+    assert_eq!(keys, [b"Items".to_vec(), b"Name".to_vec(), b"Scale".to_vec()]);
+    assert_eq!(strings, [b"Bed".to_vec(), b"Pillow".to_vec()]);
+
+    let items = root.get_path("Items", keys.as_slice()).await.map_err(|_| {}).unwrap().unwrap().into_array().map_err(|_| {}).unwrap();
+    let entry = items.into_entry(1).await.map_err(|_| {}).unwrap();
+
+    assert_eq!(entry.value(), 1);
+  }
+
+  #[tokio::test]
+  async fn writes_little_endian_out_of_line_values() {
+    let root = dictionary([("Id", BymlValue::Integer64(-2)), ("Seed", BymlValue::UnsignedInteger64(u64::MAX))]);
+
+    let data = write(BymlWriter::new(&root, Endianness::LittleEndian, 3)).await.map_err(|_| {}).unwrap();
+
+    assert_eq!(&data[..4], b"YB\x03\x00");
+
+    let (keys, _, root) = read(data, BymlHeaderConfig::default()).await;
+    let seed = root.get_path("Seed", keys.as_slice()).await.map_err(|_| {}).unwrap().unwrap();
+
+    assert_eq!(seed.into_unsigned_integer64().map_err(|_| {}).unwrap().value(), u64::MAX);
+  }
+
+  #[tokio::test]
+  async fn deduplicates_identical_containers() {
+    let array = BymlValue::Array(vec![BymlValue::Integer32(1), BymlValue::Integer32(2)]);
+    let root = dictionary([("A", array.clone()), ("B", array)]);
+
+    let data = write(BymlWriter::new(&root, Endianness::BigEndian, 2)).await.map_err(|_| {}).unwrap();
+    let (_, _, root) = read(data, BymlHeaderConfig::default()).await;
+
+    let mut entries = root.into_dictionary().map_err(|_| {}).unwrap().into_entries();
+    let a = entries.next().await.map_err(|_| {}).unwrap();
+    let b = entries.next().await.map_err(|_| {}).unwrap();
+
+    assert_eq!(a.value, b.value);
+  }
+
+  #[tokio::test]
+  async fn stores_binary_data_in_table() {
+    let config = BymlHeaderConfig::build().with_binary_data_table().build();
+    let root = dictionary([("Data", BymlValue::BinaryData(vec![1, 2, 3])), ("Other", BymlValue::BinaryData(vec![0]))]);
+
+    let data = write(BymlWriter::new(&root, Endianness::BigEndian, 1).with_header_config(config)).await.map_err(|_| {}).unwrap();
+    let (keys, _, root) = read(data, config).await;
+    let node = root.get_path("Data", keys.as_slice()).await.map_err(|_| {}).unwrap().unwrap();
+
+    assert_eq!(node.into_binary_data().map_err(|_| {}).unwrap().table_index(), Some(1));
+  }
+
+  #[tokio::test]
+  async fn rejects_unsupported_nodes() {
+    let root = dictionary([("Id", BymlValue::Integer64(1))]);
+
+    assert!(write(BymlWriter::new(&root, Endianness::BigEndian, 2)).await.is_err());
+  }
+
+  #[tokio::test]
+  async fn rejects_scalar_root() {
+    let root = BymlValue::Integer32(1);
+
+    assert!(write(BymlWriter::new(&root, Endianness::BigEndian, 2)).await.is_err());
+  }
+}
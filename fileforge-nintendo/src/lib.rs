@@ -1,4 +1,10 @@
 #![cfg_attr(not(test), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub mod back_reference;
+pub mod bios;
 pub mod byml;
 pub mod sead;
+pub mod yay0;
@@ -0,0 +1,162 @@
+use alloc::vec::Vec;
+use core::hash::Hasher;
+
+use fileforge::binary_reader::{endianness::Endianness, primitive::Primitive};
+
+use crate::sead::sarc::{
+  builder::{SarcBuilder, SarcBuilderFile, SarcEncodeError},
+  header::SARC_HEADER_SIZE,
+  sfat::{entry::SFAT_ENTRY_SIZE, header::SFAT_HEADER_SIZE, name_table::hasher::SfntHasher, name_table::header::SFNT_HEADER_SIZE},
+};
+
+const SARC_VERSION: u16 = 0x0100;
+const NAME_ALIGNMENT: u64 = 4;
+const MAX_HASH_INDEX: u64 = 0xFF_FFFF;
+
+fn align_to(position: u64, alignment: u64) -> u64 {
+  position.next_multiple_of(alignment)
+}
+
+struct Buffer {
+  data: Vec<u8>,
+  endianness: Endianness,
+}
+
+impl Buffer {
+  fn put<const SIZE: usize, P: Primitive<SIZE>>(&mut self, value: P) {
+    let mut bytes = [0; SIZE];
+    value.write(&mut bytes, self.endianness);
+    self.data.extend_from_slice(&bytes);
+  }
+
+  fn pad_to(&mut self, position: u64) {
+    self.data.resize(position as usize, 0);
+  }
+}
+
+struct Placed<'a> {
+  file: &'a SarcBuilderFile,
+  hash: u32,
+  sequence: u8,
+  hash_index: u32,
+  start: u32,
+  end: u32,
+}
+
+fn offset(position: u64) -> Result<u32, SarcEncodeError> {
+  u32::try_from(position).map_err(|_| SarcEncodeError::TooLarge)
+}
+
+pub(super) fn encode(builder: &SarcBuilder) -> Result<Vec<u8>, SarcEncodeError> {
+  let count = u16::try_from(builder.files.len()).map_err(|_| SarcEncodeError::TooManyFiles { count: builder.files.len() })?;
+  let hash = |name: &[u8]| {
+    let mut hasher = SfntHasher::new(builder.hash_multiplier, builder.hash_mode);
+    hasher.write(name);
+    hasher.get_hash()
+  };
+
+  for file in &builder.files {
+    if file.name.as_bytes().contains(&0) {
+      return Err(SarcEncodeError::ContainsNul);
+    }
+
+    if !file.alignment.is_power_of_two() {
+      return Err(SarcEncodeError::InvalidAlignment { alignment: file.alignment });
+    }
+  }
+
+  let mut placed = builder
+    .files
+    .iter()
+    .map(|file| Placed {
+      file,
+      hash: hash(file.name.as_bytes()),
+      sequence: 0,
+      hash_index: 0,
+      start: 0,
+      end: 0,
+    })
+    .collect::<Vec<_>>();
+
+  // stable, so names sharing a hash keep the order they were added in
+  placed.sort_by_key(|file| file.hash);
+
+  let name_table_start = SARC_HEADER_SIZE as u64 + SFAT_HEADER_SIZE as u64 + count as u64 * SFAT_ENTRY_SIZE + SFNT_HEADER_SIZE as u64;
+  let mut name_position = 0;
+
+  for index in 0..placed.len() {
+    let collisions = placed[..index].iter().rev().take_while(|other| other.hash == placed[index].hash);
+
+    if collisions.clone().any(|other| other.file.name == placed[index].file.name) {
+      return Err(SarcEncodeError::DuplicateName);
+    }
+
+    placed[index].sequence = u8::try_from(collisions.count() + 1).map_err(|_| SarcEncodeError::TooManyCollisions)?;
+
+    if !builder.names {
+      continue;
+    }
+
+    if name_position / NAME_ALIGNMENT > MAX_HASH_INDEX {
+      return Err(SarcEncodeError::TooLarge);
+    }
+
+    placed[index].hash_index = (name_position / NAME_ALIGNMENT) as u32;
+    name_position = align_to(name_position + placed[index].file.name.len() as u64 + 1, NAME_ALIGNMENT);
+  }
+
+  let data_alignment = placed.iter().map(|file| file.file.alignment as u64).fold(NAME_ALIGNMENT, u64::max);
+  let data_section_offset = align_to(name_table_start + name_position, data_alignment);
+  let mut data_position = 0;
+
+  for file in &mut placed {
+    data_position = align_to(data_position, file.file.alignment as u64);
+    file.start = offset(data_position)?;
+    data_position += file.file.data.len() as u64;
+    file.end = offset(data_position)?;
+  }
+
+  let size = offset(data_section_offset + data_position)?;
+  let mut buffer = Buffer {
+    data: Vec::with_capacity(size as usize),
+    endianness: builder.endianness,
+  };
+
+  buffer.data.extend_from_slice(b"SARC");
+  buffer.put(SARC_HEADER_SIZE);
+  buffer.put(0xFEFFu16);
+  buffer.put(size);
+  buffer.put(data_section_offset as u32);
+  buffer.put(SARC_VERSION);
+  buffer.put(0u16);
+
+  buffer.data.extend_from_slice(b"SFAT");
+  buffer.put(SFAT_HEADER_SIZE);
+  buffer.put(count);
+  buffer.put(builder.hash_multiplier);
+
+  for file in &placed {
+    buffer.put(file.hash);
+    buffer.put(if builder.names { (file.sequence as u32) << 24 | file.hash_index } else { 0 });
+    buffer.put(file.start);
+    buffer.put(file.end);
+  }
+
+  buffer.data.extend_from_slice(b"SFNT");
+  buffer.put(SFNT_HEADER_SIZE);
+  buffer.put(0u16);
+
+  for file in placed.iter().filter(|_| builder.names) {
+    buffer.data.extend_from_slice(file.file.name.as_bytes());
+    buffer.pad_to(align_to(buffer.data.len() as u64 + 1, NAME_ALIGNMENT));
+  }
+
+  buffer.pad_to(data_section_offset);
+
+  for file in &placed {
+    buffer.pad_to(data_section_offset + file.start as u64);
+    buffer.data.extend_from_slice(&file.file.data);
+  }
+
+  Ok(buffer.data)
+}
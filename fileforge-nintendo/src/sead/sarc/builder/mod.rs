@@ -0,0 +1,259 @@
+mod encoder;
+
+use alloc::{string::String, vec::Vec};
+
+use fileforge::{
+  binary_reader::{endianness::Endianness, writable::Writable, BinaryReader},
+  error::render::builtin::number::formatted_unsigned::FormattedUnsigned,
+  stream::{error::user_overwrite::UserOverwriteError, ResizableStream, StreamOverwriteError},
+};
+use fileforge_macros::FileforgeError;
+
+use crate::sead::sarc::sfat::name_table::hasher::{HashMode, DEFAULT_HASH_MULTIPLIER};
+
+const WRITE_CHUNK_SIZE: usize = 64;
+
+struct SarcBuilderFile {
+  name: String,
+  data: Vec<u8>,
+  alignment: u32,
+}
+
+/// Builds a SARC archive.
+///
+/// Entries are sorted by name hash, and names sharing a hash are numbered in the order they were added. File data is laid out in the same order,
+/// each file aligned to its own requirement and the data section aligned to the largest of them.
+pub struct SarcBuilder {
+  files: Vec<SarcBuilderFile>,
+  endianness: Endianness,
+  hash_multiplier: u32,
+  hash_mode: HashMode,
+  names: bool,
+}
+
+impl SarcBuilder {
+  pub fn new(endianness: Endianness) -> Self {
+    Self {
+      files: Vec::new(),
+      endianness,
+      hash_multiplier: DEFAULT_HASH_MULTIPLIER,
+      hash_mode: HashMode::default(),
+      names: true,
+    }
+  }
+
+  pub fn with_hash_multiplier(mut self, multiplier: u32) -> Self {
+    self.hash_multiplier = multiplier;
+    self
+  }
+
+  /// Sets how name bytes are hashed. Wii U archives hash them as [`HashMode::Unsigned`].
+  pub fn with_hash_mode(mut self, mode: HashMode) -> Self {
+    self.hash_mode = mode;
+    self
+  }
+
+  /// Leaves every name out of the name table, so files can only be found by hash.
+  pub fn without_names(mut self) -> Self {
+    self.names = false;
+    self
+  }
+
+  /// Adds a file whose data starts at a multiple of `alignment`, which must be a power of two.
+  pub fn add_file(&mut self, name: impl Into<String>, data: impl Into<Vec<u8>>, alignment: u32) -> &mut Self {
+    self.files.push(SarcBuilderFile {
+      name: name.into(),
+      data: data.into(),
+      alignment,
+    });
+    self
+  }
+}
+
+#[derive(FileforgeError)]
+pub enum SarcEncodeError {
+  #[report(&"Too many files")]
+  #[flag("{count} files don't fit in a 16-bit count", count = FormattedUnsigned::new(*count as u128))]
+  TooManyFiles { count: usize },
+
+  #[report(&"File names can't contain NUL bytes")]
+  ContainsNul,
+
+  #[report(&"Two files have the same name")]
+  DuplicateName,
+
+  #[report(&"Too many names share a hash")]
+  TooManyCollisions,
+
+  #[report(&"Invalid alignment")]
+  #[flag("{alignment} is not a power of two", alignment = FormattedUnsigned::new(*alignment as u128))]
+  InvalidAlignment { alignment: u32 },
+
+  #[report(&"Archive is too large to be addressed by 32-bit offsets")]
+  TooLarge,
+}
+
+#[derive(FileforgeError)]
+pub enum SarcWriteError<U: UserOverwriteError> {
+  Encode(#[from] SarcEncodeError),
+
+  #[report(&"Failed to write the archive")]
+  Overwrite(StreamOverwriteError<U>),
+}
+
+impl<'pool: 'l, 'l, S: ResizableStream<Type = u8> + 'l> Writable<'pool, 'l, S> for SarcBuilder {
+  type Error = SarcWriteError<S::OverwriteError>;
+
+  /// Inserts the encoded archive at the reader's position.
+  async fn overwrite_into(&self, reader: &'l mut BinaryReader<'pool, S>) -> Result<(), Self::Error> {
+    let data = encoder::encode(self)?;
+    let stream = reader.stream_mut();
+    let mut chunks = data.chunks_exact(WRITE_CHUNK_SIZE);
+
+    for chunk in &mut chunks {
+      stream
+        .overwrite(0, <[u8; WRITE_CHUNK_SIZE]>::try_from(chunk).expect("chunks are exact"))
+        .await
+        .map_err(SarcWriteError::Overwrite)?;
+    }
+
+    for byte in chunks.remainder() {
+      stream.overwrite(0, [*byte]).await.map_err(SarcWriteError::Overwrite)?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use fileforge::{
+    binary_reader::{endianness::Endianness, BinaryReader, PrimitiveReader},
+    provider::hint::ReadHint,
+    stream::{builtin::provider::ProviderStream, ReadableStream},
+  };
+
+  use crate::sead::sarc::{
+    builder::{SarcBuilder, SarcEncodeError, SarcWriteError},
+    sfat::name_table::hasher::HashMode,
+    Sarc,
+  };
+
+  async fn write(builder: &SarcBuilder) -> Result<Vec<u8>, SarcWriteError<<ProviderStream<Vec<u8>> as fileforge::stream::ResizableStream>::OverwriteError>> {
+    let mut reader = BinaryReader::new_from_provider(Vec::new(), Endianness::BigEndian, ReadHint::new());
+
+    reader.overwrite(builder).await?;
+
+    Ok(reader.into_stream().into_provider())
+  }
+
+  async fn read(builder: &SarcBuilder, mode: HashMode) -> Sarc<'static, ProviderStream<Vec<u8>>> {
+    let data = write(builder).await.map_err(|_| {}).unwrap();
+
+    BinaryReader::new_from_provider(data, Endianness::BigEndian, ReadHint::new())
+      .into_with::<Sarc<_>>(mode)
+      .await
+      .map_err(|_| {})
+      .unwrap()
+  }
+
+  async fn contents(sarc: &mut Sarc<'static, ProviderStream<Vec<u8>>>, name: &str) -> Option<Vec<u8>> {
+    let mut reader = sarc.open(name).await.map_err(|_| {}).unwrap()?;
+    let mut contents = Vec::new();
+
+    for _ in 0..reader.stream().len().unwrap() {
+      contents.push(reader.get::<u8>().await.map_err(|_| {}).unwrap());
+    }
+
+    Some(contents)
+  }
+
+  #[tokio::test]
+  async fn round_trips_both_endiannesses() {
+    for endianness in [Endianness::BigEndian, Endianness::LittleEndian] {
+      let mut builder = SarcBuilder::new(endianness);
+
+      builder.add_file("Stage.byml", b"BY\x00\x02".to_vec(), 4).add_file("Layout/Main.bflyt", b"FLYT".to_vec(), 4);
+
+      let mut sarc = read(&builder, HashMode::default()).await;
+
+      assert_eq!(sarc.header().endianness, endianness);
+      assert_eq!(sarc.header().version, (1, 0));
+      assert_eq!(contents(&mut sarc, "Stage.byml").await.unwrap(), b"BY\x00\x02");
+      assert_eq!(contents(&mut sarc, "Layout/Main.bflyt").await.unwrap(), b"FLYT");
+    }
+  }
+
+  #[tokio::test]
+  async fn honours_alignment() {
+    let mut builder = SarcBuilder::new(Endianness::LittleEndian);
+
+    builder.add_file("a", b"1".to_vec(), 4).add_file("b", b"2".to_vec(), 0x80).add_file("c", b"3".to_vec(), 0x2000);
+
+    let mut sarc = read(&builder, HashMode::default()).await;
+    let data_section_offset = sarc.header().data_section_offset;
+
+    assert_eq!(data_section_offset % 0x2000, 0);
+
+    for index in 0..sarc.len() {
+      let entry = sarc.entry(index).await.map_err(|_| {}).unwrap();
+      let name = sarc.file(index).await.map_err(|_| {}).unwrap().name.unwrap();
+      let alignment = match name.as_str() {
+        "a" => 4,
+        "b" => 0x80,
+        _ => 0x2000,
+      };
+
+      assert_eq!((data_section_offset + entry.start_offset) % alignment, 0);
+    }
+  }
+
+  #[tokio::test]
+  async fn numbers_colliding_names() {
+    // with a multiplier of 1 the hash is the sum of the bytes, so every permutation collides
+    let mut builder = SarcBuilder::new(Endianness::BigEndian).with_hash_multiplier(1);
+
+    builder.add_file("abc", b"1".to_vec(), 4).add_file("cab", b"2".to_vec(), 4).add_file("bca", b"3".to_vec(), 4);
+
+    let mut sarc = read(&builder, HashMode::default()).await;
+
+    for index in 0..3 {
+      let attributes = sarc.entry(index).await.map_err(|_| {}).unwrap().filename_attributes.unwrap();
+
+      assert_eq!(attributes.sequence.get(), index as u8 + 1);
+    }
+
+    assert_eq!(contents(&mut sarc, "abc").await.unwrap(), b"1");
+    assert_eq!(contents(&mut sarc, "cab").await.unwrap(), b"2");
+    assert_eq!(contents(&mut sarc, "bca").await.unwrap(), b"3");
+    assert_eq!(contents(&mut sarc, "acb").await, None);
+  }
+
+  #[tokio::test]
+  async fn hashes_in_unsigned_mode() {
+    let mut builder = SarcBuilder::new(Endianness::BigEndian).with_hash_mode(HashMode::Unsigned);
+
+    builder.add_file("Kinopio_Ä.bfres", b"FRES".to_vec(), 0x2000);
+
+    assert_eq!(contents(&mut read(&builder, HashMode::Unsigned).await, "Kinopio_Ä.bfres").await.unwrap(), b"FRES");
+    assert_eq!(contents(&mut read(&builder, HashMode::Signed).await, "Kinopio_Ä.bfres").await, None);
+  }
+
+  #[tokio::test]
+  async fn rejects_invalid_files() {
+    let mut builder = SarcBuilder::new(Endianness::BigEndian);
+    builder.add_file("a", Vec::new(), 3);
+
+    assert!(matches!(write(&builder).await, Err(SarcWriteError::Encode(SarcEncodeError::InvalidAlignment { alignment: 3 }))));
+
+    let mut builder = SarcBuilder::new(Endianness::BigEndian);
+    builder.add_file("a\0b", Vec::new(), 4);
+
+    assert!(matches!(write(&builder).await, Err(SarcWriteError::Encode(SarcEncodeError::ContainsNul))));
+
+    let mut builder = SarcBuilder::new(Endianness::BigEndian);
+    builder.add_file("a", Vec::new(), 4).add_file("a", Vec::new(), 4);
+
+    assert!(matches!(write(&builder).await, Err(SarcWriteError::Encode(SarcEncodeError::DuplicateName))));
+  }
+}
@@ -0,0 +1,41 @@
+use alloc::string::String;
+
+use fileforge::{binary_reader::BinaryReader, stream::RestorableStream};
+
+use crate::sead::sarc::{stream::SarcFileStream, Sarc, SarcAccessError};
+
+/// A file in a [`Sarc`], with a reader bounded to its data.
+pub struct SarcFile<'a, 'pool, S: RestorableStream<Type = u8>> {
+  pub hash: u32,
+  /// `None` when the entry has no name in the name table. An [`SfntHashDictionary`] can often recover it from [`Self::hash`].
+  ///
+  /// [`SfntHashDictionary`]: crate::sead::sarc::sfat::name_table::dictionary::SfntHashDictionary
+  pub name: Option<String>,
+  pub reader: BinaryReader<'pool, SarcFileStream<&'a mut S>>,
+}
+
+/// Visits every file in a [`Sarc`] in SFAT order, which is sorted by name hash.
+///
+/// Files without a name that share their hash with another such file can't be told apart, so they're given as
+/// [`SarcAccessError::HashCollision`] instead. They can still be read by index with [`Sarc::file`].
+pub struct SarcFiles<'a, 'pool, S: RestorableStream<Type = u8>> {
+  pub(crate) sarc: &'a mut Sarc<'pool, S>,
+  pub(crate) index: u16,
+}
+
+impl<'a, 'pool, S: RestorableStream<Type = u8>> SarcFiles<'a, 'pool, S> {
+  pub async fn next(&mut self) -> Option<Result<SarcFile<'_, 'pool, S>, SarcAccessError<'pool, S>>> {
+    if self.index >= self.sarc.len() {
+      return None;
+    }
+
+    let index = self.index;
+    self.index += 1;
+
+    if let Err(error) = self.sarc.check_unnamed_collision(index).await {
+      return Some(Err(error));
+    }
+
+    Some(self.sarc.file(index).await)
+  }
+}
@@ -1,13 +1,12 @@
 pub mod readable;
 
-use fileforge::{binary_reader::endianness::Endianness, stream::ReadableStream};
+use fileforge::binary_reader::endianness::Endianness;
 
-use crate::sead::sarc::sfat::SfatTable;
+pub const SARC_HEADER_SIZE: u16 = 0x14;
 
-pub struct SarcHeader<'pool, UnderlyingStream: ReadableStream<Type = u8>> {
+pub struct SarcHeader {
   pub endianness: Endianness,
   pub size: u32,
   pub data_section_offset: u32,
   pub version: (u8, u8),
-  pub sfat_table: SfatTable<'pool, UnderlyingStream>,
 }
@@ -1,83 +1,76 @@
-use core::ops::Sub;
-
 use fileforge::{
   binary_reader::{
     endianness::Endianness,
-    error::{primitive_name_annotation::PrimitiveName, GetPrimitiveError},
+    error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError},
     readable::Readable,
-    PrimitiveReader,
+    BinaryReader, PrimitiveReader,
   },
-  error::{ext::annotations::annotated::Annotated, FileforgeError},
+  error::{ext::annotations::annotated::Annotated, render::builtin::number::formatted_unsigned::FormattedUnsigned},
   stream::{error::user_read::UserReadError, ReadableStream},
 };
-
+use fileforge_macros::FileforgeError;
 use fileforge_std::{
   byte_order_mark::{error::ByteOrderMarkError, ByteOrderMark},
   magic::{Magic, MagicError},
 };
 
-use crate::sead::sarc::{header::SarcHeader, sfat::SfatTable};
+use crate::sead::sarc::header::{SarcHeader, SARC_HEADER_SIZE};
 
 pub const SARC_MAGIC: Magic<4> = Magic::from_byte_ref(b"SARC");
 pub const SARC_BOM: ByteOrderMark = ByteOrderMark::from_byte_ref(Endianness::BigEndian, &[0xFE, 0xFF]);
 
-impl<'pool, S: ReadableStream<Type = u8>> Readable<'pool, S> for SarcHeader<'pool, S> {
+impl<'pool, S: ReadableStream<Type = u8>> Readable<'pool, S> for SarcHeader {
   type Error = SarcHeaderReadError<'pool, S::ReadError>;
   type Argument = ();
 
-  async fn read(reader: &mut fileforge::binary_reader::BinaryReader<'pool, S>, _: Self::Argument) -> Result<Self, Self::Error> {
-    reader.read_with::<Magic<4>>(SARC_MAGIC).await.map_err(|e| SarcHeaderReadError::Magic(e))?;
+  async fn read(reader: &mut BinaryReader<'pool, S>, _: Self::Argument) -> Result<Self, Self::Error> {
+    reader.read_with::<Magic<4>>(SARC_MAGIC).await.map_err(SarcHeaderReadError::Magic)?;
 
-    let header_length: u16 = reader.get().await.map_err(|e| SarcHeaderReadError::HeaderLength(e))?;
+    // the header length comes before the BOM, so it's read in whatever order the reader is in and swapped afterwards if needed
+    let reader_endianness = reader.get_endianness();
+    let header_length: u16 = reader.get().await.map_err(SarcHeaderReadError::HeaderLength)?;
 
-    if header_length != 0x14 {
-      panic!("todo: make this an error");
-    }
+    let endianness = reader.read_with::<ByteOrderMark>(SARC_BOM).await.map_err(SarcHeaderReadError::BOM)?.endianness();
 
-    let endianness = reader.read_with::<ByteOrderMark>(SARC_BOM).await.map_err(|e| SarcHeaderReadError::BOM(e))?.endianness();
+    let header_length = if endianness == reader_endianness { header_length } else { header_length.swap_bytes() };
+
+    if header_length != SARC_HEADER_SIZE {
+      return Err(SarcHeaderReadError::InvalidHeaderLength { length: header_length });
+    }
 
     let mut reader = reader.borrow_fork();
 
     reader.set_endianness(endianness);
 
-    let size = reader.get().await.map_err(|e| SarcHeaderReadError::Size(e))?;
-    let data_section_offset: u32 = reader.get().await.map_err(|e| SarcHeaderReadError::DataSectionOffset(e))?;
-    let version: u16 = reader.get().await.map_err(|e| SarcHeaderReadError::Version(e))?;
+    let size = reader.get().await.map_err(SarcHeaderReadError::Size)?;
+    let data_section_offset: u32 = reader.get().await.map_err(SarcHeaderReadError::DataSectionOffset)?;
+    let version: u16 = reader.get().await.map_err(SarcHeaderReadError::Version)?;
     let version = ((version >> 8) as u8, (version & 0xFF) as u8);
 
-    let _unused: u16 = reader.get().await.map_err(|e| SarcHeaderReadError::Unused(e))?;
+    let _unused: u16 = reader.get().await.map_err(SarcHeaderReadError::Unused)?;
 
-    let Some(sfat_table_size) = data_section_offset.checked_sub(header_length as u32) else {
-      panic!("todo: make this an error");
-    };
-
-    todo!();
-    // Ok(SarcHeader {
-    //   endianness,
-    //   size,
-    //   version,
-    //   data_section_offset,
-    //   sfat_table:
-    // })
+    Ok(SarcHeader {
+      endianness,
+      size,
+      data_section_offset,
+      version,
+    })
   }
 }
 
+#[derive(FileforgeError)]
 pub enum SarcHeaderReadError<'pool, U: UserReadError> {
   Magic(MagicError<'pool, 4, U>),
   BOM(ByteOrderMarkError<'pool, U>),
-  Size(Annotated<PrimitiveName<fileforge::binary_reader::error::common::Read>, GetPrimitiveError<'pool, U>>),
-  DataSectionOffset(Annotated<PrimitiveName<fileforge::binary_reader::error::common::Read>, GetPrimitiveError<'pool, U>>),
-  Version(Annotated<PrimitiveName<fileforge::binary_reader::error::common::Read>, GetPrimitiveError<'pool, U>>),
-  Unused(Annotated<PrimitiveName<fileforge::binary_reader::error::common::Read>, GetPrimitiveError<'pool, U>>),
-  HeaderLength(Annotated<PrimitiveName<fileforge::binary_reader::error::common::Read>, GetPrimitiveError<'pool, U>>),
-}
-
-impl<'pool, U: UserReadError> FileforgeError for SarcHeaderReadError<'pool, U> {
-  fn render_into_report<P: fileforge::diagnostic::pool::DiagnosticPoolProvider + Clone, const ITEM_NAME_SIZE: usize>(
-    &self,
-    provider: P,
-    callback: impl for<'tag, 'b> FnOnce(fileforge::error::report::Report<'tag, 'b, ITEM_NAME_SIZE, P>) -> (),
-  ) {
-    todo!()
-  }
+  Size(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  DataSectionOffset(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  Version(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  Unused(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  HeaderLength(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+
+  #[report(&"Unexpected SARC header length")]
+  #[flag("Expected a length of 20, found {length}", length = FormattedUnsigned::new(*length as u128))]
+  InvalidHeaderLength {
+    length: u16,
+  },
 }
@@ -1,5 +1,512 @@
+#[cfg(feature = "alloc")]
+pub mod builder;
+#[cfg(feature = "alloc")]
+pub mod file;
 pub mod header;
 pub mod readable;
 pub mod sfat;
+pub mod stream;
 
-pub struct Sarc {}
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+use fileforge::{
+  binary_reader::{
+    error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError, SkipError},
+    snapshot::BinaryReaderSnapshot,
+    BinaryReader, PrimitiveReader,
+  },
+  error::{ext::annotations::annotated::Annotated, render::builtin::number::formatted_unsigned::FormattedUnsigned},
+  stream::{error::stream_restore::StreamRestoreError, RestorableStream},
+};
+use fileforge_macros::FileforgeError;
+
+#[cfg(feature = "alloc")]
+use crate::sead::sarc::file::{SarcFile, SarcFiles};
+use crate::sead::sarc::{
+  header::{SarcHeader, SARC_HEADER_SIZE},
+  sfat::{
+    entry::{attributes::FilenameAttributes, SfatEntry, SfatEntryError, SFAT_ENTRY_SIZE},
+    header::SFAT_HEADER_SIZE,
+    name_table::header::SFNT_HEADER_SIZE,
+    SfatTable,
+  },
+  stream::SarcFileStream,
+};
+
+/// Names are padded to this alignment, and an entry's `hash_index` counts in units of it.
+const NAME_ALIGNMENT: u64 = 4;
+
+/// A SARC archive.
+///
+/// Entries, names and file data are reached by restoring to the start of the archive and skipping forwards, so they can be visited in any order.
+pub struct Sarc<'pool, S: RestorableStream<Type = u8>> {
+  header: SarcHeader,
+  sfat: SfatTable,
+  has_name_table: bool,
+  start: BinaryReaderSnapshot<'pool, S>,
+  reader: BinaryReader<'pool, S>,
+}
+
+#[derive(FileforgeError)]
+pub enum SarcAccessError<'pool, S: RestorableStream<Type = u8>> {
+  #[report(&"Failed to restore to the start of the archive")]
+  Restore(StreamRestoreError<S::RestoreError>),
+
+  Skip(#[from] SkipError<'pool, S::SkipError>),
+  ReadEntry(#[from] SfatEntryError<'pool, S::ReadError>),
+  ReadName(#[from] Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>),
+
+  #[report(&"File index out of bounds")]
+  #[flag("Index {index} is out of bounds for {count} files", index = FormattedUnsigned::new(*index as u128), count = FormattedUnsigned::new(*count as u128))]
+  IndexOutOfBounds {
+    index: u16,
+    count: u16,
+  },
+
+  #[report(&"File data ends before it starts")]
+  InvalidRange,
+
+  #[report(&"File name is not valid UTF-8")]
+  InvalidUtf8,
+
+  #[report(&"Entry has a name, but the archive has no name table")]
+  MissingNameTable,
+
+  #[report(&"Hash collision between unnamed files")]
+  #[flag("{count} files without names share the hash {hash}, so none of them can be told apart", count = FormattedUnsigned::new(*count as u128), hash = FormattedUnsigned::new(*hash as u128))]
+  HashCollision {
+    hash: u32,
+    count: u16,
+  },
+}
+
+impl<'pool, S: RestorableStream<Type = u8>> Sarc<'pool, S> {
+  pub fn header(&self) -> &SarcHeader {
+    &self.header
+  }
+
+  pub fn sfat(&self) -> &SfatTable {
+    &self.sfat
+  }
+
+  pub fn len(&self) -> u16 {
+    self.sfat.header().file_count
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Whether the archive has an SFNT section. Archives without one only identify their files by hash.
+  pub fn has_name_table(&self) -> bool {
+    self.has_name_table
+  }
+
+  pub(crate) fn entries_end(&self) -> u64 {
+    SARC_HEADER_SIZE as u64 + SFAT_HEADER_SIZE as u64 + self.len() as u64 * SFAT_ENTRY_SIZE
+  }
+
+  pub(crate) fn name_table_offset(&self) -> u64 {
+    self.entries_end() + SFNT_HEADER_SIZE as u64
+  }
+
+  /// Moves to `position`, relative to the start of the archive.
+  async fn seek(&mut self, position: u64) -> Result<(), SarcAccessError<'pool, S>> {
+    self.reader.restore(self.start.clone()).await.map_err(SarcAccessError::Restore)?;
+    self.reader.set_endianness(self.header.endianness);
+
+    Ok(self.reader.skip(position).await?)
+  }
+
+  pub async fn entry(&mut self, index: u16) -> Result<SfatEntry, SarcAccessError<'pool, S>> {
+    if index >= self.len() {
+      return Err(SarcAccessError::IndexOutOfBounds { index, count: self.len() });
+    }
+
+    self.seek(SARC_HEADER_SIZE as u64 + SFAT_HEADER_SIZE as u64 + index as u64 * SFAT_ENTRY_SIZE).await?;
+
+    Ok(self.reader.read().await?)
+  }
+
+  async fn seek_name(&mut self, attributes: &FilenameAttributes) -> Result<(), SarcAccessError<'pool, S>> {
+    if !self.has_name_table {
+      return Err(SarcAccessError::MissingNameTable);
+    }
+
+    self.seek(self.name_table_offset() + attributes.hash_index as u64 * NAME_ALIGNMENT).await
+  }
+
+  /// Compares the name at `attributes` against `name` a padded chunk at a time, stopping at the first difference.
+  async fn name_matches(&mut self, attributes: &FilenameAttributes, name: &[u8]) -> Result<bool, SarcAccessError<'pool, S>> {
+    self.seek_name(attributes).await?;
+
+    for chunk_start in (0..=name.len()).step_by(NAME_ALIGNMENT as usize) {
+      let chunk: [u8; NAME_ALIGNMENT as usize] = self.reader.get().await?;
+      let expected = &name[chunk_start..name.len().min(chunk_start + NAME_ALIGNMENT as usize)];
+
+      if chunk[..expected.len()] != *expected || (expected.len() < chunk.len() && chunk[expected.len()] != 0) {
+        return Ok(false);
+      }
+    }
+
+    Ok(true)
+  }
+
+  #[cfg(feature = "alloc")]
+  async fn read_name(&mut self, attributes: &FilenameAttributes) -> Result<String, SarcAccessError<'pool, S>> {
+    self.seek_name(attributes).await?;
+
+    let mut name = Vec::new();
+
+    loop {
+      let chunk: [u8; NAME_ALIGNMENT as usize] = self.reader.get().await?;
+      let non_null = chunk.split(|&v| v == 0).next().unwrap();
+
+      name.extend_from_slice(non_null);
+
+      if non_null.len() != chunk.len() {
+        break;
+      }
+    }
+
+    String::from_utf8(name).map_err(|_| SarcAccessError::InvalidUtf8)
+  }
+
+  /// Gives a reader over `entry`'s data. Its offsets are relative to the start of the file.
+  pub async fn file_reader(&mut self, entry: &SfatEntry) -> Result<BinaryReader<'pool, SarcFileStream<&mut S>>, SarcAccessError<'pool, S>> {
+    let length = entry.end_offset.checked_sub(entry.start_offset).ok_or(SarcAccessError::InvalidRange)?;
+
+    self.seek(self.header.data_section_offset as u64 + entry.start_offset as u64).await?;
+
+    Ok(BinaryReader::new(SarcFileStream::new(self.reader.stream_mut(), length as u64), self.header.endianness))
+  }
+
+  /// Finds the file called `name`.
+  ///
+  /// Entries are sorted by name hash, so this binary searches for the first entry with `name`'s hash and then compares names
+  /// through each entry's `hash_index` until the hash changes. Entries without a name are matched by their hash alone, which
+  /// fails with [`SarcAccessError::HashCollision`] when more than one of them has `name`'s hash.
+  pub async fn open(&mut self, name: &str) -> Result<Option<BinaryReader<'pool, SarcFileStream<&mut S>>>, SarcAccessError<'pool, S>> {
+    let name = name.as_bytes();
+    let hash = self.sfat.hash(name);
+
+    let mut low = 0;
+    let mut high = self.len();
+
+    while low < high {
+      let middle = low + (high - low) / 2;
+
+      if self.entry(middle).await?.filename_hash < hash {
+        low = middle + 1;
+      } else {
+        high = middle;
+      }
+    }
+
+    let mut unnamed = None;
+    let mut unnamed_count = 0;
+
+    for index in low..self.len() {
+      let entry = self.entry(index).await?;
+
+      if entry.filename_hash != hash {
+        break;
+      }
+
+      match &entry.filename_attributes {
+        Some(attributes) => {
+          if self.name_matches(attributes, name).await? {
+            return Ok(Some(self.file_reader(&entry).await?));
+          }
+        }
+        None => {
+          unnamed_count += 1;
+          unnamed.get_or_insert(entry);
+        }
+      }
+    }
+
+    match unnamed {
+      Some(_) if unnamed_count > 1 => Err(SarcAccessError::HashCollision { hash, count: unnamed_count }),
+      Some(entry) => Ok(Some(self.file_reader(&entry).await?)),
+      None => Ok(None),
+    }
+  }
+
+  /// Fails with [`SarcAccessError::HashCollision`] if the entry at `index` has no name and shares its hash with another entry without one.
+  ///
+  /// Entries are sorted by hash, so only the entries next to it need checking.
+  pub async fn check_unnamed_collision(&mut self, index: u16) -> Result<(), SarcAccessError<'pool, S>> {
+    let entry = self.entry(index).await?;
+
+    if entry.filename_attributes.is_some() {
+      return Ok(());
+    }
+
+    let hash = entry.filename_hash;
+    let mut count = 1;
+
+    for neighbour in (0..index).rev() {
+      let neighbour = self.entry(neighbour).await?;
+
+      if neighbour.filename_hash != hash {
+        break;
+      }
+
+      if neighbour.filename_attributes.is_none() {
+        count += 1;
+      }
+    }
+
+    for neighbour in index + 1..self.len() {
+      let neighbour = self.entry(neighbour).await?;
+
+      if neighbour.filename_hash != hash {
+        break;
+      }
+
+      if neighbour.filename_attributes.is_none() {
+        count += 1;
+      }
+    }
+
+    match count {
+      1 => Ok(()),
+      count => Err(SarcAccessError::HashCollision { hash, count }),
+    }
+  }
+
+  #[cfg(feature = "alloc")]
+  pub async fn file(&mut self, index: u16) -> Result<SarcFile<'_, 'pool, S>, SarcAccessError<'pool, S>> {
+    let entry = self.entry(index).await?;
+
+    let name = match &entry.filename_attributes {
+      Some(attributes) => Some(self.read_name(attributes).await?),
+      None => None,
+    };
+
+    Ok(SarcFile {
+      hash: entry.filename_hash,
+      name,
+      reader: self.file_reader(&entry).await?,
+    })
+  }
+
+  #[cfg(feature = "alloc")]
+  pub fn files(&mut self) -> SarcFiles<'_, 'pool, S> {
+    SarcFiles { sarc: self, index: 0 }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use fileforge::{
+    binary_reader::{endianness::Endianness, BinaryReader, PrimitiveReader},
+    provider::hint::ReadHint,
+    stream::{builtin::provider::ProviderStream, ReadableStream},
+  };
+
+  use crate::sead::{
+    sarc::{
+      builder::SarcBuilder,
+      sfat::name_table::{dictionary::SfntHashDictionary, hasher::HashMode},
+      Sarc, SarcAccessError,
+    },
+    yaz0::{readable::Immutable, Yaz0Stream},
+  };
+
+  async fn stage_map() -> Sarc<'static, Yaz0Stream<'static, ProviderStream<Vec<u8>>, Immutable>> {
+    let reader = BinaryReader::new_from_provider(
+      include_bytes!("../../../../fileforge-test/binaries/SkyWorldHomeStageMap.szs").to_vec(),
+      Endianness::BigEndian,
+      ReadHint::new(),
+    );
+    let stream = reader.into_with::<Yaz0Stream<_, _>>(Immutable).await.map_err(|_| {}).unwrap();
+
+    BinaryReader::new(stream, Endianness::BigEndian)
+      .into_with::<Sarc<_>>(HashMode::default())
+      .await
+      .map_err(|_| {})
+      .unwrap()
+  }
+
+  async fn write(builder: &SarcBuilder) -> Vec<u8> {
+    let mut reader = BinaryReader::new_from_provider(Vec::new(), Endianness::BigEndian, ReadHint::new());
+
+    reader.overwrite(builder).await.map_err(|_| {}).unwrap();
+    reader.into_stream().into_provider()
+  }
+
+  async fn read(data: Vec<u8>) -> Sarc<'static, ProviderStream<Vec<u8>>> {
+    BinaryReader::new_from_provider(data, Endianness::LittleEndian, ReadHint::new())
+      .into_with::<Sarc<_>>(HashMode::default())
+      .await
+      .map_err(|_| {})
+      .unwrap()
+  }
+
+  /// Every seek into the Yaz0 stage map replays it from the start, so tests that visit every file build a small archive instead.
+  async fn build(builder: SarcBuilder) -> Sarc<'static, ProviderStream<Vec<u8>>> {
+    read(write(&builder).await).await
+  }
+
+  fn builder(multiplier: u32, files: &[(&str, &[u8])]) -> SarcBuilder {
+    let mut builder = SarcBuilder::new(Endianness::BigEndian).with_hash_multiplier(multiplier);
+
+    for (name, data) in files {
+      builder.add_file(*name, *data, 4);
+    }
+
+    builder
+  }
+
+  #[tokio::test]
+  async fn reads_stage_map_header() {
+    let sarc = stage_map().await;
+
+    assert_eq!(sarc.header().endianness, Endianness::LittleEndian);
+    assert_eq!(sarc.header().data_section_offset, 0x200);
+    assert_eq!(sarc.sfat().header().hash_multiplier, 0x65);
+    assert_eq!(sarc.len(), 8);
+  }
+
+  #[tokio::test]
+  async fn opens_stage_map_file() {
+    let mut sarc = stage_map().await;
+    let mut reader = sarc.open("SkyWorldHomeStage_6_x_02.byml").await.map_err(|_| {}).unwrap().unwrap();
+
+    assert_eq!(reader.stream().len(), Some(650108));
+    assert_eq!(&reader.get::<[u8; 4]>().await.map_err(|_| {}).unwrap(), b"YB\x03\x00");
+  }
+
+  #[tokio::test]
+  async fn misses_unknown_names() {
+    let mut sarc = stage_map().await;
+
+    assert!(sarc.open("Missing.byml").await.map_err(|_| {}).unwrap().is_none());
+    assert!(sarc.open("scenarioinfo.byml").await.map_err(|_| {}).unwrap().is_none());
+  }
+
+  #[tokio::test]
+  async fn lists_files() {
+    let mut sarc = build(builder(0x65, &[("Stage.byml", b"BY"), ("Camera.byml", b"YB\x03"), ("A/Long/Path.bin", b"")])).await;
+    let mut files = sarc.files();
+    let mut listed = Vec::new();
+
+    while let Some(file) = files.next().await {
+      let mut file = file.map_err(|_| {}).unwrap();
+      let length = file.reader.stream().len().unwrap();
+      let mut contents = Vec::new();
+
+      for _ in 0..length {
+        contents.push(file.reader.get::<u8>().await.map_err(|_| {}).unwrap());
+      }
+
+      listed.push((file.name.unwrap(), contents));
+    }
+
+    listed.sort();
+
+    assert_eq!(
+      listed,
+      [
+        ("A/Long/Path.bin".into(), b"".to_vec()),
+        ("Camera.byml".into(), b"YB\x03".to_vec()),
+        ("Stage.byml".into(), b"BY".to_vec()),
+      ]
+    );
+  }
+
+  #[tokio::test]
+  async fn resolves_hash_collisions() {
+    // with a multiplier of 1 the hash is the sum of the bytes, so every permutation collides
+    let mut sarc = build(builder(1, &[("abc", b"1"), ("cab", b"2"), ("bca", b"3")])).await;
+
+    for (name, contents) in [("abc", 1), ("cab", 2), ("bca", 3)] {
+      let mut reader = sarc.open(name).await.map_err(|_| {}).unwrap().unwrap();
+
+      assert_eq!(reader.get::<u8>().await.map_err(|_| {}).unwrap(), b'0' + contents);
+    }
+
+    assert!(sarc.open("acb").await.map_err(|_| {}).unwrap().is_none());
+  }
+
+  #[tokio::test]
+  async fn file_reader_is_bounded() {
+    let mut sarc = build(builder(0x65, &[("First", b"12345"), ("Second", b"6789")])).await;
+    let mut reader = sarc.open("First").await.map_err(|_| {}).unwrap().unwrap();
+
+    assert!(reader.skip(5).await.is_ok());
+    assert!(reader.get::<u8>().await.is_err());
+  }
+
+  #[tokio::test]
+  async fn reads_archives_without_names() {
+    let mut sarc = build(builder(0x65, &[("Stage.byml", b"BY"), ("Camera.byml", b"YB")]).without_names()).await;
+    let mut dictionary = SfntHashDictionary::new(sarc.sfat().header().hash_multiplier);
+    dictionary.extend(["Camera.byml", "Stage.byml", "Unused.byml"]);
+
+    let mut files = sarc.files();
+    let mut recovered = Vec::new();
+
+    while let Some(file) = files.next().await {
+      let file = file.map_err(|_| {}).unwrap();
+
+      assert_eq!(file.name, None);
+      recovered.push(dictionary.get(file.hash).unwrap().to_string());
+    }
+
+    recovered.sort();
+
+    assert_eq!(recovered, ["Camera.byml", "Stage.byml"]);
+    assert!(sarc.open("Stage.byml").await.map_err(|_| {}).unwrap().is_some());
+    assert!(sarc.open("Missing.byml").await.map_err(|_| {}).unwrap().is_none());
+  }
+
+  #[tokio::test]
+  async fn reads_archives_without_name_table_section() {
+    let mut data = write(&builder(0x65, &[("Stage.byml", b"BY")]).without_names()).await;
+
+    // drop the empty SFNT section and pull the data section back to meet the entries
+    data.drain(0x30..0x38);
+    let size = data.len() as u32;
+
+    data[0x08..0x0C].copy_from_slice(&size.to_be_bytes());
+    data[0x0C..0x10].copy_from_slice(&0x30u32.to_be_bytes());
+
+    let mut sarc = read(data).await;
+
+    assert!(!sarc.has_name_table());
+
+    let mut reader = sarc.open("Stage.byml").await.map_err(|_| {}).unwrap().unwrap();
+
+    assert_eq!(&reader.get::<[u8; 2]>().await.map_err(|_| {}).unwrap(), b"BY");
+  }
+
+  #[tokio::test]
+  async fn flags_unnamed_hash_collisions() {
+    let mut sarc = build(builder(1, &[("abc", b"1"), ("cab", b"2")]).without_names()).await;
+
+    assert!(matches!(sarc.open("abc").await, Err(SarcAccessError::HashCollision { count: 2, .. })));
+  }
+
+  #[tokio::test]
+  async fn lists_unnamed_hash_collisions() {
+    let mut sarc = build(builder(1, &[("abc", b"1"), ("cab", b"2"), ("Stage.byml", b"BY")]).without_names()).await;
+    let mut files = sarc.files();
+    let mut collisions = 0;
+    let mut listed = 0;
+
+    while let Some(file) = files.next().await {
+      match file {
+        Ok(_) => listed += 1,
+        Err(SarcAccessError::HashCollision { count: 2, .. }) => collisions += 1,
+        Err(_) => panic!("only the colliding files fail"),
+      }
+    }
+
+    assert_eq!((collisions, listed), (2, 1));
+    assert!(sarc.file(0).await.is_ok());
+  }
+}
@@ -1,17 +1,69 @@
-use core::convert::Infallible;
-
 use fileforge::{
-  binary_reader::{readable::IntoReadable, BinaryReader},
-  stream::ReadableStream,
+  binary_reader::{error::SkipError, readable::IntoReadable, BinaryReader},
+  stream::RestorableStream,
+};
+use fileforge_macros::FileforgeError;
+
+use crate::sead::sarc::{
+  header::{readable::SarcHeaderReadError, SarcHeader},
+  sfat::{
+    entry::SFAT_ENTRY_SIZE,
+    header::{readable::SfatHeaderReadError, SfatHeader},
+    name_table::{
+      hasher::HashMode,
+      header::{readable::SfntHeaderReadError, SfntHeader},
+    },
+    SfatTable,
+  },
+  Sarc,
 };
 
-use crate::sead::sarc::{header::SarcHeader, Sarc};
+#[derive(FileforgeError)]
+pub enum SarcReadError<'pool, S: RestorableStream<Type = u8>> {
+  Header(#[from] SarcHeaderReadError<'pool, S::ReadError>),
+  SfatHeader(#[from] SfatHeaderReadError<'pool, S::ReadError>),
+  SkipEntries(#[from] SkipError<'pool, S::SkipError>),
+  SfntHeader(#[from] SfntHeaderReadError<'pool, S::ReadError>),
+
+  #[report(&"Data section starts inside the file tables")]
+  InvalidDataSectionOffset,
+}
+
+impl<'pool, S: RestorableStream<Type = u8>> IntoReadable<'pool, S> for Sarc<'pool, S> {
+  /// How name bytes are hashed. Only names with bytes above `0x7F` are affected.
+  type Argument = HashMode;
+  type Error = SarcReadError<'pool, S>;
+
+  async fn read(mut reader: BinaryReader<'pool, S>, mode: Self::Argument) -> Result<Self, Self::Error> {
+    let start = reader.snapshot();
+    let header: SarcHeader = reader.read().await?;
+
+    reader.set_endianness(header.endianness);
+
+    let sfat_header: SfatHeader = reader.read().await?;
+
+    reader.skip(sfat_header.file_count as u64 * SFAT_ENTRY_SIZE).await?;
+
+    let mut sarc = Sarc {
+      header,
+      sfat: SfatTable::new(sfat_header, mode),
+      has_name_table: false,
+      start,
+      reader,
+    };
+
+    let data_section_offset = sarc.header.data_section_offset as u64;
+
+    if data_section_offset < sarc.entries_end() {
+      return Err(SarcReadError::InvalidDataSectionOffset);
+    }
 
-// impl<'pool, S: ReadableStream<Type = u8>> IntoReadable<'pool, S> for Sarc {
-//   type Argument = ();
-//   type Error = Infallible;
+    // archives without names can leave out the SFNT section entirely, in which case the data follows the entries directly
+    if data_section_offset >= sarc.name_table_offset() {
+      sarc.reader.read::<SfntHeader>().await?;
+      sarc.has_name_table = true;
+    }
 
-//   async fn read(mut reader: BinaryReader<'pool, S>, argument: Self::Argument) -> Result<Self, Self::Error> {
-//     let header: SarcHeader = reader.read().await?;
-//   }
-// }
+    Ok(sarc)
+  }
+}
@@ -39,10 +39,10 @@ impl<'pool, S: ReadableStream<Type = u8>> Readable<'pool, S> for SfatEntry {
 
   async fn read(reader: &mut BinaryReader<'pool, S>, _: Self::Argument) -> Result<Self, Self::Error> {
     Ok(SfatEntry {
-      filename_hash: reader.get().await.map_err(|e| SfatEntryError::FilenameHashReadError(e))?,
-      filename_attributes: FilenameAttributes::from_bits(reader.get().await.map_err(|e| SfatEntryError::FilenameHashReadError(e))?)?,
-      start_offset: reader.get().await.map_err(|e| SfatEntryError::StartOffsetReadError(e))?,
-      end_offset: reader.get().await.map_err(|e| SfatEntryError::EndOffsetReadError(e))?,
+      filename_hash: reader.get().await.map_err(SfatEntryError::FilenameHashReadError)?,
+      filename_attributes: FilenameAttributes::from_bits(reader.get().await.map_err(SfatEntryError::FilenameAttributesReadError)?)?,
+      start_offset: reader.get().await.map_err(SfatEntryError::StartOffsetReadError)?,
+      end_offset: reader.get().await.map_err(SfatEntryError::EndOffsetReadError)?,
     })
   }
 }
@@ -1,5 +1,7 @@
 pub mod readable;
 
+pub const SFAT_HEADER_SIZE: u16 = 0x0C;
+
 pub struct SfatHeader {
   pub file_count: u16,
   pub hash_multiplier: u32,
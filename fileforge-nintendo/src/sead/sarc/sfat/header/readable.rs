@@ -1,15 +1,16 @@
 use fileforge::{
   binary_reader::{
-    error::{primitive_name_annotation::PrimitiveName, GetPrimitiveError},
+    error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError},
     readable::Readable,
     BinaryReader, PrimitiveReader,
   },
-  error::{ext::annotations::annotated::Annotated, FileforgeError},
+  error::{ext::annotations::annotated::Annotated, render::builtin::number::formatted_unsigned::FormattedUnsigned},
   stream::{error::user_read::UserReadError, ReadableStream},
 };
+use fileforge_macros::FileforgeError;
 use fileforge_std::magic::{Magic, MagicError};
 
-use super::SfatHeader;
+use super::{SfatHeader, SFAT_HEADER_SIZE};
 
 pub const SFAT_MAGIC: Magic<4> = Magic::from_byte_ref(b"SFAT");
 
@@ -18,30 +19,31 @@ impl<'pool, S: ReadableStream<Type = u8>> Readable<'pool, S> for SfatHeader {
   type Argument = ();
 
   async fn read(reader: &mut BinaryReader<'pool, S>, _: Self::Argument) -> Result<Self, Self::Error> {
-    reader.read_with::<Magic<4>>(SFAT_MAGIC).await.map_err(|e| SfatHeaderReadError::Magic(e))?;
+    reader.read_with::<Magic<4>>(SFAT_MAGIC).await.map_err(SfatHeaderReadError::Magic)?;
 
-    let _header_length: u16 = reader.get().await.map_err(|e| SfatHeaderReadError::HeaderLength(e))?;
+    let header_length: u16 = reader.get().await.map_err(SfatHeaderReadError::HeaderLength)?;
+
+    if header_length != SFAT_HEADER_SIZE {
+      return Err(SfatHeaderReadError::InvalidHeaderLength { length: header_length });
+    }
 
     Ok(SfatHeader {
-      file_count: reader.get().await.map_err(|e| SfatHeaderReadError::FileCount(e))?,
-      hash_multiplier: reader.get().await.map_err(|e| SfatHeaderReadError::HashMultiplier(e))?,
+      file_count: reader.get().await.map_err(SfatHeaderReadError::FileCount)?,
+      hash_multiplier: reader.get().await.map_err(SfatHeaderReadError::HashMultiplier)?,
     })
   }
 }
 
+#[derive(FileforgeError)]
 pub enum SfatHeaderReadError<'pool, U: UserReadError> {
   Magic(MagicError<'pool, 4, U>),
-  HeaderLength(Annotated<PrimitiveName<fileforge::binary_reader::error::common::Read>, GetPrimitiveError<'pool, U>>),
-  FileCount(Annotated<PrimitiveName<fileforge::binary_reader::error::common::Read>, GetPrimitiveError<'pool, U>>),
-  HashMultiplier(Annotated<PrimitiveName<fileforge::binary_reader::error::common::Read>, GetPrimitiveError<'pool, U>>),
-}
-
-impl<'pool, U: UserReadError> FileforgeError for SfatHeaderReadError<'pool, U> {
-  fn render_into_report<P: fileforge::diagnostic::pool::DiagnosticPoolProvider + Clone, const ITEM_NAME_SIZE: usize>(
-    &self,
-    provider: P,
-    callback: impl for<'tag, 'b> FnOnce(fileforge::error::report::Report<'tag, 'b, ITEM_NAME_SIZE, P>) -> (),
-  ) {
-    unimplemented!()
-  }
+  HeaderLength(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  FileCount(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  HashMultiplier(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+
+  #[report(&"Unexpected SFAT header length")]
+  #[flag("Expected a length of 12, found {length}", length = FormattedUnsigned::new(*length as u128))]
+  InvalidHeaderLength {
+    length: u16,
+  },
 }
@@ -1,19 +1,37 @@
-use fileforge::{binary_reader::BinaryReader, stream::ReadableStream};
+use core::hash::Hasher;
 
-use crate::sead::sarc::sfat::{header::SfatHeader, name_table::hasher::SfntHasher};
+use crate::sead::sarc::sfat::{
+  header::SfatHeader,
+  name_table::hasher::{HashMode, SfntHasher},
+};
 
 pub mod entry;
 pub mod header;
 pub mod name_table;
 pub mod stream;
 
-pub struct SfatTable<'pool, UnderlyingStream: ReadableStream<Type = u8>> {
+pub struct SfatTable {
   hasher: SfntHasher,
   header: SfatHeader,
-  reader: BinaryReader<'pool, UnderlyingStream>,
 }
 
-// Note:
-// there's a weird logic hole.
-// if there's only one file in the SfatTable
-// then i cannot validate hash collisions
+impl SfatTable {
+  pub fn new(header: SfatHeader, mode: HashMode) -> Self {
+    Self {
+      hasher: SfntHasher::new(header.hash_multiplier, mode),
+      header,
+    }
+  }
+
+  pub fn header(&self) -> &SfatHeader {
+    &self.header
+  }
+
+  /// Hashes `name` with this table's multiplier, giving the value its entry is sorted by.
+  pub fn hash(&self, name: &[u8]) -> u32 {
+    let mut hasher = self.hasher;
+
+    hasher.write(name);
+    hasher.get_hash()
+  }
+}
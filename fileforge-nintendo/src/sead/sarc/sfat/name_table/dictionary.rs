@@ -0,0 +1,119 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::hash::Hasher;
+
+use crate::sead::sarc::sfat::name_table::hasher::{HashMode, SfntHasher};
+
+/// Recovers names for entries that only have a hash, from a list of names they might have.
+///
+/// Every candidate is hashed in both [`HashMode`]s, so the dictionary works whether or not the archive was built by a Wii U toolchain.
+/// Every candidate for a hash is kept, so names that collide are never silently picked between.
+pub struct SfntHashDictionary {
+  multiplier: u32,
+  names: BTreeMap<u32, Vec<String>>,
+}
+
+impl SfntHashDictionary {
+  /// Creates an empty dictionary. `multiplier` must match the archive's, which is usually [`DEFAULT_HASH_MULTIPLIER`].
+  ///
+  /// [`DEFAULT_HASH_MULTIPLIER`]: crate::sead::sarc::sfat::name_table::hasher::DEFAULT_HASH_MULTIPLIER
+  pub fn new(multiplier: u32) -> Self {
+    Self { multiplier, names: BTreeMap::new() }
+  }
+
+  pub fn multiplier(&self) -> u32 {
+    self.multiplier
+  }
+
+  pub fn insert(&mut self, name: impl Into<String>) {
+    let name = name.into();
+
+    for mode in [HashMode::Signed, HashMode::Unsigned] {
+      let mut hasher = SfntHasher::new(self.multiplier, mode);
+      hasher.write(name.as_bytes());
+
+      let candidates = self.names.entry(hasher.get_hash()).or_default();
+
+      if !candidates.contains(&name) {
+        candidates.push(name.clone());
+      }
+    }
+  }
+
+  /// The name with `hash`, or `None` if no candidate has it or more than one does.
+  pub fn get(&self, hash: u32) -> Option<&str> {
+    match self.candidates(hash) {
+      [name] => Some(name),
+      _ => None,
+    }
+  }
+
+  /// Every candidate with `hash`, in the order they were added.
+  pub fn candidates(&self, hash: u32) -> &[String] {
+    self.names.get(&hash).map_or(&[], Vec::as_slice)
+  }
+
+  /// The hashes that more than one candidate has, with those candidates.
+  pub fn collisions(&self) -> impl Iterator<Item = (u32, &[String])> {
+    self
+      .names
+      .iter()
+      .filter(|(_, candidates)| candidates.len() > 1)
+      .map(|(hash, candidates)| (*hash, candidates.as_slice()))
+  }
+
+  /// The number of distinct hashes.
+  pub fn len(&self) -> usize {
+    self.names.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.names.is_empty()
+  }
+}
+
+impl<N: Into<String>> Extend<N> for SfntHashDictionary {
+  fn extend<T: IntoIterator<Item = N>>(&mut self, names: T) {
+    for name in names {
+      self.insert(name);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::sead::sarc::sfat::name_table::{
+    dictionary::SfntHashDictionary,
+    hasher::{SfntHasher, DEFAULT_HASH_MULTIPLIER},
+  };
+  use core::hash::Hasher;
+
+  fn hash(name: &str, hasher: SfntHasher) -> u32 {
+    let mut hasher = hasher;
+    hasher.write(name.as_bytes());
+    hasher.get_hash()
+  }
+
+  #[test]
+  fn recovers_names_in_both_modes() {
+    let mut dictionary = SfntHashDictionary::new(DEFAULT_HASH_MULTIPLIER);
+    dictionary.extend(["Stage.byml", "Kinopio_Ä.bfres"]);
+
+    assert_eq!(dictionary.get(hash("Stage.byml", SfntHasher::new_signed(DEFAULT_HASH_MULTIPLIER))), Some("Stage.byml"));
+    assert_eq!(dictionary.get(hash("Kinopio_Ä.bfres", SfntHasher::new_signed(DEFAULT_HASH_MULTIPLIER))), Some("Kinopio_Ä.bfres"));
+    assert_eq!(dictionary.get(hash("Kinopio_Ä.bfres", SfntHasher::new_unsigned(DEFAULT_HASH_MULTIPLIER))), Some("Kinopio_Ä.bfres"));
+    assert_eq!(dictionary.get(hash("Missing.byml", SfntHasher::new_signed(DEFAULT_HASH_MULTIPLIER))), None);
+  }
+
+  #[test]
+  fn keeps_every_colliding_name() {
+    let mut dictionary = SfntHashDictionary::new(1);
+    dictionary.extend(["abc", "cab", "Stage.byml"]);
+
+    let collision = hash("cab", SfntHasher::new_signed(1));
+
+    assert_eq!(dictionary.get(collision), None);
+    assert_eq!(dictionary.candidates(collision), ["abc", "cab"]);
+    assert_eq!(dictionary.collisions().map(|(hash, _)| hash).collect::<Vec<_>>(), [collision]);
+    assert_eq!(dictionary.get(hash("Stage.byml", SfntHasher::new_signed(1))), Some("Stage.byml"));
+  }
+}
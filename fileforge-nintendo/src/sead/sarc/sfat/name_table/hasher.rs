@@ -1,5 +1,8 @@
 use core::hash::Hasher;
 
+/// The multiplier every known sead archive uses.
+pub const DEFAULT_HASH_MULTIPLIER: u32 = 0x65;
+
 /// Hash mode for sead name table entries.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum HashMode {
@@ -26,7 +29,7 @@ impl Default for SfntHasher {
   fn default() -> Self {
     SfntHasher {
       hash: 0,
-      multiplier: 0x65,
+      multiplier: DEFAULT_HASH_MULTIPLIER,
       mode: HashMode::default(),
     }
   }
@@ -0,0 +1,6 @@
+pub mod readable;
+
+pub const SFNT_HEADER_SIZE: u16 = 0x08;
+
+/// The SFNT header only marks the start of the name table. Names follow it directly, each NUL terminated and padded to 4 bytes.
+pub struct SfntHeader;
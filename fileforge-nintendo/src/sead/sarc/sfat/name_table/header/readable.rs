@@ -0,0 +1,47 @@
+use fileforge::{
+  binary_reader::{
+    error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError},
+    readable::Readable,
+    BinaryReader, PrimitiveReader,
+  },
+  error::{ext::annotations::annotated::Annotated, render::builtin::number::formatted_unsigned::FormattedUnsigned},
+  stream::{error::user_read::UserReadError, ReadableStream},
+};
+use fileforge_macros::FileforgeError;
+use fileforge_std::magic::{Magic, MagicError};
+
+use super::{SfntHeader, SFNT_HEADER_SIZE};
+
+pub const SFNT_MAGIC: Magic<4> = Magic::from_byte_ref(b"SFNT");
+
+impl<'pool, S: ReadableStream<Type = u8>> Readable<'pool, S> for SfntHeader {
+  type Error = SfntHeaderReadError<'pool, S::ReadError>;
+  type Argument = ();
+
+  async fn read(reader: &mut BinaryReader<'pool, S>, _: Self::Argument) -> Result<Self, Self::Error> {
+    reader.read_with::<Magic<4>>(SFNT_MAGIC).await.map_err(SfntHeaderReadError::Magic)?;
+
+    let header_length: u16 = reader.get().await.map_err(SfntHeaderReadError::HeaderLength)?;
+
+    if header_length != SFNT_HEADER_SIZE {
+      return Err(SfntHeaderReadError::InvalidHeaderLength { length: header_length });
+    }
+
+    let _reserved: u16 = reader.get().await.map_err(SfntHeaderReadError::Reserved)?;
+
+    Ok(SfntHeader)
+  }
+}
+
+#[derive(FileforgeError)]
+pub enum SfntHeaderReadError<'pool, U: UserReadError> {
+  Magic(MagicError<'pool, 4, U>),
+  HeaderLength(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  Reserved(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+
+  #[report(&"Unexpected SFNT header length")]
+  #[flag("Expected a length of 8, found {length}", length = FormattedUnsigned::new(*length as u128))]
+  InvalidHeaderLength {
+    length: u16,
+  },
+}
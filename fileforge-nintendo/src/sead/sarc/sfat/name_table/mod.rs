@@ -1,5 +1,8 @@
+#[cfg(feature = "alloc")]
+pub mod dictionary;
 pub mod entry;
 pub mod hasher;
+pub mod header;
 
 use fileforge::{binary_reader::BinaryReader, stream::ReadableStream};
 
@@ -0,0 +1,61 @@
+use fileforge::stream::{
+  error::{stream_exhausted::StreamExhaustedError, stream_restore::StreamRestoreError},
+  ReadableStream, RestorableStream, StreamReadError, StreamSkipError,
+};
+
+/// A view over one file's data, starting at the stream's current offset and ending `length` bytes later.
+pub struct SarcFileStream<S: ReadableStream<Type = u8>> {
+  stream: S,
+  start: u64,
+  length: u64,
+}
+
+impl<S: ReadableStream<Type = u8>> SarcFileStream<S> {
+  pub fn new(stream: S, length: u64) -> Self {
+    Self {
+      start: stream.offset(),
+      stream,
+      length,
+    }
+  }
+}
+
+impl<S: ReadableStream<Type = u8>> ReadableStream for SarcFileStream<S> {
+  type Type = u8;
+
+  type ReadError = S::ReadError;
+  type SkipError = S::SkipError;
+
+  fn len(&self) -> Option<u64> {
+    Some(self.length)
+  }
+
+  fn offset(&self) -> u64 {
+    self.stream.offset() - self.start
+  }
+
+  async fn read<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[Self::Type; SIZE]) -> V) -> Result<V, StreamReadError<Self::ReadError>> {
+    StreamExhaustedError::assert(self.length, self.offset(), SIZE as u64)?;
+
+    self.stream.read(reader).await
+  }
+
+  async fn skip(&mut self, size: u64) -> Result<(), StreamSkipError<Self::SkipError>> {
+    StreamSkipError::assert_relative_forwards(self.length, self.offset(), size)?;
+
+    self.stream.skip(size).await
+  }
+}
+
+impl<S: RestorableStream<Type = u8>> RestorableStream for SarcFileStream<S> {
+  type Snapshot = S::Snapshot;
+  type RestoreError = S::RestoreError;
+
+  fn snapshot(&self) -> Self::Snapshot {
+    self.stream.snapshot()
+  }
+
+  async fn restore(&mut self, snapshot: Self::Snapshot) -> Result<(), StreamRestoreError<Self::RestoreError>> {
+    self.stream.restore(snapshot).await
+  }
+}
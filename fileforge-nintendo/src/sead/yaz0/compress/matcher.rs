@@ -0,0 +1,217 @@
+/// Furthest a readback may reach (12-bit offset, stored minus one).
+pub const WINDOW_SIZE: usize = 0x1000;
+/// Shortest run worth encoding as a readback.
+pub const MIN_MATCH: usize = 3;
+/// Longest run a single readback can encode (`0xFF + 0x12`).
+pub const MAX_MATCH: usize = 0x111;
+
+const HASH_BITS: u32 = 12;
+const HASH_SIZE: usize = 1 << HASH_BITS;
+const NIL: u32 = u32::MAX;
+
+#[inline]
+fn hash(data: &[u8], position: usize) -> usize {
+  let key = (data[position] as u32) << 16 | (data[position + 1] as u32) << 8 | data[position + 2] as u32;
+  (key.wrapping_mul(0x9E37_79B1) >> (32 - HASH_BITS)) as usize
+}
+
+/// Length of the common prefix of `data[candidate..]` and `data[position..]`, up to `limit`.
+///
+/// `candidate < position`, so the source may run into the bytes being matched, exactly like a
+/// decoder copying an overlapping readback one byte at a time.
+#[inline]
+pub(crate) fn match_length(data: &[u8], candidate: usize, position: usize, limit: usize) -> usize {
+  data[candidate..].iter().zip(&data[position..position + limit]).take_while(|(a, b)| a == b).count()
+}
+
+/// A match found in the window, as `(offset, length)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Match {
+  pub offset: u16,
+  pub length: u16,
+}
+
+/// Hash chains over every 3-byte prefix inside the last [`WINDOW_SIZE`] bytes.
+///
+/// Positions are absolute indices into the buffer being compressed. Every position before the one
+/// being searched must have been [`insert`](Self::insert)ed, in order.
+#[derive(Clone)]
+pub struct HashChain {
+  head: [u32; HASH_SIZE],
+  prev: [u32; WINDOW_SIZE],
+  max_chain: usize,
+}
+
+impl HashChain {
+  /// `max_chain` bounds how many candidates a single search may visit.
+  pub fn new(max_chain: usize) -> Self {
+    Self {
+      head: [NIL; HASH_SIZE],
+      prev: [NIL; WINDOW_SIZE],
+      max_chain,
+    }
+  }
+
+  pub fn insert(&mut self, data: &[u8], position: usize) {
+    if position + MIN_MATCH > data.len() {
+      return;
+    }
+
+    let bucket = hash(data, position);
+    self.prev[position % WINDOW_SIZE] = self.head[bucket];
+    self.head[bucket] = position as u32;
+  }
+
+  /// Rebases every position by `amount` after that many bytes are dropped from the front of the
+  /// buffer. `amount` must be a multiple of [`WINDOW_SIZE`] so chain slots stay where they are.
+  pub fn slide(&mut self, amount: usize) {
+    assert!(amount.is_multiple_of(WINDOW_SIZE), "chains can only slide by whole windows");
+
+    for position in self.head.iter_mut().chain(self.prev.iter_mut()) {
+      *position = match *position {
+        NIL => NIL,
+        v if (v as usize) < amount => NIL,
+        v => v - amount as u32,
+      };
+    }
+  }
+
+  /// Longest match for `data[position..]`, preferring the nearest one on ties.
+  pub fn find(&self, data: &[u8], position: usize) -> Option<Match> {
+    self.find_up_to(data, position, MAX_MATCH)
+  }
+
+  /// [`find`](Self::find) for formats whose readbacks have a different maximum length.
+  pub fn find_up_to(&self, data: &[u8], position: usize, max_length: usize) -> Option<Match> {
+    let limit = (data.len() - position).min(max_length).min(u16::MAX as usize);
+
+    if limit < MIN_MATCH {
+      return None;
+    }
+
+    let mut best = Match { offset: 0, length: 0 };
+    let mut candidate = self.head[hash(data, position)];
+
+    for _ in 0..self.max_chain {
+      if candidate == NIL || position - candidate as usize > WINDOW_SIZE {
+        break;
+      }
+
+      let start = candidate as usize;
+      let length = best.length as usize;
+
+      if data[start + length] == data[position + length] {
+        let length = match_length(data, start, position, limit);
+
+        if length > best.length as usize {
+          best = Match {
+            offset: (position - start) as u16,
+            length: length as u16,
+          };
+
+          if length == limit {
+            break;
+          }
+        }
+      }
+
+      candidate = self.prev[start % WINDOW_SIZE];
+    }
+
+    (best.length as usize >= MIN_MATCH).then_some(best)
+  }
+}
+
+/// Longest match for `data[position..]` found by walking every offset in the window.
+///
+/// Only kept as the reference the chains are checked against.
+#[cfg(test)]
+fn scan(data: &[u8], position: usize) -> Option<Match> {
+  let limit = (data.len() - position).min(MAX_MATCH);
+
+  if limit < MIN_MATCH {
+    return None;
+  }
+
+  let (first, second, third) = (data[position], data[position + 1], data[position + 2]);
+  let mut best = Match { offset: 0, length: 0 };
+
+  for start in (position.saturating_sub(WINDOW_SIZE)..position).rev() {
+    if data[start] != first || data[start + 1] != second || data[start + 2] != third {
+      continue;
+    }
+
+    let length = match_length(data, start, position, limit);
+
+    if length > best.length as usize {
+      best = Match {
+        offset: (position - start) as u16,
+        length: length as u16,
+      };
+
+      if length == limit {
+        break;
+      }
+    }
+  }
+
+  (best.length as usize >= MIN_MATCH).then_some(best)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn chain_over(data: &[u8], until: usize) -> HashChain {
+    let mut chain = HashChain::new(WINDOW_SIZE);
+    (0..until).for_each(|position| chain.insert(data, position));
+    chain
+  }
+
+  #[test]
+  fn finds_nearest_longest_match() {
+    let data = b"abcdXabcdYabcdX";
+    let found = chain_over(data, 10).find(data, 10).unwrap();
+
+    assert_eq!(found, Match { offset: 10, length: 5 });
+    assert_eq!(scan(data, 10), Some(found));
+  }
+
+  #[test]
+  fn overlapping_run() {
+    let data = [7u8; 400];
+    let found = chain_over(&data, 1).find(&data, 1).unwrap();
+
+    assert_eq!(found, Match { offset: 1, length: MAX_MATCH as u16 });
+    assert_eq!(scan(&data, 1), Some(found));
+  }
+
+  #[test]
+  fn ignores_matches_outside_window() {
+    let mut data = vec![0u8; WINDOW_SIZE + 8];
+    data[..4].copy_from_slice(b"wxyz");
+    (4..WINDOW_SIZE + 4).for_each(|i| data[i] = (i % 251) as u8 | 0x80);
+    data[WINDOW_SIZE + 4..].copy_from_slice(b"wxyz");
+
+    assert_eq!(chain_over(&data, WINDOW_SIZE + 4).find(&data, WINDOW_SIZE + 4), None);
+    assert_eq!(scan(&data, WINDOW_SIZE + 4), None);
+
+    data[4..8].copy_from_slice(b"wxyz");
+    let found = chain_over(&data, WINDOW_SIZE + 4).find(&data, WINDOW_SIZE + 4);
+    assert_eq!(
+      found,
+      Some(Match {
+        offset: WINDOW_SIZE as u16,
+        length: 4
+      })
+    );
+    assert_eq!(scan(&data, WINDOW_SIZE + 4), found);
+  }
+
+  #[test]
+  fn short_tail_has_no_match() {
+    let data = b"abcab";
+    assert_eq!(chain_over(data, 3).find(data, 3), None);
+    assert_eq!(scan(data, 3), None);
+  }
+}
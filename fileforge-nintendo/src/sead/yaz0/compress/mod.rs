@@ -0,0 +1,187 @@
+pub mod matcher;
+#[cfg(feature = "alloc")]
+mod optimal;
+pub(crate) mod parse;
+
+use crate::sead::yaz0::{
+  compress::parse::Yaz0Parse,
+  parser::data::{Block, Operation},
+};
+
+/// How hard [`Yaz0Compressor`] looks for matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Yaz0CompressionLevel {
+  /// Greedy parse over short hash chains.
+  Fast,
+  /// One byte of lookahead before committing to a match, as Nintendo's encoder does.
+  #[default]
+  Lazy,
+  /// Minimum encoded size over exhaustive hash chains. Plans all the input it can see up front.
+  #[cfg(feature = "alloc")]
+  Optimal,
+}
+
+impl Yaz0CompressionLevel {
+  fn max_chain(self) -> usize {
+    match self {
+      Self::Fast => 16,
+      Self::Lazy => 256,
+      #[cfg(feature = "alloc")]
+      Self::Optimal => matcher::WINDOW_SIZE,
+    }
+  }
+}
+
+/// Compresses a buffer into the [`Operation`]s of a Yaz0 stream.
+///
+/// Iterating yields operations in order; [`blocks`](Self::blocks) groups them for the parser.
+pub struct Yaz0Compressor<'a> {
+  data: &'a [u8],
+  parse: Yaz0Parse,
+}
+
+impl<'a> Yaz0Compressor<'a> {
+  pub fn new(data: &'a [u8], level: Yaz0CompressionLevel) -> Self {
+    Self { data, parse: Yaz0Parse::new(level) }
+  }
+
+  pub fn level(&self) -> Yaz0CompressionLevel {
+    self.parse.level()
+  }
+
+  pub fn blocks(self) -> Yaz0CompressorBlocks<'a> {
+    Yaz0CompressorBlocks { operations: self }
+  }
+}
+
+impl Iterator for Yaz0Compressor<'_> {
+  type Item = Operation;
+
+  fn next(&mut self) -> Option<Operation> {
+    self.parse.next(self.data)
+  }
+}
+
+/// [`Yaz0Compressor`]'s operations, eight to a [`Block`].
+pub struct Yaz0CompressorBlocks<'a> {
+  operations: Yaz0Compressor<'a>,
+}
+
+impl Iterator for Yaz0CompressorBlocks<'_> {
+  type Item = Block;
+
+  fn next(&mut self) -> Option<Block> {
+    let mut block = Block::empty();
+
+    while !block.is_full() {
+      match self.operations.next() {
+        Some(operation) => block.operations.push(operation).unwrap(),
+        None => break,
+      }
+    }
+
+    (!block.is_empty()).then_some(block)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use fileforge::{
+    binary_reader::{endianness::Endianness, BinaryReader},
+    provider::hint::ReadHint,
+    stream::{ReadableStream, SINGLE},
+  };
+
+  use super::*;
+  use crate::sead::yaz0::{readable::Immutable, state::Yaz0State, tests::yaz0_file, Yaz0Stream};
+
+  const LEVELS: [Yaz0CompressionLevel; 3] = [Yaz0CompressionLevel::Fast, Yaz0CompressionLevel::Lazy, Yaz0CompressionLevel::Optimal];
+
+  const BED: &[u8] = include_bytes!("../../../../../fileforge-test/binaries/Bed.byml");
+  const BED_YAZ0: &[u8] = include_bytes!("../../../../../fileforge-test/binaries/Bed.byml.yaz0");
+  const REAL: &[u8] = include_bytes!("../../../../../fileforge-test/binaries/real.byml");
+
+  fn inflate(blocks: impl Iterator<Item = Block>) -> Vec<u8> {
+    let mut state = Yaz0State::empty();
+    let mut out = Vec::new();
+
+    for block in blocks {
+      state.feed(block).unwrap();
+      out.extend(state.take(usize::MAX));
+    }
+
+    out
+  }
+
+  fn encoded_len(data: &[u8], level: Yaz0CompressionLevel) -> usize {
+    Yaz0Compressor::new(data, level).blocks().map(|block| block.encode().len()).sum()
+  }
+
+  fn samples() -> Vec<Vec<u8>> {
+    let mut noise = Vec::new();
+    let mut seed = 0x1234_5678u32;
+    for _ in 0..10_000 {
+      seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+      noise.push((seed >> 24) as u8 & 0x0F);
+    }
+
+    vec![Vec::new(), b"a".to_vec(), b"abcabcabcabcabx".to_vec(), vec![0; 5000], noise, BED.to_vec(), REAL.to_vec()]
+  }
+
+  #[test]
+  fn round_trips_at_every_level() {
+    for level in LEVELS {
+      for sample in samples() {
+        assert_eq!(inflate(Yaz0Compressor::new(&sample, level).blocks()), sample, "{level:?}");
+      }
+    }
+  }
+
+  #[test]
+  fn blocks_are_full_until_the_last() {
+    let blocks: Vec<_> = Yaz0Compressor::new(REAL, Yaz0CompressionLevel::Lazy).blocks().collect();
+
+    assert!(blocks[..blocks.len() - 1].iter().all(Block::is_full));
+    assert!(!blocks.last().unwrap().is_empty());
+  }
+
+  #[test]
+  fn runs_use_maximal_readbacks() {
+    let operations: Vec<_> = Yaz0Compressor::new(&[9; 1 + 0x111 * 2], Yaz0CompressionLevel::Fast).collect();
+
+    assert_eq!(operations, [Operation::lit(9), Operation::readback(1, 0x111).unwrap(), Operation::readback(1, 0x111).unwrap()]);
+  }
+
+  #[test]
+  fn optimal_is_never_larger() {
+    for sample in samples() {
+      let optimal = encoded_len(&sample, Yaz0CompressionLevel::Optimal);
+
+      assert!(optimal <= encoded_len(&sample, Yaz0CompressionLevel::Lazy));
+      assert!(optimal <= encoded_len(&sample, Yaz0CompressionLevel::Fast));
+    }
+  }
+
+  #[test]
+  fn matches_nintendo_output_size() {
+    let nintendo = BED_YAZ0.len() - 0x10;
+
+    assert!(encoded_len(BED, Yaz0CompressionLevel::Lazy) <= nintendo);
+    assert!(encoded_len(BED, Yaz0CompressionLevel::Optimal) <= nintendo);
+  }
+
+  #[tokio::test]
+  async fn parses_with_yaz0_stream() {
+    let reader = BinaryReader::new_from_provider(yaz0_file(BED, Yaz0CompressionLevel::Optimal), Endianness::BigEndian, ReadHint::new());
+    let mut stream = reader.into_with::<Yaz0Stream<_, _>>(Immutable).await.map_err(|_| {}).unwrap();
+    let mut out = Vec::new();
+
+    assert_eq!(stream.len(), Some(BED.len() as u64));
+
+    while out.len() < BED.len() {
+      out.push(stream.read(SINGLE).await.map_err(|_| {}).unwrap());
+    }
+
+    assert_eq!(out, BED);
+  }
+}
@@ -0,0 +1,71 @@
+use alloc::vec::Vec;
+
+use crate::sead::yaz0::{
+  compress::matcher::{HashChain, Match, MIN_MATCH},
+  parser::data::Operation,
+};
+
+/// Encoded size in bits of each operation, counting its flag bit in the block header.
+const LITERAL_COST: u32 = 9;
+const SHORT_READBACK_COST: u32 = 17;
+const LONG_READBACK_COST: u32 = 25;
+
+#[inline]
+fn readback_cost(length: usize) -> u32 {
+  if length < 0x12 {
+    SHORT_READBACK_COST
+  } else {
+    LONG_READBACK_COST
+  }
+}
+
+/// Parses `data[start..]` into the operations with the smallest encoded size, inserting every
+/// position into `chain` as it goes. Positions before `start` must already be inserted.
+///
+/// A readback's cost doesn't depend on its offset, so the longest match at each position (and
+/// every prefix of it) is all the parse needs to consider.
+pub(crate) fn parse(chain: &mut HashChain, data: &[u8], start: usize) -> Vec<Operation> {
+  let mut matches = Vec::with_capacity(data.len() - start);
+
+  for position in start..data.len() {
+    matches.push(chain.find(data, position));
+    chain.insert(data, position);
+  }
+
+  let data = &data[start..];
+
+  // `cost[i]` is the cheapest encoding of `data[i..]`, `choice[i]` the length taken at `i`.
+  let mut cost = alloc::vec![0u32; data.len() + 1];
+  let mut choice = alloc::vec![1u16; data.len()];
+
+  for position in (0..data.len()).rev() {
+    cost[position] = LITERAL_COST + cost[position + 1];
+
+    if let Some(Match { length, .. }) = matches[position] {
+      for length in MIN_MATCH..=length as usize {
+        let candidate = readback_cost(length) + cost[position + length];
+
+        if candidate < cost[position] {
+          cost[position] = candidate;
+          choice[position] = length as u16;
+        }
+      }
+    }
+  }
+
+  let mut operations = Vec::new();
+  let mut position = 0;
+
+  while position < data.len() {
+    let length = choice[position];
+
+    operations.push(match matches[position] {
+      Some(Match { offset, .. }) if length > 1 => Operation::readback(offset, length).unwrap(),
+      _ => Operation::lit(data[position]),
+    });
+
+    position += length as usize;
+  }
+
+  operations
+}
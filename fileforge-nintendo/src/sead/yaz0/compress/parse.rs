@@ -0,0 +1,125 @@
+use crate::sead::yaz0::{
+  compress::{
+    matcher::{HashChain, Match},
+    Yaz0CompressionLevel,
+  },
+  parser::data::Operation,
+};
+
+/// How far into a buffer a parse has got, independent of who owns the buffer.
+///
+/// Each call sees the input as a slice whose start is position 0. Operations never run past the
+/// end of that slice, so callers streaming their input should keep [`Self::LOOKAHEAD`] bytes
+/// ahead of [`position`](Self::position) until the input ends.
+#[derive(Clone)]
+pub(crate) struct Yaz0Parse {
+  level: Yaz0CompressionLevel,
+  chain: HashChain,
+  position: usize,
+  inserted: usize,
+  pending: Option<Match>,
+  #[cfg(feature = "alloc")]
+  planned: alloc::vec::IntoIter<Operation>,
+}
+
+impl Yaz0Parse {
+  /// Bytes past the current position a lazy match may look at.
+  pub(crate) const LOOKAHEAD: usize = super::matcher::MAX_MATCH + 1;
+
+  pub(crate) fn new(level: Yaz0CompressionLevel) -> Self {
+    Self {
+      level,
+      chain: HashChain::new(level.max_chain()),
+      position: 0,
+      inserted: 0,
+      pending: None,
+      #[cfg(feature = "alloc")]
+      planned: alloc::vec::Vec::new().into_iter(),
+    }
+  }
+
+  /// Starts parsing at `position`, treating everything before it as history readbacks may reach into.
+  pub(crate) fn resume(level: Yaz0CompressionLevel, position: usize) -> Self {
+    Self { position, ..Self::new(level) }
+  }
+
+  pub(crate) fn level(&self) -> Yaz0CompressionLevel {
+    self.level
+  }
+
+  pub(crate) fn position(&self) -> usize {
+    self.position
+  }
+
+  /// Whether operations have already been planned past [`position`](Self::position).
+  pub(crate) fn is_planned(&self) -> bool {
+    #[cfg(feature = "alloc")]
+    return self.planned.len() != 0;
+
+    #[cfg(not(feature = "alloc"))]
+    false
+  }
+
+  /// Moves every position back by `amount` after the caller drops that many bytes from the front.
+  pub(crate) fn slide(&mut self, amount: usize) {
+    self.chain.slide(amount);
+    self.position -= amount;
+    self.inserted -= amount;
+  }
+
+  /// Inserts every position before `position` into the chains.
+  fn insert_until(&mut self, data: &[u8], position: usize) {
+    while self.inserted < position {
+      self.chain.insert(data, self.inserted);
+      self.inserted += 1;
+    }
+  }
+
+  fn find(&mut self, data: &[u8], position: usize) -> Option<Match> {
+    self.insert_until(data, position);
+    self.chain.find(data, position)
+  }
+
+  pub(crate) fn next(&mut self, data: &[u8]) -> Option<Operation> {
+    #[cfg(feature = "alloc")]
+    if self.level == Yaz0CompressionLevel::Optimal {
+      if self.planned.len() == 0 && self.position < data.len() {
+        self.insert_until(data, self.position);
+        self.planned = super::optimal::parse(&mut self.chain, data, self.position).into_iter();
+        self.inserted = data.len();
+      }
+
+      let operation = self.planned.next()?;
+      self.position += operation.len() as usize;
+      return Some(operation);
+    }
+
+    let position = self.position;
+    let byte = *data.get(position)?;
+
+    let found = match self.pending.take() {
+      Some(found) => Some(found),
+      None => match self.find(data, position) {
+        Some(found) if self.level == Yaz0CompressionLevel::Lazy => match self.find(data, position + 1) {
+          Some(next) if next.length >= found.length + 2 => {
+            self.pending = Some(next);
+            None
+          }
+          _ => Some(found),
+        },
+        found => found,
+      },
+    };
+
+    Some(match found {
+      Some(Match { offset, length }) => {
+        self.position += length as usize;
+        Operation::readback(offset, length).unwrap()
+      }
+      None => {
+        self.position += 1;
+        Operation::lit(byte)
+      }
+    })
+  }
+}
@@ -0,0 +1,24 @@
+use fileforge::{
+  error::render::builtin::number::formatted_unsigned::FormattedUnsigned,
+  stream::{error::user_skip::UserSkipError, StreamReadError, UserReadError},
+};
+use fileforge_macros::FileforgeError;
+
+#[derive(FileforgeError)]
+pub enum Yaz0EncoderError {
+  #[report(&"Can't size a Yaz0 header for a stream of unknown length")]
+  UnknownLength,
+
+  #[report(&"Input is too large for Yaz0")]
+  #[flag("{length} bytes don't fit in a 32-bit decompressed size", length = FormattedUnsigned::new(*length as u128))]
+  TooLarge { length: u64 },
+}
+
+#[derive(FileforgeError)]
+pub enum Yaz0EncoderReadError<U: UserReadError> {
+  #[report(&"Failed to read the uncompressed stream")]
+  Read(StreamReadError<U>),
+}
+
+impl<U: UserReadError> UserReadError for Yaz0EncoderReadError<U> {}
+impl<U: UserReadError> UserSkipError for Yaz0EncoderReadError<U> {}
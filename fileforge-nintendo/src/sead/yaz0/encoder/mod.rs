@@ -0,0 +1,398 @@
+pub mod error;
+
+use core::ops::Range;
+
+use fileforge::stream::{
+  error::{stream_exhausted::StreamExhaustedError, stream_seek_out_of_bounds::StreamSeekOutOfBoundsError},
+  ReadableStream, StreamReadError, StreamSkipError,
+};
+
+use crate::sead::yaz0::{
+  compress::{matcher::WINDOW_SIZE, parse::Yaz0Parse, Yaz0CompressionLevel},
+  encoder::error::{Yaz0EncoderError, Yaz0EncoderReadError},
+  header::Yaz0Header,
+  parser::data::Block,
+};
+
+/// Uncompressed bytes the encoder keeps in memory: one window of history, plus room to read ahead.
+pub const ENCODER_WINDOW_LENGTH: usize = 3 * WINDOW_SIZE;
+
+const READ_CHUNK_SIZE: usize = 256;
+
+/// Largest encoded block: a header byte and eight long readbacks.
+const MAX_ENCODED_BLOCK_LENGTH: usize = 1 + 8 * 3;
+
+/// Compresses an uncompressed byte stream into a complete Yaz0 file as it is read.
+///
+/// Only [`ENCODER_WINDOW_LENGTH`] bytes of input are held at once, so the source never needs to
+/// fit in memory. The source's remaining length becomes the header's decompressed size.
+pub struct Yaz0Encoder<S: ReadableStream<Type = u8>> {
+  stream: S,
+  header: Yaz0Header,
+  parse: Yaz0Parse,
+  window: [u8; ENCODER_WINDOW_LENGTH],
+  filled: usize,
+  unread: u64,
+  header_written: bool,
+  queued: heapless::Deque<u8, MAX_ENCODED_BLOCK_LENGTH>,
+  /// Output a read that ran past the end gave back. Only used once the file is complete, when the window's input is done with.
+  returned: Range<usize>,
+  produced: u64,
+  length: Option<u64>,
+  offset: u64,
+}
+
+impl<S: ReadableStream<Type = u8>> Yaz0Encoder<S> {
+  pub fn new(stream: S, level: Yaz0CompressionLevel) -> Result<Self, Yaz0EncoderError> {
+    let length = stream.remaining().ok_or(Yaz0EncoderError::UnknownLength)?;
+    let decompressed_size = u32::try_from(length).map_err(|_| Yaz0EncoderError::TooLarge { length })?;
+
+    Ok(Self {
+      stream,
+      header: Yaz0Header::empty().with_decompressed_size(decompressed_size),
+      parse: Yaz0Parse::new(level),
+      window: [0; ENCODER_WINDOW_LENGTH],
+      filled: 0,
+      unread: length,
+      header_written: false,
+      queued: heapless::Deque::new(),
+      returned: 0..0,
+      produced: 0,
+      length: None,
+      offset: 0,
+    })
+  }
+
+  /// Sets the alignment recorded in the header. Has no effect once the header has been read.
+  pub fn with_alignment(self, alignment: u32) -> Self {
+    Self {
+      header: self.header.with_alignment(alignment),
+      ..self
+    }
+  }
+
+  pub fn header(&self) -> &Yaz0Header {
+    &self.header
+  }
+
+  pub fn level(&self) -> Yaz0CompressionLevel {
+    self.parse.level()
+  }
+
+  pub fn into_inner(self) -> S {
+    self.stream
+  }
+
+  /// Keeps enough input past the parse position for it to find full-length matches.
+  async fn fill(&mut self) -> Result<(), StreamReadError<S::ReadError>> {
+    if self.unread == 0 || self.parse.is_planned() || self.filled >= self.parse.position() + Yaz0Parse::LOOKAHEAD {
+      return Ok(());
+    }
+
+    if self.filled == ENCODER_WINDOW_LENGTH {
+      self.window.copy_within(WINDOW_SIZE.., 0);
+      self.filled -= WINDOW_SIZE;
+      self.parse.slide(WINDOW_SIZE);
+    }
+
+    while self.unread > 0 && self.filled < ENCODER_WINDOW_LENGTH {
+      if self.unread >= READ_CHUNK_SIZE as u64 && ENCODER_WINDOW_LENGTH - self.filled >= READ_CHUNK_SIZE {
+        let chunk: [u8; READ_CHUNK_SIZE] = self.stream.read(async |bytes: &[u8; READ_CHUNK_SIZE]| *bytes).await?;
+        self.window[self.filled..self.filled + READ_CHUNK_SIZE].copy_from_slice(&chunk);
+        self.filled += READ_CHUNK_SIZE;
+        self.unread -= READ_CHUNK_SIZE as u64;
+      } else {
+        self.window[self.filled] = self.stream.read(async |bytes: &[u8; 1]| bytes[0]).await?;
+        self.filled += 1;
+        self.unread -= 1;
+      }
+    }
+
+    Ok(())
+  }
+
+  async fn next_block(&mut self) -> Result<Option<Block>, StreamReadError<S::ReadError>> {
+    let mut block = Block::empty();
+
+    while !block.is_full() {
+      self.fill().await?;
+
+      match self.parse.next(&self.window[..self.filled]) {
+        Some(operation) => block.operations.push(operation).unwrap(),
+        None => break,
+      }
+    }
+
+    Ok((!block.is_empty()).then_some(block))
+  }
+
+  /// Queues the next piece of output, returning `false` once the file is complete.
+  async fn refill(&mut self) -> Result<bool, Yaz0EncoderReadError<S::ReadError>> {
+    if !self.header_written {
+      self.header_written = true;
+      self.queue(&self.header.encode());
+      return Ok(true);
+    }
+
+    let Some(block) = self.next_block().await.map_err(Yaz0EncoderReadError::Read)? else {
+      self.length = Some(self.produced);
+      return Ok(false);
+    };
+
+    self.queue(&block.encode());
+    Ok(true)
+  }
+
+  fn queue(&mut self, bytes: &[u8]) {
+    self.queued.extend(bytes.iter().copied());
+    self.produced += bytes.len() as u64;
+  }
+
+  fn pop(&mut self) -> Option<u8> {
+    match self.returned.next() {
+      Some(index) => Some(self.window[index]),
+      None => self.queued.pop_front(),
+    }
+  }
+
+  /// Keeps the output a failed read took, so it can still be read. Only called once the file is complete.
+  ///
+  /// Anything longer than the window can't be kept, and is consumed instead.
+  fn give_back(&mut self, bytes: &[u8]) {
+    if bytes.len() > ENCODER_WINDOW_LENGTH {
+      self.offset += bytes.len() as u64;
+      return;
+    }
+
+    self.window[..bytes.len()].copy_from_slice(bytes);
+    self.returned = 0..bytes.len();
+  }
+}
+
+impl<S: ReadableStream<Type = u8>> ReadableStream for Yaz0Encoder<S> {
+  type Type = u8;
+
+  type ReadError = Yaz0EncoderReadError<S::ReadError>;
+  type SkipError = Yaz0EncoderReadError<S::ReadError>;
+
+  /// Only known once the last block has been produced.
+  fn len(&self) -> Option<u64> {
+    self.length
+  }
+
+  fn offset(&self) -> u64 {
+    self.offset
+  }
+
+  /// A read that runs past the end of the file before its length is known leaves the bytes it found to be read again,
+  /// unless there are more than [`ENCODER_WINDOW_LENGTH`] of them.
+  async fn read<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[Self::Type; SIZE]) -> V) -> Result<V, StreamReadError<Self::ReadError>> {
+    let read_offset = self.offset;
+
+    if let Some(length) = self.length {
+      StreamExhaustedError::assert(length, read_offset, SIZE as u64)?;
+    }
+    let mut buffer = heapless::Vec::<u8, SIZE>::new();
+
+    loop {
+      while !buffer.is_full() {
+        match self.pop() {
+          Some(byte) => buffer.push(byte).unwrap(),
+          None => break,
+        }
+      }
+
+      if buffer.is_full() {
+        break;
+      }
+
+      if !self.refill().await? {
+        self.give_back(&buffer);
+
+        Err(StreamExhaustedError {
+          read_length: SIZE as u64,
+          read_offset,
+          stream_length: read_offset + buffer.len() as u64,
+        })?
+      }
+    }
+
+    self.offset += SIZE as u64;
+    Ok(reader(&buffer.into_array::<SIZE>().unwrap()).await)
+  }
+
+  /// A skip that runs past the end of the file before its length is known consumes everything up to the end.
+  async fn skip(&mut self, size: u64) -> Result<(), StreamSkipError<Self::SkipError>> {
+    if let Some(length) = self.length {
+      StreamSkipError::assert_relative_forwards(length, self.offset, size)?;
+    }
+
+    let mut skipped = 0;
+
+    while skipped < size {
+      match self.pop() {
+        Some(_) => skipped += 1,
+        None if self.refill().await? => {}
+        None => {
+          let seek_point = self.offset + size;
+          self.offset += skipped;
+
+          Err(StreamSeekOutOfBoundsError {
+            seek_point,
+            stream_length: self.offset,
+          })?
+        }
+      }
+    }
+
+    self.offset += size;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use fileforge::{
+    binary_reader::{endianness::Endianness, BinaryReader},
+    provider::hint::ReadHint,
+    stream::{builtin::provider::ProviderStream, ReadableStream, SINGLE},
+  };
+
+  use super::*;
+  use crate::sead::yaz0::{compress::Yaz0Compressor, header::YAZ0_HEADER_SIZE, readable::Immutable, Yaz0Stream};
+
+  const REAL: &[u8] = include_bytes!("../../../../../fileforge-test/binaries/real.byml");
+
+  fn encoder(data: &[u8], level: Yaz0CompressionLevel) -> Yaz0Encoder<ProviderStream<Vec<u8>>> {
+    Yaz0Encoder::new(ProviderStream::new(data.to_vec(), ReadHint::new()), level).map_err(|_| {}).unwrap()
+  }
+
+  async fn drain<S: ReadableStream<Type = u8>>(stream: &mut S) -> Vec<u8> {
+    let mut out = Vec::new();
+
+    while let Ok(byte) = stream.read(SINGLE).await {
+      out.push(byte);
+    }
+
+    out
+  }
+
+  async fn decompress(file: Vec<u8>) -> Vec<u8> {
+    let reader = BinaryReader::new_from_provider(file, Endianness::BigEndian, ReadHint::new());
+    let mut stream = reader.into_with::<Yaz0Stream<_, _>>(Immutable).await.map_err(|_| {}).unwrap();
+    let mut out = Vec::new();
+
+    while let Ok(byte) = stream.read(SINGLE).await {
+      out.push(byte);
+    }
+
+    out
+  }
+
+  /// Enough repetitive and novel data to slide the window several times.
+  fn sample() -> Vec<u8> {
+    let mut seed = 0xC0FF_EE11u32;
+
+    (0..5 * ENCODER_WINDOW_LENGTH)
+      .map(|i| {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        if (i / 700) % 3 == 0 {
+          (seed >> 24) as u8
+        } else {
+          REAL[i % REAL.len()]
+        }
+      })
+      .collect()
+  }
+
+  #[tokio::test]
+  async fn matches_whole_buffer_compressor() {
+    let data = sample();
+
+    for level in [Yaz0CompressionLevel::Fast, Yaz0CompressionLevel::Lazy] {
+      let mut expected = Yaz0Header::empty().with_decompressed_size(data.len() as u32).encode().to_vec();
+      Yaz0Compressor::new(&data, level).blocks().for_each(|block| expected.extend(block.encode()));
+
+      assert_eq!(drain(&mut encoder(&data, level)).await, expected, "{level:?}");
+    }
+  }
+
+  #[tokio::test]
+  async fn round_trips_through_yaz0_stream() {
+    let data = sample();
+
+    for level in [Yaz0CompressionLevel::Fast, Yaz0CompressionLevel::Lazy, Yaz0CompressionLevel::Optimal] {
+      let file = drain(&mut encoder(&data, level)).await;
+      assert_eq!(decompress(file).await, data, "{level:?}");
+    }
+  }
+
+  #[tokio::test]
+  async fn writes_header() {
+    let mut encoder = encoder(REAL, Yaz0CompressionLevel::Fast).with_alignment(0x80);
+    let header: [u8; YAZ0_HEADER_SIZE] = encoder.read(async |bytes: &[u8; YAZ0_HEADER_SIZE]| *bytes).await.map_err(|_| {}).unwrap();
+
+    assert_eq!(&header[..4], b"Yaz0");
+    assert_eq!(u32::from_be_bytes(header[4..8].try_into().unwrap()), REAL.len() as u32);
+    assert_eq!(u32::from_be_bytes(header[8..12].try_into().unwrap()), 0x80);
+    assert_eq!(encoder.offset(), YAZ0_HEADER_SIZE as u64);
+  }
+
+  #[tokio::test]
+  async fn reads_in_chunks() {
+    let expected = drain(&mut encoder(REAL, Yaz0CompressionLevel::Lazy)).await;
+    let mut encoder = encoder(REAL, Yaz0CompressionLevel::Lazy);
+    let mut out = Vec::new();
+
+    for _ in 0..expected.len() / 64 {
+      out.extend(encoder.read(async |bytes: &[u8; 64]| *bytes).await.map_err(|_| {}).unwrap());
+    }
+
+    out.extend(drain(&mut encoder).await);
+    assert_eq!(out, expected);
+    assert_eq!(encoder.len(), Some(expected.len() as u64));
+  }
+
+  #[tokio::test]
+  async fn skips_output() {
+    let expected = drain(&mut encoder(REAL, Yaz0CompressionLevel::Lazy)).await;
+    let mut encoder = encoder(REAL, Yaz0CompressionLevel::Lazy);
+
+    encoder.skip(100).await.map_err(|_| {}).unwrap();
+    assert_eq!(drain(&mut encoder).await, expected[100..]);
+    assert!(encoder.skip(1).await.is_err());
+  }
+
+  #[tokio::test]
+  async fn overlong_read_leaves_the_end_to_read() {
+    let expected = drain(&mut encoder(REAL, Yaz0CompressionLevel::Lazy)).await;
+    let mut encoder = encoder(REAL, Yaz0CompressionLevel::Lazy);
+
+    encoder.skip(expected.len() as u64 - 2).await.map_err(|_| {}).unwrap();
+    assert!(encoder.read(async |bytes: &[u8; 4]| *bytes).await.is_err());
+    assert_eq!(encoder.offset(), expected.len() as u64 - 2);
+    assert_eq!(encoder.len(), Some(expected.len() as u64));
+
+    let end: [u8; 2] = encoder.read(async |bytes: &[u8; 2]| *bytes).await.map_err(|_| {}).unwrap();
+    assert_eq!(end, expected[expected.len() - 2..]);
+    assert!(encoder.read(SINGLE).await.is_err());
+  }
+
+  #[tokio::test]
+  async fn overlong_skip_consumes_to_the_end() {
+    let expected = drain(&mut encoder(REAL, Yaz0CompressionLevel::Lazy)).await;
+    let mut encoder = encoder(REAL, Yaz0CompressionLevel::Lazy);
+
+    assert!(encoder.skip(expected.len() as u64 + 10).await.is_err());
+    assert_eq!(encoder.offset(), expected.len() as u64);
+    assert_eq!(encoder.remaining(), Some(0));
+  }
+
+  #[tokio::test]
+  async fn encodes_empty_input() {
+    let file = drain(&mut encoder(&[], Yaz0CompressionLevel::Lazy)).await;
+
+    assert_eq!(file, Yaz0Header::empty().encode());
+    assert!(decompress(file).await.is_empty());
+  }
+}
@@ -0,0 +1,13 @@
+use fileforge::error::render::builtin::number::formatted_unsigned::FormattedUnsigned;
+use fileforge_macros::FileforgeError;
+
+#[derive(FileforgeError)]
+pub enum Yaz0IndexMismatchError {
+  #[report(&"Yaz0 index was built for a different file")]
+  #[flag(
+    "The index covers {indexed} bytes, but the file decompresses to {expected}",
+    indexed = FormattedUnsigned::new(*indexed as u128),
+    expected = FormattedUnsigned::new(*expected as u128)
+  )]
+  DecompressedSize { indexed: u32, expected: u32 },
+}
@@ -1,4 +1,7 @@
+#[cfg(feature = "alloc")]
+pub mod index;
 pub mod overwrite;
+pub mod seek;
 
 use fileforge::{
   diagnostic::pool::DiagnosticPoolProvider,
@@ -0,0 +1,16 @@
+use fileforge::stream::error::{stream_seek::StreamSeekError, stream_skip::StreamSkipError, user_read::UserReadError, user_rewind::UserRewindError, user_seek::UserSeekError};
+use fileforge_macros::FileforgeError;
+
+use crate::sead::yaz0::error::Yaz0Error;
+
+#[derive(FileforgeError)]
+pub enum Yaz0SeekError<SURE: UserReadError, SUSE: UserSeekError> {
+  #[report(&"Failed to move the compressed stream to where decompression restarts")]
+  Restart(StreamSeekError<SUSE>),
+
+  #[report(&"Failed to decompress up to the seek point")]
+  Decompress(StreamSkipError<Yaz0Error<SURE>>),
+}
+
+impl<SURE: UserReadError, SUSE: UserSeekError> UserSeekError for Yaz0SeekError<SURE, SUSE> {}
+impl<SURE: UserReadError, SUSE: UserSeekError> UserRewindError for Yaz0SeekError<SURE, SUSE> {}
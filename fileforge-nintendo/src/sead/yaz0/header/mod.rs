@@ -1,7 +1,7 @@
 pub mod mutable;
 pub mod readable;
 
-pub static YAZ0_HEADER_SIZE: usize = 0x10;
+pub const YAZ0_HEADER_SIZE: usize = 0x10;
 
 pub struct Yaz0Header {
   decompressed_size: u32,
@@ -34,4 +34,16 @@ impl Yaz0Header {
   pub fn alignment(&self) -> u32 {
     self.data_alignment
   }
+
+  /// The header's bytes as they appear at the start of a Yaz0 file.
+  pub fn encode(&self) -> [u8; YAZ0_HEADER_SIZE] {
+    let mut bytes = [0; YAZ0_HEADER_SIZE];
+
+    bytes[0x0..0x4].copy_from_slice(b"Yaz0");
+    bytes[0x4..0x8].copy_from_slice(&self.decompressed_size.to_be_bytes());
+    bytes[0x8..0xC].copy_from_slice(&self.data_alignment.to_be_bytes());
+    bytes[0xC..0x10].copy_from_slice(&self.unused.to_be_bytes());
+
+    bytes
+  }
 }
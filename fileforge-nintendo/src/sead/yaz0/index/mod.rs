@@ -0,0 +1,274 @@
+pub mod readable;
+
+use alloc::vec::Vec;
+
+use crate::sead::yaz0::state::Yaz0State;
+
+pub const YAZ0_INDEX_HEADER_SIZE: usize = 0x10;
+
+/// A point in a Yaz0 file where decompression can restart without reading anything before it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Yaz0Checkpoint {
+  decompressed_offset: u32,
+  compressed_offset: u64,
+  blocks: u64,
+  seekback: Vec<u8>,
+}
+
+impl Yaz0Checkpoint {
+  pub fn decompressed_offset(&self) -> u32 {
+    self.decompressed_offset
+  }
+
+  /// Offset of the next block's header, relative to the end of the Yaz0 header.
+  pub fn compressed_offset(&self) -> u64 {
+    self.compressed_offset
+  }
+
+  pub fn blocks(&self) -> u64 {
+    self.blocks
+  }
+
+  /// Up to 4096 bytes decompressed just before this checkpoint, for back-references to reach into.
+  pub fn seekback(&self) -> &[u8] {
+    &self.seekback
+  }
+}
+
+/// Checkpoints taken every `interval` bytes of a Yaz0 file's output, letting
+/// [`Yaz0Stream`](crate::sead::yaz0::Yaz0Stream) seek without decompressing from the start.
+///
+/// Each checkpoint costs up to 4 KiB. An index only knows the decompressed size of the file it
+/// was built from, so attaching one to a different file with the same size gives garbage.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Yaz0Index {
+  decompressed_size: u32,
+  interval: u32,
+  checkpoints: Vec<Yaz0Checkpoint>,
+}
+
+impl Yaz0Index {
+  /// An empty index, filled in as a stream decompresses the file.
+  pub fn new(decompressed_size: u32, interval: u32) -> Self {
+    Self {
+      decompressed_size,
+      interval,
+      checkpoints: Vec::new(),
+    }
+  }
+
+  pub fn decompressed_size(&self) -> u32 {
+    self.decompressed_size
+  }
+
+  pub fn interval(&self) -> u32 {
+    self.interval
+  }
+
+  pub fn checkpoints(&self) -> &[Yaz0Checkpoint] {
+    &self.checkpoints
+  }
+
+  /// The last checkpoint at or before `offset`.
+  pub fn checkpoint_before(&self, offset: u64) -> Option<&Yaz0Checkpoint> {
+    let after = self.checkpoints.partition_point(|checkpoint| checkpoint.decompressed_offset as u64 <= offset);
+
+    after.checked_sub(1).map(|index| &self.checkpoints[index])
+  }
+
+  /// Records `state` if it's at least an interval past the last checkpoint. `state` must be at a
+  /// block boundary with nothing left to take.
+  pub(crate) fn record(&mut self, state: &Yaz0State, compressed_offset: u64, blocks: u64) {
+    let last = self.checkpoints.last().map_or(0, |checkpoint| checkpoint.decompressed_offset as u64);
+
+    if state.offset() > last && state.offset() >= last + self.interval as u64 {
+      self.checkpoints.push(Yaz0Checkpoint {
+        decompressed_offset: state.offset() as u32,
+        compressed_offset,
+        blocks,
+        seekback: state.seekback().collect(),
+      });
+    }
+  }
+
+  /// Forgets every checkpoint at or past `decompressed_offset`, for a file re-encoded from there on to
+  /// `decompressed_size` bytes.
+  pub(crate) fn invalidate_from(&mut self, decompressed_offset: u64, decompressed_size: u32) {
+    let kept = self.checkpoints.partition_point(|checkpoint| (checkpoint.decompressed_offset as u64) < decompressed_offset);
+
+    self.checkpoints.truncate(kept);
+    self.decompressed_size = decompressed_size;
+  }
+
+  /// The index in its big-endian file form, to cache next to the file it was built from.
+  pub fn encode(&self) -> Vec<u8> {
+    let mut out = Vec::with_capacity(YAZ0_INDEX_HEADER_SIZE + self.checkpoints.iter().map(|checkpoint| 22 + checkpoint.seekback.len()).sum::<usize>());
+
+    out.extend_from_slice(b"Yz0I");
+    out.extend(self.decompressed_size.to_be_bytes());
+    out.extend(self.interval.to_be_bytes());
+    out.extend((self.checkpoints.len() as u32).to_be_bytes());
+
+    for checkpoint in &self.checkpoints {
+      out.extend(checkpoint.decompressed_offset.to_be_bytes());
+      out.extend(checkpoint.compressed_offset.to_be_bytes());
+      out.extend(checkpoint.blocks.to_be_bytes());
+      out.extend((checkpoint.seekback.len() as u16).to_be_bytes());
+      out.extend_from_slice(&checkpoint.seekback);
+    }
+
+    out
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use fileforge::{
+    binary_reader::{endianness::Endianness, BinaryReader},
+    provider::hint::ReadHint,
+    stream::{builtin::provider::ProviderStream, ReadableStream, RewindableStream, SeekableStream},
+  };
+
+  use super::*;
+  use crate::sead::yaz0::{
+    compress::{Yaz0CompressionLevel, Yaz0Compressor},
+    error::index::Yaz0IndexMismatchError,
+    index::readable::Yaz0IndexReadError,
+    readable::Immutable,
+    Yaz0Stream,
+  };
+
+  const REAL: &[u8] = include_bytes!("../../../../../fileforge-test/binaries/real.byml");
+  const COPIES: usize = 12;
+  const SIZE: u64 = (COPIES * (REAL.len() + 1000)) as u64;
+  const CHUNK: usize = 0x100;
+  const POSITIONS: [u64; 6] = [0x2_8000, 0x10, 0x1_8123, SIZE - CHUNK as u64, 0x9876, 0x1_8000];
+
+  type Stream = Yaz0Stream<'static, ProviderStream<Vec<u8>>, Immutable>;
+
+  /// Copies of a BYML file with noise between them, so back-references keep crossing checkpoints.
+  fn file() -> Vec<u8> {
+    let mut data = Vec::new();
+    let mut seed = 0x5EED_1234u32;
+
+    for _ in 0..COPIES {
+      data.extend_from_slice(REAL);
+
+      for _ in 0..1000 {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        data.push((seed >> 24) as u8 & 0x0F);
+      }
+    }
+
+    let mut file = b"Yaz0".to_vec();
+    file.extend((data.len() as u32).to_be_bytes());
+    file.extend([0; 8]);
+    Yaz0Compressor::new(&data, Yaz0CompressionLevel::Fast).blocks().for_each(|block| file.extend(block.encode()));
+    file
+  }
+
+  async fn stream() -> Stream {
+    let reader = BinaryReader::new_from_provider(file(), Endianness::BigEndian, ReadHint::new());
+    reader.into_with::<Yaz0Stream<_, _>>(Immutable).await.map_err(|_| {}).unwrap()
+  }
+
+  async fn chunk(stream: &mut Stream) -> [u8; CHUNK] {
+    stream.read(async |bytes: &[u8; CHUNK]| *bytes).await.map_err(|_| {}).unwrap()
+  }
+
+  /// The chunk at each of `POSITIONS`, found by decompressing forwards only.
+  async fn expected() -> Vec<[u8; CHUNK]> {
+    let mut positions = POSITIONS;
+    positions.sort();
+
+    let mut stream = stream().await;
+    let mut sorted = Vec::new();
+
+    for position in positions {
+      stream.skip(position - stream.offset()).await.map_err(|_| {}).unwrap();
+      sorted.push((position, chunk(&mut stream).await));
+    }
+
+    POSITIONS.iter().map(|position| sorted.iter().find(|(p, _)| p == position).unwrap().1).collect()
+  }
+
+  async fn indexed(interval: u32) -> Stream {
+    let mut stream = stream().await;
+
+    stream.attach_index(Yaz0Index::new(SIZE as u32, interval)).map_err(|_| {}).unwrap();
+    stream.skip(SIZE).await.map_err(|_| {}).unwrap();
+    stream
+  }
+
+  async fn assert_seeks(stream: &mut Stream) {
+    for (position, expected) in POSITIONS.into_iter().zip(expected().await) {
+      stream.seek(position).await.map_err(|_| {}).unwrap();
+
+      assert_eq!(chunk(stream).await, expected, "{position:#X}");
+    }
+  }
+
+  #[tokio::test]
+  async fn seeks_without_an_index() {
+    let mut stream = stream().await;
+
+    assert_seeks(&mut stream).await;
+    assert!(stream.seek(SIZE + 1).await.is_err());
+  }
+
+  #[tokio::test]
+  async fn seeks_from_checkpoints() {
+    let mut stream = indexed(0x4000).await;
+    let checkpoints = stream.index().unwrap().checkpoints();
+
+    assert_eq!(checkpoints.len() as u64, SIZE / 0x4000);
+    assert!(checkpoints.iter().all(|checkpoint| checkpoint.seekback().len() == 4096));
+
+    assert_seeks(&mut stream).await;
+
+    stream.rewind(0x1_0000).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.offset(), POSITIONS[5] + CHUNK as u64 - 0x1_0000);
+    assert_eq!(stream.index().unwrap().checkpoints().len() as u64, SIZE / 0x4000);
+  }
+
+  #[tokio::test]
+  async fn cached_index_round_trips() {
+    let index = indexed(0x8000).await.take_index().unwrap();
+    let mut reader = BinaryReader::new_from_provider(index.encode(), Endianness::BigEndian, ReadHint::new());
+    let cached = reader.read::<Yaz0Index>().await.map_err(|_| {}).unwrap();
+
+    assert_eq!(cached, index);
+
+    let mut stream = stream().await;
+
+    stream.attach_index(cached).map_err(|_| {}).unwrap();
+    assert_seeks(&mut stream).await;
+  }
+
+  #[tokio::test]
+  async fn rejects_index_for_another_file() {
+    let mut stream = stream().await;
+
+    assert!(matches!(
+      stream.attach_index(Yaz0Index::new(0x100, 0x10)),
+      Err(Yaz0IndexMismatchError::DecompressedSize { indexed: 0x100, expected }) if expected as u64 == SIZE
+    ));
+  }
+
+  #[tokio::test]
+  async fn rejects_misplaced_checkpoints() {
+    let mut index = Yaz0Index::new(0x3000, 0x1000);
+    let checkpoint = |offset: u32| Yaz0Checkpoint {
+      decompressed_offset: offset,
+      compressed_offset: 0,
+      blocks: 0,
+      seekback: vec![0; offset.min(4096) as usize],
+    };
+
+    index.checkpoints.extend([checkpoint(0x2000), checkpoint(0x1000)]);
+
+    let mut reader = BinaryReader::new_from_provider(index.encode(), Endianness::BigEndian, ReadHint::new());
+
+    assert!(matches!(reader.read::<Yaz0Index>().await, Err(Yaz0IndexReadError::MisplacedCheckpoint { offset: 0x1000 })));
+  }
+}
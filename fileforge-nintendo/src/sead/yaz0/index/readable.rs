@@ -0,0 +1,105 @@
+use alloc::vec::Vec;
+
+use fileforge::{
+  binary_reader::{
+    error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError},
+    readable::Readable,
+    BinaryReader, PrimitiveReader,
+  },
+  error::{ext::annotations::annotated::Annotated, render::builtin::number::formatted_unsigned::FormattedUnsigned},
+  stream::{error::user_read::UserReadError, ReadableStream},
+};
+use fileforge_macros::FileforgeError;
+use fileforge_std::magic::{Magic, MagicError};
+
+use crate::sead::yaz0::{
+  index::{Yaz0Checkpoint, Yaz0Index},
+  state::SEEKBACK_BUFFER_LENGTH,
+};
+
+pub const YAZ0_INDEX_MAGIC: Magic<4> = Magic::from_byte_ref(b"Yz0I");
+
+/// Reads an index written by [`Yaz0Index::encode`], which expects a big-endian reader.
+impl<'pool, S: ReadableStream<Type = u8>> Readable<'pool, S> for Yaz0Index {
+  type Error = Yaz0IndexReadError<'pool, S::ReadError>;
+  type Argument = ();
+
+  async fn read(reader: &mut BinaryReader<'pool, S>, _: Self::Argument) -> Result<Self, Self::Error> {
+    reader.read_with::<Magic<4>>(YAZ0_INDEX_MAGIC).await?;
+
+    let decompressed_size: u32 = reader.get().await.map_err(Yaz0IndexReadError::DecompressedSize)?;
+    let interval: u32 = reader.get().await.map_err(Yaz0IndexReadError::Interval)?;
+    let count: u32 = reader.get().await.map_err(Yaz0IndexReadError::CheckpointCount)?;
+    let mut checkpoints: Vec<Yaz0Checkpoint> = Vec::new();
+
+    for _ in 0..count {
+      let decompressed_offset: u32 = reader.get().await.map_err(Yaz0IndexReadError::DecompressedOffset)?;
+      let compressed_offset: u64 = reader.get().await.map_err(Yaz0IndexReadError::CompressedOffset)?;
+      let blocks: u64 = reader.get().await.map_err(Yaz0IndexReadError::Blocks)?;
+      let seekback_length: u16 = reader.get().await.map_err(Yaz0IndexReadError::SeekbackLength)?;
+
+      let previous = checkpoints.last().map_or(0, |checkpoint| checkpoint.decompressed_offset);
+
+      if decompressed_offset <= previous || decompressed_offset > decompressed_size {
+        return Err(Yaz0IndexReadError::MisplacedCheckpoint { offset: decompressed_offset });
+      }
+
+      if seekback_length as usize != (decompressed_offset as usize).min(SEEKBACK_BUFFER_LENGTH) {
+        return Err(Yaz0IndexReadError::InvalidSeekback {
+          offset: decompressed_offset,
+          length: seekback_length,
+        });
+      }
+
+      let mut seekback = Vec::with_capacity(seekback_length as usize);
+
+      for _ in 0..seekback_length {
+        seekback.push(reader.get().await.map_err(Yaz0IndexReadError::Seekback)?);
+      }
+
+      checkpoints.push(Yaz0Checkpoint {
+        decompressed_offset,
+        compressed_offset,
+        blocks,
+        seekback,
+      });
+    }
+
+    Ok(Yaz0Index {
+      decompressed_size,
+      interval,
+      checkpoints,
+    })
+  }
+}
+
+#[derive(FileforgeError)]
+#[report(&"Failed to read Yaz0 Index")]
+pub enum Yaz0IndexReadError<'pool, U: UserReadError> {
+  Magic(#[from] MagicError<'pool, 4, U>),
+  DecompressedSize(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  Interval(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  CheckpointCount(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  DecompressedOffset(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  CompressedOffset(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  Blocks(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  SeekbackLength(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  Seekback(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+
+  #[report(&"Yaz0 index checkpoint is out of order")]
+  #[flag("Checkpoint at {offset} is past the end of the file or not after the one before it", offset = FormattedUnsigned::new(*offset as u128))]
+  MisplacedCheckpoint {
+    offset: u32,
+  },
+
+  #[report(&"Yaz0 index checkpoint has the wrong amount of seekback")]
+  #[flag(
+    "Checkpoint at {offset} stores {length} seekback bytes",
+    offset = FormattedUnsigned::new(*offset as u128),
+    length = FormattedUnsigned::new(*length as u128)
+  )]
+  InvalidSeekback {
+    offset: u32,
+    length: u16,
+  },
+}
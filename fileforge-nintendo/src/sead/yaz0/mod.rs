@@ -2,17 +2,20 @@ use core::u32;
 
 use fileforge::stream::{
   error::{
-    stream_exhausted::StreamExhaustedError, stream_overwrite::StreamOverwriteError, stream_read::StreamReadError, stream_restore::StreamRestoreError,
-    stream_seek_out_of_bounds::StreamSeekOutOfBoundsError, stream_skip::StreamSkipError,
+    stream_exhausted::StreamExhaustedError, stream_overwrite::StreamOverwriteError, stream_read::StreamReadError, stream_restore::StreamRestoreError, stream_rewind::StreamRewindError,
+    stream_seek::StreamSeekError, stream_seek_out_of_bounds::StreamSeekOutOfBoundsError, stream_skip::StreamSkipError,
   },
-  MutableStream, ReadableStream, ResizableStream, RestorableStream, StaticPartitionableStream, CLONED,
+  MutableStream, ReadableStream, ResizableStream, RestorableStream, RewindableStream, SeekableStream, StaticPartitionableStream, CLONED,
 };
 
 use crate::sead::yaz0::{
-  error::{overwrite::Yaz0OverwriteError, Yaz0Error},
+  compress::{
+    matcher::{MAX_MATCH, WINDOW_SIZE},
+    Yaz0CompressionLevel,
+  },
+  error::{overwrite::Yaz0OverwriteError, seek::Yaz0SeekError, Yaz0Error},
   header::YAZ0_HEADER_SIZE,
   parser::{
-    block_inflate_pair::inflate_pair,
     data::{Block, Operation},
     Yaz0Parser,
   },
@@ -20,9 +23,15 @@ use crate::sead::yaz0::{
   state::{reference::ReadbackReference, Yaz0State},
   store::{MaybeSnapshotStore, SnapshotStore},
 };
+#[cfg(feature = "alloc")]
+use crate::sead::yaz0::{error::index::Yaz0IndexMismatchError, index::Yaz0Index};
 
+pub mod compress;
+pub mod encoder;
 pub mod error;
 pub mod header;
+#[cfg(feature = "alloc")]
+pub mod index;
 pub mod parser;
 pub mod readable;
 pub mod state;
@@ -33,6 +42,47 @@ pub struct Yaz0Stream<'pool, OriginalStream: ReadableStream<Type = u8>, A: Yaz0S
   stream: Yaz0Parser<<A::HeaderView as HeaderView<'pool, OriginalStream>>::OtherStream>,
   state: Yaz0State,
   store: A::StoreType,
+  #[cfg(feature = "alloc")]
+  index: Option<Yaz0Index>,
+}
+
+impl<'pool, S: ReadableStream<Type = u8>, St: Yaz0StreamReadArgument<'pool, S>> Yaz0Stream<'pool, S, St> {
+  /// Starts filling in `index` as this stream decompresses, and lets seeks restart from its
+  /// checkpoints. A fresh index can be made with [`Yaz0Index::new`], or a cached one read back.
+  #[cfg(feature = "alloc")]
+  pub fn attach_index(&mut self, index: Yaz0Index) -> Result<(), Yaz0IndexMismatchError> {
+    let expected = self.stream.decoded_length();
+
+    if index.decompressed_size() != expected {
+      return Err(Yaz0IndexMismatchError::DecompressedSize {
+        indexed: index.decompressed_size(),
+        expected,
+      });
+    }
+
+    self.index = Some(index);
+    Ok(())
+  }
+
+  #[cfg(feature = "alloc")]
+  pub fn index(&self) -> Option<&Yaz0Index> {
+    self.index.as_ref()
+  }
+
+  #[cfg(feature = "alloc")]
+  pub fn take_index(&mut self) -> Option<Yaz0Index> {
+    self.index.take()
+  }
+
+  /// Called at each block boundary, once everything decompressed so far has been taken.
+  fn checkpoint(&mut self) {
+    self.store.store_snapshot(&self.stream, self.state.clone());
+
+    #[cfg(feature = "alloc")]
+    if let Some(index) = &mut self.index {
+      index.record(&self.state, self.stream.compressed_offset(), self.stream.offset());
+    }
+  }
 }
 
 impl<'pool, S: ReadableStream<Type = u8>, St: Yaz0StreamReadArgument<'pool, S>> ReadableStream for Yaz0Stream<'pool, S, St> {
@@ -42,7 +92,7 @@ impl<'pool, S: ReadableStream<Type = u8>, St: Yaz0StreamReadArgument<'pool, S>>
   type SkipError = Yaz0Error<<<St::HeaderView as HeaderView<'pool, S>>::OtherStream as ReadableStream>::ReadError>;
 
   fn len(&self) -> Option<u64> {
-    Some(self.header.value().decompressed_size().into())
+    Some(self.stream.decoded_length().into())
   }
 
   fn offset(&self) -> u64 {
@@ -55,13 +105,13 @@ impl<'pool, S: ReadableStream<Type = u8>, St: Yaz0StreamReadArgument<'pool, S>>
 
     buffer.extend(self.state.take(buffer.capacity() - buffer.len()));
 
-    while self.offset() < self.header.value().decompressed_size() as u64 && !buffer.is_full() {
-      self.store.store_snapshot(&self.stream, self.state.clone());
+    while self.offset() < self.stream.decoded_length() as u64 && !buffer.is_full() {
+      self.checkpoint();
       let operation = self.stream.read(CLONED).await.map_err(|e| match e {
         StreamReadError::StreamExhausted(_) => StreamReadError::StreamExhausted(StreamExhaustedError {
           read_length: SIZE as u64,
           read_offset,
-          stream_length: self.header.value().decompressed_size() as u64,
+          stream_length: self.stream.decoded_length() as u64,
         }),
         StreamReadError::User(u) => StreamReadError::User(Yaz0Error::ParseError(StreamReadError::User(u))),
       })?;
@@ -74,7 +124,7 @@ impl<'pool, S: ReadableStream<Type = u8>, St: Yaz0StreamReadArgument<'pool, S>>
       Err(StreamExhaustedError {
         read_length: SIZE as u64,
         read_offset,
-        stream_length: self.header.value().decompressed_size() as u64,
+        stream_length: self.stream.decoded_length() as u64,
       })?
     }
 
@@ -87,12 +137,12 @@ impl<'pool, S: ReadableStream<Type = u8>, St: Yaz0StreamReadArgument<'pool, S>>
 
     read_length -= self.state.take(read_length as usize).len() as u64;
 
-    while self.offset() < self.header.value().decompressed_size() as u64 && read_length != 0 {
-      self.store.store_snapshot(&self.stream, self.state.clone());
+    while self.offset() < self.stream.decoded_length() as u64 && read_length != 0 {
+      self.checkpoint();
       let block = self.stream.read(CLONED).await.map_err(|e| match e {
         StreamReadError::StreamExhausted(_) => StreamSkipError::OutOfBounds(StreamSeekOutOfBoundsError {
           seek_point: read_offset + original_read_length,
-          stream_length: self.header.value().decompressed_size() as u64,
+          stream_length: self.stream.decoded_length() as u64,
         }),
         StreamReadError::User(u) => StreamSkipError::User(Yaz0Error::ParseError(StreamReadError::User(u))),
       })?;
@@ -104,7 +154,7 @@ impl<'pool, S: ReadableStream<Type = u8>, St: Yaz0StreamReadArgument<'pool, S>>
     if read_length != 0 {
       Err(StreamSeekOutOfBoundsError {
         seek_point: read_offset + original_read_length,
-        stream_length: self.header.value().decompressed_size() as u64,
+        stream_length: self.stream.decoded_length() as u64,
       })?
     }
 
@@ -135,137 +185,92 @@ where
   }
 }
 
-enum ReencodeData {
-  Starting(Block),
-  StartingWithSkip(Block, u64),
-  With(Block),
-}
-
-impl<'pool, S: ReadableStream<Type = u8> + StaticPartitionableStream<YAZ0_HEADER_SIZE>, Sta: Yaz0StreamReadArgument<'pool, S>> Yaz0Stream<'pool, S, Sta>
+impl<'pool, S: ReadableStream<Type = u8>, Sta: Yaz0StreamReadArgument<'pool, S>> Yaz0Stream<'pool, S, Sta>
 where
-  <Sta::HeaderView as HeaderView<'pool, S>>::OtherStream: RestorableStream + ResizableStream + MutableStream,
-  <S as StaticPartitionableStream<YAZ0_HEADER_SIZE>>::PartitionLeft: MutableStream<Type = u8> + RestorableStream,
-  Sta::HeaderView: MutHeaderView<'pool, S, <S as StaticPartitionableStream<YAZ0_HEADER_SIZE>>::PartitionLeft>,
-  Sta::StoreType: SnapshotStore<<Sta::HeaderView as HeaderView<'pool, S>>::OtherStream>,
+  <Sta::HeaderView as HeaderView<'pool, S>>::OtherStream: SeekableStream,
 {
-  // PLAN: add `until` function that returns a boolean `true` to continue, `false` to stop.
-  // PRECONDITION: `offset` MUST BE CONTAINED WITHIN THE FIRST BLOCK.
-  // PRECONDITION: if `offset` is
-  async fn re_encode_slice<'a, const C: usize>(
-    &mut self,
-    state: &mut Yaz0State,
-    data: ReencodeData,
-    length: &mut u64,
-    mut replacement_data: ReadbackReference<'a, C>,
-  ) -> Result<(Block, (Option<u8>, Block)), <Self as ResizableStream>::OverwriteError> {
-    let mut tail_block = (None, Block::empty());
-    let (mut current_block, mut offset) = match data {
-      ReencodeData::Starting(block) => {
-        let len = block.len() as u64;
-        (block, len)
+  /// Moves back to the latest point at or before `offset` that decompression can restart from,
+  /// unless carrying on from the current position is no slower.
+  async fn restart_before(&mut self, offset: u64) -> Result<(), StreamSeekError<<<Sta::HeaderView as HeaderView<'pool, S>>::OtherStream as SeekableStream>::SeekError>> {
+    #[cfg(feature = "alloc")]
+    if let Some(checkpoint) = self.index.as_ref().and_then(|index| index.checkpoint_before(offset)) {
+      if self.offset() > offset || checkpoint.decompressed_offset() as u64 > self.offset() {
+        self.stream.resume(checkpoint.compressed_offset(), checkpoint.blocks(), checkpoint.decompressed_offset()).await?;
+        self.state = Yaz0State::resume(checkpoint.decompressed_offset().into(), checkpoint.seekback());
+        self.store = Default::default();
       }
-      ReencodeData::StartingWithSkip(block, skip) => {
-        let len = block.len() as u64;
-        (block, skip + len)
-      }
-      ReencodeData::With(block) => (block, 0),
-    };
-
-    while let Some(operation) = self.state.compress(&mut replacement_data) {
-      self.state.feed_operation(operation).unwrap();
-      current_block.operations.push(operation).unwrap();
-
-      if current_block.is_full() {
-        if *length > 0 || offset > 0 {
-          self
-            .stream
-            .mutate(async |data: &mut [Block; 1]| {
-              let new_tail = if offset > 0 {
-                let (_, _, tail_underflow, new_tail) = data[0].clone().split_at_with_mid(offset, state).map_err(|e| Yaz0OverwriteError::MalformedStream(e))?;
 
-                if tail_underflow.is_some() {
-                  *length -= 1;
-                }
-
-                new_tail
-              } else {
-                data[0].clone()
-              };
-
-              let (consumed, consumed_overflow, tail_underflow, new_tail) = new_tail.split_at_with_pre(*length, state).map_err(|e| Yaz0OverwriteError::MalformedStream(e))?;
-
-              if consumed_overflow.is_some() {
-                *length -= 1;
-              }
-
-              *length -= consumed.len() as u64;
-
-              if !new_tail.is_empty() {
-                tail_block = (tail_underflow, new_tail);
-              }
-
-              data[0] = current_block.clone();
-
-              Ok::<_, <Self as ResizableStream>::OverwriteError>(())
-            })
-            .await
-            .map_err(|e| Yaz0OverwriteError::MutateBlockFailed(e))??;
-        } else {
-          self.stream.overwrite(0, [current_block.clone()]).await.map_err(|e| Yaz0OverwriteError::OverwriteBlockFailed(e))?;
-        }
-
-        offset = 0;
-        current_block = Block::empty();
-      }
+      return Ok(());
     }
 
-    if *length > 0 {
-      let mut overwrite_count = 0;
+    if self.offset() > offset {
+      self.stream.resume(0, 0, 0).await?;
+      self.state = Yaz0State::empty();
+      self.store = Default::default();
+    }
 
-      let snapshot = self.stream.snapshot();
+    Ok(())
+  }
 
-      while *length > 0 {
-        self
-          .stream
-          .read(async |data: &[Block; 1]| {
-            let new_tail = if offset > 0 {
-              let (_, _, tail_underflow, new_tail) = data[0].clone().split_at_with_mid(offset, state).map_err(|e| Yaz0OverwriteError::MalformedStream(e))?;
+  async fn seek_within(
+    &mut self,
+    offset: u64,
+  ) -> Result<
+    (),
+    Yaz0SeekError<<<Sta::HeaderView as HeaderView<'pool, S>>::OtherStream as ReadableStream>::ReadError, <<Sta::HeaderView as HeaderView<'pool, S>>::OtherStream as SeekableStream>::SeekError>,
+  > {
+    self.restart_before(offset).await.map_err(Yaz0SeekError::Restart)?;
+    self.skip(offset - self.offset()).await.map_err(Yaz0SeekError::Decompress)
+  }
+}
 
-              if tail_underflow.is_some() {
-                *length -= 1;
-              }
+impl<'pool, S: ReadableStream<Type = u8>, Sta: Yaz0StreamReadArgument<'pool, S>> RewindableStream for Yaz0Stream<'pool, S, Sta>
+where
+  <Sta::HeaderView as HeaderView<'pool, S>>::OtherStream: SeekableStream,
+{
+  type RewindError =
+    Yaz0SeekError<<<Sta::HeaderView as HeaderView<'pool, S>>::OtherStream as ReadableStream>::ReadError, <<Sta::HeaderView as HeaderView<'pool, S>>::OtherStream as SeekableStream>::SeekError>;
 
-              new_tail
-            } else {
-              data[0].clone()
-            };
+  async fn rewind(&mut self, size: u64) -> Result<(), StreamRewindError<Self::RewindError>> {
+    let offset = StreamRewindError::assert_relative_backwards(self.stream.decoded_length() as u64, self.offset(), size)?;
 
-            let (consumed, consumed_overflow, tail_underflow, new_tail) = new_tail.split_at_with_pre(*length, state).map_err(|e| Yaz0OverwriteError::MalformedStream(e))?;
+    self.seek_within(offset).await.map_err(StreamRewindError::User)
+  }
+}
 
-            if consumed_overflow.is_some() {
-              *length -= 1;
-            }
+impl<'pool, S: ReadableStream<Type = u8>, Sta: Yaz0StreamReadArgument<'pool, S>> SeekableStream for Yaz0Stream<'pool, S, Sta>
+where
+  <Sta::HeaderView as HeaderView<'pool, S>>::OtherStream: SeekableStream,
+{
+  type SeekError =
+    Yaz0SeekError<<<Sta::HeaderView as HeaderView<'pool, S>>::OtherStream as ReadableStream>::ReadError, <<Sta::HeaderView as HeaderView<'pool, S>>::OtherStream as SeekableStream>::SeekError>;
 
-            *length -= consumed.len() as u64;
+  async fn seek(&mut self, offset: u64) -> Result<(), StreamSeekError<Self::SeekError>> {
+    StreamSeekOutOfBoundsError::assert(self.stream.decoded_length() as u64, offset)?;
 
-            if !new_tail.is_empty() {
-              tail_block = (tail_underflow, new_tail);
-            }
+    self.seek_within(offset).await.map_err(StreamSeekError::User)
+  }
+}
 
-            Ok::<_, <Self as ResizableStream>::OverwriteError>(())
-          })
-          .await
-          .map_err(|e| Yaz0OverwriteError::ReadBlockFailed(e))??;
+/// Most bytes a single block can decode to: eight of the longest readbacks.
+const MAX_BLOCK_LENGTH: usize = 8 * MAX_MATCH;
 
-        offset = 0;
-        overwrite_count += 1;
-      }
+impl<'pool, S: ReadableStream<Type = u8> + StaticPartitionableStream<YAZ0_HEADER_SIZE>, Sta: Yaz0StreamReadArgument<'pool, S>> Yaz0Stream<'pool, S, Sta>
+where
+  <Sta::HeaderView as HeaderView<'pool, S>>::OtherStream: RestorableStream + ResizableStream + MutableStream,
+  <S as StaticPartitionableStream<YAZ0_HEADER_SIZE>>::PartitionLeft: MutableStream<Type = u8> + RestorableStream,
+  Sta::HeaderView: MutHeaderView<'pool, S, <S as StaticPartitionableStream<YAZ0_HEADER_SIZE>>::PartitionLeft>,
+  Sta::StoreType: SnapshotStore<<Sta::HeaderView as HeaderView<'pool, S>>::OtherStream>,
+{
+  /// Removes the next block from the compressed stream, returning what it held.
+  async fn remove_block(&mut self) -> Result<Block, <Self as ResizableStream>::OverwriteError> {
+    let snapshot = self.stream.snapshot();
+    let block = self.stream.read(CLONED).await.map_err(Yaz0OverwriteError::ReadBlockFailed)?;
 
-      self.stream.restore(snapshot).await.map_err(|e| Yaz0OverwriteError::RestoreFailed(e))?;
-      self.stream.overwrite(overwrite_count, []).await.map_err(|e| Yaz0OverwriteError::OverwriteBlockFailed(e))?;
-    }
+    self.stream.restore(snapshot).await.map_err(Yaz0OverwriteError::RestoreFailed)?;
+    self.stream.overwrite(1, []).await.map_err(Yaz0OverwriteError::OverwriteBlockFailed)?;
 
-    Ok((current_block, tail_block))
+    Ok(block)
   }
 }
 
@@ -285,150 +290,302 @@ where
     <Yaz0Parser<<Sta::HeaderView as HeaderView<'pool, S>>::OtherStream> as ResizableStream>::OverwriteError,
   >;
 
-  async fn overwrite<const SIZE: usize>(&mut self, mut length: u64, data: [Self::Type; SIZE]) -> Result<(), StreamOverwriteError<Self::OverwriteError>> {
-    let current_offset = self.offset();
-    let current_block = self.state.current_block().clone();
+  /// Re-encodes from the start of the current block until a whole window past the replaced bytes,
+  /// so that no readback after the re-encoded blocks reaches into bytes that have changed.
+  ///
+  /// An attached index loses its checkpoints from the start of the re-encoded blocks on.
+  async fn overwrite<const SIZE: usize>(&mut self, length: u64, data: [Self::Type; SIZE]) -> Result<(), StreamOverwriteError<Self::OverwriteError>> {
+    let offset = self.offset();
+    let old_size = self.stream.decoded_length() as u64;
 
-    if let Some(snapshot) = self.store.snapshot().cloned() {
-      self.stream.restore(snapshot).await.map_err(|e| Yaz0OverwriteError::RestoreFailed(e))?;
-      self.state = self.store.state();
-    } else {
-      assert!(current_offset == 0);
-    };
+    StreamExhaustedError::assert(old_size, offset, length).map_err(StreamOverwriteError::StreamExhausted)?;
 
-    let uncompressed_size = self
-      .header
-      .value()
-      .decompressed_size()
-      .saturating_sub(length.try_into().unwrap_or(u32::MAX))
-      .checked_add(data.len().try_into().map_err(|_| Yaz0OverwriteError::TooMuchData)?)
-      .ok_or(Yaz0OverwriteError::TooMuchData)?;
+    let new_size = u32::try_from(old_size - length + SIZE as u64).map_err(|_| Yaz0OverwriteError::TooMuchData)?;
+
+    let base = match self.store.snapshot().cloned() {
+      Some(snapshot) => {
+        self.stream.restore(snapshot).await.map_err(Yaz0OverwriteError::RestoreFailed)?;
+        self.store.state()
+      }
+      None => self.state.clone(),
+    };
+    let start = self.stream.snapshot();
 
     self
       .header
       .mutate()
       .await
-      .map_err(|e| Yaz0OverwriteError::MutateHeaderError(e))?
-      .with_uncompressed_size(uncompressed_size)
+      .map_err(Yaz0OverwriteError::MutateHeaderError)?
+      .with_uncompressed_size(new_size)
       .await
-      .map_err(|e| Yaz0OverwriteError::MutateHeaderFieldError(e))?;
+      .map_err(Yaz0OverwriteError::MutateHeaderFieldError)?;
 
-    let block_offset = current_offset - self.offset();
+    #[cfg(feature = "alloc")]
+    if let Some(index) = &mut self.index {
+      index.invalidate_from(base.offset(), new_size);
+    }
 
-    let (starting_block, starting_overflow, _, _) = current_block.split_at_with_pre(block_offset, &mut self.state).unwrap();
+    let mut old = base.clone();
+    let mut compressor = base.compressor(Yaz0CompressionLevel::default());
+    let mut block = Block::empty();
 
-    let mut original_state = self.state.clone();
+    // The re-encoded bytes: what the current block held before `offset`, `data`, then what comes
+    // after the replaced bytes, decoded a block at a time.
+    let mut prefix = heapless::Vec::<u8, MAX_BLOCK_LENGTH>::new();
+    let mut suffix = heapless::Vec::<u8, { WINDOW_SIZE + MAX_BLOCK_LENGTH }>::new();
+    let (mut prefix_used, mut data_used, mut suffix_used, mut suffix_seen) = (0, 0, 0, 0);
 
-    let reencode_data = if let Some(overflow) = starting_overflow {
-      let skip_len = starting_block.len();
-      self.stream.overwrite(0, [starting_block]).await.map_err(|e| Yaz0OverwriteError::OverwriteBlockFailed(e))?;
-      ReencodeData::StartingWithSkip(Block::of(Operation::lit(overflow)), skip_len as u64)
-    } else {
-      ReencodeData::Starting(starting_block)
-    };
+    loop {
+      if old.offset() < old_size && (suffix_seen < WINDOW_SIZE || !block.is_empty()) {
+        let removed = self.remove_block().await?;
+        let mut position = old.offset();
+
+        old.feed(removed).map_err(Yaz0OverwriteError::MalformedStream)?;
+
+        suffix.copy_within(suffix_used.., 0);
+        suffix.truncate(suffix.len() - suffix_used);
+        suffix_used = 0;
+
+        for byte in old.take(usize::MAX) {
+          if position < offset {
+            prefix.push(byte).unwrap();
+          } else if position >= offset + length {
+            suffix.push(byte).unwrap();
+            suffix_seen += 1;
+          }
 
-    let (current_block, tail) = self.re_encode_slice(&mut original_state, reencode_data, &mut length, ReadbackReference::of(&data)).await?;
-    let tail_len = tail.1.len() as u64 + (if tail.0.is_some() { 1 } else { 0 });
+          position += 1;
+        }
 
-    let mut repair_bytes = 0;
-    let mut bytes_seeked = tail_len;
-    let pre_read = self.stream.snapshot();
+        if suffix_seen < WINDOW_SIZE {
+          continue;
+        }
+      }
 
-    let mut fork_original_state = original_state.clone();
+      loop {
+        let mut input = ReadbackReference::from_parts([&prefix[prefix_used..], &data[data_used..], &suffix[suffix_used..]]);
+        let available = input.len();
 
-    if let Some(overflow) = tail.0 {
-      fork_original_state.feed(Block::of(Operation::lit(overflow))).unwrap();
-    }
+        let Some(operation) = compressor.compress(&mut input) else {
+          break;
+        };
 
-    fork_original_state.feed(tail.1).map_err(|e| Yaz0OverwriteError::MalformedStream(e))?;
+        let mut consumed = available - input.len();
 
-    while bytes_seeked < 4096 {
-      let block: Block = self.stream.read(CLONED).await.map_err(|e| Yaz0OverwriteError::ReadBlockFailed(e))?;
-      let (head, head_overflow, _, _) = block
-        .split_at_with_pre(4096 - bytes_seeked, &mut fork_original_state)
-        .map_err(|e| Yaz0OverwriteError::MalformedStream(e))?;
+        for (used, part) in [(&mut prefix_used, prefix.len()), (&mut data_used, SIZE), (&mut suffix_used, suffix.len())] {
+          let step = consumed.min(part - *used);
+          *used += step;
+          consumed -= step;
+        }
 
-      for operation in head.operations {
-        bytes_seeked += operation.len() as u64;
+        block.operations.push(operation).unwrap();
 
-        if let Operation::LongReadback { offset, .. } | Operation::ShortReadback { offset, .. } = operation {
-          if (offset.get() as u64).saturating_sub(bytes_seeked) > 0 {
-            repair_bytes = bytes_seeked;
-          }
+        if block.is_full() {
+          self.stream.overwrite(0, [block]).await.map_err(Yaz0OverwriteError::OverwriteBlockFailed)?;
+          block = Block::empty();
         }
       }
 
-      if head_overflow.is_some() {
-        bytes_seeked += 1;
+      if block.is_empty() || old.offset() >= old_size {
+        break;
       }
     }
 
-    self.stream.restore(pre_read).await.map_err(|e| Yaz0OverwriteError::RestoreFailed(e))?;
-
-    let repair = fork_original_state.readback().slice(0..(repair_bytes - tail_len) as usize).unwrap();
-
-    let repair_len = repair.len();
-
-    let (mut block, tail) = self.re_encode_slice(&mut original_state, ReencodeData::With(current_block), &mut (repair_len as u64), repair).await?;
-
-    if let Some(byte) = tail.0 {
-      // we KNOW block is not full
-      block.operations.push(Operation::Literal(byte)).unwrap();
+    if !block.is_empty() {
+      self.stream.overwrite(0, [block]).await.map_err(Yaz0OverwriteError::OverwriteBlockFailed)?;
     }
 
-    let mut tail = tail.1;
-    let mut overwrite = false;
+    // Decompress back up to just after the written bytes, checkpointing along the way.
+    self.stream.restore(start).await.map_err(Yaz0OverwriteError::RestoreFailed)?;
+    self.state = base;
 
-    loop {
-      self.state.feed(tail.clone()).map_err(|e| Yaz0OverwriteError::MalformedStream(e))?;
-
-      inflate_pair([&mut block, &mut tail], &self.state).unwrap();
-
-      block = if !block.is_full() {
-        if self.stream.remaining_decoded_bytes() == 0 {
-          self
-            .stream
-            .overwrite(if overwrite { 1 } else { 0 }, [block])
-            .await
-            .map_err(|e| Yaz0OverwriteError::OverwriteBlockFailed(e))?;
-          return Ok(());
-        }
+    let mut remaining = (offset + SIZE as u64 - self.offset()) as usize;
 
-        block
-      } else if !tail.is_full() {
-        self
-          .stream
-          .overwrite(if overwrite { 1 } else { 0 }, [block])
-          .await
-          .map_err(|e| Yaz0OverwriteError::OverwriteBlockFailed(e))?;
-
-        tail
-      } else {
-        self
-          .stream
-          .overwrite(if overwrite { 1 } else { 0 }, [block])
-          .await
-          .map_err(|e| Yaz0OverwriteError::OverwriteBlockFailed(e))?;
-        self.stream.overwrite(0, [tail]).await.map_err(|e| Yaz0OverwriteError::OverwriteBlockFailed(e))?;
-
-        return Ok(());
-      };
+    loop {
+      remaining -= self.state.take(remaining).len();
 
-      if block.is_empty() {
+      if remaining == 0 {
         return Ok(());
       }
 
-      let back = self.stream.snapshot();
-      tail = self.stream.read(CLONED).await.map_err(|e| Yaz0OverwriteError::ReadBlockFailed(e))?;
-      self.stream.restore(back).await.map_err(|e| Yaz0OverwriteError::RestoreFailed(e))?;
-
-      overwrite = true;
+      self.checkpoint();
+      let block = self.stream.read(CLONED).await.map_err(Yaz0OverwriteError::ReadBlockFailed)?;
+      self.state.feed(block).map_err(Yaz0OverwriteError::MalformedStream)?;
     }
   }
 }
 
-// RewindableStream NOT FEASIBLE :(
-// SeekableStream NOT FEASIBLE :(
+// RewindableStream FEASIBLE :) GIVEN Substream: SeekableStream
+// SeekableStream FEASIBLE :) GIVEN Substream: SeekableStream, FAST GIVEN AN INDEX
 // MutableStream FEASIBLE :) GIVEN Substream: RestorableStream + ResizableStream + MutableStream
 // ResizableStream FEASIBLE :) GIVEN Substream: RestorableStream + ResizableStream + MutableStream
 // RestorableStream FEASIBLE :) GIVEN Substream: RestorableStream
+
+#[cfg(test)]
+pub(crate) mod tests {
+  use fileforge::{
+    binary_reader::{endianness::Endianness, BinaryReader},
+    provider::hint::ReadHint,
+    stream::{builtin::ephemeral::EphemeralStream, ReadableStream, ResizableStream, SeekableStream, CLONED},
+    testing,
+  };
+
+  use crate::sead::yaz0::{
+    compress::{Yaz0CompressionLevel, Yaz0Compressor},
+    index::Yaz0Index,
+    readable::{Immutable, Mutable},
+    Yaz0Stream,
+  };
+
+  pub(crate) fn yaz0_file(data: &[u8], level: Yaz0CompressionLevel) -> Vec<u8> {
+    let mut file = b"Yaz0".to_vec();
+    file.extend((data.len() as u32).to_be_bytes());
+    file.extend([0; 8]);
+    Yaz0Compressor::new(data, level).blocks().for_each(|block| file.extend(block.encode()));
+    file
+  }
+
+  /// Text that compresses well, changing every few hundred bytes so readbacks reach back across many blocks.
+  fn sample(length: usize) -> Vec<u8> {
+    (0..length).map(|i| b"the quick brown fox jumps over the lazy dog "[i % 44] ^ (i / 300) as u8).collect()
+  }
+
+  async fn read_to_end<S: ReadableStream<Type = u8>>(stream: &mut S) -> Vec<u8> {
+    let mut out = Vec::new();
+
+    while stream.remaining() != Some(0) {
+      out.push(stream.read(CLONED).await.map_err(|_| {}).unwrap());
+    }
+
+    out
+  }
+
+  async fn decompress(file: Vec<u8>) -> Vec<u8> {
+    let reader = BinaryReader::new_from_provider(file, Endianness::BigEndian, ReadHint::new());
+
+    read_to_end(&mut reader.into_with::<Yaz0Stream<_, _>>(Immutable).await.map_err(|_| {}).unwrap()).await
+  }
+
+  #[tokio::test]
+  async fn overwrite_re_encodes_what_follows() {
+    let data = sample(8000);
+
+    for (offset, replaced) in [(0, 0), (3000, 4), (3000, 2500), (5000, 3000), (7990, 10), (8000, 0)] {
+      let mut file = yaz0_file(&data, Yaz0CompressionLevel::Lazy);
+      let mut expected = data.clone();
+      expected.splice(offset..offset + replaced, *b"REPLACEMENT");
+
+      let reader = BinaryReader::new_from_provider(&mut file, Endianness::BigEndian, ReadHint::new());
+      let mut stream = reader.into_with::<Yaz0Stream<_, _>>(Mutable).await.map_err(|_| {}).unwrap();
+
+      stream.seek(offset as u64).await.map_err(|_| {}).unwrap();
+      stream.overwrite(replaced as u64, *b"REPLACEMENT").await.map_err(|_| {}).unwrap();
+
+      assert_eq!(stream.offset(), offset as u64 + 11, "overwriting {replaced} bytes at {offset}");
+      assert_eq!(stream.len(), Some(expected.len() as u64), "overwriting {replaced} bytes at {offset}");
+      assert_eq!(read_to_end(&mut stream).await, expected[offset + 11..], "overwriting {replaced} bytes at {offset}");
+
+      drop(stream);
+      assert!(decompress(file).await == expected, "overwriting {replaced} bytes at {offset}");
+    }
+  }
+
+  #[tokio::test]
+  async fn overwrite_keeps_compressing() {
+    let data = sample(8000);
+    let mut file = yaz0_file(&data, Yaz0CompressionLevel::Lazy);
+    let original = file.len();
+
+    let reader = BinaryReader::new_from_provider(&mut file, Endianness::BigEndian, ReadHint::new());
+    let mut stream = reader.into_with::<Yaz0Stream<_, _>>(Mutable).await.map_err(|_| {}).unwrap();
+
+    stream.seek(4000).await.map_err(|_| {}).unwrap();
+    stream.overwrite(3, *b"fox").await.map_err(|_| {}).unwrap();
+    drop(stream);
+
+    assert!(file.len() < original + 64, "re-encoding grew the file from {original} to {} bytes", file.len());
+  }
+
+  #[tokio::test]
+  async fn overwrite_invalidates_the_index() {
+    let data = sample(20000);
+    let mut file = yaz0_file(&data, Yaz0CompressionLevel::Lazy);
+    let mut expected = data.clone();
+    expected.splice(6000..6004, *b"REPLACEMENT");
+
+    let reader = BinaryReader::new_from_provider(&mut file, Endianness::BigEndian, ReadHint::new());
+    let mut stream = reader.into_with::<Yaz0Stream<_, _>>(Mutable).await.map_err(|_| {}).unwrap();
+
+    stream.attach_index(Yaz0Index::new(data.len() as u32, 0x800)).map_err(|_| {}).unwrap();
+    read_to_end(&mut stream).await;
+    assert!(stream.index().unwrap().checkpoints().len() > 5);
+
+    stream.seek(6000).await.map_err(|_| {}).unwrap();
+    stream.overwrite(4, *b"REPLACEMENT").await.map_err(|_| {}).unwrap();
+
+    let index = stream.index().unwrap();
+    assert_eq!(index.decompressed_size(), expected.len() as u32);
+    assert!(index.checkpoints().iter().all(|checkpoint| checkpoint.decompressed_offset() <= 6011));
+
+    for offset in [15000, 6000, 19000, 100, 8000] {
+      stream.seek(offset).await.map_err(|_| {}).unwrap();
+      let chunk: [u8; 64] = stream.read(async |bytes: &[u8; 64]| *bytes).await.map_err(|_| {}).unwrap();
+      assert_eq!(chunk, expected[offset as usize..][..64], "reading at {offset}");
+    }
+
+    let index = stream.take_index().unwrap();
+    let reader = BinaryReader::new_from_provider(&mut file, Endianness::BigEndian, ReadHint::new());
+    let mut stream = reader.into_with::<Yaz0Stream<_, _>>(Immutable).await.map_err(|_| {}).unwrap();
+
+    stream.attach_index(index).map_err(|_| {}).unwrap();
+    stream.seek(18000).await.map_err(|_| {}).unwrap();
+    assert_eq!(read_to_end(&mut stream).await, expected[18000..]);
+  }
+
+  #[tokio::test]
+  async fn yaz0_stream_conforms_to_the_stream_contracts() {
+    let data = sample(5000);
+    let file = yaz0_file(&data, Yaz0CompressionLevel::Lazy);
+    let plain = async || {
+      let reader = BinaryReader::new_from_provider(file.clone(), Endianness::BigEndian, ReadHint::new());
+
+      reader.into_with::<Yaz0Stream<_, _>>(Immutable).await.map_err(|_| {}).unwrap()
+    };
+
+    let mut stream = plain().await;
+    stream.attach_index(Yaz0Index::new(data.len() as u32, 0x400)).map_err(|_| {}).unwrap();
+    read_to_end(&mut stream).await;
+
+    let index = stream.take_index().unwrap();
+    let indexed = async || {
+      let mut stream = plain().await;
+      stream.attach_index(index.clone()).map_err(|_| {}).unwrap();
+      stream
+    };
+
+    assert!(index.checkpoints().len() > 2);
+
+    testing::stream::readable(&plain, &data).await;
+    testing::stream::rewindable(&plain, &data).await;
+    testing::stream::seekable(&plain, &data).await;
+    testing::stream::restorable(&plain, &data).await;
+
+    testing::stream::readable(&indexed, &data).await;
+    testing::stream::rewindable(&indexed, &data).await;
+    testing::stream::seekable(&indexed, &data).await;
+    testing::stream::restorable(&indexed, &data).await;
+  }
+
+  #[tokio::test]
+  async fn yaz0_stream_conforms_to_the_resizable_contract() {
+    let data = sample(100);
+    let file = yaz0_file(&data, Yaz0CompressionLevel::Lazy);
+    let factory = async || {
+      let reader = BinaryReader::new(EphemeralStream::from(file.clone()), Endianness::BigEndian);
+
+      reader.into_with::<Yaz0Stream<_, _>>(Mutable).await.map_err(|_| {}).unwrap()
+    };
+
+    testing::stream::resizable(&factory, &data).await;
+  }
+}
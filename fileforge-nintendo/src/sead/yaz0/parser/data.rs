@@ -30,6 +30,18 @@ impl Block {
     self.operations.len() == 8
   }
 
+  /// The block's header byte followed by each operation's bytes.
+  pub fn encode(&self) -> heapless::Vec<u8, 25> {
+    let mut bytes = heapless::Vec::new();
+    bytes.push(self.compute_header()).unwrap();
+
+    for operation in &self.operations {
+      bytes.extend_from_slice(&operation.encode()).unwrap();
+    }
+
+    bytes
+  }
+
   pub fn compute_header(&self) -> u8 {
     let mut header = 0;
 
@@ -300,6 +312,27 @@ impl Operation {
       Self::LongReadback { .. } => 3,
     }
   }
+
+  /// The operation's bytes as they appear in a Yaz0 stream.
+  pub fn encode(self) -> heapless::Vec<u8, 3> {
+    let bytes: &[u8] = match self {
+      Self::Literal(v) => &[v],
+      Self::ShortReadback { offset, length } => {
+        let n = length.get() - 2;
+        let r = offset.get() - 1;
+
+        &[(n << 4 | r >> 8) as u8, (r & 0xFF) as u8]
+      }
+      Self::LongReadback { offset, length } => {
+        let n = length.get() - 0x12;
+        let r = offset.get() - 1;
+
+        &[(r >> 8) as u8, (r & 0xFF) as u8, n as u8]
+      }
+    };
+
+    heapless::Vec::from_slice(bytes).unwrap()
+  }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
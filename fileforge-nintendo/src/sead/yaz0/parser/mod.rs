@@ -10,8 +10,11 @@ use core::num::NonZeroU16;
 use fileforge::{
   control_flow::ControlFlow,
   stream::{
-    error::{stream_mutate::StreamMutateError, stream_overwrite::StreamOverwriteError, stream_read::StreamReadError, stream_restore::StreamRestoreError, stream_skip::StreamSkipError},
-    MutableStream, ReadableStream, ResizableStream, RestorableStream, CLONED, DOUBLE, SINGLE,
+    error::{
+      stream_mutate::StreamMutateError, stream_overwrite::StreamOverwriteError, stream_read::StreamReadError, stream_restore::StreamRestoreError, stream_seek::StreamSeekError,
+      stream_skip::StreamSkipError,
+    },
+    MutableStream, ReadableStream, ResizableStream, RestorableStream, SeekableStream, CLONED, DOUBLE, SINGLE,
   },
 };
 
@@ -22,6 +25,7 @@ use crate::sead::yaz0::parser::{
 
 pub struct Yaz0Parser<UnderlyingStream: ReadableStream<Type = u8>> {
   underlying: UnderlyingStream,
+  origin: u64,
   offset: u64,
   decoded_bytes_thusfar: u32,
   total_decoded_byte_length: u32,
@@ -30,6 +34,7 @@ pub struct Yaz0Parser<UnderlyingStream: ReadableStream<Type = u8>> {
 impl<S: ReadableStream<Type = u8>> Yaz0Parser<S> {
   pub fn new(underlying: S, decoded_length: u32) -> Self {
     Self {
+      origin: underlying.offset(),
       underlying,
       offset: 0,
       decoded_bytes_thusfar: 0,
@@ -37,9 +42,30 @@ impl<S: ReadableStream<Type = u8>> Yaz0Parser<S> {
     }
   }
 
+  /// How many bytes the whole stream decodes to, kept up to date as blocks are rewritten.
+  pub fn decoded_length(&self) -> u32 {
+    self.total_decoded_byte_length
+  }
+
   pub fn remaining_decoded_bytes(&self) -> u32 {
     self.total_decoded_byte_length.saturating_sub(self.decoded_bytes_thusfar)
   }
+
+  /// How far the underlying stream has been read, relative to where the compressed data starts.
+  pub fn compressed_offset(&self) -> u64 {
+    self.underlying.offset() - self.origin
+  }
+}
+
+impl<S: SeekableStream<Type = u8>> Yaz0Parser<S> {
+  /// Moves to the block boundary at `compressed_offset`, after `blocks` blocks that decoded to
+  /// `decoded_bytes` bytes.
+  pub(crate) async fn resume(&mut self, compressed_offset: u64, blocks: u64, decoded_bytes: u32) -> Result<(), StreamSeekError<S::SeekError>> {
+    self.underlying.seek(self.origin + compressed_offset).await?;
+    self.offset = blocks;
+    self.decoded_bytes_thusfar = decoded_bytes;
+    Ok(())
+  }
 }
 
 impl<S: ReadableStream<Type = u8>> ReadableStream for Yaz0Parser<S> {
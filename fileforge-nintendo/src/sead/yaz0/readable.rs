@@ -128,6 +128,8 @@ impl<'pool, S: ReadableStream<Type = u8>, A: Yaz0StreamReadArgument<'pool, S>> I
       stream: Yaz0Parser::new(stream, header.value().decompressed_size()),
       header,
       store: A::StoreType::default(),
+      #[cfg(feature = "alloc")]
+      index: None,
     })
   }
 }
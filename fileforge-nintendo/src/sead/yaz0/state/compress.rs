@@ -1,42 +1,91 @@
-use core::{cmp::min, slice::SliceIndex};
-
 use crate::sead::yaz0::{
+  compress::{matcher::WINDOW_SIZE, parse::Yaz0Parse, Yaz0CompressionLevel},
   parser::data::Block,
   state::{reference::ReadbackReference, Operation, Yaz0State},
 };
 
+/// Bytes a [`Yaz0StateCompressor`] keeps in memory: its history, plus room to read ahead.
+const STATE_COMPRESSOR_WINDOW_LENGTH: usize = 3 * WINDOW_SIZE;
+
+/// Compresses bytes that carry on from a [`Yaz0State`], so readbacks can reach into its seekback buffer.
+///
+/// Matches are found through hash chains over the history and everything compressed since, the same way
+/// [`Yaz0Encoder`](crate::sead::yaz0::encoder::Yaz0Encoder) does. Each call takes the bytes still to compress,
+/// starting right after the last operation it returned.
+pub struct Yaz0StateCompressor {
+  parse: Yaz0Parse,
+  window: [u8; STATE_COMPRESSOR_WINDOW_LENGTH],
+  filled: usize,
+}
+
 impl Yaz0State {
-  pub fn compress_block<const C: usize>(&self, data: &mut ReadbackReference<C>) -> Block {
-    let mut operations = heapless::Vec::<Operation, 8>::new();
+  /// A compressor carrying on from this state's seekback buffer.
+  pub fn compressor(&self, level: Yaz0CompressionLevel) -> Yaz0StateCompressor {
+    let mut window = [0; STATE_COMPRESSOR_WINDOW_LENGTH];
+    let mut filled = 0;
+
+    for byte in self.seekback() {
+      window[filled] = byte;
+      filled += 1;
+    }
 
-    while let Some(operation) = self.compress(data) {
-      operations.push(operation).map_err(|_| {}).unwrap();
+    Yaz0StateCompressor {
+      parse: Yaz0Parse::resume(level, filled),
+      window,
+      filled,
     }
+  }
 
-    Block { operations }
+  pub fn compress_block<const C: usize>(&self, data: &mut ReadbackReference<C>) -> Block {
+    self.compressor(Yaz0CompressionLevel::default()).compress_block(data)
   }
+}
+
+impl Yaz0StateCompressor {
+  pub fn level(&self) -> Yaz0CompressionLevel {
+    self.parse.level()
+  }
+
+  /// Copies enough of `data` past the parse position for it to find full-length matches.
+  fn fill<const C: usize>(&mut self, data: &ReadbackReference<C>) {
+    let lookahead = self.filled - self.parse.position();
 
-  pub(crate) fn compress<const C: usize>(&self, data: &mut ReadbackReference<C>) -> Option<Operation> {
-    // If no history or not enough lookahead for a match, emit a literal.
-    if self.seekback_buffer.len() == 0 || data.len() < 3 {
-      let b = data.get(0)?;
-      *data = data.slice(1..).unwrap();
-      return Some(Operation::lit(b));
+    if self.parse.is_planned() || lookahead >= Yaz0Parse::LOOKAHEAD || lookahead == data.len() {
+      return;
     }
 
-    let max_len = min(data.len(), 0x111);
+    if self.filled == STATE_COMPRESSOR_WINDOW_LENGTH {
+      self.window.copy_within(WINDOW_SIZE.., 0);
+      self.filled -= WINDOW_SIZE;
+      self.parse.slide(WINDOW_SIZE);
+    }
+
+    for byte in data.slice(lookahead..).unwrap().take(STATE_COMPRESSOR_WINDOW_LENGTH - self.filled) {
+      self.window[self.filled] = byte;
+      self.filled += 1;
+    }
+  }
+
+  pub fn compress<const C: usize>(&mut self, data: &mut ReadbackReference<C>) -> Option<Operation> {
+    self.fill(data);
 
-    let best = (1..=self.seekback_buffer.len())
-      .filter_map(|off| {
-        let base = self.last_n(off)?;
+    let operation = self.parse.next(&self.window[..self.filled])?;
 
-        Operation::readback(off as u16, (0..max_len).map(|i| (data[i], base.get(i % off).unwrap())).take_while(|(a, b)| *a == *b).count() as u16)
-      })
-      .max_by_key(|v| v.len())
-      .unwrap_or_else(|| Operation::Literal(data.get(0).unwrap()));
+    *data = data.slice(operation.len() as usize..).unwrap();
+    Some(operation)
+  }
+
+  pub fn compress_block<const C: usize>(&mut self, data: &mut ReadbackReference<C>) -> Block {
+    let mut block = Block::empty();
+
+    while !block.is_full() {
+      match self.compress(data) {
+        Some(operation) => block.operations.push(operation).unwrap(),
+        None => break,
+      }
+    }
 
-    *data = data.slice(best.len() as usize..).unwrap();
-    Some(best)
+    block
   }
 }
 
@@ -55,14 +104,17 @@ mod tests {
   fn none_when_input_empty() {
     let st = Yaz0State::empty();
     let mut data: &[u8] = &[];
-    assert!(st.compress(&mut ReadbackReference::of(data)).is_none(), "empty input should yield None");
+    assert!(
+      st.compressor(Yaz0CompressionLevel::Lazy).compress(&mut ReadbackReference::of(data)).is_none(),
+      "empty input should yield None"
+    );
   }
 
   #[test]
   fn literal_when_no_history() {
     let st = Yaz0State::empty();
     let mut data = ReadbackReference::of(b"AB");
-    let op = st.compress(&mut data).expect("some op");
+    let op = st.compressor(Yaz0CompressionLevel::Lazy).compress(&mut data).expect("some op");
     match op {
       Operation::Literal(b) => assert_eq!(b, b'A'),
       _ => panic!("expected Literal"),
@@ -76,7 +128,7 @@ mod tests {
     let mut st = Yaz0State::empty();
     seed_history(&mut st, b"XYZ"); // history exists
     let mut data = ReadbackReference::of(b"Q!"); // only 2 bytes of lookahead
-    let op = st.compress(&mut data).expect("some op");
+    let op = st.compressor(Yaz0CompressionLevel::Lazy).compress(&mut data).expect("some op");
     match op {
       Operation::Literal(b) => assert_eq!(b, b'Q'),
       _ => panic!("expected Literal"),
@@ -92,7 +144,7 @@ mod tests {
     seed_history(&mut st, b"A");
     let mut data = ReadbackReference::of(b"AAAAA"); // 5 A's available
 
-    let op = st.compress(&mut data).expect("some op");
+    let op = st.compressor(Yaz0CompressionLevel::Lazy).compress(&mut data).expect("some op");
     match op {
       Operation::ShortReadback { offset, length } => {
         assert_eq!(offset.get(), 1, "periodic 1-byte window");
@@ -110,7 +162,7 @@ mod tests {
     seed_history(&mut st, b"ZZZZABCDABCD");
     let mut data = ReadbackReference::of(b"ABCDABCDX"); // best match length = 8
 
-    let op = st.compress(&mut data).expect("some op");
+    let op = st.compressor(Yaz0CompressionLevel::Lazy).compress(&mut data).expect("some op");
     match op {
       Operation::ShortReadback { offset, length } => {
         assert!(offset.get() == 4 || offset.get() == 8, "offset should be a divisor of the repeated pattern (got {})", offset);
@@ -130,7 +182,7 @@ mod tests {
     let mut data_vec = vec![b'A'; 300];
     let mut data = ReadbackReference::of(&data_vec);
 
-    let op = st.compress(&mut data).expect("some op");
+    let op = st.compressor(Yaz0CompressionLevel::Lazy).compress(&mut data).expect("some op");
     match op {
       Operation::LongReadback { offset, length } | Operation::ShortReadback { offset, length } => {
         assert_eq!(offset.get(), 1, "still periodic single-byte source");
@@ -148,7 +200,7 @@ mod tests {
     seed_history(&mut st, b"ABCDEFGH");
     let mut data = ReadbackReference::of(b"QRSXYZ");
 
-    let op = st.compress(&mut data).expect("some op");
+    let op = st.compressor(Yaz0CompressionLevel::Lazy).compress(&mut data).expect("some op");
     match op {
       Operation::Literal(b) => assert_eq!(b, b'Q'),
       _ => panic!("expected Literal"),
@@ -163,7 +215,7 @@ mod tests {
     seed_history(&mut st, b"AB"); // only 2 bytes periodic pattern
     let mut data = ReadbackReference::of(b"ABZ"); // only first two match, third differs
 
-    let op = st.compress(&mut data).expect("some op");
+    let op = st.compressor(Yaz0CompressionLevel::Lazy).compress(&mut data).expect("some op");
     match op {
       Operation::Literal(b) => assert_eq!(b, b'A'),
       _ => panic!("expected Literal because match < 3"),
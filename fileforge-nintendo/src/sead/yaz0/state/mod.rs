@@ -28,6 +28,16 @@ impl Yaz0State {
     }
   }
 
+  /// A state at a block boundary `offset` bytes into the output, with `seekback` as the most
+  /// recently decompressed bytes.
+  pub(crate) fn resume(offset: u64, seekback: &[u8]) -> Self {
+    let mut state = Self::empty();
+
+    seekback.iter().for_each(|&b| state.push_byte(b));
+    state.offset = offset;
+    state
+  }
+
   #[inline]
   fn push_byte(&mut self, b: u8) -> () {
     if self.seekback_buffer.is_full() {
@@ -57,6 +67,11 @@ impl Yaz0State {
     self.last_n(ub).unwrap().slice(0..taken).unwrap()
   }
 
+  /// The bytes a back-reference could reach, oldest first.
+  pub(crate) fn seekback(&self) -> impl Iterator<Item = u8> + '_ {
+    self.seekback_buffer.iter().copied()
+  }
+
   pub fn drop_all(&mut self) {
     self.offset += self.unread_bytes;
     self.unread_bytes = 0;
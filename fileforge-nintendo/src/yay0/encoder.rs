@@ -0,0 +1,77 @@
+use alloc::vec::Vec;
+
+use fileforge::error::render::builtin::number::formatted_unsigned::FormattedUnsigned;
+use fileforge_macros::FileforgeError;
+
+use crate::{
+  sead::yaz0::{
+    compress::{Yaz0CompressionLevel, Yaz0Compressor},
+    parser::data::Operation,
+  },
+  yay0::header::{Yay0Header, YAY0_HEADER_SIZE},
+};
+
+#[derive(FileforgeError)]
+pub enum Yay0EncodeError {
+  #[report(&"Input is too large for Yay0")]
+  #[flag("{length} bytes don't fit in a 32-bit decompressed size", length = FormattedUnsigned::new(*length as u128))]
+  TooLarge { length: u64 },
+}
+
+/// Compresses `data` into a complete Yay0 file.
+///
+/// The parse is the same as [`Yaz0Compressor`]'s at `level`; only the layout differs. Section
+/// offsets depend on the size of every section before them, so the whole file is built in memory.
+pub fn encode(data: &[u8], level: Yaz0CompressionLevel) -> Result<Vec<u8>, Yay0EncodeError> {
+  let too_large = || Yay0EncodeError::TooLarge { length: data.len() as u64 };
+  let decompressed_size = u32::try_from(data.len()).map_err(|_| too_large())?;
+
+  let mut masks = Vec::new();
+  let mut links = Vec::new();
+  let mut chunks = Vec::new();
+  let (mut mask, mut flags) = (0u32, 0);
+
+  for operation in Yaz0Compressor::new(data, level) {
+    mask <<= 1;
+
+    match operation {
+      Operation::Literal(byte) => {
+        mask |= 1;
+        chunks.push(byte);
+      }
+      Operation::ShortReadback { offset, length } => links.extend(((length.get() - 2) << 12 | (offset.get() - 1)).to_be_bytes()),
+      Operation::LongReadback { offset, length } => {
+        links.extend((offset.get() - 1).to_be_bytes());
+        chunks.push((length.get() - 0x12) as u8);
+      }
+    }
+
+    flags += 1;
+
+    if flags == 32 {
+      masks.extend(mask.to_be_bytes());
+      (mask, flags) = (0, 0);
+    }
+  }
+
+  if flags != 0 {
+    masks.extend((mask << (32 - flags)).to_be_bytes());
+  }
+
+  let link_offset = YAY0_HEADER_SIZE + masks.len();
+  let chunk_offset = link_offset + links.len();
+
+  let header = Yay0Header::empty()
+    .with_decompressed_size(decompressed_size)
+    .with_link_offset(u32::try_from(link_offset).map_err(|_| too_large())?)
+    .with_chunk_offset(u32::try_from(chunk_offset).map_err(|_| too_large())?);
+
+  let mut file = Vec::with_capacity(chunk_offset + chunks.len());
+
+  file.extend(header.encode());
+  file.extend(masks);
+  file.extend(links);
+  file.extend(chunks);
+
+  Ok(file)
+}
@@ -0,0 +1,37 @@
+use fileforge::{
+  binary_reader::error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError, SkipError},
+  diagnostic::value::DiagnosticValue,
+  error::{ext::annotations::annotated::Annotated, render::builtin::number::formatted_unsigned::FormattedUnsigned},
+  stream::{
+    error::{stream_restore::StreamRestoreError, user_read::UserReadError, user_skip::UserSkipError},
+    RestorableStream,
+  },
+};
+use fileforge_macros::FileforgeError;
+
+use crate::back_reference::BackReference;
+
+#[derive(FileforgeError)]
+pub enum Yay0Error<'pool, S: RestorableStream<Type = u8>> {
+  #[report(&"Failed to restore to the start of the Yay0 file")]
+  Restore(StreamRestoreError<S::RestoreError>),
+
+  Skip(#[from] SkipError<'pool, S::SkipError>),
+  Mask(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>),
+  Link(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>),
+  Chunk(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>),
+
+  #[report(&"Yay0 back-reference reaches before the start of the data")]
+  InvalidBackReference {
+    #[error(
+      "Reaches back {distance} bytes, but only {available} have been decompressed",
+      distance = FormattedUnsigned::new(reference.value().distance as u128),
+      available = FormattedUnsigned::new(*available as u128)
+    )]
+    reference: DiagnosticValue<'pool, BackReference>,
+    available: u16,
+  },
+}
+
+impl<'pool, S: RestorableStream<Type = u8>> UserReadError for Yay0Error<'pool, S> {}
+impl<'pool, S: RestorableStream<Type = u8>> UserSkipError for Yay0Error<'pool, S> {}
@@ -0,0 +1,55 @@
+pub mod readable;
+
+pub const YAY0_HEADER_SIZE: usize = 0x10;
+
+/// Header of a Yay0 file. Both section offsets are relative to the start of the file; the mask
+/// section always follows the header directly.
+pub struct Yay0Header {
+  decompressed_size: u32,
+  link_offset: u32,
+  chunk_offset: u32,
+}
+
+impl Yay0Header {
+  pub fn empty() -> Self {
+    Self {
+      decompressed_size: 0,
+      link_offset: YAY0_HEADER_SIZE as u32,
+      chunk_offset: YAY0_HEADER_SIZE as u32,
+    }
+  }
+
+  pub fn with_decompressed_size(self, size: u32) -> Self {
+    Self { decompressed_size: size, ..self }
+  }
+
+  pub fn with_link_offset(self, offset: u32) -> Self {
+    Self { link_offset: offset, ..self }
+  }
+
+  pub fn with_chunk_offset(self, offset: u32) -> Self {
+    Self { chunk_offset: offset, ..self }
+  }
+
+  pub fn decompressed_size(&self) -> u32 {
+    self.decompressed_size
+  }
+  pub fn link_offset(&self) -> u32 {
+    self.link_offset
+  }
+  pub fn chunk_offset(&self) -> u32 {
+    self.chunk_offset
+  }
+
+  /// The header's bytes as they appear at the start of a Yay0 file.
+  pub fn encode(&self) -> [u8; YAY0_HEADER_SIZE] {
+    let mut bytes = [0; YAY0_HEADER_SIZE];
+
+    bytes[0x0..0x4].copy_from_slice(b"Yay0");
+    bytes[0x4..0x8].copy_from_slice(&self.decompressed_size.to_be_bytes());
+    bytes[0x8..0xC].copy_from_slice(&self.link_offset.to_be_bytes());
+    bytes[0xC..0x10].copy_from_slice(&self.chunk_offset.to_be_bytes());
+
+    bytes
+  }
+}
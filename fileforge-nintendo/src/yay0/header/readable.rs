@@ -0,0 +1,53 @@
+use fileforge::{
+  binary_reader::{
+    error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError},
+    readable::Readable,
+    BinaryReader, PrimitiveReader,
+  },
+  error::{ext::annotations::annotated::Annotated, render::builtin::number::formatted_unsigned::FormattedUnsigned},
+  stream::{error::user_read::UserReadError, ReadableStream},
+};
+use fileforge_macros::FileforgeError;
+use fileforge_std::magic::{Magic, MagicError};
+
+use super::{Yay0Header, YAY0_HEADER_SIZE};
+
+pub const YAY0_MAGIC: Magic<4> = Magic::from_byte_ref(b"Yay0");
+
+impl<'pool, S: ReadableStream<Type = u8>> Readable<'pool, S> for Yay0Header {
+  type Error = Yay0HeaderReadError<'pool, S::ReadError>;
+  type Argument = ();
+
+  async fn read(reader: &mut BinaryReader<'pool, S>, _: Self::Argument) -> Result<Self, Self::Error> {
+    reader.read_with::<Magic<4>>(YAY0_MAGIC).await?;
+
+    let header = Yay0Header {
+      decompressed_size: reader.get().await.map_err(Yay0HeaderReadError::TotalSize)?,
+      link_offset: reader.get().await.map_err(Yay0HeaderReadError::LinkOffset)?,
+      chunk_offset: reader.get().await.map_err(Yay0HeaderReadError::ChunkOffset)?,
+    };
+
+    for offset in [header.link_offset, header.chunk_offset] {
+      if offset < YAY0_HEADER_SIZE as u32 {
+        return Err(Yay0HeaderReadError::InvalidSectionOffset { offset });
+      }
+    }
+
+    Ok(header)
+  }
+}
+
+#[derive(FileforgeError)]
+#[report(&"Failed to read Yay0 Header")]
+pub enum Yay0HeaderReadError<'pool, U: UserReadError> {
+  Magic(#[from] MagicError<'pool, 4, U>),
+  TotalSize(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  LinkOffset(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  ChunkOffset(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+
+  #[report(&"Yay0 section starts inside the header")]
+  #[flag("Sections start at 16 or later, found {offset}", offset = FormattedUnsigned::new(*offset as u128))]
+  InvalidSectionOffset {
+    offset: u32,
+  },
+}
@@ -0,0 +1,294 @@
+#[cfg(feature = "alloc")]
+pub mod encoder;
+pub mod error;
+pub mod header;
+pub mod readable;
+
+use fileforge::{
+  binary_reader::{snapshot::BinaryReaderSnapshot, BinaryReader, PrimitiveReader},
+  diagnostic::value::DiagnosticSaturation,
+  stream::{
+    error::{stream_exhausted::StreamExhaustedError, stream_read::StreamReadError, stream_skip::StreamSkipError},
+    ReadableStream, RestorableStream,
+  },
+};
+
+use crate::{
+  back_reference::BackReference,
+  sead::yaz0::{parser::data::Operation, state::Yaz0State},
+  yay0::{error::Yay0Error, header::Yay0Header},
+};
+
+/// The 32 flags of a mask word, most significant first. A set flag copies a byte from the chunk
+/// section, a clear one takes a readback from the link section.
+#[derive(Clone, Copy)]
+struct Mask {
+  bits: u32,
+  remaining: u8,
+}
+
+impl Mask {
+  fn empty() -> Self {
+    Self { bits: 0, remaining: 0 }
+  }
+
+  fn take(&mut self) -> Option<bool> {
+    if self.remaining == 0 {
+      return None;
+    }
+
+    let bit = self.bits & 0x8000_0000 != 0;
+    self.bits <<= 1;
+    self.remaining -= 1;
+    Some(bit)
+  }
+}
+
+/// A decompressed view of a Yay0 file.
+///
+/// Yay0 encodes the same operations as Yaz0, but splits their flags, readbacks and literal bytes
+/// into three sections that are read side by side. Moving between them restores to the start of
+/// the file and skips forwards, so the underlying stream must be restorable.
+pub struct Yay0Stream<'pool, S: RestorableStream<Type = u8>> {
+  header: Yay0Header,
+  start: BinaryReaderSnapshot<'pool, S>,
+  origin: u64,
+  reader: BinaryReader<'pool, S>,
+  mask: Mask,
+  mask_offset: u64,
+  link_offset: u64,
+  chunk_offset: u64,
+  state: Yaz0State,
+}
+
+impl<'pool, S: RestorableStream<Type = u8>> Yay0Stream<'pool, S> {
+  pub fn header(&self) -> &Yay0Header {
+    &self.header
+  }
+
+  /// Moves to `position`, relative to the start of the file.
+  async fn seek(&mut self, position: u64) -> Result<(), Yay0Error<'pool, S>> {
+    let current = self.reader.offset() - self.origin;
+
+    if position < current {
+      self.reader.restore(self.start.clone()).await.map_err(Yay0Error::Restore)?;
+      self.reader.skip(position).await?;
+    } else {
+      self.reader.skip(position - current).await?;
+    }
+
+    Ok(())
+  }
+
+  async fn chunk_byte(&mut self) -> Result<u8, Yay0Error<'pool, S>> {
+    self.seek(self.chunk_offset).await?;
+    self.chunk_offset += 1;
+
+    self.reader.get().await.map_err(Yay0Error::Chunk)
+  }
+
+  async fn next_operation(&mut self) -> Result<Operation, Yay0Error<'pool, S>> {
+    let literal = match self.mask.take() {
+      Some(literal) => literal,
+      None => {
+        self.seek(self.mask_offset).await?;
+        self.mask_offset += 4;
+        self.mask = Mask {
+          bits: self.reader.get().await.map_err(Yay0Error::Mask)?,
+          remaining: 32,
+        };
+        self.mask.take().unwrap()
+      }
+    };
+
+    if literal {
+      return Ok(Operation::lit(self.chunk_byte().await?));
+    }
+
+    let link_position = self.link_offset;
+
+    self.seek(link_position).await?;
+    self.link_offset += 2;
+
+    let link: u16 = self.reader.get().await.map_err(Yay0Error::Link)?;
+    let distance = (link & 0xFFF) + 1;
+    let length = match link >> 12 {
+      0 => self.chunk_byte().await? as u16 + 0x12,
+      n => n + 2,
+    };
+
+    let available = self.state.readback().len() as u16;
+
+    if distance > available {
+      let reference = BackReference {
+        distance: distance as u32,
+        length: length as u32,
+      };
+      let relative = link_position as i128 - (self.reader.offset() - self.origin) as i128;
+
+      return Err(Yay0Error::InvalidBackReference {
+        reference: self.reader.create_physical_diagnostic(relative, Some(2), "Yay0 Link").saturate(reference),
+        available,
+      });
+    }
+
+    Ok(Operation::readback(distance, length).unwrap())
+  }
+
+  /// Decodes the next operation into the readback state.
+  async fn advance(&mut self) -> Result<(), Yay0Error<'pool, S>> {
+    let operation = self.next_operation().await?;
+
+    self.state.feed_operation(operation).expect("readback distance was checked against the seekback buffer");
+    Ok(())
+  }
+}
+
+impl<'pool, S: RestorableStream<Type = u8>> ReadableStream for Yay0Stream<'pool, S> {
+  type Type = u8;
+
+  type ReadError = Yay0Error<'pool, S>;
+  type SkipError = Yay0Error<'pool, S>;
+
+  fn len(&self) -> Option<u64> {
+    Some(self.header.decompressed_size().into())
+  }
+
+  fn offset(&self) -> u64 {
+    self.state.offset()
+  }
+
+  async fn read<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[Self::Type; SIZE]) -> V) -> Result<V, StreamReadError<Self::ReadError>> {
+    StreamExhaustedError::assert(self.header.decompressed_size() as u64, self.offset(), SIZE as u64)?;
+
+    let mut buffer = heapless::Vec::<u8, SIZE>::new();
+
+    buffer.extend(self.state.take(SIZE));
+
+    while !buffer.is_full() {
+      self.advance().await?;
+      buffer.extend(self.state.take(SIZE - buffer.len()));
+    }
+
+    Ok(reader(&buffer.into_array::<SIZE>().unwrap()).await)
+  }
+
+  async fn skip(&mut self, size: u64) -> Result<(), StreamSkipError<Self::SkipError>> {
+    StreamSkipError::assert_relative_forwards(self.header.decompressed_size() as u64, self.offset(), size)?;
+
+    let mut remaining = size - self.state.take(size as usize).len() as u64;
+
+    while remaining != 0 {
+      self.advance().await?;
+      remaining -= self.state.take(remaining as usize).len() as u64;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use fileforge::{
+    binary_reader::{endianness::Endianness, BinaryReader},
+    provider::hint::ReadHint,
+    stream::{ReadableStream, StreamReadError, SINGLE},
+  };
+
+  use super::*;
+  use crate::{
+    sead::yaz0::compress::Yaz0CompressionLevel,
+    yay0::{encoder::encode, header::readable::Yay0HeaderReadError},
+  };
+
+  const REAL: &[u8] = include_bytes!("../../../fileforge-test/binaries/real.byml");
+
+  async fn decompress(file: Vec<u8>) -> Vec<u8> {
+    let reader = BinaryReader::new_from_provider(file, Endianness::BigEndian, ReadHint::new());
+    let mut stream = reader.into::<Yay0Stream<_>>().await.map_err(|_| {}).unwrap();
+    let mut out = Vec::new();
+
+    while let Ok(byte) = stream.read(SINGLE).await {
+      out.push(byte);
+    }
+
+    assert_eq!(stream.len(), Some(out.len() as u64));
+    out
+  }
+
+  fn samples() -> Vec<Vec<u8>> {
+    let mut noise = Vec::new();
+    let mut seed = 0x8765_4321u32;
+    for _ in 0..10_000 {
+      seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+      noise.push((seed >> 24) as u8 & 0x1F);
+    }
+
+    vec![Vec::new(), b"a".to_vec(), vec![0; 5000], noise, REAL.to_vec()]
+  }
+
+  /// "ab", then a four byte readback two bytes back.
+  fn handmade() -> Vec<u8> {
+    let mut file = b"Yay0".to_vec();
+    file.extend(6u32.to_be_bytes());
+    file.extend(0x14u32.to_be_bytes());
+    file.extend(0x16u32.to_be_bytes());
+    file.extend(0xC000_0000u32.to_be_bytes());
+    file.extend(0x2001u16.to_be_bytes());
+    file.extend(b"ab");
+    file
+  }
+
+  #[tokio::test]
+  async fn decodes_sections() {
+    assert_eq!(decompress(handmade()).await, b"ababab");
+  }
+
+  #[tokio::test]
+  async fn round_trips_at_every_level() {
+    for level in [Yaz0CompressionLevel::Fast, Yaz0CompressionLevel::Lazy, Yaz0CompressionLevel::Optimal] {
+      for sample in samples() {
+        assert_eq!(decompress(encode(&sample, level).map_err(|_| {}).unwrap()).await, sample, "{level:?}");
+      }
+    }
+  }
+
+  #[tokio::test]
+  async fn reads_and_skips_in_chunks() {
+    let file = encode(REAL, Yaz0CompressionLevel::Lazy).map_err(|_| {}).unwrap();
+    let reader = BinaryReader::new_from_provider(file, Endianness::BigEndian, ReadHint::new());
+    let mut stream = reader.into::<Yay0Stream<_>>().await.map_err(|_| {}).unwrap();
+
+    let head = stream.read(async |bytes: &[u8; 300]| *bytes).await.map_err(|_| {}).unwrap();
+    stream.skip(1000).await.map_err(|_| {}).unwrap();
+    let tail = stream.read(async |bytes: &[u8; 64]| *bytes).await.map_err(|_| {}).unwrap();
+
+    assert_eq!(head[..], REAL[..300]);
+    assert_eq!(tail[..], REAL[1300..1364]);
+    assert!(stream.skip(REAL.len() as u64).await.is_err());
+  }
+
+  #[tokio::test]
+  async fn rejects_readback_before_start() {
+    let mut file = handmade();
+    file[0x14..0x16].copy_from_slice(&0x2002u16.to_be_bytes());
+
+    let reader = BinaryReader::new_from_provider(file, Endianness::BigEndian, ReadHint::new());
+    let mut stream = reader.into::<Yay0Stream<_>>().await.map_err(|_| {}).unwrap();
+
+    assert!(matches!(
+      stream.read(async |bytes: &[u8; 6]| *bytes).await,
+      Err(StreamReadError::User(Yay0Error::InvalidBackReference { reference, available: 2 })) if *reference.value() == BackReference { distance: 3, length: 4 }
+    ));
+  }
+
+  #[tokio::test]
+  async fn rejects_sections_inside_header() {
+    let mut file = handmade();
+    file[0x8..0xC].copy_from_slice(&8u32.to_be_bytes());
+
+    let reader = BinaryReader::new_from_provider(file, Endianness::BigEndian, ReadHint::new());
+
+    assert!(matches!(reader.into::<Yay0Stream<_>>().await, Err(Yay0HeaderReadError::InvalidSectionOffset { offset: 8 })));
+  }
+}
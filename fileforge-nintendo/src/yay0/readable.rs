@@ -0,0 +1,35 @@
+use fileforge::{
+  binary_reader::{readable::IntoReadable, BinaryReader},
+  stream::RestorableStream,
+};
+
+use crate::{
+  sead::yaz0::state::Yaz0State,
+  yay0::{
+    header::{readable::Yay0HeaderReadError, Yay0Header, YAY0_HEADER_SIZE},
+    Mask, Yay0Stream,
+  },
+};
+
+impl<'pool, S: RestorableStream<Type = u8>> IntoReadable<'pool, S> for Yay0Stream<'pool, S> {
+  type Argument = ();
+  type Error = Yay0HeaderReadError<'pool, S::ReadError>;
+
+  async fn read(mut reader: BinaryReader<'pool, S>, _: Self::Argument) -> Result<Self, Self::Error> {
+    let start = reader.snapshot();
+    let origin = reader.offset();
+    let header: Yay0Header = reader.read().await?;
+
+    Ok(Yay0Stream {
+      mask_offset: YAY0_HEADER_SIZE as u64,
+      link_offset: header.link_offset() as u64,
+      chunk_offset: header.chunk_offset() as u64,
+      mask: Mask::empty(),
+      state: Yaz0State::empty(),
+      header,
+      origin,
+      start,
+      reader,
+    })
+  }
+}
@@ -1,6 +1,11 @@
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 pub mod byte_order_mark;
 pub mod encodings;
 pub mod magic;
+#[cfg(feature = "alloc")]
+pub mod patch;
 pub mod version;
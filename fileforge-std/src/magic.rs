@@ -55,6 +55,9 @@ impl<const SIZE: usize> Magic<SIZE> {
   pub const fn from_byte_ref(bytes: &[u8; SIZE]) -> Magic<SIZE> {
     Self { bytes: *bytes }
   }
+  pub const fn bytes(&self) -> [u8; SIZE] {
+    self.bytes
+  }
 }
 
 impl<'pool, const SIZE: usize, S: ReadableStream<Type = u8>> Readable<'pool, S> for Magic<SIZE> {
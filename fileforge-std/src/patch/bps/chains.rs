@@ -0,0 +1,72 @@
+use alloc::{vec, vec::Vec};
+
+use crate::patch::bps::{match_length, MIN_COPY};
+
+const HASH_BITS: u32 = 16;
+const EMPTY: u32 = u32::MAX;
+
+/// Every position of a file indexed by the [`MIN_COPY`] bytes starting there, newest first, for
+/// finding earlier copies of what's being diffed.
+pub(super) struct HashChains {
+  head: Vec<u32>,
+  previous: Vec<u32>,
+}
+
+impl HashChains {
+  pub(super) fn new(length: usize) -> Self {
+    Self {
+      head: vec![EMPTY; 1 << HASH_BITS],
+      previous: vec![EMPTY; length],
+    }
+  }
+
+  fn hash(bytes: &[u8]) -> usize {
+    let key = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+
+    (key.wrapping_mul(0x9E3779B1) >> (32 - HASH_BITS)) as usize
+  }
+
+  /// Indexes `data[index..]`, which has to be inserted after every earlier position.
+  pub(super) fn insert(&mut self, data: &[u8], index: usize) {
+    if index + MIN_COPY > data.len() {
+      return;
+    }
+
+    let hash = Self::hash(&data[index..]);
+
+    self.previous[index] = self.head[hash];
+    self.head[hash] = index as u32;
+  }
+
+  /// The offset and length of the longest prefix of `needle` found in `data`, trying at most
+  /// `limit` candidates.
+  pub(super) fn longest_match(&self, data: &[u8], needle: &[u8], limit: usize) -> (usize, usize) {
+    let mut best = (0, 0);
+
+    if needle.len() < MIN_COPY {
+      return best;
+    }
+
+    let mut candidate = self.head[Self::hash(needle)];
+
+    for _ in 0..limit {
+      if candidate == EMPTY {
+        break;
+      }
+
+      let length = match_length(&data[candidate as usize..], needle);
+
+      if length > best.1 {
+        best = (candidate as usize, length);
+
+        if length == needle.len() {
+          break;
+        }
+      }
+
+      candidate = self.previous[candidate as usize];
+    }
+
+    best
+  }
+}
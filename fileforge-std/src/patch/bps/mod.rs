@@ -0,0 +1,494 @@
+pub mod readable;
+
+mod chains;
+
+use alloc::{vec, vec::Vec};
+
+use fileforge::{
+  error::render::builtin::number::formatted_unsigned::FormattedUnsigned,
+  provider::{Provider, ResizableProvider},
+};
+use fileforge_macros::FileforgeError;
+
+use crate::patch::{
+  bps::{chains::HashChains, readable::BPS_MAGIC},
+  crc32::Crc32,
+  error::{PatchApplyError, PatchDiffError},
+  number,
+};
+
+/// Unchanged bytes at the same offset are only read from the source when at least this many
+/// match, as a shorter run costs more than the literal bytes it saves.
+const MIN_SOURCE_READ: usize = 2;
+
+/// Shortest match copied from elsewhere in either file when diffing, which is also how many bytes
+/// the hash chains index.
+const MIN_COPY: usize = 4;
+
+/// How many earlier occurrences of a sequence are tried when looking for the longest copy.
+const MAX_CHAIN: usize = 64;
+
+/// One step of building the target, appending `length` bytes to it. Copy offsets are absolute;
+/// they're only stored relative to the previous copy in the encoded patch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BpsAction {
+  /// Copies the source's bytes at the offset being written in the target.
+  SourceRead {
+    length: u64,
+  },
+  /// Bytes stored in the patch.
+  TargetRead(Vec<u8>),
+  SourceCopy {
+    offset: u64,
+    length: u64,
+  },
+  /// Copies target bytes already written, a byte at a time, so the copy may overlap its own output.
+  TargetCopy {
+    offset: u64,
+    length: u64,
+  },
+}
+
+impl BpsAction {
+  pub fn length(&self) -> u64 {
+    match self {
+      BpsAction::SourceRead { length } | BpsAction::SourceCopy { length, .. } | BpsAction::TargetCopy { length, .. } => *length,
+      BpsAction::TargetRead(bytes) => bytes.len() as u64,
+    }
+  }
+
+  fn command(&self) -> u64 {
+    match self {
+      BpsAction::SourceRead { .. } => 0,
+      BpsAction::TargetRead(_) => 1,
+      BpsAction::SourceCopy { .. } => 2,
+      BpsAction::TargetCopy { .. } => 3,
+    }
+  }
+}
+
+/// A BPS patch: actions building the target from the source, earlier target bytes and literals,
+/// with both sides' sizes and checksums and free-form metadata (usually XML).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BpsPatch {
+  pub source_size: u64,
+  pub target_size: u64,
+  pub metadata: Vec<u8>,
+  pub actions: Vec<BpsAction>,
+  pub source_checksum: u32,
+  pub target_checksum: u32,
+}
+
+impl BpsPatch {
+  /// Actions that write nothing are left out, as BPS can't encode them.
+  pub fn encode(&self) -> Vec<u8> {
+    let mut out = Vec::from(BPS_MAGIC.bytes());
+
+    number::encode(self.source_size, &mut out);
+    number::encode(self.target_size, &mut out);
+    number::encode(self.metadata.len() as u64, &mut out);
+    out.extend(&self.metadata);
+
+    let mut source_relative = 0;
+    let mut target_relative = 0;
+
+    for action in self.actions.iter().filter(|action| action.length() != 0) {
+      number::encode(((action.length() - 1) << 2) | action.command(), &mut out);
+
+      match action {
+        BpsAction::SourceRead { .. } => {}
+        BpsAction::TargetRead(bytes) => out.extend(bytes),
+        BpsAction::SourceCopy { offset, length } => {
+          encode_offset(*offset, source_relative, &mut out);
+          source_relative = offset + length;
+        }
+        BpsAction::TargetCopy { offset, length } => {
+          encode_offset(*offset, target_relative, &mut out);
+          target_relative = offset + length;
+        }
+      }
+    }
+
+    out.extend(self.source_checksum.to_le_bytes());
+    out.extend(self.target_checksum.to_le_bytes());
+    out.extend(Crc32::checksum(&out).to_le_bytes());
+
+    out
+  }
+
+  /// Writes the patched `source` to `target`, replacing whatever it held. The source is checked
+  /// against the patch before anything is written.
+  pub async fn apply<S: Provider<Type = u8>, T: ResizableProvider<Type = u8>>(
+    &self,
+    source: &S,
+    target: &mut T,
+  ) -> Result<(), PatchApplyError<S::ReadError, T::ResizeError, T::MutateError, BpsApplyError>> {
+    crate::patch::apply(source, target, |source| self.patch(source)).await
+  }
+
+  /// Makes a patch turning `source` into `target`, without metadata.
+  pub async fn diff<S: Provider<Type = u8>, T: Provider<Type = u8>>(source: &S, target: &T) -> Result<Self, PatchDiffError<S::ReadError, T::ReadError, BpsDiffError>> {
+    crate::patch::diff(source, target, Self::diff_bytes).await
+  }
+
+  fn patch(&self, source: &[u8]) -> Result<Vec<u8>, BpsApplyError> {
+    if source.len() as u64 != self.source_size {
+      return Err(BpsApplyError::SourceSize {
+        expected: self.source_size,
+        found: source.len() as u64,
+      });
+    }
+
+    let checksum = Crc32::checksum(source);
+
+    if checksum != self.source_checksum {
+      return Err(BpsApplyError::SourceChecksum {
+        expected: self.source_checksum,
+        found: checksum,
+      });
+    }
+
+    // the size is read from the patch, so the target only grows as far as the actions write it
+    let mut target = Vec::new();
+
+    for action in &self.actions {
+      if (target.len() as u64).saturating_add(action.length()) > self.target_size {
+        return Err(BpsApplyError::TargetSize {
+          expected: self.target_size,
+          found: (target.len() as u64).saturating_add(action.length()),
+        });
+      }
+
+      match action {
+        BpsAction::SourceRead { length } => target.extend_from_slice(source_range(source, target.len() as u64, *length)?),
+        BpsAction::TargetRead(bytes) => target.extend_from_slice(bytes),
+        BpsAction::SourceCopy { offset, length } => target.extend_from_slice(source_range(source, *offset, *length)?),
+        BpsAction::TargetCopy { offset, length } => {
+          if *offset >= target.len() as u64 {
+            return Err(BpsApplyError::TargetOutOfBounds {
+              offset: *offset,
+              written: target.len() as u64,
+            });
+          }
+
+          for index in *offset..offset + length {
+            target.push(target[index as usize]);
+          }
+        }
+      }
+    }
+
+    if target.len() as u64 != self.target_size {
+      return Err(BpsApplyError::TargetSize {
+        expected: self.target_size,
+        found: target.len() as u64,
+      });
+    }
+
+    let checksum = Crc32::checksum(&target);
+
+    if checksum != self.target_checksum {
+      return Err(BpsApplyError::TargetChecksum {
+        expected: self.target_checksum,
+        found: checksum,
+      });
+    }
+
+    Ok(target)
+  }
+
+  fn diff_bytes(source: &[u8], target: &[u8]) -> Result<Self, BpsDiffError> {
+    for data in [source, target] {
+      if data.len() as u64 >= u32::MAX as u64 {
+        return Err(BpsDiffError::TooLarge { length: data.len() as u64 });
+      }
+    }
+
+    let mut source_chains = HashChains::new(source.len());
+    let mut target_chains = HashChains::new(target.len());
+
+    for index in 0..source.len() {
+      source_chains.insert(source, index);
+    }
+
+    let mut actions = vec![];
+    let mut literal = vec![];
+    let mut position = 0;
+
+    while position < target.len() {
+      let remaining = &target[position..];
+      let source_read = source.get(position..).map_or(0, |source| match_length(source, remaining));
+      let source_copy = source_chains.longest_match(source, remaining, MAX_CHAIN);
+      let target_copy = target_chains.longest_match(target, remaining, MAX_CHAIN);
+
+      let action = if source_read >= MIN_SOURCE_READ && source_read >= source_copy.1 && source_read >= target_copy.1 {
+        Some(BpsAction::SourceRead { length: source_read as u64 })
+      } else if source_copy.1 >= MIN_COPY && source_copy.1 >= target_copy.1 {
+        Some(BpsAction::SourceCopy {
+          offset: source_copy.0 as u64,
+          length: source_copy.1 as u64,
+        })
+      } else if target_copy.1 >= MIN_COPY {
+        Some(BpsAction::TargetCopy {
+          offset: target_copy.0 as u64,
+          length: target_copy.1 as u64,
+        })
+      } else {
+        None
+      };
+
+      let length = match action {
+        Some(action) => {
+          if !literal.is_empty() {
+            actions.push(BpsAction::TargetRead(core::mem::take(&mut literal)));
+          }
+
+          let length = action.length() as usize;
+          actions.push(action);
+          length
+        }
+        None => {
+          literal.push(target[position]);
+          1
+        }
+      };
+
+      for index in position..position + length {
+        target_chains.insert(target, index);
+      }
+
+      position += length;
+    }
+
+    if !literal.is_empty() {
+      actions.push(BpsAction::TargetRead(literal));
+    }
+
+    Ok(Self {
+      source_size: source.len() as u64,
+      target_size: target.len() as u64,
+      metadata: vec![],
+      actions,
+      source_checksum: Crc32::checksum(source),
+      target_checksum: Crc32::checksum(target),
+    })
+  }
+}
+
+fn encode_offset(offset: u64, relative: u64, out: &mut Vec<u8>) {
+  if offset >= relative {
+    number::encode((offset - relative) << 1, out);
+  } else {
+    number::encode(((relative - offset) << 1) | 1, out);
+  }
+}
+
+fn match_length(a: &[u8], b: &[u8]) -> usize {
+  a.iter().zip(b).take_while(|(a, b)| a == b).count()
+}
+
+fn source_range(source: &[u8], offset: u64, length: u64) -> Result<&[u8], BpsApplyError> {
+  offset
+    .checked_add(length)
+    .and_then(|end| source.get(offset as usize..end as usize))
+    .ok_or(BpsApplyError::SourceOutOfBounds {
+      offset,
+      length,
+      size: source.len() as u64,
+    })
+}
+
+#[derive(FileforgeError)]
+pub enum BpsApplyError {
+  #[report(&"Source is the wrong size for this BPS patch")]
+  #[flag(
+    "The patch expects {expected} bytes, but the source holds {found}",
+    expected = FormattedUnsigned::new(*expected as u128),
+    found = FormattedUnsigned::new(*found as u128)
+  )]
+  SourceSize { expected: u64, found: u64 },
+
+  #[report(&"Source doesn't match the BPS patch's checksum")]
+  #[flag(
+    "The patch expects a CRC-32 of {expected}, but the source's is {found}",
+    expected = FormattedUnsigned::new(*expected as u128).base(16).padding(8).uppercase(),
+    found = FormattedUnsigned::new(*found as u128).base(16).padding(8).uppercase()
+  )]
+  SourceChecksum { expected: u32, found: u32 },
+
+  #[report(&"Patched target doesn't match the BPS patch's checksum")]
+  #[flag(
+    "The patch expects a CRC-32 of {expected}, but the target's is {found}",
+    expected = FormattedUnsigned::new(*expected as u128).base(16).padding(8).uppercase(),
+    found = FormattedUnsigned::new(*found as u128).base(16).padding(8).uppercase()
+  )]
+  TargetChecksum { expected: u32, found: u32 },
+
+  #[report(&"BPS patch reads past the end of the source")]
+  #[flag(
+    "Reading {length} bytes at {offset} from a source of {size}",
+    length = FormattedUnsigned::new(*length as u128),
+    offset = FormattedUnsigned::new(*offset as u128),
+    size = FormattedUnsigned::new(*size as u128)
+  )]
+  SourceOutOfBounds { offset: u64, length: u64, size: u64 },
+
+  #[report(&"BPS patch copies target bytes that haven't been written yet")]
+  #[flag(
+    "Copying from {offset}, but only {written} bytes have been written",
+    offset = FormattedUnsigned::new(*offset as u128),
+    written = FormattedUnsigned::new(*written as u128)
+  )]
+  TargetOutOfBounds { offset: u64, written: u64 },
+
+  #[report(&"BPS patch doesn't build a target of the size it declares")]
+  #[flag(
+    "The patch declares {expected} bytes, but writes {found}",
+    expected = FormattedUnsigned::new(*expected as u128),
+    found = FormattedUnsigned::new(*found as u128)
+  )]
+  TargetSize { expected: u64, found: u64 },
+}
+
+#[derive(FileforgeError)]
+pub enum BpsDiffError {
+  #[report(&"File is too large to diff into a BPS patch")]
+  #[flag(
+    "The file holds {length} bytes, but diffing supports at most {max}",
+    length = FormattedUnsigned::new(*length as u128),
+    max = FormattedUnsigned::new(u32::MAX as u128 - 1)
+  )]
+  TooLarge { length: u64 },
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::{vec, vec::Vec};
+
+  use fileforge::{
+    binary_reader::{endianness::Endianness, BinaryReader},
+    provider::hint::ReadHint,
+  };
+
+  use super::*;
+
+  async fn read(bytes: Vec<u8>) -> BpsPatch {
+    let mut reader = BinaryReader::new_from_provider(bytes, Endianness::LittleEndian, ReadHint::new());
+    reader.read::<BpsPatch>().await.map_err(|_| {}).unwrap()
+  }
+
+  #[tokio::test]
+  async fn round_trips_through_a_diff() {
+    let source: Vec<u8> = (0..0x400u32).map(|index| (index * index / 3) as u8).collect();
+    let mut target = source[..0x100].to_vec();
+
+    target.extend(&source[0x300..0x380]);
+    target.extend(b"some new bytes that aren't anywhere in the source");
+    target.extend(&source[0x200..0x300]);
+    target.extend(&target[0x180..0x1A0].to_vec());
+
+    let patch = BpsPatch::diff(&source, &target).await.map_err(|_| {}).unwrap();
+    let commands: Vec<_> = patch.actions.iter().map(BpsAction::command).collect();
+
+    assert_eq!(commands, [0, 2, 1, 2, 3]);
+    assert!(patch.encode().len() < 0x80);
+
+    let patch = read(patch.encode()).await;
+    let mut patched = vec![0; 0x800];
+
+    patch.apply(&source, &mut patched).await.map_err(|_| {}).unwrap();
+    assert_eq!(patched, target);
+  }
+
+  #[tokio::test]
+  async fn target_copies_overlap_their_output() {
+    let target = b"abababababab";
+    let patch = BpsPatch {
+      source_size: 0,
+      target_size: target.len() as u64,
+      metadata: b"<patch/>".to_vec(),
+      actions: vec![BpsAction::TargetRead(b"ab".to_vec()), BpsAction::TargetCopy { offset: 0, length: 10 }],
+      source_checksum: Crc32::checksum(&[]),
+      target_checksum: Crc32::checksum(target),
+    };
+
+    let read_back = read(patch.encode()).await;
+    let mut patched = Vec::new();
+
+    assert_eq!(read_back, patch);
+
+    read_back.apply(&Vec::<u8>::new(), &mut patched).await.map_err(|_| {}).unwrap();
+    assert_eq!(patched, target);
+  }
+
+  #[tokio::test]
+  async fn rejects_the_wrong_source() {
+    let source = vec![1u8; 0x20];
+    let patch = BpsPatch::diff(&source, &vec![2u8; 0x20]).await.map_err(|_| {}).unwrap();
+    let mut patched = Vec::new();
+
+    let result = patch.apply(&vec![3u8; 0x20], &mut patched).await;
+
+    assert!(matches!(result, Err(PatchApplyError::Patch(BpsApplyError::SourceChecksum { .. }))));
+  }
+
+  #[tokio::test]
+  async fn rejects_corrupted_patches() {
+    let patch = BpsPatch::diff(&vec![1u8; 0x20], &vec![2u8; 0x20]).await.map_err(|_| {}).unwrap();
+    let mut bytes = patch.encode();
+    let last = bytes.len() - 13;
+    bytes[last] ^= 1;
+
+    let mut reader = BinaryReader::new_from_provider(bytes, Endianness::LittleEndian, ReadHint::new());
+
+    assert!(matches!(reader.read::<BpsPatch>().await, Err(readable::BpsPatchReadError::PatchChecksum { .. })));
+  }
+
+  #[tokio::test]
+  async fn rejects_impossible_target_sizes() {
+    let target = b"abababababab";
+    let patch = BpsPatch {
+      source_size: 0,
+      target_size: u64::MAX >> 4,
+      metadata: vec![],
+      actions: vec![BpsAction::TargetRead(b"ab".to_vec()), BpsAction::TargetCopy { offset: 0, length: 10 }],
+      source_checksum: Crc32::checksum(&[]),
+      target_checksum: Crc32::checksum(target),
+    };
+
+    let patch = read(patch.encode()).await;
+    let mut patched = Vec::new();
+
+    let result = patch.apply(&Vec::<u8>::new(), &mut patched).await;
+
+    assert!(matches!(result, Err(PatchApplyError::Patch(BpsApplyError::TargetSize { found: 12, .. }))));
+    assert!(patched.is_empty());
+  }
+
+  #[tokio::test]
+  async fn leaves_out_empty_actions() {
+    let target = b"abababababab";
+    let patch = BpsPatch {
+      source_size: 0,
+      target_size: target.len() as u64,
+      metadata: vec![],
+      actions: vec![
+        BpsAction::TargetRead(vec![]),
+        BpsAction::TargetRead(b"ab".to_vec()),
+        BpsAction::SourceCopy { offset: 0, length: 0 },
+        BpsAction::TargetCopy { offset: 0, length: 10 },
+        BpsAction::SourceRead { length: 0 },
+      ],
+      source_checksum: Crc32::checksum(&[]),
+      target_checksum: Crc32::checksum(target),
+    };
+
+    let read_back = read(patch.encode()).await;
+    let mut patched = Vec::new();
+
+    assert_eq!(read_back.actions, [BpsAction::TargetRead(b"ab".to_vec()), BpsAction::TargetCopy { offset: 0, length: 10 }]);
+
+    read_back.apply(&Vec::<u8>::new(), &mut patched).await.map_err(|_| {}).unwrap();
+    assert_eq!(patched, target);
+  }
+}
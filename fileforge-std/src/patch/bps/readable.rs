@@ -0,0 +1,155 @@
+use alloc::vec::Vec;
+
+use fileforge::{
+  binary_reader::{
+    error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError},
+    readable::Readable,
+    BinaryReader,
+  },
+  error::{ext::annotations::annotated::Annotated, render::builtin::number::formatted_unsigned::FormattedUnsigned},
+  stream::{error::user_read::UserReadError, ReadableStream},
+};
+use fileforge_macros::FileforgeError;
+
+use crate::{
+  magic::{Magic, MagicError},
+  patch::{
+    bps::{BpsAction, BpsPatch},
+    crc32::Crc32,
+    number::{self, NumberReadError},
+    read_byte, read_checksum,
+  },
+};
+
+pub const BPS_MAGIC: Magic<4> = Magic::from_byte_ref(b"BPS1");
+
+/// The source, target and patch checksums at the end of the patch.
+const FOOTER_SIZE: u64 = 12;
+
+/// Reads a copy's offset, stored as a signed distance from where the last copy of the same kind
+/// ended.
+async fn read_offset<'pool, S: ReadableStream<Type = u8>>(reader: &mut BinaryReader<'pool, S>, crc: &mut Crc32, relative: u64) -> Result<u64, BpsPatchReadError<'pool, S::ReadError>> {
+  let data = number::read(reader, crc).await?;
+  let distance = data >> 1;
+
+  let offset = if data & 1 == 0 {
+    relative.checked_add(distance).ok_or(NumberReadError::TooLarge)?
+  } else {
+    relative.checked_sub(distance).ok_or(BpsPatchReadError::NegativeOffset { relative, distance })?
+  };
+
+  Ok(offset)
+}
+
+impl<'pool, S: ReadableStream<Type = u8>> Readable<'pool, S> for BpsPatch {
+  type Error = BpsPatchReadError<'pool, S::ReadError>;
+  type Argument = ();
+
+  async fn read(reader: &mut BinaryReader<'pool, S>, _: Self::Argument) -> Result<Self, Self::Error> {
+    // actions run up to the footer, so the patch has to know where it ends
+    let remaining = reader.stream().remaining().ok_or(BpsPatchReadError::UnknownLength)?;
+    let end = (reader.offset() + remaining).saturating_sub(FOOTER_SIZE);
+    let mut crc = Crc32::new();
+
+    reader.read_with::<Magic<4>>(BPS_MAGIC).await?;
+    crc.update(&BPS_MAGIC.bytes());
+
+    let source_size = number::read(reader, &mut crc).await?;
+    let target_size = number::read(reader, &mut crc).await?;
+    let metadata_size = number::read(reader, &mut crc).await?;
+    let mut metadata = Vec::new();
+
+    for _ in 0..metadata_size {
+      metadata.push(read_byte(reader, &mut crc).await.map_err(BpsPatchReadError::Metadata)?);
+    }
+
+    let mut actions = Vec::new();
+    let mut source_relative = 0u64;
+    let mut target_relative = 0u64;
+
+    while reader.offset() < end {
+      let data = number::read(reader, &mut crc).await?;
+      let length = (data >> 2) + 1;
+
+      let action = match data & 3 {
+        0 => BpsAction::SourceRead { length },
+        1 => {
+          let mut bytes = Vec::new();
+
+          for _ in 0..length {
+            bytes.push(read_byte(reader, &mut crc).await.map_err(BpsPatchReadError::Data)?);
+          }
+
+          BpsAction::TargetRead(bytes)
+        }
+        2 => {
+          let offset = read_offset(reader, &mut crc, source_relative).await?;
+          source_relative = offset.checked_add(length).ok_or(NumberReadError::TooLarge)?;
+
+          BpsAction::SourceCopy { offset, length }
+        }
+        _ => {
+          let offset = read_offset(reader, &mut crc, target_relative).await?;
+          target_relative = offset.checked_add(length).ok_or(NumberReadError::TooLarge)?;
+
+          BpsAction::TargetCopy { offset, length }
+        }
+      };
+
+      actions.push(action);
+    }
+
+    let source_checksum = read_checksum(reader, &mut crc).await.map_err(BpsPatchReadError::Checksum)?;
+    let target_checksum = read_checksum(reader, &mut crc).await.map_err(BpsPatchReadError::Checksum)?;
+    let found = crc.finish();
+    let expected = read_checksum(reader, &mut crc).await.map_err(BpsPatchReadError::Checksum)?;
+
+    if expected != found {
+      return Err(BpsPatchReadError::PatchChecksum { expected, found });
+    }
+
+    Ok(BpsPatch {
+      source_size,
+      target_size,
+      metadata,
+      actions,
+      source_checksum,
+      target_checksum,
+    })
+  }
+}
+
+#[derive(FileforgeError)]
+#[report(&"Failed to read BPS patch")]
+pub enum BpsPatchReadError<'pool, U: UserReadError> {
+  #[report(&"BPS patches can only be read from streams of known length")]
+  UnknownLength,
+
+  Magic(#[from] MagicError<'pool, 4, U>),
+  Number(#[from] NumberReadError<'pool, U>),
+  Metadata(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  Data(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  Checksum(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+
+  #[report(&"BPS copy starts before the beginning of the file")]
+  #[flag(
+    "Moving back {distance} bytes from {relative}",
+    distance = FormattedUnsigned::new(*distance as u128),
+    relative = FormattedUnsigned::new(*relative as u128)
+  )]
+  NegativeOffset {
+    relative: u64,
+    distance: u64,
+  },
+
+  #[report(&"BPS patch is corrupted")]
+  #[flag(
+    "The patch stores a CRC-32 of {expected}, but its contents hash to {found}",
+    expected = FormattedUnsigned::new(*expected as u128).base(16).padding(8).uppercase(),
+    found = FormattedUnsigned::new(*found as u128).base(16).padding(8).uppercase()
+  )]
+  PatchChecksum {
+    expected: u32,
+    found: u32,
+  },
+}
@@ -0,0 +1,65 @@
+const TABLE: [u32; 0x100] = {
+  let mut table = [0; 0x100];
+  let mut index = 0;
+
+  while index < 0x100 {
+    let mut value = index as u32;
+    let mut bit = 0;
+
+    while bit < 8 {
+      value = if value & 1 == 1 { (value >> 1) ^ 0xEDB88320 } else { value >> 1 };
+      bit += 1;
+    }
+
+    table[index] = value;
+    index += 1;
+  }
+
+  table
+};
+
+/// The CRC-32 used by zip and PNG, which UPS and BPS patches store for their source, target and
+/// the patch itself.
+#[derive(Clone, Copy, Debug)]
+pub struct Crc32 {
+  state: u32,
+}
+
+impl Crc32 {
+  pub fn new() -> Self {
+    Self { state: !0 }
+  }
+
+  pub fn checksum(data: &[u8]) -> u32 {
+    let mut crc = Self::new();
+    crc.update(data);
+    crc.finish()
+  }
+
+  pub fn update(&mut self, data: &[u8]) {
+    for &byte in data {
+      self.state = TABLE[((self.state ^ byte as u32) & 0xFF) as usize] ^ (self.state >> 8);
+    }
+  }
+
+  pub fn finish(&self) -> u32 {
+    !self.state
+  }
+}
+
+impl Default for Crc32 {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn matches_the_check_value() {
+    assert_eq!(Crc32::checksum(b"123456789"), 0xCBF43926);
+    assert_eq!(Crc32::checksum(b""), 0);
+  }
+}
@@ -0,0 +1,22 @@
+use fileforge::{
+  error::FileforgeError,
+  provider::error::{user_mutate::UserMutateError, user_read::UserReadError, user_resize::UserResizeError},
+};
+use fileforge_macros::FileforgeError;
+
+/// Applying a patch failed, either in the providers or in the patch itself.
+#[derive(FileforgeError)]
+pub enum PatchApplyError<SourceRead: UserReadError, TargetResize: UserResizeError, TargetWrite: UserMutateError, Patch: FileforgeError> {
+  Source(SourceRead),
+  Resize(TargetResize),
+  Write(TargetWrite),
+  Patch(Patch),
+}
+
+/// Making a patch from two providers failed.
+#[derive(FileforgeError)]
+pub enum PatchDiffError<SourceRead: UserReadError, TargetRead: UserReadError, Patch: FileforgeError> {
+  Source(SourceRead),
+  Target(TargetRead),
+  Patch(Patch),
+}
@@ -0,0 +1,300 @@
+pub mod readable;
+
+use alloc::{vec, vec::Vec};
+
+use fileforge::provider::{Provider, ResizableProvider};
+use fileforge_macros::FileforgeError;
+
+use fileforge::error::render::builtin::number::formatted_unsigned::FormattedUnsigned;
+
+use crate::patch::{
+  error::{PatchApplyError, PatchDiffError},
+  ips::readable::IPS_MAGIC,
+};
+
+/// The offset that would read as the end-of-file marker, which no record can start at.
+const EOF_OFFSET: u32 = 0x454F46;
+
+/// Largest target an IPS patch can describe, since offsets and the truncation length are 24-bit.
+pub const MAX_TARGET_SIZE: u64 = 0xFFFFFF;
+
+/// Runs of the same byte at least this long are stored as run-length records when diffing.
+const MIN_RUN_LENGTH: usize = 8;
+
+/// Unchanged bytes shorter than this between two changes are rewritten rather than starting a
+/// new record, whose header would cost more.
+const MAX_RECORD_GAP: usize = 6;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IpsData {
+  Bytes(Vec<u8>),
+  Run { length: u16, value: u8 },
+}
+
+/// Bytes written to the target at `offset`, growing it if they run past its end.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IpsRecord {
+  pub offset: u32,
+  pub data: IpsData,
+}
+
+impl IpsRecord {
+  pub fn length(&self) -> usize {
+    match &self.data {
+      IpsData::Bytes(bytes) => bytes.len(),
+      IpsData::Run { length, .. } => *length as usize,
+    }
+  }
+}
+
+/// An IPS patch: records applied in order over a copy of the source, then an optional length to
+/// cut the target down to, as written by Lunar IPS.
+///
+/// IPS stores no checksums, so a patch applies to any source whatever it was made against.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IpsPatch {
+  pub records: Vec<IpsRecord>,
+  pub truncate: Option<u32>,
+}
+
+impl IpsPatch {
+  pub fn encode(&self) -> Vec<u8> {
+    let mut out = Vec::from(IPS_MAGIC.bytes());
+
+    for record in &self.records {
+      out.extend(&record.offset.to_be_bytes()[1..]);
+
+      match &record.data {
+        IpsData::Bytes(bytes) => {
+          out.extend((bytes.len() as u16).to_be_bytes());
+          out.extend(bytes);
+        }
+        IpsData::Run { length, value } => {
+          out.extend([0, 0]);
+          out.extend(length.to_be_bytes());
+          out.push(*value);
+        }
+      }
+    }
+
+    out.extend(&EOF_OFFSET.to_be_bytes()[1..]);
+
+    if let Some(truncate) = self.truncate {
+      out.extend(&truncate.to_be_bytes()[1..]);
+    }
+
+    out
+  }
+
+  /// Writes the patched `source` to `target`, replacing whatever it held.
+  pub async fn apply<S: Provider<Type = u8>, T: ResizableProvider<Type = u8>>(
+    &self,
+    source: &S,
+    target: &mut T,
+  ) -> Result<(), PatchApplyError<S::ReadError, T::ResizeError, T::MutateError, core::convert::Infallible>> {
+    crate::patch::apply(source, target, |source| Ok(self.patch(source))).await
+  }
+
+  /// Makes a patch turning `source` into `target`.
+  pub async fn diff<S: Provider<Type = u8>, T: Provider<Type = u8>>(source: &S, target: &T) -> Result<Self, PatchDiffError<S::ReadError, T::ReadError, IpsDiffError>> {
+    crate::patch::diff(source, target, Self::diff_bytes).await
+  }
+
+  fn patch(&self, source: &[u8]) -> Vec<u8> {
+    let mut target = source.to_vec();
+
+    for record in &self.records {
+      let start = record.offset as usize;
+      let end = start + record.length();
+
+      if target.len() < end {
+        target.resize(end, 0);
+      }
+
+      match &record.data {
+        IpsData::Bytes(bytes) => target[start..end].copy_from_slice(bytes),
+        IpsData::Run { value, .. } => target[start..end].fill(*value),
+      }
+    }
+
+    if let Some(truncate) = self.truncate {
+      target.truncate(truncate as usize);
+    }
+
+    target
+  }
+
+  fn diff_bytes(source: &[u8], target: &[u8]) -> Result<Self, IpsDiffError> {
+    if target.len() as u64 > MAX_TARGET_SIZE {
+      return Err(IpsDiffError::TooLarge { length: target.len() as u64 });
+    }
+
+    // everything past the end of the source has to be written to grow the target
+    let differs = |index: usize| source.get(index) != Some(&target[index]);
+    let mut records = Vec::new();
+    let mut index = 0;
+
+    while index < target.len() {
+      if !differs(index) {
+        index += 1;
+        continue;
+      }
+
+      let mut end = index;
+
+      loop {
+        while end < target.len() && differs(end) {
+          end += 1;
+        }
+
+        match (end..target.len().min(end + MAX_RECORD_GAP)).find(|&next| differs(next)) {
+          Some(next) => end = next,
+          None => break,
+        }
+      }
+
+      records.extend(Self::records(target, index, end));
+      index = end;
+    }
+
+    Ok(Self {
+      records,
+      truncate: (target.len() < source.len()).then_some(target.len() as u32),
+    })
+  }
+
+  /// Records writing `target[start..end]`.
+  fn records(target: &[u8], mut start: usize, end: usize) -> Vec<IpsRecord> {
+    let run_length = |from: usize, limit: usize| target[from..end.min(from + limit)].iter().take_while(|&&byte| byte == target[from]).count();
+    let mut records = vec![];
+
+    while start < end {
+      // rewriting the byte before costs less than a record the reader would take for the end
+      if start as u32 == EOF_OFFSET {
+        start -= 1;
+      }
+
+      let run = run_length(start, u16::MAX as usize);
+
+      if run >= MIN_RUN_LENGTH {
+        records.push(IpsRecord {
+          offset: start as u32,
+          data: IpsData::Run {
+            length: run as u16,
+            value: target[start],
+          },
+        });
+        start += run;
+        continue;
+      }
+
+      let mut literal_end = start + 1;
+
+      while literal_end < end && literal_end - start < u16::MAX as usize && run_length(literal_end, MIN_RUN_LENGTH) < MIN_RUN_LENGTH {
+        literal_end += 1;
+      }
+
+      records.push(IpsRecord {
+        offset: start as u32,
+        data: IpsData::Bytes(target[start..literal_end].to_vec()),
+      });
+      start = literal_end;
+    }
+
+    records
+  }
+}
+
+#[derive(FileforgeError)]
+pub enum IpsDiffError {
+  #[report(&"Target is too large for an IPS patch")]
+  #[flag(
+    "The target holds {length} bytes, but IPS can only address {max}",
+    length = FormattedUnsigned::new(*length as u128),
+    max = FormattedUnsigned::new(MAX_TARGET_SIZE as u128)
+  )]
+  TooLarge { length: u64 },
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::{vec, vec::Vec};
+
+  use fileforge::{
+    binary_reader::{endianness::Endianness, BinaryReader},
+    provider::hint::ReadHint,
+  };
+
+  use super::*;
+
+  fn files() -> (Vec<u8>, Vec<u8>) {
+    let source: Vec<u8> = (0..0x200u32).map(|index| (index * 13) as u8).collect();
+    let mut target = source.clone();
+
+    target[0x10] ^= 0xFF;
+    target[0x14] ^= 0xFF;
+    target[0x80..0xA0].fill(0x55);
+    target.extend([1, 2, 3]);
+
+    (source, target)
+  }
+
+  async fn read(bytes: Vec<u8>) -> IpsPatch {
+    let mut reader = BinaryReader::new_from_provider(bytes, Endianness::BigEndian, ReadHint::new());
+    reader.read::<IpsPatch>().await.map_err(|_| {}).unwrap()
+  }
+
+  #[tokio::test]
+  async fn round_trips_through_a_diff() {
+    let (source, target) = files();
+    let patch = IpsPatch::diff(&source, &target).await.map_err(|_| {}).unwrap();
+
+    assert_eq!(
+      patch.records.iter().map(|record| (record.offset, record.length())).collect::<Vec<_>>(),
+      [(0x10, 5), (0x80, 0x20), (0x200, 3)]
+    );
+    assert!(matches!(patch.records[1].data, IpsData::Run { length: 0x20, value: 0x55 }));
+
+    let patch = read(patch.encode()).await;
+    let mut patched = Vec::new();
+
+    patch.apply(&source, &mut patched).await.map_err(|_| {}).unwrap();
+    assert_eq!(patched, target);
+  }
+
+  #[tokio::test]
+  async fn truncates_shorter_targets() {
+    let source = vec![1u8; 0x40];
+    let target = vec![1u8; 0x20];
+    let patch = read(IpsPatch::diff(&source, &target).await.map_err(|_| {}).unwrap().encode()).await;
+    let mut patched = vec![0xFF; 3];
+
+    assert_eq!(
+      patch,
+      IpsPatch {
+        records: vec![],
+        truncate: Some(0x20)
+      }
+    );
+
+    patch.apply(&source, &mut patched).await.map_err(|_| {}).unwrap();
+    assert_eq!(patched, target);
+  }
+
+  #[tokio::test]
+  async fn avoids_the_end_of_file_offset() {
+    let source = vec![0u8; EOF_OFFSET as usize + 4];
+    let mut target = source.clone();
+    target[EOF_OFFSET as usize] = 1;
+
+    let patch = IpsPatch::diff(&source, &target).await.map_err(|_| {}).unwrap();
+
+    assert_eq!(
+      patch.records,
+      [IpsRecord {
+        offset: EOF_OFFSET - 1,
+        data: IpsData::Bytes(vec![0, 1])
+      }]
+    );
+  }
+}
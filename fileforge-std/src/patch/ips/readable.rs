@@ -0,0 +1,82 @@
+use alloc::vec::Vec;
+
+use fileforge::{
+  binary_reader::{
+    error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError},
+    readable::Readable,
+    BinaryReader, PrimitiveReader,
+  },
+  error::ext::annotations::annotated::Annotated,
+  stream::{error::user_read::UserReadError, ReadableStream},
+};
+use fileforge_macros::FileforgeError;
+
+use crate::{
+  magic::{Magic, MagicError},
+  patch::ips::{IpsData, IpsPatch, IpsRecord, EOF_OFFSET},
+};
+
+pub const IPS_MAGIC: Magic<5> = Magic::from_byte_ref(b"PATCH");
+
+fn u24(bytes: [u8; 3]) -> u32 {
+  u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]])
+}
+
+impl<'pool, S: ReadableStream<Type = u8>> Readable<'pool, S> for IpsPatch {
+  type Error = IpsPatchReadError<'pool, S::ReadError>;
+  type Argument = ();
+
+  async fn read(reader: &mut BinaryReader<'pool, S>, _: Self::Argument) -> Result<Self, Self::Error> {
+    reader.read_with::<Magic<5>>(IPS_MAGIC).await?;
+
+    let mut records = Vec::new();
+
+    loop {
+      let offset = u24(reader.get().await.map_err(IpsPatchReadError::Offset)?);
+
+      if offset == EOF_OFFSET {
+        break;
+      }
+
+      let size = u16::from_be_bytes(reader.get().await.map_err(IpsPatchReadError::Size)?);
+
+      let data = if size == 0 {
+        IpsData::Run {
+          length: u16::from_be_bytes(reader.get().await.map_err(IpsPatchReadError::RunLength)?),
+          value: reader.get().await.map_err(IpsPatchReadError::RunValue)?,
+        }
+      } else {
+        let mut bytes = Vec::with_capacity(size as usize);
+
+        for _ in 0..size {
+          bytes.push(reader.get().await.map_err(IpsPatchReadError::Data)?);
+        }
+
+        IpsData::Bytes(bytes)
+      };
+
+      records.push(IpsRecord { offset, data });
+    }
+
+    // the truncation length is an extension, so it's only there if the patch goes on past the marker
+    let truncate = if reader.stream().remaining().is_some_and(|remaining| remaining >= 3) {
+      Some(u24(reader.get().await.map_err(IpsPatchReadError::Truncate)?))
+    } else {
+      None
+    };
+
+    Ok(IpsPatch { records, truncate })
+  }
+}
+
+#[derive(FileforgeError)]
+#[report(&"Failed to read IPS patch")]
+pub enum IpsPatchReadError<'pool, U: UserReadError> {
+  Magic(#[from] MagicError<'pool, 5, U>),
+  Offset(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  Size(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  RunLength(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  RunValue(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  Data(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  Truncate(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+}
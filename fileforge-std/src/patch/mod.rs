@@ -0,0 +1,131 @@
+//! ROM patch formats: IPS, UPS and BPS.
+//!
+//! Patches are read with [`BinaryReader`](fileforge::binary_reader::BinaryReader) and written
+//! with their `encode` methods. Applying or making one loads the files involved into memory, so
+//! checksums can be verified before the target is touched.
+
+pub mod bps;
+pub mod crc32;
+pub mod error;
+pub mod ips;
+pub mod number;
+pub mod ups;
+
+use alloc::vec::Vec;
+
+use fileforge::{
+  binary_reader::{
+    error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError},
+    BinaryReader, PrimitiveReader,
+  },
+  error::{ext::annotations::annotated::Annotated, FileforgeError},
+  provider::{
+    error::{provider_mutate::ProviderMutateError, provider_read::ProviderReadError, provider_resize::ProviderResizeError, user_read::UserReadError},
+    hint::ReadHint,
+    Provider, ResizableProvider,
+  },
+  stream::ReadableStream,
+};
+
+use crate::patch::{
+  crc32::Crc32,
+  error::{PatchApplyError, PatchDiffError},
+};
+
+/// Bytes moved by a single read or mutation when loading or storing a whole provider.
+const CHUNK_SIZE: usize = 0x1000;
+
+/// Reads a byte of a patch, adding it to the patch's checksum.
+async fn read_byte<'pool, S: ReadableStream<Type = u8>>(reader: &mut BinaryReader<'pool, S>, crc: &mut Crc32) -> Result<u8, Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>> {
+  let byte = reader.get::<u8>().await?;
+
+  crc.update(&[byte]);
+  Ok(byte)
+}
+
+/// Reads a little-endian CRC-32 stored in a patch's footer, adding its bytes to the patch's
+/// checksum.
+async fn read_checksum<'pool, S: ReadableStream<Type = u8>>(
+  reader: &mut BinaryReader<'pool, S>,
+  crc: &mut Crc32,
+) -> Result<u32, Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, S::ReadError>>> {
+  let bytes: [u8; 4] = reader.get().await?;
+
+  crc.update(&bytes);
+  Ok(u32::from_le_bytes(bytes))
+}
+
+async fn read_all<P: Provider<Type = u8>>(provider: &P) -> Result<Vec<u8>, P::ReadError> {
+  let length = provider.len();
+  let mut data = Vec::with_capacity(length as usize);
+  let hint = ReadHint::once().with_read_ahead(CHUNK_SIZE as u64);
+
+  while data.len() as u64 + CHUNK_SIZE as u64 <= length {
+    let offset = data.len() as u64;
+    provider.read(offset, hint, async |chunk: &[u8; CHUNK_SIZE]| data.extend_from_slice(chunk)).await.map_err(read_error)?;
+  }
+
+  while (data.len() as u64) < length {
+    let offset = data.len() as u64;
+    let byte = provider.read(offset, hint, async |byte: &[u8; 1]| byte[0]).await.map_err(read_error)?;
+
+    data.push(byte);
+  }
+
+  Ok(data)
+}
+
+fn read_error<E: UserReadError>(error: ProviderReadError<E>) -> E {
+  match error {
+    ProviderReadError::User(error) => error,
+    ProviderReadError::OutOfBounds(_) => unreachable!("Reads stay within the provider's length"),
+  }
+}
+
+/// Loads `source`, patches it with `patch`, and replaces everything in `target` with the result.
+async fn apply<S: Provider<Type = u8>, T: ResizableProvider<Type = u8>, E: FileforgeError>(
+  source: &S,
+  target: &mut T,
+  patch: impl FnOnce(&[u8]) -> Result<Vec<u8>, E>,
+) -> Result<(), PatchApplyError<S::ReadError, T::ResizeError, T::MutateError, E>> {
+  let source = read_all(source).await.map_err(PatchApplyError::Source)?;
+  let data = patch(&source).map_err(PatchApplyError::Patch)?;
+
+  target.resize_at(0, target.len(), data.len() as u64).await.map_err(|error| match error {
+    ProviderResizeError::User(error) => PatchApplyError::Resize(error),
+    ProviderResizeError::OutOfBounds(_) => unreachable!("The whole target is always in bounds"),
+  })?;
+
+  let write_error = |error| match error {
+    ProviderMutateError::User(error) => PatchApplyError::Write(error),
+    ProviderMutateError::OutOfBounds(_) => unreachable!("The target was just resized to fit"),
+  };
+
+  let chunks = data.chunks_exact(CHUNK_SIZE);
+  let remainder = chunks.remainder();
+  let mut offset = 0;
+
+  for chunk in chunks {
+    target.mutate(offset, async |bytes: &mut [u8; CHUNK_SIZE]| bytes.copy_from_slice(chunk)).await.map_err(write_error)?;
+    offset += CHUNK_SIZE as u64;
+  }
+
+  for &byte in remainder {
+    target.mutate(offset, async |bytes: &mut [u8; 1]| bytes[0] = byte).await.map_err(write_error)?;
+    offset += 1;
+  }
+
+  Ok(())
+}
+
+/// Loads `source` and `target`, and makes a patch between them with `diff`.
+async fn diff<S: Provider<Type = u8>, T: Provider<Type = u8>, P, E: FileforgeError>(
+  source: &S,
+  target: &T,
+  diff: impl FnOnce(&[u8], &[u8]) -> Result<P, E>,
+) -> Result<P, PatchDiffError<S::ReadError, T::ReadError, E>> {
+  let source = read_all(source).await.map_err(PatchDiffError::Source)?;
+  let target = read_all(target).await.map_err(PatchDiffError::Target)?;
+
+  diff(&source, &target).map_err(PatchDiffError::Patch)
+}
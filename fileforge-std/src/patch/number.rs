@@ -0,0 +1,58 @@
+//! The variable-length numbers of UPS and BPS patches: seven bits per byte, least significant
+//! first, with the top bit marking the last byte. Each continuation also adds one, so every
+//! number has exactly one encoding.
+
+use alloc::vec::Vec;
+
+use fileforge::{
+  binary_reader::{
+    error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError},
+    BinaryReader,
+  },
+  error::ext::annotations::annotated::Annotated,
+  stream::{error::user_read::UserReadError, ReadableStream},
+};
+use fileforge_macros::FileforgeError;
+
+use crate::patch::{crc32::Crc32, read_byte};
+
+pub(super) fn encode(mut value: u64, out: &mut Vec<u8>) {
+  loop {
+    let low = (value & 0x7F) as u8;
+    value >>= 7;
+
+    if value == 0 {
+      out.push(0x80 | low);
+      return;
+    }
+
+    out.push(low);
+    value -= 1;
+  }
+}
+
+pub(super) async fn read<'pool, S: ReadableStream<Type = u8>>(reader: &mut BinaryReader<'pool, S>, crc: &mut Crc32) -> Result<u64, NumberReadError<'pool, S::ReadError>> {
+  let mut value = 0u64;
+  let mut shift = 1u64;
+
+  loop {
+    let byte = read_byte(reader, crc).await.map_err(NumberReadError::Byte)?;
+
+    value = (byte as u64 & 0x7F).checked_mul(shift).and_then(|part| value.checked_add(part)).ok_or(NumberReadError::TooLarge)?;
+
+    if byte & 0x80 != 0 {
+      return Ok(value);
+    }
+
+    shift = shift.checked_mul(0x80).ok_or(NumberReadError::TooLarge)?;
+    value = value.checked_add(shift).ok_or(NumberReadError::TooLarge)?;
+  }
+}
+
+#[derive(FileforgeError)]
+pub enum NumberReadError<'pool, U: UserReadError> {
+  Byte(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+
+  #[report(&"Patch number doesn't fit in 64 bits")]
+  TooLarge,
+}
@@ -0,0 +1,267 @@
+pub mod readable;
+
+use alloc::vec::Vec;
+
+use fileforge::{
+  error::render::builtin::number::formatted_unsigned::FormattedUnsigned,
+  provider::{Provider, ResizableProvider},
+};
+use fileforge_macros::FileforgeError;
+
+use crate::patch::{
+  crc32::Crc32,
+  error::{PatchApplyError, PatchDiffError},
+  number,
+  ups::readable::UPS_MAGIC,
+};
+
+/// Bytes XORed into the target starting at `offset`. Hunks end at the first byte that's the same
+/// in both files, so `xor` never holds a zero.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpsHunk {
+  pub offset: u64,
+  pub xor: Vec<u8>,
+}
+
+/// A UPS patch: the XOR of the source and target, with both sides' sizes and checksums.
+///
+/// Anything past the end of the shorter file reads as zero, so the same patch also turns the
+/// target back into the source.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpsPatch {
+  pub source_size: u64,
+  pub target_size: u64,
+  pub hunks: Vec<UpsHunk>,
+  pub source_checksum: u32,
+  pub target_checksum: u32,
+}
+
+impl UpsPatch {
+  pub fn encode(&self) -> Vec<u8> {
+    let mut out = Vec::from(UPS_MAGIC.bytes());
+
+    number::encode(self.source_size, &mut out);
+    number::encode(self.target_size, &mut out);
+
+    let mut relative = 0;
+
+    for hunk in &self.hunks {
+      number::encode(hunk.offset - relative, &mut out);
+      out.extend(&hunk.xor);
+      out.push(0);
+      relative = hunk.offset + hunk.xor.len() as u64 + 1;
+    }
+
+    out.extend(self.source_checksum.to_le_bytes());
+    out.extend(self.target_checksum.to_le_bytes());
+    out.extend(Crc32::checksum(&out).to_le_bytes());
+
+    out
+  }
+
+  /// Writes the patched `source` to `target`, replacing whatever it held. The source is checked
+  /// against the patch before anything is written.
+  pub async fn apply<S: Provider<Type = u8>, T: ResizableProvider<Type = u8>>(
+    &self,
+    source: &S,
+    target: &mut T,
+  ) -> Result<(), PatchApplyError<S::ReadError, T::ResizeError, T::MutateError, UpsApplyError>> {
+    crate::patch::apply(source, target, |source| self.patch(source)).await
+  }
+
+  /// Makes a patch turning `source` into `target`.
+  pub async fn diff<S: Provider<Type = u8>, T: Provider<Type = u8>>(source: &S, target: &T) -> Result<Self, PatchDiffError<S::ReadError, T::ReadError, core::convert::Infallible>> {
+    crate::patch::diff(source, target, |source, target| Ok(Self::diff_bytes(source, target))).await
+  }
+
+  fn patch(&self, source: &[u8]) -> Result<Vec<u8>, UpsApplyError> {
+    if source.len() as u64 != self.source_size {
+      return Err(UpsApplyError::SourceSize {
+        expected: self.source_size,
+        found: source.len() as u64,
+      });
+    }
+
+    let checksum = Crc32::checksum(source);
+
+    if checksum != self.source_checksum {
+      return Err(UpsApplyError::SourceChecksum {
+        expected: self.source_checksum,
+        found: checksum,
+      });
+    }
+
+    // the size is read from the patch, so a corrupt one has to fail here rather than abort on allocating it
+    let too_large = || UpsApplyError::TargetTooLarge { size: self.target_size };
+    let target_size = usize::try_from(self.target_size).map_err(|_| too_large())?;
+    let mut target = source.to_vec();
+
+    target.truncate(target_size);
+    target.try_reserve_exact(target_size - target.len()).map_err(|_| too_large())?;
+    target.resize(target_size, 0);
+
+    for hunk in &self.hunks {
+      // bytes past the end of the target only turn the source's tail back into zeros
+      for (index, xor) in (hunk.offset..self.target_size).zip(&hunk.xor) {
+        target[index as usize] ^= xor;
+      }
+    }
+
+    let checksum = Crc32::checksum(&target);
+
+    if checksum != self.target_checksum {
+      return Err(UpsApplyError::TargetChecksum {
+        expected: self.target_checksum,
+        found: checksum,
+      });
+    }
+
+    Ok(target)
+  }
+
+  fn diff_bytes(source: &[u8], target: &[u8]) -> Self {
+    let xor = |index: usize| source.get(index).copied().unwrap_or(0) ^ target.get(index).copied().unwrap_or(0);
+    let length = source.len().max(target.len());
+    let mut hunks = Vec::new();
+    let mut index = 0;
+
+    while index < length {
+      if xor(index) == 0 {
+        index += 1;
+        continue;
+      }
+
+      let offset = index;
+
+      while index < length && xor(index) != 0 {
+        index += 1;
+      }
+
+      hunks.push(UpsHunk {
+        offset: offset as u64,
+        xor: (offset..index).map(xor).collect(),
+      });
+    }
+
+    Self {
+      source_size: source.len() as u64,
+      target_size: target.len() as u64,
+      hunks,
+      source_checksum: Crc32::checksum(source),
+      target_checksum: Crc32::checksum(target),
+    }
+  }
+}
+
+#[derive(FileforgeError)]
+pub enum UpsApplyError {
+  #[report(&"Source is the wrong size for this UPS patch")]
+  #[flag(
+    "The patch expects {expected} bytes, but the source holds {found}",
+    expected = FormattedUnsigned::new(*expected as u128),
+    found = FormattedUnsigned::new(*found as u128)
+  )]
+  SourceSize { expected: u64, found: u64 },
+
+  #[report(&"Source doesn't match the UPS patch's checksum")]
+  #[flag(
+    "The patch expects a CRC-32 of {expected}, but the source's is {found}",
+    expected = FormattedUnsigned::new(*expected as u128).base(16).padding(8).uppercase(),
+    found = FormattedUnsigned::new(*found as u128).base(16).padding(8).uppercase()
+  )]
+  SourceChecksum { expected: u32, found: u32 },
+
+  #[report(&"Patched target doesn't match the UPS patch's checksum")]
+  #[flag(
+    "The patch expects a CRC-32 of {expected}, but the target's is {found}",
+    expected = FormattedUnsigned::new(*expected as u128).base(16).padding(8).uppercase(),
+    found = FormattedUnsigned::new(*found as u128).base(16).padding(8).uppercase()
+  )]
+  TargetChecksum { expected: u32, found: u32 },
+
+  #[report(&"Target of the UPS patch is too large to build")]
+  #[flag("The patch declares a target of {size} bytes", size = FormattedUnsigned::new(*size as u128))]
+  TargetTooLarge { size: u64 },
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::{vec, vec::Vec};
+
+  use fileforge::{
+    binary_reader::{endianness::Endianness, BinaryReader},
+    provider::hint::ReadHint,
+  };
+
+  use super::*;
+
+  async fn read(bytes: Vec<u8>) -> UpsPatch {
+    let mut reader = BinaryReader::new_from_provider(bytes, Endianness::LittleEndian, ReadHint::new());
+    reader.read::<UpsPatch>().await.map_err(|_| {}).unwrap()
+  }
+
+  #[tokio::test]
+  async fn round_trips_through_a_diff_both_ways() {
+    let source: Vec<u8> = (0..0x300u32).map(|index| (index * 7) as u8).collect();
+    let mut target = source[..0x280].to_vec();
+
+    target[0x20..0x24].copy_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+    target[0x100] = 0;
+
+    let patch = read(UpsPatch::diff(&source, &target).await.map_err(|_| {}).unwrap().encode()).await;
+    let mut patched = Vec::new();
+
+    patch.apply(&source, &mut patched).await.map_err(|_| {}).unwrap();
+    assert_eq!(patched, target);
+
+    let reverse = UpsPatch {
+      source_size: patch.target_size,
+      target_size: patch.source_size,
+      source_checksum: patch.target_checksum,
+      target_checksum: patch.source_checksum,
+      ..patch
+    };
+
+    reverse.apply(&target, &mut patched).await.map_err(|_| {}).unwrap();
+    assert_eq!(patched, source);
+  }
+
+  #[tokio::test]
+  async fn rejects_the_wrong_source() {
+    let source = vec![1u8; 0x20];
+    let target = vec![2u8; 0x20];
+    let patch = UpsPatch::diff(&source, &target).await.map_err(|_| {}).unwrap();
+    let mut patched = Vec::new();
+
+    let result = patch.apply(&target, &mut patched).await;
+
+    assert!(matches!(result, Err(PatchApplyError::Patch(UpsApplyError::SourceChecksum { .. }))));
+    assert!(patched.is_empty());
+  }
+
+  #[tokio::test]
+  async fn rejects_corrupted_patches() {
+    let patch = UpsPatch::diff(&vec![1u8; 0x20], &vec![2u8; 0x20]).await.map_err(|_| {}).unwrap();
+    let mut bytes = patch.encode();
+    bytes[6] ^= 1;
+
+    let mut reader = BinaryReader::new_from_provider(bytes, Endianness::LittleEndian, ReadHint::new());
+
+    assert!(matches!(reader.read::<UpsPatch>().await, Err(readable::UpsPatchReadError::PatchChecksum { .. })));
+  }
+
+  #[tokio::test]
+  async fn rejects_impossible_target_sizes() {
+    let source = vec![1u8; 0x20];
+    let mut patch = UpsPatch::diff(&source, &vec![2u8; 0x20]).await.map_err(|_| {}).unwrap();
+    patch.target_size = u64::MAX >> 4;
+
+    let patch = read(patch.encode()).await;
+    let mut patched = Vec::new();
+
+    let result = patch.apply(&source, &mut patched).await;
+
+    assert!(matches!(result, Err(PatchApplyError::Patch(UpsApplyError::TargetTooLarge { .. }))));
+    assert!(patched.is_empty());
+  }
+}
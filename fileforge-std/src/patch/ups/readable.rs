@@ -0,0 +1,102 @@
+use alloc::vec::Vec;
+
+use fileforge::{
+  binary_reader::{
+    error::{common::Read, primitive_name_annotation::PrimitiveName, GetPrimitiveError},
+    readable::Readable,
+    BinaryReader,
+  },
+  error::{ext::annotations::annotated::Annotated, render::builtin::number::formatted_unsigned::FormattedUnsigned},
+  stream::{error::user_read::UserReadError, ReadableStream},
+};
+use fileforge_macros::FileforgeError;
+
+use crate::{
+  magic::{Magic, MagicError},
+  patch::{
+    crc32::Crc32,
+    number::{self, NumberReadError},
+    read_byte, read_checksum,
+    ups::{UpsHunk, UpsPatch},
+  },
+};
+
+pub const UPS_MAGIC: Magic<4> = Magic::from_byte_ref(b"UPS1");
+
+/// The source, target and patch checksums at the end of the patch.
+const FOOTER_SIZE: u64 = 12;
+
+impl<'pool, S: ReadableStream<Type = u8>> Readable<'pool, S> for UpsPatch {
+  type Error = UpsPatchReadError<'pool, S::ReadError>;
+  type Argument = ();
+
+  async fn read(reader: &mut BinaryReader<'pool, S>, _: Self::Argument) -> Result<Self, Self::Error> {
+    // hunks run up to the footer, so the patch has to know where it ends
+    let remaining = reader.stream().remaining().ok_or(UpsPatchReadError::UnknownLength)?;
+    let end = (reader.offset() + remaining).saturating_sub(FOOTER_SIZE);
+    let mut crc = Crc32::new();
+
+    reader.read_with::<Magic<4>>(UPS_MAGIC).await?;
+    crc.update(&UPS_MAGIC.bytes());
+
+    let source_size = number::read(reader, &mut crc).await?;
+    let target_size = number::read(reader, &mut crc).await?;
+    let mut hunks = Vec::new();
+    let mut relative = 0u64;
+
+    while reader.offset() < end {
+      let offset = relative.checked_add(number::read(reader, &mut crc).await?).ok_or(NumberReadError::TooLarge)?;
+      let mut xor = Vec::new();
+
+      loop {
+        match read_byte(reader, &mut crc).await.map_err(UpsPatchReadError::Data)? {
+          0 => break,
+          byte => xor.push(byte),
+        }
+      }
+
+      relative = offset + xor.len() as u64 + 1;
+      hunks.push(UpsHunk { offset, xor });
+    }
+
+    let source_checksum = read_checksum(reader, &mut crc).await.map_err(UpsPatchReadError::Checksum)?;
+    let target_checksum = read_checksum(reader, &mut crc).await.map_err(UpsPatchReadError::Checksum)?;
+    let found = crc.finish();
+    let expected = read_checksum(reader, &mut crc).await.map_err(UpsPatchReadError::Checksum)?;
+
+    if expected != found {
+      return Err(UpsPatchReadError::PatchChecksum { expected, found });
+    }
+
+    Ok(UpsPatch {
+      source_size,
+      target_size,
+      hunks,
+      source_checksum,
+      target_checksum,
+    })
+  }
+}
+
+#[derive(FileforgeError)]
+#[report(&"Failed to read UPS patch")]
+pub enum UpsPatchReadError<'pool, U: UserReadError> {
+  #[report(&"UPS patches can only be read from streams of known length")]
+  UnknownLength,
+
+  Magic(#[from] MagicError<'pool, 4, U>),
+  Number(#[from] NumberReadError<'pool, U>),
+  Data(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+  Checksum(Annotated<PrimitiveName<Read>, GetPrimitiveError<'pool, U>>),
+
+  #[report(&"UPS patch is corrupted")]
+  #[flag(
+    "The patch stores a CRC-32 of {expected}, but its contents hash to {found}",
+    expected = FormattedUnsigned::new(*expected as u128).base(16).padding(8).uppercase(),
+    found = FormattedUnsigned::new(*found as u128).base(16).padding(8).uppercase()
+  )]
+  PatchChecksum {
+    expected: u32,
+    found: u32,
+  },
+}
@@ -0,0 +1,68 @@
+//! Compares `Yaz0Compressor` against the `yaz0` crate on the sample binaries.
+//!
+//! Run with `cargo bench -p fileforge-test --bench yaz0_compress`.
+
+use std::{
+  io::Cursor,
+  time::{Duration, Instant},
+};
+
+use fileforge_nintendo::sead::yaz0::compress::{Yaz0CompressionLevel, Yaz0Compressor};
+use yaz0::{CompressionLevel, Yaz0Archive, Yaz0Writer};
+
+const ITERATIONS: u32 = 3;
+
+fn fastest<T>(mut run: impl FnMut() -> T) -> (T, Duration) {
+  let mut best = None;
+  let mut fastest = Duration::MAX;
+
+  for _ in 0..ITERATIONS {
+    let start = Instant::now();
+    let value = run();
+    fastest = fastest.min(start.elapsed());
+    best = Some(value);
+  }
+
+  (best.unwrap(), fastest)
+}
+
+fn fileforge(data: &[u8], level: Yaz0CompressionLevel) -> usize {
+  0x10 + Yaz0Compressor::new(data, level).blocks().map(|block| block.encode().len()).sum::<usize>()
+}
+
+fn yaz0_crate(data: &[u8], level: CompressionLevel) -> usize {
+  let mut out = Vec::new();
+  Yaz0Writer::new(&mut out).compress_and_write(data, level).unwrap();
+  out.len()
+}
+
+fn report(name: &str, input: usize, (size, time): (usize, Duration)) {
+  let throughput = input as f64 / time.as_secs_f64() / (1024.0 * 1024.0);
+  println!("  {name:<28} {size:>10} bytes {:>10.2?} {throughput:>8.2} MiB/s", time);
+}
+
+fn main() {
+  let samples: [(&str, Vec<u8>, Option<usize>); 3] = [
+    ("Bed.byml", include_bytes!("../binaries/Bed.byml").to_vec(), Some(include_bytes!("../binaries/Bed.byml.yaz0").len())),
+    ("real.byml", include_bytes!("../binaries/real.byml").to_vec(), None),
+    (
+      "SkyWorldHomeStageMap.szs",
+      Yaz0Archive::new(Cursor::new(include_bytes!("../binaries/SkyWorldHomeStageMap.szs"))).unwrap().decompress().unwrap(),
+      Some(include_bytes!("../binaries/SkyWorldHomeStageMap.szs").len()),
+    ),
+  ];
+
+  for (name, data, nintendo) in samples {
+    println!("{name} ({} bytes)", data.len());
+
+    if let Some(nintendo) = nintendo {
+      println!("  {:<28} {nintendo:>10} bytes", "nintendo");
+    }
+
+    report("fileforge fast", data.len(), fastest(|| fileforge(&data, Yaz0CompressionLevel::Fast)));
+    report("fileforge lazy", data.len(), fastest(|| fileforge(&data, Yaz0CompressionLevel::Lazy)));
+    report("fileforge optimal", data.len(), fastest(|| fileforge(&data, Yaz0CompressionLevel::Optimal)));
+    report("yaz0 naive (quality 10)", data.len(), fastest(|| yaz0_crate(&data, CompressionLevel::Naive { quality: 10 })));
+    report("yaz0 lookahead (quality 10)", data.len(), fastest(|| yaz0_crate(&data, CompressionLevel::Lookahead { quality: 10 })));
+  }
+}
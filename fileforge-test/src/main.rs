@@ -18,7 +18,7 @@ async fn main() {
   let pool = DynamicDiagnosticPool::new();
 
   let r = Vec::from_iter(*include_bytes!("../binaries/Bed.byml.yaz0"));
-  let r = BinaryReader::new_from_provider(r, Endianness::BigEndian, ReadHint {});
+  let r = BinaryReader::new_from_provider(r, Endianness::BigEndian, ReadHint::new());
   let r = BinaryReader::new(
     r.into_with::<Yaz0Stream<_, _>>(Immutable).await.unwrap_renderable::<32>(RenderMode::TerminalAnsi, &pool),
     Endianness::BigEndian,
@@ -65,6 +65,10 @@ where
   pub fn set_hint(&mut self, hint: ReadHint) {
     self.stream.set_read_hint(hint);
   }
+
+  pub fn hint(&self) -> ReadHint {
+    self.stream.read_hint()
+  }
 }
 
 impl<'pool, S: ReadableStream<Type = u8>> BinaryReader<'pool, S> {
@@ -117,3 +117,28 @@ impl<'pool, S: ReadableStream<Type = u8>, T: Readable<'pool, S>, Gen: FnMut(u64)
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use alloc::vec::Vec;
+
+  use super::Contiguous;
+  use crate::{
+    binary_reader::{endianness::Endianness, BinaryReader},
+    provider::hint::ReadHint,
+    testing, ResultIgnoreExt,
+  };
+
+  #[tokio::test]
+  async fn conforms_to_the_stream_contracts() {
+    let values = [0x11223344u32, 5, 0xFFFFFFFF, 0, 7, 0x80000000, 42];
+    let bytes = values.iter().flat_map(|value| value.to_le_bytes()).collect::<Vec<u8>>();
+    let factory = async || {
+      let reader = BinaryReader::new_from_provider(bytes.clone(), Endianness::LittleEndian, ReadHint::new());
+
+      reader.into_with::<Contiguous<_, u32, _>>(|_| {}).await.ignore()
+    };
+
+    testing::stream::readable(factory, &values).await;
+  }
+}
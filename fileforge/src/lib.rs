@@ -34,6 +34,9 @@ extern crate std;
 #[cfg(feature = "story")]
 pub mod storybook;
 
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
 pub trait ResultIgnoreExt<R> {
   fn ignore(self) -> R;
 }
@@ -0,0 +1,195 @@
+use alloc::vec::Vec;
+
+use crate::provider::{
+  builtins::{
+    composite::{error::PartReadError, part_error, read_elements},
+    slice::{dynamic::DynamicSliceProvider, fixed::FixedSliceProvider},
+  },
+  error::{out_of_bounds::OutOfBoundsError, provider_read::ProviderReadError, provider_slice::ProviderSliceError},
+  hint::ReadHint,
+  Provider,
+};
+
+/// Several providers laid end to end as one, such as the pieces of a dump split into
+/// `.xci.0`, `.xci.1` and so on.
+///
+/// Reads within one part are handed to it as they are. Reads spanning parts are gathered one
+/// element at a time.
+pub struct ConcatProvider<P: Provider> {
+  parts: Vec<P>,
+  /// Where each part starts, followed by the total length.
+  starts: Vec<u64>,
+}
+
+impl<P: Provider> ConcatProvider<P> {
+  pub fn new(parts: Vec<P>) -> Self {
+    let mut starts = Vec::with_capacity(parts.len() + 1);
+    let mut start = 0;
+
+    starts.push(start);
+
+    for part in &parts {
+      start += part.len();
+      starts.push(start);
+    }
+
+    Self { parts, starts }
+  }
+
+  pub fn parts(&self) -> &[P] {
+    &self.parts
+  }
+
+  /// Where part `index` starts.
+  pub fn part_offset(&self, index: usize) -> u64 {
+    self.starts[index]
+  }
+
+  pub fn into_parts(self) -> Vec<P> {
+    self.parts
+  }
+
+  /// The part holding `offset`, skipping empty parts.
+  fn part_at(&self, offset: u64) -> usize {
+    self.starts.partition_point(|&start| start <= offset) - 1
+  }
+}
+
+impl<P: Provider> Provider for ConcatProvider<P>
+where
+  P::Type: Copy + Default,
+{
+  type Type = P::Type;
+
+  type ReadError = PartReadError<P::ReadError>;
+  type SliceError = core::convert::Infallible;
+
+  type StaticSliceProvider<'l, const SIZE: usize>
+    = FixedSliceProvider<SIZE, &'l ConcatProvider<P>>
+  where
+    Self: 'l;
+
+  type DynamicSliceProvider<'l>
+    = DynamicSliceProvider<&'l ConcatProvider<P>>
+  where
+    Self: 'l;
+
+  fn len(&self) -> u64 {
+    *self.starts.last().unwrap()
+  }
+
+  async fn read<const SIZE: usize, V>(&self, offset: u64, hint: ReadHint, reader: impl AsyncFnOnce(&[P::Type; SIZE]) -> V) -> Result<V, ProviderReadError<Self::ReadError>> {
+    OutOfBoundsError::assert(self.len(), offset, Some(SIZE as u64))?;
+
+    let end = offset + SIZE as u64;
+    let mut part = self.part_at(offset);
+
+    if part < self.parts.len() && end <= self.starts[part + 1] {
+      return self.parts[part].read(offset - self.starts[part], hint, reader).await.map_err(|error| part_error(part, error));
+    }
+
+    let mut buffer = [P::Type::default(); SIZE];
+    let mut position = offset;
+
+    while position < end {
+      let length = end.min(self.starts[part + 1]) - position;
+      let filled = (position - offset) as usize;
+
+      read_elements(&self.parts[part], part, position - self.starts[part], hint, &mut buffer[filled..filled + length as usize]).await?;
+
+      position += length;
+      part += 1;
+    }
+
+    Ok(reader(&buffer).await)
+  }
+
+  fn slice<'l, const SIZE: usize>(&'l self, start: u64) -> Result<Self::StaticSliceProvider<'l, SIZE>, ProviderSliceError<Self::SliceError>> {
+    Ok(FixedSliceProvider::new(start, self)?)
+  }
+
+  fn slice_dynamic<'l>(&'l self, start: u64, size: Option<u64>) -> Result<Self::DynamicSliceProvider<'l>, ProviderSliceError<Self::SliceError>> {
+    Ok(DynamicSliceProvider::new(start, size, self)?)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::{vec, vec::Vec};
+
+  use super::*;
+
+  fn parts() -> ConcatProvider<Vec<u8>> {
+    ConcatProvider::new(vec![vec![0, 1, 2, 3], vec![], vec![4, 5], vec![6, 7, 8, 9]])
+  }
+
+  async fn read<const SIZE: usize, P: Provider<Type = u8>>(provider: &P, offset: u64) -> Result<[u8; SIZE], ProviderReadError<P::ReadError>> {
+    provider.read(offset, ReadHint::new(), async |bytes: &[u8; SIZE]| *bytes).await
+  }
+
+  #[tokio::test]
+  async fn reads_within_and_across_parts() {
+    let provider = parts();
+
+    assert_eq!(provider.len(), 10);
+    assert_eq!(provider.part_offset(3), 6);
+    assert_eq!(read::<2, _>(&provider, 1).await.map_err(|_| {}).unwrap(), [1, 2]);
+    assert_eq!(read::<2, _>(&provider, 4).await.map_err(|_| {}).unwrap(), [4, 5]);
+    assert_eq!(read::<5, _>(&provider, 3).await.map_err(|_| {}).unwrap(), [3, 4, 5, 6, 7]);
+    assert_eq!(read::<10, _>(&provider, 0).await.map_err(|_| {}).unwrap(), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    assert_eq!(read::<0, _>(&provider, 10).await.map_err(|_| {}).unwrap(), []);
+    assert!(matches!(read::<2, _>(&provider, 9).await, Err(ProviderReadError::OutOfBounds(_))));
+  }
+
+  #[tokio::test]
+  async fn slices_span_parts() {
+    let provider = parts();
+    let slice = provider.slice_dynamic(2, Some(6)).map_err(|_| {}).unwrap();
+
+    assert_eq!(slice.len(), 6);
+    assert_eq!(read::<4, _>(&slice, 1).await.map_err(|_| {}).unwrap(), [3, 4, 5, 6]);
+    assert!(matches!(read::<4, _>(&slice, 3).await, Err(ProviderReadError::OutOfBounds(_))));
+  }
+
+  #[tokio::test]
+  async fn blames_the_failing_part() {
+    // a part reporting more bytes than it can read, like a file truncated after it was opened
+    struct Truncated;
+
+    impl Provider for Truncated {
+      type Type = u8;
+      type ReadError = core::convert::Infallible;
+      type SliceError = core::convert::Infallible;
+
+      type StaticSliceProvider<'l, const SIZE: usize> = &'l [u8; SIZE];
+      type DynamicSliceProvider<'l> = &'l [u8];
+
+      fn len(&self) -> u64 {
+        4
+      }
+
+      async fn read<const SIZE: usize, V>(&self, offset: u64, hint: ReadHint, reader: impl AsyncFnOnce(&[u8; SIZE]) -> V) -> Result<V, ProviderReadError<Self::ReadError>> {
+        [0u8; 2].as_slice().read(offset, hint, reader).await
+      }
+
+      fn slice<'l, const SIZE: usize>(&'l self, _: u64) -> Result<Self::StaticSliceProvider<'l, SIZE>, ProviderSliceError<Self::SliceError>> {
+        unimplemented!()
+      }
+
+      fn slice_dynamic<'l>(&'l self, _: u64, _: Option<u64>) -> Result<Self::DynamicSliceProvider<'l>, ProviderSliceError<Self::SliceError>> {
+        unimplemented!()
+      }
+    }
+
+    let provider = ConcatProvider::new(vec![Truncated, Truncated]);
+
+    assert!(matches!(
+      read::<2, _>(&provider, 5).await,
+      Err(ProviderReadError::User(PartReadError::OutOfBounds {
+        part: 1,
+        error: OutOfBoundsError { read_offset: 1, provider_size: 2, .. }
+      }))
+    ));
+    assert!(matches!(read::<2, _>(&provider, 3).await, Err(ProviderReadError::User(PartReadError::OutOfBounds { part: 0, .. }))));
+  }
+}
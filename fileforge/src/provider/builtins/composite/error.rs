@@ -0,0 +1,49 @@
+use fileforge_macros::FileforgeError;
+
+use crate::{
+  error::render::builtin::number::formatted_unsigned::FormattedUnsigned,
+  provider::error::{out_of_bounds::OutOfBoundsError, user_read::UserReadError},
+};
+
+/// A read from one of the parts behind a composite provider failed.
+#[derive(Debug, FileforgeError)]
+pub enum PartReadError<E: UserReadError> {
+  User(E),
+
+  #[report(&"Part of a composite provider is shorter than expected")]
+  #[flag(
+    "Part {part} holds {size} bytes, but was asked for {length} bytes at {offset}",
+    part = FormattedUnsigned::new(*part as u128),
+    size = FormattedUnsigned::new(error.provider_size as u128),
+    length = FormattedUnsigned::new(error.read_length.unwrap_or(0) as u128),
+    offset = FormattedUnsigned::new(error.read_offset as u128)
+  )]
+  OutOfBounds {
+    part: usize,
+    error: OutOfBoundsError,
+  },
+}
+
+impl<E: UserReadError> UserReadError for PartReadError<E> {}
+
+/// A part couldn't be mapped into a [`SparseProvider`](super::sparse::SparseProvider).
+#[derive(Debug, FileforgeError)]
+pub enum SparseMapError {
+  #[report(&"Sparse provider part doesn't fit")]
+  #[flag(
+    "{length} bytes at {offset} run past the end of the provider, at {size}",
+    length = FormattedUnsigned::new(*length as u128),
+    offset = FormattedUnsigned::new(*offset as u128),
+    size = FormattedUnsigned::new(*size as u128)
+  )]
+  OutOfBounds { offset: u64, length: u64, size: u64 },
+
+  #[report(&"Sparse provider parts overlap")]
+  #[flag(
+    "{length} bytes at {offset} overlap the part at {other_offset}",
+    length = FormattedUnsigned::new(*length as u128),
+    offset = FormattedUnsigned::new(*offset as u128),
+    other_offset = FormattedUnsigned::new(*other_offset as u128)
+  )]
+  Overlap { offset: u64, length: u64, other_offset: u64 },
+}
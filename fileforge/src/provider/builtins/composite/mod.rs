@@ -0,0 +1,34 @@
+pub mod concat;
+pub mod error;
+pub mod sparse;
+
+use crate::provider::{
+  builtins::composite::error::PartReadError,
+  error::{provider_read::ProviderReadError, user_read::UserReadError},
+  hint::ReadHint,
+  Provider,
+};
+
+/// Blames a failed read on part `part`.
+fn part_error<E: UserReadError>(part: usize, error: ProviderReadError<E>) -> ProviderReadError<PartReadError<E>> {
+  ProviderReadError::User(match error {
+    ProviderReadError::User(error) => PartReadError::User(error),
+    ProviderReadError::OutOfBounds(error) => PartReadError::OutOfBounds { part, error },
+  })
+}
+
+/// Fills `buffer` from `provider` at `offset` one element at a time, for reads that span several
+/// parts and so can't be handed to any one of them whole.
+async fn read_elements<P: Provider>(provider: &P, part: usize, offset: u64, hint: ReadHint, buffer: &mut [P::Type]) -> Result<(), ProviderReadError<PartReadError<P::ReadError>>>
+where
+  P::Type: Copy,
+{
+  for (index, element) in buffer.iter_mut().enumerate() {
+    *element = provider
+      .read(offset + index as u64, hint, async |value: &[P::Type; 1]| value[0])
+      .await
+      .map_err(|error| part_error(part, error))?;
+  }
+
+  Ok(())
+}
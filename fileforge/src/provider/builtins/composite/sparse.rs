@@ -0,0 +1,174 @@
+use alloc::vec::Vec;
+
+use crate::provider::{
+  builtins::{
+    composite::{
+      error::{PartReadError, SparseMapError},
+      part_error, read_elements,
+    },
+    slice::{dynamic::DynamicSliceProvider, fixed::FixedSliceProvider},
+  },
+  error::{out_of_bounds::OutOfBoundsError, provider_read::ProviderReadError, provider_slice::ProviderSliceError},
+  hint::ReadHint,
+  Provider,
+};
+
+struct Part<P> {
+  offset: u64,
+  provider: P,
+}
+
+impl<P: Provider> Part<P> {
+  fn end(&self) -> u64 {
+    self.offset + self.provider.len()
+  }
+}
+
+/// A provider of a fixed length with some of its ranges backed by other providers, and
+/// everything in between reading as default values, which is zero for bytes.
+///
+/// Parts are numbered in order of offset, whatever order they were mapped in.
+pub struct SparseProvider<P: Provider> {
+  len: u64,
+  parts: Vec<Part<P>>,
+}
+
+impl<P: Provider> SparseProvider<P> {
+  /// A provider of `len` default values, with nothing mapped yet.
+  pub fn new(len: u64) -> Self {
+    Self { len, parts: Vec::new() }
+  }
+
+  /// Backs the range starting at `offset`, as long as `provider`, with `provider`.
+  pub fn map(&mut self, offset: u64, provider: P) -> Result<(), SparseMapError> {
+    let length = provider.len();
+    let end = offset
+      .checked_add(length)
+      .filter(|&end| end <= self.len)
+      .ok_or(SparseMapError::OutOfBounds { offset, length, size: self.len })?;
+    let index = self.parts.partition_point(|part| part.offset <= offset);
+
+    let before = index.checked_sub(1).map(|before| &self.parts[before]).filter(|before| before.end() > offset);
+    let after = self.parts.get(index).filter(|after| after.offset < end);
+
+    if let Some(other) = before.or(after) {
+      return Err(SparseMapError::Overlap {
+        offset,
+        length,
+        other_offset: other.offset,
+      });
+    }
+
+    self.parts.insert(index, Part { offset, provider });
+    Ok(())
+  }
+
+  /// Every part and where it starts, in order of offset.
+  pub fn parts(&self) -> impl Iterator<Item = (u64, &P)> {
+    self.parts.iter().map(|part| (part.offset, &part.provider))
+  }
+}
+
+impl<P: Provider> Provider for SparseProvider<P>
+where
+  P::Type: Copy + Default,
+{
+  type Type = P::Type;
+
+  type ReadError = PartReadError<P::ReadError>;
+  type SliceError = core::convert::Infallible;
+
+  type StaticSliceProvider<'l, const SIZE: usize>
+    = FixedSliceProvider<SIZE, &'l SparseProvider<P>>
+  where
+    Self: 'l;
+
+  type DynamicSliceProvider<'l>
+    = DynamicSliceProvider<&'l SparseProvider<P>>
+  where
+    Self: 'l;
+
+  fn len(&self) -> u64 {
+    self.len
+  }
+
+  async fn read<const SIZE: usize, V>(&self, offset: u64, hint: ReadHint, reader: impl AsyncFnOnce(&[P::Type; SIZE]) -> V) -> Result<V, ProviderReadError<Self::ReadError>> {
+    OutOfBoundsError::assert(self.len, offset, Some(SIZE as u64))?;
+
+    let end = offset + SIZE as u64;
+    let mut index = self.parts.partition_point(|part| part.end() <= offset);
+
+    if let Some(part) = self.parts.get(index).filter(|part| part.offset <= offset && end <= part.end()) {
+      return part.provider.read(offset - part.offset, hint, reader).await.map_err(|error| part_error(index, error));
+    }
+
+    let mut buffer = [P::Type::default(); SIZE];
+
+    while let Some(part) = self.parts.get(index).filter(|part| part.offset < end) {
+      let from = offset.max(part.offset);
+      let to = end.min(part.end());
+
+      read_elements(&part.provider, index, from - part.offset, hint, &mut buffer[(from - offset) as usize..(to - offset) as usize]).await?;
+      index += 1;
+    }
+
+    Ok(reader(&buffer).await)
+  }
+
+  fn slice<'l, const SIZE: usize>(&'l self, start: u64) -> Result<Self::StaticSliceProvider<'l, SIZE>, ProviderSliceError<Self::SliceError>> {
+    Ok(FixedSliceProvider::new(start, self)?)
+  }
+
+  fn slice_dynamic<'l>(&'l self, start: u64, size: Option<u64>) -> Result<Self::DynamicSliceProvider<'l>, ProviderSliceError<Self::SliceError>> {
+    Ok(DynamicSliceProvider::new(start, size, self)?)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::{vec, vec::Vec};
+
+  use super::*;
+
+  fn sparse() -> SparseProvider<Vec<u8>> {
+    let mut provider = SparseProvider::new(12);
+
+    provider.map(8, vec![8, 9]).unwrap();
+    provider.map(2, vec![2, 3, 4]).unwrap();
+
+    provider
+  }
+
+  async fn read<const SIZE: usize, P: Provider<Type = u8>>(provider: &P, offset: u64) -> Result<[u8; SIZE], ProviderReadError<P::ReadError>> {
+    provider.read(offset, ReadHint::new(), async |bytes: &[u8; SIZE]| *bytes).await
+  }
+
+  #[tokio::test]
+  async fn fills_holes_with_zeroes() {
+    let provider = sparse();
+
+    assert_eq!(provider.parts().map(|(offset, _)| offset).collect::<Vec<_>>(), [2, 8]);
+    assert_eq!(read::<2, _>(&provider, 3).await.map_err(|_| {}).unwrap(), [3, 4]);
+    assert_eq!(read::<3, _>(&provider, 5).await.map_err(|_| {}).unwrap(), [0, 0, 0]);
+    assert_eq!(read::<12, _>(&provider, 0).await.map_err(|_| {}).unwrap(), [0, 0, 2, 3, 4, 0, 0, 0, 8, 9, 0, 0]);
+    assert!(matches!(read::<4, _>(&provider, 10).await, Err(ProviderReadError::OutOfBounds(_))));
+  }
+
+  #[tokio::test]
+  async fn slices_span_parts_and_holes() {
+    let provider = sparse();
+    let slice = provider.slice_dynamic(4, Some(6)).map_err(|_| {}).unwrap();
+
+    assert_eq!(read::<6, _>(&slice, 0).await.map_err(|_| {}).unwrap(), [4, 0, 0, 0, 8, 9]);
+  }
+
+  #[test]
+  fn rejects_overlapping_and_oversized_parts() {
+    let mut provider = sparse();
+
+    assert!(matches!(provider.map(4, vec![0; 2]), Err(SparseMapError::Overlap { other_offset: 2, .. })));
+    assert!(matches!(provider.map(6, vec![0; 3]), Err(SparseMapError::Overlap { other_offset: 8, .. })));
+    assert!(matches!(provider.map(10, vec![0; 3]), Err(SparseMapError::OutOfBounds { size: 12, .. })));
+    assert!(provider.map(5, vec![5, 6, 7]).is_ok());
+  }
+}
@@ -0,0 +1,19 @@
+use fileforge_macros::FileforgeError;
+
+use crate::provider::error::{user_mutate::UserMutateError, user_read::UserReadError, user_resize::UserResizeError};
+
+#[derive(Debug, FileforgeError)]
+pub enum FileProviderError {
+  #[report(&"Failed to read from the file")]
+  Read(std::io::Error),
+
+  #[report(&"Failed to write to the file")]
+  Write(std::io::Error),
+
+  #[report(&"Failed to resize the file")]
+  Resize(std::io::Error),
+}
+
+impl UserReadError for FileProviderError {}
+impl UserMutateError for FileProviderError {}
+impl UserResizeError for FileProviderError {}
@@ -0,0 +1,487 @@
+pub mod error;
+
+use core::cell::RefCell;
+use std::{fs::File, io, path::Path, vec, vec::Vec};
+
+use crate::provider::{
+  builtins::{
+    file::error::FileProviderError,
+    slice::{dynamic::DynamicSliceProvider, fixed::FixedSliceProvider},
+  },
+  error::{out_of_bounds::OutOfBoundsError, provider_mutate::ProviderMutateError, provider_read::ProviderReadError, provider_resize::ProviderResizeError, provider_slice::ProviderSliceError},
+  hint::{ReadHint, ReadPriority},
+  MutProvider, Provider, ResizableProvider,
+};
+
+/// Smallest amount fetched from the file on a cache miss. Pages start on multiples of this.
+pub const PAGE_SIZE: u64 = 0x1000;
+
+/// Largest page fetched on a cache miss, however much read-ahead the [`ReadHint`] asks for.
+pub const MAX_PAGE_SIZE: u64 = 0x10_0000;
+
+/// Pages a [`FileProvider`] keeps unless told otherwise.
+pub const DEFAULT_CACHED_PAGES: usize = 64;
+
+/// Bytes moved or zeroed at a time when [`FileProvider::resize_at`] shifts the rest of the file.
+const MOVE_CHUNK_SIZE: usize = 0x10000;
+
+#[cfg(unix)]
+fn read_at(file: &File, buffer: &mut [u8], offset: u64) -> io::Result<()> {
+  std::os::unix::fs::FileExt::read_exact_at(file, buffer, offset)
+}
+
+#[cfg(unix)]
+fn write_at(file: &File, buffer: &[u8], offset: u64) -> io::Result<()> {
+  std::os::unix::fs::FileExt::write_all_at(file, buffer, offset)
+}
+
+#[cfg(windows)]
+fn read_at(file: &File, mut buffer: &mut [u8], mut offset: u64) -> io::Result<()> {
+  while !buffer.is_empty() {
+    match std::os::windows::fs::FileExt::seek_read(file, buffer, offset)? {
+      0 => return Err(io::ErrorKind::UnexpectedEof.into()),
+      read => {
+        buffer = &mut buffer[read..];
+        offset += read as u64;
+      }
+    }
+  }
+
+  Ok(())
+}
+
+#[cfg(windows)]
+fn write_at(file: &File, mut buffer: &[u8], mut offset: u64) -> io::Result<()> {
+  while !buffer.is_empty() {
+    match std::os::windows::fs::FileExt::seek_write(file, buffer, offset)? {
+      0 => return Err(io::ErrorKind::WriteZero.into()),
+      written => {
+        buffer = &buffer[written..];
+        offset += written as u64;
+      }
+    }
+  }
+
+  Ok(())
+}
+
+struct Page {
+  start: u64,
+  data: Vec<u8>,
+  priority: ReadPriority,
+  last_used: u64,
+}
+
+impl Page {
+  fn end(&self) -> u64 {
+    self.start + self.data.len() as u64
+  }
+}
+
+/// Pages of a file, evicting the least recently used page of the lowest priority first. Pages
+/// never overlap one another.
+struct PageCache {
+  pages: Vec<Page>,
+  capacity: usize,
+  clock: u64,
+}
+
+impl PageCache {
+  fn get(&mut self, offset: u64, length: usize, priority: ReadPriority) -> Option<&[u8]> {
+    self.clock += 1;
+
+    let page = self.pages.iter_mut().find(|page| page.start <= offset && offset + length as u64 <= page.end())?;
+    let start = (offset - page.start) as usize;
+
+    page.priority = priority;
+    page.last_used = self.clock;
+    Some(&page.data[start..start + length])
+  }
+
+  fn insert(&mut self, start: u64, data: Vec<u8>, priority: ReadPriority) {
+    let end = start + data.len() as u64;
+
+    self.pages.retain(|page| page.end() <= start || end <= page.start);
+
+    if self.pages.len() >= self.capacity {
+      if let Some(oldest) = self.pages.iter().enumerate().min_by_key(|(_, page)| (page.priority, page.last_used)).map(|(index, _)| index) {
+        self.pages.swap_remove(oldest);
+      }
+    }
+
+    if self.capacity > 0 {
+      self.pages.push(Page {
+        start,
+        data,
+        priority,
+        last_used: self.clock,
+      });
+    }
+  }
+
+  /// Copies `data`, just written at `offset`, into every page it overlaps.
+  fn update(&mut self, offset: u64, data: &[u8]) {
+    let end = offset + data.len() as u64;
+
+    for page in self.pages.iter_mut().filter(|page| page.start < end && offset < page.end()) {
+      let from = offset.max(page.start);
+      let to = end.min(page.end());
+
+      page.data[(from - page.start) as usize..(to - page.start) as usize].copy_from_slice(&data[(from - offset) as usize..(to - offset) as usize]);
+    }
+  }
+}
+
+/// A provider over a [`File`], reading and writing at positions without moving its cursor.
+///
+/// Reads are served from a small cache of pages, at least [`PAGE_SIZE`] bytes each and up to
+/// [`MAX_PAGE_SIZE`] when the [`ReadHint`] asks for read-ahead. Reads hinted to happen once bypass the cache, and
+/// pages read with a higher priority outlive those read with a lower one. Mutations are written
+/// through to the file immediately.
+pub struct FileProvider {
+  file: File,
+  len: u64,
+  cache: RefCell<PageCache>,
+}
+
+impl FileProvider {
+  pub fn new(file: File) -> io::Result<Self> {
+    Ok(Self {
+      len: file.metadata()?.len(),
+      file,
+      cache: RefCell::new(PageCache {
+        pages: Vec::new(),
+        capacity: DEFAULT_CACHED_PAGES,
+        clock: 0,
+      }),
+    })
+  }
+
+  /// Opens the file at `path` for reading only. Open it with write access and use
+  /// [`FileProvider::new`] to mutate or resize it.
+  pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+    Self::new(File::open(path)?)
+  }
+
+  /// Keeps up to `pages` pages cached. Zero turns the cache off.
+  pub fn with_cached_pages(self, pages: usize) -> Self {
+    self.cache.borrow_mut().capacity = pages;
+    self.cache.borrow_mut().pages.truncate(pages);
+    self
+  }
+
+  pub fn file(&self) -> &File {
+    &self.file
+  }
+
+  pub fn into_file(self) -> File {
+    self.file
+  }
+
+  fn read_into(&self, offset: u64, hint: ReadHint, buffer: &mut [u8]) -> Result<(), FileProviderError> {
+    let mut cache = self.cache.borrow_mut();
+
+    if let Some(cached) = cache.get(offset, buffer.len(), hint.priority()) {
+      buffer.copy_from_slice(cached);
+      return Ok(());
+    }
+
+    if !hint.should_cache() {
+      return read_at(&self.file, buffer, offset).map_err(FileProviderError::Read);
+    }
+
+    let start = offset - offset % PAGE_SIZE;
+    let end = (offset + hint.read_ahead())
+      .max(start + PAGE_SIZE)
+      .min(start + MAX_PAGE_SIZE)
+      .max(offset + buffer.len() as u64)
+      .min(self.len);
+    let mut page = vec![0; (end - start) as usize];
+
+    read_at(&self.file, &mut page, start).map_err(FileProviderError::Read)?;
+    buffer.copy_from_slice(&page[(offset - start) as usize..][..buffer.len()]);
+    cache.insert(start, page, hint.priority());
+
+    Ok(())
+  }
+
+  /// Moves `length` bytes from `from` to `to`, which may overlap.
+  fn move_bytes(&self, from: u64, to: u64, length: u64) -> io::Result<()> {
+    let mut buffer = vec![0; MOVE_CHUNK_SIZE.min(length as usize)];
+    let mut moved = 0;
+
+    while moved < length {
+      let chunk = (length - moved).min(MOVE_CHUNK_SIZE as u64);
+      // moving right has to start from the end, so nothing is overwritten before it's moved
+      let position = if to > from { length - moved - chunk } else { moved };
+      let buffer = &mut buffer[..chunk as usize];
+
+      read_at(&self.file, buffer, from + position)?;
+      write_at(&self.file, buffer, to + position)?;
+      moved += chunk;
+    }
+
+    Ok(())
+  }
+
+  /// Overwrites `length` bytes at `offset` with zeroes.
+  fn zero_bytes(&self, offset: u64, length: u64) -> io::Result<()> {
+    let buffer = vec![0; MOVE_CHUNK_SIZE.min(length as usize)];
+    let mut zeroed = 0;
+
+    while zeroed < length {
+      let chunk = (length - zeroed).min(MOVE_CHUNK_SIZE as u64);
+
+      write_at(&self.file, &buffer[..chunk as usize], offset + zeroed)?;
+      zeroed += chunk;
+    }
+
+    Ok(())
+  }
+}
+
+impl Provider for FileProvider {
+  type Type = u8;
+
+  type ReadError = FileProviderError;
+  type SliceError = core::convert::Infallible;
+
+  type StaticSliceProvider<'l, const SIZE: usize>
+    = FixedSliceProvider<SIZE, &'l FileProvider>
+  where
+    Self: 'l;
+
+  type DynamicSliceProvider<'l>
+    = DynamicSliceProvider<&'l FileProvider>
+  where
+    Self: 'l;
+
+  fn len(&self) -> u64 {
+    self.len
+  }
+
+  async fn read<const SIZE: usize, V>(&self, offset: u64, hint: ReadHint, reader: impl AsyncFnOnce(&[u8; SIZE]) -> V) -> Result<V, ProviderReadError<Self::ReadError>> {
+    OutOfBoundsError::assert(self.len, offset, Some(SIZE as u64))?;
+
+    let mut buffer = [0; SIZE];
+    self.read_into(offset, hint, &mut buffer)?;
+
+    Ok(reader(&buffer).await)
+  }
+
+  fn slice<'l, const SIZE: usize>(&'l self, start: u64) -> Result<Self::StaticSliceProvider<'l, SIZE>, ProviderSliceError<Self::SliceError>> {
+    Ok(FixedSliceProvider::new(start, self)?)
+  }
+
+  fn slice_dynamic<'l>(&'l self, start: u64, size: Option<u64>) -> Result<Self::DynamicSliceProvider<'l>, ProviderSliceError<Self::SliceError>> {
+    Ok(DynamicSliceProvider::new(start, size, self)?)
+  }
+}
+
+impl MutProvider for FileProvider {
+  type MutateError = FileProviderError;
+
+  type StaticMutSliceProvider<'l, const SIZE: usize>
+    = FixedSliceProvider<SIZE, &'l mut FileProvider>
+  where
+    Self: 'l;
+
+  type DynamicMutSliceProvider<'l>
+    = DynamicSliceProvider<&'l mut FileProvider>
+  where
+    Self: 'l;
+
+  async fn mutate<const SIZE: usize, V>(&mut self, offset: u64, writer: impl AsyncFnOnce(&mut [u8; SIZE]) -> V) -> Result<V, ProviderMutateError<Self::MutateError>> {
+    OutOfBoundsError::assert(self.len, offset, Some(SIZE as u64))?;
+
+    let mut buffer = [0; SIZE];
+    self.read_into(offset, ReadHint::new(), &mut buffer)?;
+
+    let value = writer(&mut buffer).await;
+
+    write_at(&self.file, &buffer, offset).map_err(FileProviderError::Write)?;
+    self.cache.get_mut().update(offset, &buffer);
+
+    Ok(value)
+  }
+
+  fn mut_slice<'l, const SIZE: usize>(&'l mut self, start: u64) -> Result<Self::StaticMutSliceProvider<'l, SIZE>, ProviderSliceError<Self::SliceError>> {
+    Ok(FixedSliceProvider::new(start, self)?)
+  }
+
+  fn mut_slice_dynamic<'l>(&'l mut self, start: u64, size: Option<u64>) -> Result<Self::DynamicMutSliceProvider<'l>, ProviderSliceError<Self::SliceError>> {
+    Ok(DynamicSliceProvider::new(start, size, self)?)
+  }
+}
+
+impl ResizableProvider for FileProvider {
+  type ResizeError = FileProviderError;
+
+  /// Grows or shrinks the `old_len` bytes at `offset` to `new_len`, moving the rest of the file
+  /// along. Grown regions are zero-filled at their end, as with `Vec`.
+  async fn resize_at(&mut self, offset: u64, old_len: u64, new_len: u64) -> Result<(), ProviderResizeError<Self::ResizeError>> {
+    OutOfBoundsError::assert(self.len, offset, Some(old_len))?;
+
+    if old_len == new_len {
+      return Ok(());
+    }
+
+    let tail = offset + old_len;
+    let new_tail = offset + new_len;
+    let tail_len = self.len - tail;
+    let new_total = new_tail + tail_len;
+
+    self.cache.get_mut().pages.clear();
+
+    if new_len > old_len {
+      self.file.set_len(new_total).map_err(FileProviderError::Resize)?;
+      self.move_bytes(tail, new_tail, tail_len).map_err(FileProviderError::Write)?;
+      self.zero_bytes(tail, new_tail - tail).map_err(FileProviderError::Write)?;
+    } else {
+      self.move_bytes(tail, new_tail, tail_len).map_err(FileProviderError::Write)?;
+      self.file.set_len(new_total).map_err(FileProviderError::Resize)?;
+    }
+
+    self.len = new_total;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::provider::builtins::temp_file::{self, pattern, TempFile};
+
+  fn provider(file: &TempFile) -> FileProvider {
+    FileProvider::new(file.open()).unwrap()
+  }
+
+  async fn read<const SIZE: usize>(provider: &FileProvider, offset: u64, hint: ReadHint) -> [u8; SIZE] {
+    provider.read(offset, hint, async |bytes: &[u8; SIZE]| *bytes).await.unwrap()
+  }
+
+  #[tokio::test]
+  async fn reads_across_pages() {
+    let data = pattern(0x3000);
+    let file = TempFile::new("reads_across_pages", &data);
+    let provider = provider(&file);
+
+    assert_eq!(provider.len(), 0x3000);
+    assert_eq!(read::<16>(&provider, 0, ReadHint::new()).await[..], data[..16]);
+    assert_eq!(read::<0x20>(&provider, 0xFF0, ReadHint::new()).await[..], data[0xFF0..0x1010]);
+    assert_eq!(read::<4>(&provider, 0x2FFC, ReadHint::new()).await[..], data[0x2FFC..]);
+    assert!(matches!(provider.read(0x2FFD, ReadHint::new(), async |_: &[u8; 4]| {}).await, Err(ProviderReadError::OutOfBounds(_))));
+  }
+
+  #[tokio::test]
+  async fn read_ahead_fills_larger_pages() {
+    let data = pattern(0x10000);
+    let file = TempFile::new("read_ahead_fills_larger_pages", &data);
+    let provider = provider(&file);
+
+    read::<1>(&provider, 0x100, ReadHint::new().with_read_ahead(0x8000)).await;
+
+    assert_eq!(provider.cache.borrow().pages.len(), 1);
+    assert_eq!(provider.cache.borrow().pages[0].data.len(), 0x8100);
+
+    // served from the same page
+    assert_eq!(read::<4>(&provider, 0x7000, ReadHint::new()).await[..], data[0x7000..0x7004]);
+    assert_eq!(provider.cache.borrow().pages.len(), 1);
+  }
+
+  #[tokio::test]
+  async fn caps_read_ahead_pages() {
+    let data = pattern(0x20_0000);
+    let file = TempFile::new("caps_read_ahead_pages", &data);
+    let provider = provider(&file);
+
+    read::<1>(&provider, 0x100, ReadHint::new().with_read_ahead(u64::MAX / 2)).await;
+
+    assert_eq!(provider.cache.borrow().pages[0].data.len(), MAX_PAGE_SIZE as usize);
+    assert_eq!(read::<4>(&provider, 0x10_0000, ReadHint::new()).await[..], data[0x10_0000..0x10_0004]);
+  }
+
+  #[tokio::test]
+  async fn evicts_least_recently_used_pages() {
+    let data = pattern(0x4000);
+    let file = TempFile::new("evicts_least_recently_used_pages", &data);
+    let provider = provider(&file).with_cached_pages(2);
+
+    read::<1>(&provider, 0x0000, ReadHint::new()).await;
+    read::<1>(&provider, 0x1000, ReadHint::new()).await;
+    read::<1>(&provider, 0x0000, ReadHint::new()).await;
+    read::<1>(&provider, 0x2000, ReadHint::new()).await;
+
+    let mut starts: Vec<u64> = provider.cache.borrow().pages.iter().map(|page| page.start).collect();
+    starts.sort();
+
+    assert_eq!(starts, [0x0000, 0x2000]);
+  }
+
+  #[tokio::test]
+  async fn honours_read_patterns() {
+    let data = pattern(0x10000);
+    let file = TempFile::new("honours_read_patterns", &data);
+    let provider = provider(&file);
+
+    assert_eq!(read::<4>(&provider, 0x100, ReadHint::once().with_read_ahead(0x8000)).await[..], data[0x100..0x104]);
+    assert!(provider.cache.borrow().pages.is_empty());
+
+    read::<1>(&provider, 0x100, ReadHint::random().with_read_ahead(0x8000)).await;
+    assert_eq!(provider.cache.borrow().pages[0].data.len(), PAGE_SIZE as usize);
+  }
+
+  #[tokio::test]
+  async fn evicts_low_priority_pages_first() {
+    let data = pattern(0x4000);
+    let file = TempFile::new("evicts_low_priority_pages_first", &data);
+    let provider = provider(&file).with_cached_pages(2);
+
+    read::<1>(&provider, 0x0000, ReadHint::new().with_priority(ReadPriority::High)).await;
+    read::<1>(&provider, 0x1000, ReadHint::new().with_priority(ReadPriority::Low)).await;
+    read::<1>(&provider, 0x2000, ReadHint::new()).await;
+
+    let mut starts: Vec<u64> = provider.cache.borrow().pages.iter().map(|page| page.start).collect();
+    starts.sort();
+
+    assert_eq!(starts, [0x0000, 0x2000]);
+  }
+
+  #[tokio::test]
+  async fn mutations_write_through() {
+    let data = pattern(0x2000);
+    let file = TempFile::new("mutations_write_through", &data);
+    let mut provider = provider(&file);
+
+    read::<1>(&provider, 0xFFE, ReadHint::new()).await;
+    provider.mutate(0xFFE, async |bytes: &mut [u8; 4]| *bytes = [1, 2, 3, 4]).await.unwrap();
+
+    assert_eq!(read::<4>(&provider, 0xFFE, ReadHint::new()).await, [1, 2, 3, 4]);
+    assert_eq!(file.contents()[0xFFE..0x1002], [1, 2, 3, 4]);
+  }
+
+  #[tokio::test]
+  async fn resizes_like_vec() {
+    let data = pattern(0x30000);
+    let file = TempFile::new("resizes_like_vec", &data);
+    let mut provider = provider(&file);
+    let mut expected = data.clone();
+
+    read::<1>(&provider, 0x20000, ReadHint::new()).await;
+    temp_file::resizes_like_vec(&mut provider, &file, &mut expected, &[(0x100, 0x10, 0x1_8010), (0x50, 0x2_0000, 0x8)], |_, _| {}).await;
+
+    // the page cached before resizing doesn't survive it
+    assert_eq!(read::<4>(&provider, 0x20000, ReadHint::new()).await[..], expected[0x20000..0x20004]);
+  }
+
+  #[tokio::test]
+  async fn slices_read_through_the_file() {
+    let data = pattern(0x2000);
+    let file = TempFile::new("slices_read_through_the_file", &data);
+    let provider = provider(&file);
+    let slice = provider.slice_dynamic(0x1000, Some(0x10)).unwrap();
+
+    assert_eq!(slice.len(), 0x10);
+    assert_eq!(slice.read(0xC, ReadHint::new(), async |bytes: &[u8; 4]| *bytes).await.unwrap()[..], data[0x100C..0x1010]);
+  }
+}
@@ -0,0 +1,17 @@
+use fileforge_macros::FileforgeError;
+
+use crate::provider::error::user_resize::UserResizeError;
+
+#[derive(Debug, FileforgeError)]
+pub enum MmapProviderError {
+  #[report(&"Failed to resize the mapped file")]
+  Resize(std::io::Error),
+
+  #[report(&"Failed to map the file again after resizing it")]
+  Remap(std::io::Error),
+
+  #[report(&"Failed to flush the mapping before resizing the file")]
+  Flush(std::io::Error),
+}
+
+impl UserResizeError for MmapProviderError {}
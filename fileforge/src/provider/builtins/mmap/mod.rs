@@ -0,0 +1,319 @@
+pub mod error;
+
+use std::{fs::File, io};
+
+use memmap2::{Mmap, MmapMut};
+
+use crate::provider::{
+  builtins::{
+    mmap::error::MmapProviderError,
+    slice::{dynamic::DynamicSliceProvider, fixed::FixedSliceProvider},
+  },
+  error::{out_of_bounds::OutOfBoundsError, provider_mutate::ProviderMutateError, provider_read::ProviderReadError, provider_resize::ProviderResizeError, provider_slice::ProviderSliceError},
+  hint::ReadHint,
+  MutProvider, Provider, ResizableProvider,
+};
+
+/// The kernel advice matching the access pattern of `hint`.
+#[cfg(unix)]
+fn advice(hint: ReadHint) -> memmap2::Advice {
+  use crate::provider::hint::ReadPattern;
+
+  match hint.pattern() {
+    ReadPattern::Sequential | ReadPattern::Once => memmap2::Advice::Sequential,
+    ReadPattern::Random => memmap2::Advice::Random,
+  }
+}
+
+/// Hands out `SIZE` bytes of `map` at `offset`, after the caller has checked the bounds.
+fn window<const SIZE: usize>(map: &[u8], offset: u64) -> &[u8; SIZE] {
+  map[offset as usize..].first_chunk::<SIZE>().unwrap()
+}
+
+/// A read-only provider over a memory-mapped file. Reads and slices borrow straight from the
+/// mapping, without copying.
+pub struct MmapProvider {
+  map: Mmap,
+}
+
+impl MmapProvider {
+  /// Maps all of `file`.
+  ///
+  /// # Safety
+  ///
+  /// The file must not be modified, truncated or resized, by this process or any other, while
+  /// it's mapped. See [`Mmap::map`].
+  pub unsafe fn map(file: &File) -> io::Result<Self> {
+    Ok(Self { map: Mmap::map(file)? })
+  }
+
+  pub fn as_slice(&self) -> &[u8] {
+    &self.map
+  }
+
+  /// Tells the kernel how the mapping is about to be read, so it can page it in to suit. Reads
+  /// don't take their own hint into account, since advising the kernel on every read would cost
+  /// more than it saves.
+  #[cfg(unix)]
+  pub fn advise(&self, hint: ReadHint) -> io::Result<()> {
+    self.map.advise(advice(hint))
+  }
+}
+
+impl Provider for MmapProvider {
+  type Type = u8;
+
+  type ReadError = core::convert::Infallible;
+  type SliceError = core::convert::Infallible;
+
+  type StaticSliceProvider<'l, const SIZE: usize>
+    = &'l [u8; SIZE]
+  where
+    Self: 'l;
+
+  type DynamicSliceProvider<'l>
+    = &'l [u8]
+  where
+    Self: 'l;
+
+  fn len(&self) -> u64 {
+    self.map.len() as u64
+  }
+
+  async fn read<const SIZE: usize, V>(&self, offset: u64, _hint: ReadHint, reader: impl AsyncFnOnce(&[u8; SIZE]) -> V) -> Result<V, ProviderReadError<Self::ReadError>> {
+    OutOfBoundsError::assert(self.len(), offset, Some(SIZE as u64))?;
+
+    Ok(reader(window(&self.map, offset)).await)
+  }
+
+  fn slice<'l, const SIZE: usize>(&'l self, start: u64) -> Result<Self::StaticSliceProvider<'l, SIZE>, ProviderSliceError<Self::SliceError>> {
+    OutOfBoundsError::assert(self.len(), start, Some(SIZE as u64))?;
+
+    Ok(window(&self.map, start))
+  }
+
+  fn slice_dynamic<'l>(&'l self, start: u64, size: Option<u64>) -> Result<Self::DynamicSliceProvider<'l>, ProviderSliceError<Self::SliceError>> {
+    self.map[..].slice_dynamic(start, size)
+  }
+}
+
+/// A read-write provider over a memory-mapped file. Mutations write straight into the mapping;
+/// resizing changes the file's length and maps it again.
+pub struct MmapMutProvider {
+  file: File,
+  map: MmapMut,
+}
+
+impl MmapMutProvider {
+  /// Maps all of `file`, which must have been opened for reading and writing.
+  ///
+  /// # Safety
+  ///
+  /// The file must not be modified, truncated or resized by anything but this provider while
+  /// it's mapped. See [`MmapMut::map_mut`].
+  pub unsafe fn map(file: File) -> io::Result<Self> {
+    Ok(Self { map: MmapMut::map_mut(&file)?, file })
+  }
+
+  pub fn as_slice(&self) -> &[u8] {
+    &self.map
+  }
+
+  /// Tells the kernel how the mapping is about to be read, as with [`MmapProvider::advise`].
+  /// Resizing maps the file again, which forgets the advice.
+  #[cfg(unix)]
+  pub fn advise(&self, hint: ReadHint) -> io::Result<()> {
+    self.map.advise(advice(hint))
+  }
+
+  /// Writes modified pages back to the file, waiting until they're written.
+  pub fn flush(&self) -> io::Result<()> {
+    self.map.flush()
+  }
+
+  pub fn into_file(self) -> io::Result<File> {
+    self.flush()?;
+    Ok(self.file)
+  }
+
+  /// Sets the file's length to `len` and maps it again. If the file can't be resized, it's mapped
+  /// again at the length it had.
+  fn remap(&mut self, len: u64) -> Result<(), MmapProviderError> {
+    self.map.flush().map_err(MmapProviderError::Flush)?;
+    // the old mapping has to go before the file shrinks underneath it
+    self.map = MmapMut::map_anon(0).map_err(MmapProviderError::Remap)?;
+    let resized = self.file.set_len(len).map_err(MmapProviderError::Resize);
+    // SAFETY: `map`'s contract makes this provider the only thing changing the file
+    self.map = unsafe { MmapMut::map_mut(&self.file) }.map_err(MmapProviderError::Remap)?;
+
+    resized
+  }
+}
+
+impl Provider for MmapMutProvider {
+  type Type = u8;
+
+  type ReadError = core::convert::Infallible;
+  type SliceError = core::convert::Infallible;
+
+  type StaticSliceProvider<'l, const SIZE: usize>
+    = &'l [u8; SIZE]
+  where
+    Self: 'l;
+
+  type DynamicSliceProvider<'l>
+    = &'l [u8]
+  where
+    Self: 'l;
+
+  fn len(&self) -> u64 {
+    self.map.len() as u64
+  }
+
+  async fn read<const SIZE: usize, V>(&self, offset: u64, _hint: ReadHint, reader: impl AsyncFnOnce(&[u8; SIZE]) -> V) -> Result<V, ProviderReadError<Self::ReadError>> {
+    OutOfBoundsError::assert(self.len(), offset, Some(SIZE as u64))?;
+
+    Ok(reader(window(&self.map, offset)).await)
+  }
+
+  fn slice<'l, const SIZE: usize>(&'l self, start: u64) -> Result<Self::StaticSliceProvider<'l, SIZE>, ProviderSliceError<Self::SliceError>> {
+    OutOfBoundsError::assert(self.len(), start, Some(SIZE as u64))?;
+
+    Ok(window(&self.map, start))
+  }
+
+  fn slice_dynamic<'l>(&'l self, start: u64, size: Option<u64>) -> Result<Self::DynamicSliceProvider<'l>, ProviderSliceError<Self::SliceError>> {
+    self.map[..].slice_dynamic(start, size)
+  }
+}
+
+impl MutProvider for MmapMutProvider {
+  type MutateError = core::convert::Infallible;
+
+  type StaticMutSliceProvider<'l, const SIZE: usize>
+    = FixedSliceProvider<SIZE, &'l mut MmapMutProvider>
+  where
+    Self: 'l;
+
+  type DynamicMutSliceProvider<'l>
+    = DynamicSliceProvider<&'l mut MmapMutProvider>
+  where
+    Self: 'l;
+
+  async fn mutate<const SIZE: usize, V>(&mut self, offset: u64, writer: impl AsyncFnOnce(&mut [u8; SIZE]) -> V) -> Result<V, ProviderMutateError<Self::MutateError>> {
+    OutOfBoundsError::assert(self.len(), offset, Some(SIZE as u64))?;
+
+    Ok(writer(self.map[offset as usize..].first_chunk_mut::<SIZE>().unwrap()).await)
+  }
+
+  fn mut_slice<'l, const SIZE: usize>(&'l mut self, start: u64) -> Result<Self::StaticMutSliceProvider<'l, SIZE>, ProviderSliceError<Self::SliceError>> {
+    Ok(FixedSliceProvider::new(start, self)?)
+  }
+
+  fn mut_slice_dynamic<'l>(&'l mut self, start: u64, size: Option<u64>) -> Result<Self::DynamicMutSliceProvider<'l>, ProviderSliceError<Self::SliceError>> {
+    Ok(DynamicSliceProvider::new(start, size, self)?)
+  }
+}
+
+impl ResizableProvider for MmapMutProvider {
+  type ResizeError = MmapProviderError;
+
+  /// Grows or shrinks the `old_len` bytes at `offset` to `new_len`, moving the rest of the file
+  /// along. Grown regions are zero-filled at their end, as with `Vec`.
+  async fn resize_at(&mut self, offset: u64, old_len: u64, new_len: u64) -> Result<(), ProviderResizeError<Self::ResizeError>> {
+    OutOfBoundsError::assert(self.len(), offset, Some(old_len))?;
+
+    let total = self.map.len();
+    let tail = (offset + old_len) as usize;
+    let new_tail = (offset + new_len) as usize;
+    let new_total = new_tail + (total - tail);
+
+    if new_len > old_len {
+      self.remap(new_total as u64)?;
+      self.map.copy_within(tail..total, new_tail);
+      self.map[tail..new_tail].fill(0);
+    } else if new_len < old_len {
+      self.map.copy_within(tail..total, new_tail);
+      self.remap(new_total as u64)?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::provider::builtins::temp_file::{self, pattern, TempFile};
+
+  #[tokio::test]
+  async fn reads_borrow_the_mapping() {
+    let data = pattern(0x2000);
+    let file = TempFile::new("reads_borrow_the_mapping", &data);
+    let provider = unsafe { MmapProvider::map(&file.open()) }.unwrap();
+    let start = provider.as_slice().as_ptr();
+
+    #[cfg(unix)]
+    provider.advise(ReadHint::random()).unwrap();
+
+    let address = provider.read(0x100, ReadHint::new(), async |bytes: &[u8; 4]| bytes.as_ptr()).await.unwrap();
+    let slice = provider.slice_dynamic(0x1000, Some(0x10)).unwrap();
+
+    assert_eq!(address, start.wrapping_add(0x100));
+    assert_eq!(slice, &data[0x1000..0x1010]);
+    assert_eq!(*provider.slice::<4>(0x1FFC).unwrap(), data[0x1FFC..]);
+    assert!(matches!(provider.read(0x1FFD, ReadHint::new(), async |_: &[u8; 4]| {}).await, Err(ProviderReadError::OutOfBounds(_))));
+  }
+
+  #[tokio::test]
+  async fn keeps_the_mapping_when_resizing_fails() {
+    let data = pattern(0x1000);
+    let file = TempFile::new("keeps_the_mapping_when_resizing_fails", &data);
+    let mut provider = unsafe { MmapMutProvider::map(file.open()) }.unwrap();
+
+    // longer than any file can be
+    let resized = provider.resize_at(0x800, 0, u64::MAX / 2).await;
+
+    assert!(matches!(resized, Err(ProviderResizeError::User(MmapProviderError::Resize(_)))));
+    assert_eq!(provider.len(), 0x1000);
+    assert_eq!(provider.as_slice(), data);
+
+    provider.mutate(0x10, async |bytes: &mut [u8; 4]| *bytes = [1, 2, 3, 4]).await.unwrap();
+    provider.flush().unwrap();
+
+    assert_eq!(file.contents()[0x10..0x14], [1, 2, 3, 4]);
+  }
+
+  #[tokio::test]
+  async fn mutations_reach_the_file() {
+    let data = pattern(0x1000);
+    let file = TempFile::new("mutations_reach_the_file", &data);
+    let mut provider = unsafe { MmapMutProvider::map(file.open()) }.unwrap();
+
+    provider.mutate(0x10, async |bytes: &mut [u8; 4]| *bytes = [1, 2, 3, 4]).await.unwrap();
+    provider.mut_slice_dynamic(0x20, Some(2)).unwrap().mutate(0, async |bytes: &mut [u8; 2]| *bytes = [5, 6]).await.unwrap();
+    provider.flush().unwrap();
+
+    assert_eq!(file.contents()[0x10..0x14], [1, 2, 3, 4]);
+    assert_eq!(file.contents()[0x20..0x22], [5, 6]);
+  }
+
+  #[tokio::test]
+  async fn resizes_like_vec() {
+    let data = pattern(0x3000);
+    let file = TempFile::new("resizes_like_vec", &data);
+    let mut provider = unsafe { MmapMutProvider::map(file.open()) }.unwrap();
+    let mut expected = data.clone();
+
+    temp_file::resizes_like_vec(
+      &mut provider,
+      &file,
+      &mut expected,
+      &[(0x100, 0x10, 0x2010), (0x50, 0x3000, 0x8), (0, 0x2008, 0)],
+      |provider, expected| assert_eq!(provider.as_slice(), expected),
+    )
+    .await;
+
+    assert_eq!(provider.len(), 0);
+  }
+}
@@ -1,2 +1,14 @@
+#[cfg(feature = "alloc")]
+pub mod composite;
+#[cfg(feature = "std")]
+pub mod file;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+#[cfg(feature = "alloc")]
+pub mod overlay;
 pub mod rust;
 pub mod slice;
+#[cfg(all(test, feature = "std"))]
+mod temp_file;
+#[cfg(feature = "tokio")]
+pub mod tokio_file;
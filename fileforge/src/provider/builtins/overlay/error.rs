@@ -0,0 +1,33 @@
+use fileforge_macros::FileforgeError;
+
+use crate::{
+  error::render::builtin::number::formatted_unsigned::FormattedUnsigned,
+  provider::error::{user_mutate::UserMutateError, user_read::UserReadError, user_resize::UserResizeError},
+};
+
+/// Editing an [`OverlayProvider`](super::OverlayProvider) needed the original data under the
+/// edit, and reading it failed.
+#[derive(Debug, FileforgeError)]
+pub enum OverlayMutateError<E: UserReadError> {
+  Base(E),
+}
+
+impl<E: UserReadError> UserMutateError for OverlayMutateError<E> {}
+
+/// Applying an [`OverlayPatch`](super::patch::OverlayPatch) to a provider failed.
+#[derive(Debug, FileforgeError)]
+pub enum OverlayFlushError<Re: UserResizeError, Mu: UserMutateError> {
+  #[report(&"Patch applied to the wrong data")]
+  #[flag(
+    "The patch was made against {expected} bytes, but the target holds {found}",
+    expected = FormattedUnsigned::new(*expected as u128),
+    found = FormattedUnsigned::new(*found as u128)
+  )]
+  SourceLength {
+    expected: u64,
+    found: u64,
+  },
+
+  Resize(Re),
+  Write(Mu),
+}
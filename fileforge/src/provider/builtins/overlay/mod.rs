@@ -0,0 +1,428 @@
+pub mod error;
+pub mod patch;
+
+use alloc::{vec, vec::Vec};
+
+use crate::provider::{
+  builtins::{
+    overlay::{
+      error::{OverlayFlushError, OverlayMutateError},
+      patch::{OverlayEdit, OverlayPatch},
+    },
+    slice::{dynamic::DynamicSliceProvider, fixed::FixedSliceProvider},
+  },
+  error::{out_of_bounds::OutOfBoundsError, provider_mutate::ProviderMutateError, provider_read::ProviderReadError, provider_resize::ProviderResizeError, provider_slice::ProviderSliceError},
+  hint::ReadHint,
+  MutProvider, Provider, ResizableProvider,
+};
+
+enum Piece<T> {
+  /// `length` elements of the base provider from `offset`.
+  Base { offset: u64, length: u64 },
+  /// Elements written or inserted through the overlay.
+  Data(Vec<T>),
+}
+
+impl<T> Piece<T> {
+  fn len(&self) -> u64 {
+    match self {
+      Self::Base { length, .. } => *length,
+      Self::Data(data) => data.len() as u64,
+    }
+  }
+}
+
+/// An editable layer over a provider that's never written to. Mutations and resizes are kept in
+/// memory, as a list of pieces that are either untouched ranges of the base or edited data.
+///
+/// The edits can be listed with [`OverlayProvider::edits`], written out to another provider with
+/// [`OverlayProvider::flush`], or exported with [`OverlayProvider::patch`] to apply later.
+pub struct OverlayProvider<P: Provider> {
+  base: P,
+  pieces: Vec<Piece<P::Type>>,
+  len: u64,
+}
+
+impl<P: Provider> OverlayProvider<P>
+where
+  P::Type: Copy + Default,
+{
+  pub fn new(base: P) -> Self {
+    let mut overlay = Self { pieces: Vec::new(), len: 0, base };
+
+    overlay.reset();
+    overlay
+  }
+
+  pub fn base(&self) -> &P {
+    &self.base
+  }
+
+  pub fn into_base(self) -> P {
+    self.base
+  }
+
+  /// Drops every edit, going back to the base's contents.
+  pub fn reset(&mut self) {
+    self.len = self.base.len();
+    self.pieces = vec![Piece::Base { offset: 0, length: self.len }];
+    self.pieces.retain(|piece| piece.len() > 0);
+  }
+
+  /// The edits made so far, as replacements of ranges of the base, in order of offset.
+  pub fn edits(&self) -> Vec<OverlayEdit<P::Type>> {
+    let mut edits = Vec::new();
+    let mut source = 0;
+    let mut data = Vec::new();
+
+    for piece in &self.pieces {
+      match piece {
+        Piece::Base { offset, length } => {
+          if *offset != source || !data.is_empty() {
+            edits.push(OverlayEdit {
+              offset: source,
+              removed: offset - source,
+              data: core::mem::take(&mut data),
+            });
+          }
+
+          source = offset + length;
+        }
+        Piece::Data(piece) => data.extend_from_slice(piece),
+      }
+    }
+
+    if source != self.base.len() || !data.is_empty() {
+      edits.push(OverlayEdit {
+        offset: source,
+        removed: self.base.len() - source,
+        data,
+      });
+    }
+
+    edits
+  }
+
+  /// The edits made so far, as a patch that can be applied to any copy of the base.
+  pub fn patch(&self) -> OverlayPatch<P::Type> {
+    OverlayPatch {
+      source_len: self.base.len(),
+      target_len: self.len,
+      edits: self.edits(),
+    }
+  }
+
+  /// Writes the edits made so far to `target`, which has to hold the same data as the base.
+  pub async fn flush<R: ResizableProvider<Type = P::Type>>(&self, target: &mut R) -> Result<(), OverlayFlushError<R::ResizeError, R::MutateError>> {
+    self.patch().apply(target).await
+  }
+
+  /// Makes a piece start at `offset`, returning its index.
+  fn split(&mut self, offset: u64) -> usize {
+    let mut start = 0;
+
+    for index in 0..self.pieces.len() {
+      let length = self.pieces[index].len();
+
+      if offset == start {
+        return index;
+      }
+
+      if offset < start + length {
+        let at = offset - start;
+        let right = match &mut self.pieces[index] {
+          Piece::Base { offset, length } => {
+            let right = Piece::Base {
+              offset: *offset + at,
+              length: *length - at,
+            };
+            *length = at;
+            right
+          }
+          Piece::Data(data) => Piece::Data(data.split_off(at as usize)),
+        };
+
+        self.pieces.insert(index + 1, right);
+        return index + 1;
+      }
+
+      start += length;
+    }
+
+    self.pieces.len()
+  }
+
+  /// Replaces the `removed` elements at `offset` with `data`.
+  fn replace(&mut self, offset: u64, removed: u64, data: Vec<P::Type>) {
+    let start = self.split(offset);
+    let end = self.split(offset + removed);
+
+    self.len = self.len - removed + data.len() as u64;
+
+    if data.is_empty() {
+      self.pieces.drain(start..end);
+      return;
+    }
+
+    // keep edits next to each other in one piece, so repeated small writes don't pile up pieces
+    if let Some(Piece::Data(previous)) = start.checked_sub(1).and_then(|previous| self.pieces.get_mut(previous)) {
+      previous.extend_from_slice(&data);
+      self.pieces.drain(start..end);
+    } else {
+      self.pieces.splice(start..end, [Piece::Data(data)]);
+    }
+  }
+
+  /// Copies the elements at `offset` into `buffer`, reading the base one element at a time.
+  async fn read_into(&self, offset: u64, hint: ReadHint, buffer: &mut [P::Type]) -> Result<(), ProviderReadError<P::ReadError>> {
+    let end = offset + buffer.len() as u64;
+    let mut start = 0;
+
+    for piece in &self.pieces {
+      let piece_end = start + piece.len();
+      let from = offset.max(start);
+      let to = end.min(piece_end);
+
+      if from < to {
+        let target = &mut buffer[(from - offset) as usize..(to - offset) as usize];
+
+        match piece {
+          Piece::Base { offset: base, .. } => {
+            for (index, element) in target.iter_mut().enumerate() {
+              *element = self.base.read(base + from - start + index as u64, hint, async |value: &[P::Type; 1]| value[0]).await?;
+            }
+          }
+          Piece::Data(data) => target.copy_from_slice(&data[(from - start) as usize..(to - start) as usize]),
+        }
+      }
+
+      start = piece_end;
+    }
+
+    Ok(())
+  }
+
+  /// The piece wholly holding the `length` elements at `offset`, and where in it they start.
+  fn piece_holding(&self, offset: u64, length: u64) -> Option<(&Piece<P::Type>, u64)> {
+    let mut start = 0;
+
+    for piece in &self.pieces {
+      if offset >= start && offset + length <= start + piece.len() && (length > 0 || offset < start + piece.len()) {
+        return Some((piece, offset - start));
+      }
+
+      start += piece.len();
+    }
+
+    None
+  }
+}
+
+impl<P: Provider> Provider for OverlayProvider<P>
+where
+  P::Type: Copy + Default,
+{
+  type Type = P::Type;
+
+  type ReadError = P::ReadError;
+  type SliceError = core::convert::Infallible;
+
+  type StaticSliceProvider<'l, const SIZE: usize>
+    = FixedSliceProvider<SIZE, &'l OverlayProvider<P>>
+  where
+    Self: 'l;
+
+  type DynamicSliceProvider<'l>
+    = DynamicSliceProvider<&'l OverlayProvider<P>>
+  where
+    Self: 'l;
+
+  fn len(&self) -> u64 {
+    self.len
+  }
+
+  async fn read<const SIZE: usize, V>(&self, offset: u64, hint: ReadHint, reader: impl AsyncFnOnce(&[P::Type; SIZE]) -> V) -> Result<V, ProviderReadError<Self::ReadError>> {
+    OutOfBoundsError::assert(self.len, offset, Some(SIZE as u64))?;
+
+    match self.piece_holding(offset, SIZE as u64) {
+      Some((Piece::Base { offset: base, .. }, at)) => self.base.read(base + at, hint, reader).await,
+      Some((Piece::Data(data), at)) => Ok(reader(data[at as usize..].first_chunk::<SIZE>().unwrap()).await),
+      None => {
+        let mut buffer = [P::Type::default(); SIZE];
+        self.read_into(offset, hint, &mut buffer).await?;
+
+        Ok(reader(&buffer).await)
+      }
+    }
+  }
+
+  fn slice<'l, const SIZE: usize>(&'l self, start: u64) -> Result<Self::StaticSliceProvider<'l, SIZE>, ProviderSliceError<Self::SliceError>> {
+    Ok(FixedSliceProvider::new(start, self)?)
+  }
+
+  fn slice_dynamic<'l>(&'l self, start: u64, size: Option<u64>) -> Result<Self::DynamicSliceProvider<'l>, ProviderSliceError<Self::SliceError>> {
+    Ok(DynamicSliceProvider::new(start, size, self)?)
+  }
+}
+
+impl<P: Provider> MutProvider for OverlayProvider<P>
+where
+  P::Type: Copy + Default,
+{
+  type MutateError = OverlayMutateError<P::ReadError>;
+
+  type StaticMutSliceProvider<'l, const SIZE: usize>
+    = FixedSliceProvider<SIZE, &'l mut OverlayProvider<P>>
+  where
+    Self: 'l;
+
+  type DynamicMutSliceProvider<'l>
+    = DynamicSliceProvider<&'l mut OverlayProvider<P>>
+  where
+    Self: 'l;
+
+  async fn mutate<const SIZE: usize, V>(&mut self, offset: u64, writer: impl AsyncFnOnce(&mut [P::Type; SIZE]) -> V) -> Result<V, ProviderMutateError<Self::MutateError>> {
+    OutOfBoundsError::assert(self.len, offset, Some(SIZE as u64))?;
+
+    let mut buffer = [P::Type::default(); SIZE];
+
+    self.read_into(offset, ReadHint::new(), &mut buffer).await.map_err(|error| match error {
+      ProviderReadError::User(error) => ProviderMutateError::User(OverlayMutateError::Base(error)),
+      ProviderReadError::OutOfBounds(error) => ProviderMutateError::OutOfBounds(error),
+    })?;
+
+    let value = writer(&mut buffer).await;
+
+    self.replace(offset, SIZE as u64, buffer.to_vec());
+    Ok(value)
+  }
+
+  fn mut_slice<'l, const SIZE: usize>(&'l mut self, start: u64) -> Result<Self::StaticMutSliceProvider<'l, SIZE>, ProviderSliceError<Self::SliceError>> {
+    Ok(FixedSliceProvider::new(start, self)?)
+  }
+
+  fn mut_slice_dynamic<'l>(&'l mut self, start: u64, size: Option<u64>) -> Result<Self::DynamicMutSliceProvider<'l>, ProviderSliceError<Self::SliceError>> {
+    Ok(DynamicSliceProvider::new(start, size, self)?)
+  }
+}
+
+impl<P: Provider> ResizableProvider for OverlayProvider<P>
+where
+  P::Type: Copy + Default,
+{
+  type ResizeError = core::convert::Infallible;
+
+  /// Grows or shrinks the `old_len` elements at `offset` to `new_len`, as with `Vec`: growing
+  /// inserts default values at the end of the range, shrinking removes its last elements.
+  async fn resize_at(&mut self, offset: u64, old_len: u64, new_len: u64) -> Result<(), ProviderResizeError<Self::ResizeError>> {
+    OutOfBoundsError::assert(self.len, offset, Some(old_len))?;
+
+    if new_len > old_len {
+      self.replace(offset + old_len, 0, vec![P::Type::default(); (new_len - old_len) as usize]);
+    } else if new_len < old_len {
+      self.replace(offset + new_len, old_len - new_len, Vec::new());
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::{vec, vec::Vec};
+
+  use super::*;
+  use crate::stream::{builtin::provider::ProviderStream, ResizableStream, SeekableStream};
+
+  fn base() -> Vec<u8> {
+    (0..16).collect()
+  }
+
+  async fn contents(provider: &OverlayProvider<Vec<u8>>) -> Vec<u8> {
+    let mut contents = vec![0; provider.len() as usize];
+    provider.read_into(0, ReadHint::new(), &mut contents).await.unwrap();
+    contents
+  }
+
+  #[tokio::test]
+  async fn edits_leave_the_base_alone() {
+    let mut overlay = OverlayProvider::new(base());
+
+    overlay.mutate(2, async |bytes: &mut [u8; 2]| *bytes = [0xA0, 0xA1]).await.unwrap();
+    overlay.mutate(4, async |bytes: &mut [u8; 1]| bytes[0] = 0xA2).await.unwrap();
+    overlay.resize_at(8, 2, 4).await.unwrap();
+    overlay.resize_at(12, 4, 1).await.unwrap();
+
+    assert_eq!(contents(&overlay).await, [0, 1, 0xA0, 0xA1, 0xA2, 5, 6, 7, 8, 9, 0, 0, 10, 14, 15]);
+    assert_eq!(overlay.read(2, ReadHint::new(), async |bytes: &[u8; 3]| *bytes).await.unwrap(), [0xA0, 0xA1, 0xA2]);
+    assert_eq!(overlay.read(4, ReadHint::new(), async |bytes: &[u8; 3]| *bytes).await.unwrap(), [0xA2, 5, 6]);
+    assert_eq!(overlay.base(), &base());
+    assert_eq!(overlay.pieces.len(), 6);
+  }
+
+  #[tokio::test]
+  async fn lists_edits_against_the_base() {
+    let mut overlay = OverlayProvider::new(base());
+
+    overlay.resize_at(0, 2, 0).await.unwrap();
+    overlay.mutate(4, async |bytes: &mut [u8; 2]| *bytes = [0xB0, 0xB1]).await.unwrap();
+    overlay.resize_at(14, 0, 2).await.unwrap();
+
+    assert_eq!(
+      overlay.edits(),
+      [
+        OverlayEdit { offset: 0, removed: 2, data: vec![] },
+        OverlayEdit {
+          offset: 6,
+          removed: 2,
+          data: vec![0xB0, 0xB1]
+        },
+        OverlayEdit {
+          offset: 16,
+          removed: 0,
+          data: vec![0, 0]
+        },
+      ]
+    );
+
+    overlay.reset();
+    assert!(overlay.edits().is_empty());
+  }
+
+  #[tokio::test]
+  async fn flushes_to_a_copy_of_the_base() {
+    let mut overlay = OverlayProvider::new(base());
+
+    overlay.mutate(0, async |bytes: &mut [u8; 1]| bytes[0] = 0xFF).await.unwrap();
+    overlay.resize_at(3, 2, 0x300).await.unwrap();
+    overlay.mutate(0x200, async |bytes: &mut [u8; 1]| bytes[0] = 0xEE).await.unwrap();
+    overlay.resize_at(0x302, 4, 1).await.unwrap();
+
+    let mut target = base();
+    overlay.flush(&mut target).await.unwrap();
+
+    assert_eq!(target, contents(&overlay).await);
+    assert!(matches!(overlay.flush(&mut vec![0u8; 3]).await, Err(OverlayFlushError::SourceLength { expected: 16, found: 3 })));
+  }
+
+  #[tokio::test]
+  async fn overwrites_through_a_stream() {
+    let mut stream = ProviderStream::new(OverlayProvider::new(base()), ReadHint::new());
+
+    stream.seek(4).await.map_err(|_| {}).unwrap();
+    stream.overwrite(2, [0xC0, 0xC1, 0xC2]).await.map_err(|_| {}).unwrap();
+
+    let overlay = stream.into_provider();
+
+    assert_eq!(overlay.len(), 17);
+    assert_eq!(contents(&overlay).await[3..8], [3, 0xC0, 0xC1, 0xC2, 6]);
+    assert_eq!(
+      overlay.patch().edits(),
+      [OverlayEdit {
+        offset: 4,
+        removed: 2,
+        data: vec![0xC0, 0xC1, 0xC2]
+      }]
+    );
+  }
+}
@@ -0,0 +1,104 @@
+use alloc::vec::Vec;
+
+use crate::provider::{
+  builtins::overlay::error::OverlayFlushError,
+  error::{provider_mutate::ProviderMutateError, provider_resize::ProviderResizeError},
+  ResizableProvider,
+};
+
+/// Elements written with a single [`mutate`](crate::provider::MutProvider::mutate) while
+/// applying a patch.
+const WRITE_CHUNK_SIZE: usize = 0x100;
+
+/// One replacement in a patch: `removed` elements of the original at `offset` give way to `data`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OverlayEdit<T> {
+  pub offset: u64,
+  pub removed: u64,
+  pub data: Vec<T>,
+}
+
+/// The edits that turn one provider's contents into another's, in order of offset, with offsets
+/// in the original.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OverlayPatch<T> {
+  pub(crate) source_len: u64,
+  pub(crate) target_len: u64,
+  pub(crate) edits: Vec<OverlayEdit<T>>,
+}
+
+impl<T: Copy> OverlayPatch<T> {
+  pub fn source_len(&self) -> u64 {
+    self.source_len
+  }
+
+  pub fn target_len(&self) -> u64 {
+    self.target_len
+  }
+
+  pub fn edits(&self) -> &[OverlayEdit<T>] {
+    &self.edits
+  }
+
+  pub fn into_edits(self) -> Vec<OverlayEdit<T>> {
+    self.edits
+  }
+
+  /// Applies the patch to `target`, which has to hold the data it was made against.
+  pub async fn apply<R: ResizableProvider<Type = T>>(&self, target: &mut R) -> Result<(), OverlayFlushError<R::ResizeError, R::MutateError>> {
+    if target.len() != self.source_len {
+      return Err(OverlayFlushError::SourceLength {
+        expected: self.source_len,
+        found: target.len(),
+      });
+    }
+
+    // how far the edits so far have moved the rest of the target
+    let mut shift = 0i64;
+
+    for edit in &self.edits {
+      let offset = edit.offset.wrapping_add_signed(shift);
+
+      target.resize_at(offset, edit.removed, edit.data.len() as u64).await.map_err(|error| match error {
+        ProviderResizeError::User(error) => OverlayFlushError::Resize(error),
+        ProviderResizeError::OutOfBounds(_) => unreachable!("Edits lie within the source, whose length was checked"),
+      })?;
+
+      write(target, offset, &edit.data).await?;
+      shift += edit.data.len() as i64 - edit.removed as i64;
+    }
+
+    Ok(())
+  }
+}
+
+async fn write<R: ResizableProvider>(target: &mut R, offset: u64, data: &[R::Type]) -> Result<(), OverlayFlushError<R::ResizeError, R::MutateError>>
+where
+  R::Type: Copy,
+{
+  let chunks = data.chunks_exact(WRITE_CHUNK_SIZE);
+  let remainder = chunks.remainder();
+  let mut position = offset;
+
+  for chunk in chunks {
+    target
+      .mutate(position, async |elements: &mut [R::Type; WRITE_CHUNK_SIZE]| elements.copy_from_slice(chunk))
+      .await
+      .map_err(write_error::<R>)?;
+    position += WRITE_CHUNK_SIZE as u64;
+  }
+
+  for &element in remainder {
+    target.mutate(position, async |elements: &mut [R::Type; 1]| elements[0] = element).await.map_err(write_error::<R>)?;
+    position += 1;
+  }
+
+  Ok(())
+}
+
+fn write_error<R: ResizableProvider>(error: ProviderMutateError<R::MutateError>) -> OverlayFlushError<R::ResizeError, R::MutateError> {
+  match error {
+    ProviderMutateError::User(error) => OverlayFlushError::Write(error),
+    ProviderMutateError::OutOfBounds(_) => unreachable!("Edits were just resized to fit their data"),
+  }
+}
@@ -39,7 +39,7 @@ where
   }
 
   fn slice<'a, const SIZE: usize>(&'a self, start: u64) -> Result<Self::StaticSliceProvider<'a, SIZE>, ProviderSliceError<Self::SliceError>> {
-    OutOfBoundsError::assert(SIZE as u64, start, Some(SIZE as u64))?;
+    OutOfBoundsError::assert(self.len() as u64, start, Some(SIZE as u64))?;
 
     let slice = &self[start as usize..(start + SIZE as u64) as usize];
     let slice: &[T; SIZE] = slice.try_into().unwrap();
@@ -163,6 +163,7 @@ mod tests {
     hint::ReadHint,
     MutProvider, Provider, ResizableProvider,
   };
+  use crate::testing;
 
   // A helper to produce a default ReadHint without assuming a specific variant.
   fn hint() -> ReadHint {
@@ -443,4 +444,15 @@ mod tests {
     // Expected: [0,1,2, 3,100,101,6, 0,0, 7,8,9]
     assert_eq!(v, vec![0, 1, 2, 3, 100, 101, 6, 0, 0, 7, 8, 9]);
   }
+
+  // ---------- Conformance ---------------------------------------------------
+
+  #[tokio::test]
+  async fn conforms_to_the_provider_contracts() {
+    let v = (1u8..=8).collect::<Vec<_>>();
+
+    testing::provider::readable(|| v.clone(), &v).await;
+    testing::provider::mutable(|| v.clone(), &v).await;
+    testing::provider::resizable(|| v.clone(), &v).await;
+  }
 }
@@ -1,6 +1,7 @@
 use crate::provider::builtins::rust::vec::VecSyncResize;
 use crate::provider::builtins::slice::dynamic::DynamicSliceProvider;
 use crate::provider::builtins::slice::fixed::FixedSliceProvider;
+use crate::provider::error::out_of_bounds::OutOfBoundsError;
 use crate::provider::error::provider_mutate::ProviderMutateError;
 use crate::provider::error::provider_read::ProviderReadError;
 use crate::provider::error::provider_resize::ProviderResizeError;
@@ -32,9 +33,7 @@ where
   type PartitionRightProvider = Tail<'a, T>;
 
   fn partition(self, at: u64) -> Result<(Self::PartitionLeftProvider, Self::PartitionRightProvider), ProviderPartitionError<Self::PartitionError>> {
-    if at > self.len() {
-      todo!()
-    }
+    OutOfBoundsError::assert(self.len(), 0, Some(at))?;
 
     let vec = &*UnsafeCell::from_mut(self);
 
@@ -69,6 +68,8 @@ where
   }
 
   async fn read<const SIZE: usize, V>(&self, offset: u64, _: ReadHint, reader: impl for<'v> AsyncFnOnce(&'v [Self::Type; SIZE]) -> V) -> Result<V, ProviderReadError<Self::ReadError>> {
+    OutOfBoundsError::assert(self.len(), offset, Some(SIZE as u64))?;
+
     let v: &[T; SIZE] = unsafe { &*self.vec.get() }.as_slice()[self.range.clone()][offset as usize..offset as usize + SIZE].try_into().unwrap();
     let v: [T; SIZE] = *v;
 
@@ -108,6 +109,8 @@ where
   }
 
   async fn read<const SIZE: usize, V>(&self, offset: u64, _: ReadHint, reader: impl for<'v> AsyncFnOnce(&'v [Self::Type; SIZE]) -> V) -> Result<V, ProviderReadError<Self::ReadError>> {
+    OutOfBoundsError::assert(self.len(), offset, Some(SIZE as u64))?;
+
     let v: &[T; SIZE] = unsafe { &*self.vec.get() }.as_slice()[self.start..][offset as usize..offset as usize + SIZE].try_into().unwrap();
     let v: [T; SIZE] = *v;
 
@@ -140,6 +143,8 @@ where
     Self: 'l;
 
   async fn mutate<const SIZE: usize, V>(&mut self, offset: u64, writer: impl for<'v> AsyncFnOnce(&'v mut [Self::Type; SIZE]) -> V) -> Result<V, ProviderMutateError<Self::MutateError>> {
+    OutOfBoundsError::assert(self.len(), offset, Some(SIZE as u64))?;
+
     let v: &[T; SIZE] = unsafe { &*self.vec.get() }.as_slice()[self.range.clone()][offset as usize..offset as usize + SIZE].try_into().unwrap();
     let mut v: [T; SIZE] = *v;
 
@@ -179,6 +184,8 @@ where
     Self: 'l;
 
   async fn mutate<const SIZE: usize, V>(&mut self, offset: u64, writer: impl for<'v> AsyncFnOnce(&'v mut [Self::Type; SIZE]) -> V) -> Result<V, ProviderMutateError<Self::MutateError>> {
+    OutOfBoundsError::assert(self.len(), offset, Some(SIZE as u64))?;
+
     let v: &[T; SIZE] = unsafe { &*self.vec.get() }.as_slice()[self.start..][offset as usize..offset as usize + SIZE].try_into().unwrap();
     let mut v: [T; SIZE] = *v;
 
@@ -209,8 +216,38 @@ where
   type ResizeError = Infallible;
 
   async fn resize_at(&mut self, offset: u64, old_len: u64, new_len: u64) -> Result<(), ProviderResizeError<Self::ResizeError>> {
+    OutOfBoundsError::assert(self.len(), offset, Some(old_len))?;
+
     let v = unsafe { &mut *self.vec.get() };
 
     v.resize_at_sync(self.start as u64 + offset, old_len, new_len)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use alloc::{vec, vec::Vec};
+  use core::cell::RefCell;
+
+  use crate::{provider::PartitionableProvider, testing};
+
+  #[tokio::test]
+  async fn conforms_to_the_provider_contracts() {
+    let data = (1..=8).collect::<Vec<u8>>();
+    // the halves borrow from the partitioned `Vec`, so each provider borrows its own copy
+    let mut copies = vec![data.clone(); 512];
+    let copies = RefCell::new(copies.iter_mut());
+    let vec = || copies.borrow_mut().next().expect("enough copies for every provider");
+
+    testing::provider::partitionable(vec, &data).await;
+
+    let head = || vec().partition(5).unwrap().0;
+    let tail = || vec().partition(3).unwrap().1;
+
+    testing::provider::readable(head, &data[..5]).await;
+    testing::provider::mutable(head, &data[..5]).await;
+    testing::provider::readable(tail, &data[3..]).await;
+    testing::provider::mutable(tail, &data[3..]).await;
+    testing::provider::resizable(tail, &data[3..]).await;
+  }
+}
@@ -0,0 +1,56 @@
+//! Fixtures shared by the tests of the file backed providers.
+
+use std::{
+  format,
+  fs::{File, OpenOptions},
+  path::PathBuf,
+  sync::atomic::{AtomicUsize, Ordering},
+  vec::Vec,
+};
+
+use crate::provider::{error::provider_resize::ProviderResizeError, ResizableProvider};
+
+/// A file in the temp directory, removed when dropped.
+pub(super) struct TempFile(PathBuf);
+
+impl TempFile {
+  pub(super) fn new(name: &str, contents: &[u8]) -> Self {
+    static CREATED: AtomicUsize = AtomicUsize::new(0);
+
+    let path = std::env::temp_dir().join(format!("fileforge-{}-{}-{name}", std::process::id(), CREATED.fetch_add(1, Ordering::Relaxed)));
+    std::fs::write(&path, contents).unwrap();
+    Self(path)
+  }
+
+  pub(super) fn open(&self) -> File {
+    OpenOptions::new().read(true).write(true).open(&self.0).unwrap()
+  }
+
+  pub(super) fn contents(&self) -> Vec<u8> {
+    std::fs::read(&self.0).unwrap()
+  }
+}
+
+impl Drop for TempFile {
+  fn drop(&mut self) {
+    let _ = std::fs::remove_file(&self.0);
+  }
+}
+
+pub(super) fn pattern(length: usize) -> Vec<u8> {
+  (0..length).map(|i| (i * 7 + i / 256) as u8).collect()
+}
+
+/// Resizes `provider` and `expected` alike, checking after each step that the file matches, then that resizing past the end fails.
+pub(super) async fn resizes_like_vec<P: ResizableProvider<Type = u8>>(provider: &mut P, file: &TempFile, expected: &mut Vec<u8>, resizes: &[(u64, u64, u64)], check: impl Fn(&P, &[u8])) {
+  for &(offset, old_len, new_len) in resizes {
+    provider.resize_at(offset, old_len, new_len).await.map_err(|_| {}).unwrap();
+    <Vec<u8> as ResizableProvider>::resize_at(expected, offset, old_len, new_len).await.unwrap();
+
+    assert_eq!(provider.len(), expected.len() as u64);
+    assert_eq!(file.contents(), *expected);
+    check(provider, expected);
+  }
+
+  assert!(matches!(provider.resize_at(provider.len(), 1, 0).await, Err(ProviderResizeError::OutOfBounds(_))));
+}
@@ -0,0 +1,324 @@
+use std::{
+  io::{self, SeekFrom},
+  path::Path,
+  vec,
+  vec::Vec,
+};
+
+use tokio::{
+  fs::File,
+  io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt},
+  sync::Mutex,
+};
+
+use crate::provider::{
+  builtins::{
+    file::error::FileProviderError,
+    slice::{dynamic::DynamicSliceProvider, fixed::FixedSliceProvider},
+  },
+  error::{out_of_bounds::OutOfBoundsError, provider_mutate::ProviderMutateError, provider_read::ProviderReadError, provider_slice::ProviderSliceError},
+  hint::{ReadHint, ReadPriority},
+  MutProvider, Provider,
+};
+
+/// Smallest amount read from the file when a read misses the read-ahead buffer.
+pub const MIN_READ_SIZE: u64 = 0x1000;
+
+/// Largest amount read ahead into the buffer, however much the [`ReadHint`] asks for.
+pub const MAX_READ_SIZE: u64 = 0x10_0000;
+
+async fn read_exact_at(file: &mut File, buffer: &mut [u8], offset: u64) -> io::Result<()> {
+  file.seek(SeekFrom::Start(offset)).await?;
+  file.read_exact(buffer).await?;
+  Ok(())
+}
+
+async fn write_all_at(file: &mut File, buffer: &[u8], offset: u64) -> io::Result<()> {
+  file.seek(SeekFrom::Start(offset)).await?;
+  file.write_all(buffer).await?;
+  // tokio finishes writes in the background, so wait for this one before reporting success
+  file.flush().await
+}
+
+/// The bytes most recently read from the file, starting at `start`.
+struct ReadBuffer {
+  start: u64,
+  data: Vec<u8>,
+  priority: ReadPriority,
+}
+
+impl ReadBuffer {
+  fn get(&self, offset: u64, length: usize) -> Option<&[u8]> {
+    if self.start <= offset && offset + length as u64 <= self.start + self.data.len() as u64 {
+      Some(&self.data[(offset - self.start) as usize..][..length])
+    } else {
+      None
+    }
+  }
+
+  /// Copies `data`, just written at `offset`, into the part of the buffer it overlaps.
+  fn update(&mut self, offset: u64, data: &[u8]) {
+    let end = self.start + self.data.len() as u64;
+    let from = offset.max(self.start);
+    let to = (offset + data.len() as u64).min(end);
+
+    if from < to {
+      self.data[(from - self.start) as usize..(to - self.start) as usize].copy_from_slice(&data[(from - offset) as usize..(to - offset) as usize]);
+    }
+  }
+}
+
+struct State {
+  file: File,
+  buffer: ReadBuffer,
+}
+
+/// A provider over a [`tokio::fs::File`], whose reads wait on the file without blocking the
+/// runtime.
+///
+/// Each read that misses the read-ahead buffer refills it from the read's offset, with at least
+/// [`MIN_READ_SIZE`] bytes, or as many as the [`ReadHint`] asks for up to [`MAX_READ_SIZE`].
+/// Reads hinted to happen once, and [`ReadPriority::Low`] reads while a more important read's
+/// data is buffered, leave it alone and read only what they need. Mutations are written through
+/// to the file immediately.
+pub struct TokioFileProvider {
+  len: u64,
+  state: Mutex<State>,
+}
+
+impl TokioFileProvider {
+  pub async fn new(file: File) -> io::Result<Self> {
+    Ok(Self {
+      len: file.metadata().await?.len(),
+      state: Mutex::new(State {
+        file,
+        buffer: ReadBuffer {
+          start: 0,
+          data: Vec::new(),
+          priority: ReadPriority::Low,
+        },
+      }),
+    })
+  }
+
+  /// Opens the file at `path` for reading only. Open it with write access and use
+  /// [`TokioFileProvider::new`] to mutate it.
+  pub async fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+    Self::new(File::open(path).await?).await
+  }
+
+  pub fn into_file(self) -> File {
+    self.state.into_inner().file
+  }
+
+  async fn read_into(&self, offset: u64, hint: ReadHint, buffer: &mut [u8]) -> Result<(), FileProviderError> {
+    let mut state = self.state.lock().await;
+
+    if let Some(buffered) = state.buffer.get(offset, buffer.len()) {
+      buffer.copy_from_slice(buffered);
+      return Ok(());
+    }
+
+    if !hint.should_cache() || (hint.priority() == ReadPriority::Low && state.buffer.priority > ReadPriority::Low) {
+      return read_exact_at(&mut state.file, buffer, offset).await.map_err(FileProviderError::Read);
+    }
+
+    let end = (offset + hint.read_ahead())
+      .max(offset + MIN_READ_SIZE)
+      .min(offset + MAX_READ_SIZE)
+      .max(offset + buffer.len() as u64)
+      .min(self.len);
+    let mut data = vec![0; (end - offset) as usize];
+
+    read_exact_at(&mut state.file, &mut data, offset).await.map_err(FileProviderError::Read)?;
+    buffer.copy_from_slice(&data[..buffer.len()]);
+    state.buffer = ReadBuffer {
+      start: offset,
+      data,
+      priority: hint.priority(),
+    };
+
+    Ok(())
+  }
+}
+
+impl Provider for TokioFileProvider {
+  type Type = u8;
+
+  type ReadError = FileProviderError;
+  type SliceError = core::convert::Infallible;
+
+  type StaticSliceProvider<'l, const SIZE: usize>
+    = FixedSliceProvider<SIZE, &'l TokioFileProvider>
+  where
+    Self: 'l;
+
+  type DynamicSliceProvider<'l>
+    = DynamicSliceProvider<&'l TokioFileProvider>
+  where
+    Self: 'l;
+
+  fn len(&self) -> u64 {
+    self.len
+  }
+
+  async fn read<const SIZE: usize, V>(&self, offset: u64, hint: ReadHint, reader: impl AsyncFnOnce(&[u8; SIZE]) -> V) -> Result<V, ProviderReadError<Self::ReadError>> {
+    OutOfBoundsError::assert(self.len, offset, Some(SIZE as u64))?;
+
+    let mut buffer = [0; SIZE];
+    self.read_into(offset, hint, &mut buffer).await?;
+
+    Ok(reader(&buffer).await)
+  }
+
+  fn slice<'l, const SIZE: usize>(&'l self, start: u64) -> Result<Self::StaticSliceProvider<'l, SIZE>, ProviderSliceError<Self::SliceError>> {
+    Ok(FixedSliceProvider::new(start, self)?)
+  }
+
+  fn slice_dynamic<'l>(&'l self, start: u64, size: Option<u64>) -> Result<Self::DynamicSliceProvider<'l>, ProviderSliceError<Self::SliceError>> {
+    Ok(DynamicSliceProvider::new(start, size, self)?)
+  }
+}
+
+impl MutProvider for TokioFileProvider {
+  type MutateError = FileProviderError;
+
+  type StaticMutSliceProvider<'l, const SIZE: usize>
+    = FixedSliceProvider<SIZE, &'l mut TokioFileProvider>
+  where
+    Self: 'l;
+
+  type DynamicMutSliceProvider<'l>
+    = DynamicSliceProvider<&'l mut TokioFileProvider>
+  where
+    Self: 'l;
+
+  async fn mutate<const SIZE: usize, V>(&mut self, offset: u64, writer: impl AsyncFnOnce(&mut [u8; SIZE]) -> V) -> Result<V, ProviderMutateError<Self::MutateError>> {
+    OutOfBoundsError::assert(self.len, offset, Some(SIZE as u64))?;
+
+    let mut buffer = [0; SIZE];
+    self.read_into(offset, ReadHint::new(), &mut buffer).await?;
+
+    let value = writer(&mut buffer).await;
+    let state = self.state.get_mut();
+
+    write_all_at(&mut state.file, &buffer, offset).await.map_err(FileProviderError::Write)?;
+    state.buffer.update(offset, &buffer);
+
+    Ok(value)
+  }
+
+  fn mut_slice<'l, const SIZE: usize>(&'l mut self, start: u64) -> Result<Self::StaticMutSliceProvider<'l, SIZE>, ProviderSliceError<Self::SliceError>> {
+    Ok(FixedSliceProvider::new(start, self)?)
+  }
+
+  fn mut_slice_dynamic<'l>(&'l mut self, start: u64, size: Option<u64>) -> Result<Self::DynamicMutSliceProvider<'l>, ProviderSliceError<Self::SliceError>> {
+    Ok(DynamicSliceProvider::new(start, size, self)?)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::provider::builtins::temp_file::{pattern, TempFile};
+
+  async fn provider(file: &TempFile) -> TokioFileProvider {
+    TokioFileProvider::new(tokio::fs::File::from_std(file.open())).await.unwrap()
+  }
+
+  async fn read<const SIZE: usize>(provider: &TokioFileProvider, offset: u64, hint: ReadHint) -> [u8; SIZE] {
+    provider.read(offset, hint, async |bytes: &[u8; SIZE]| *bytes).await.unwrap()
+  }
+
+  #[tokio::test]
+  async fn reads_through_the_buffer() {
+    let data = pattern(0x3000);
+    let file = TempFile::new("reads_through_the_buffer", &data);
+    let provider = provider(&file).await;
+
+    assert_eq!(provider.len(), 0x3000);
+    assert_eq!(read::<16>(&provider, 0x10, ReadHint::new()).await[..], data[0x10..0x20]);
+    assert_eq!(read::<0x20>(&provider, 0x1000, ReadHint::new()).await[..], data[0x1000..0x1020]);
+    assert_eq!(read::<4>(&provider, 0x2FFC, ReadHint::new()).await[..], data[0x2FFC..]);
+    assert!(matches!(provider.read(0x2FFD, ReadHint::new(), async |_: &[u8; 4]| {}).await, Err(ProviderReadError::OutOfBounds(_))));
+  }
+
+  #[tokio::test]
+  async fn read_ahead_fills_the_buffer() {
+    let data = pattern(0x10000);
+    let file = TempFile::new("read_ahead_fills_the_buffer", &data);
+    let provider = provider(&file).await;
+
+    read::<1>(&provider, 0x100, ReadHint::new().with_read_ahead(0x8000)).await;
+    assert_eq!(provider.state.lock().await.buffer.data.len(), 0x8000);
+
+    // served from the same buffer
+    assert_eq!(read::<4>(&provider, 0x7000, ReadHint::new()).await[..], data[0x7000..0x7004]);
+    assert_eq!(provider.state.lock().await.buffer.start, 0x100);
+  }
+
+  #[tokio::test]
+  async fn caps_read_ahead() {
+    let data = pattern(0x20_0000);
+    let file = TempFile::new("caps_read_ahead", &data);
+    let provider = provider(&file).await;
+
+    read::<1>(&provider, 0x100, ReadHint::new().with_read_ahead(u64::MAX / 2)).await;
+
+    assert_eq!(provider.state.lock().await.buffer.data.len(), MAX_READ_SIZE as usize);
+  }
+
+  #[tokio::test]
+  async fn keeps_the_buffer_for_more_important_reads() {
+    let data = pattern(0x10000);
+    let file = TempFile::new("keeps_the_buffer_for_more_important_reads", &data);
+    let provider = provider(&file).await;
+
+    read::<1>(&provider, 0x100, ReadHint::new().with_priority(ReadPriority::High)).await;
+
+    assert_eq!(read::<4>(&provider, 0x8000, ReadHint::new().with_priority(ReadPriority::Low)).await[..], data[0x8000..0x8004]);
+    assert_eq!(read::<4>(&provider, 0x9000, ReadHint::once().with_priority(ReadPriority::High)).await[..], data[0x9000..0x9004]);
+    assert_eq!(provider.state.lock().await.buffer.start, 0x100);
+
+    read::<1>(&provider, 0x8000, ReadHint::new()).await;
+    assert_eq!(provider.state.lock().await.buffer.start, 0x8000);
+  }
+
+  #[tokio::test]
+  async fn keeps_reading_ahead_after_important_reads() {
+    let data = pattern(0x10000);
+    let file = TempFile::new("keeps_reading_ahead_after_important_reads", &data);
+    let provider = provider(&file).await;
+
+    read::<1>(&provider, 0x100, ReadHint::new().with_priority(ReadPriority::High)).await;
+
+    for offset in (0x8000..0x8100).step_by(4) {
+      assert_eq!(read::<4>(&provider, offset, ReadHint::new()).await[..], data[offset as usize..][..4]);
+      assert_eq!(provider.state.lock().await.buffer.start, 0x8000);
+    }
+  }
+
+  #[tokio::test]
+  async fn mutations_write_through() {
+    let data = pattern(0x2000);
+    let file = TempFile::new("mutations_write_through", &data);
+    let mut provider = provider(&file).await;
+
+    read::<1>(&provider, 0xFFE, ReadHint::new()).await;
+    provider.mutate(0xFFE, async |bytes: &mut [u8; 4]| *bytes = [1, 2, 3, 4]).await.unwrap();
+
+    assert_eq!(read::<4>(&provider, 0xFFE, ReadHint::new()).await, [1, 2, 3, 4]);
+    assert_eq!(file.contents()[0xFFE..0x1002], [1, 2, 3, 4]);
+  }
+
+  #[tokio::test]
+  async fn slices_read_through_the_file() {
+    let data = pattern(0x2000);
+    let file = TempFile::new("slices_read_through_the_file", &data);
+    let provider = provider(&file).await;
+    let slice = provider.slice_dynamic(0x1000, Some(0x10)).unwrap();
+
+    assert_eq!(slice.len(), 0x10);
+    assert_eq!(slice.read(0xC, ReadHint::new(), async |bytes: &[u8; 4]| *bytes).await.unwrap()[..], data[0x100C..0x1010]);
+  }
+}
@@ -1,8 +1,86 @@
-#[derive(Clone, Copy, Debug)]
-pub struct ReadHint {}
+/// How the bytes around a read are expected to be accessed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReadPattern {
+  /// Reads mostly follow one another, so fetching ahead pays off.
+  #[default]
+  Sequential,
+
+  /// Reads jump around, so anything fetched beyond the read itself is likely wasted.
+  Random,
+
+  /// The bytes won't be read again, so caching them would only push out something useful.
+  Once,
+}
+
+/// How much a provider should try to keep the data of a read around, relative to other reads.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReadPriority {
+  Low,
+  #[default]
+  Normal,
+  High,
+}
+
+/// Advice on how data is about to be read, which providers may use to decide what to fetch and
+/// cache. Hints never change what a read returns.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReadHint {
+  read_ahead: u64,
+  pattern: ReadPattern,
+  priority: ReadPriority,
+}
 
 impl ReadHint {
   pub fn new() -> Self {
-    Self {}
+    Self {
+      read_ahead: 0,
+      pattern: ReadPattern::Sequential,
+      priority: ReadPriority::Normal,
+    }
+  }
+
+  /// Expect about `length` bytes to be read following each read, so providers that fetch data in
+  /// pages can fetch that much at once.
+  pub fn with_read_ahead(self, length: u64) -> Self {
+    Self { read_ahead: length, ..self }
+  }
+
+  pub fn with_pattern(self, pattern: ReadPattern) -> Self {
+    Self { pattern, ..self }
+  }
+
+  pub fn with_priority(self, priority: ReadPriority) -> Self {
+    Self { priority, ..self }
+  }
+
+  /// Shorthand for a [`ReadPattern::Random`] hint.
+  pub fn random() -> Self {
+    Self::new().with_pattern(ReadPattern::Random)
+  }
+
+  /// Shorthand for a [`ReadPattern::Once`] hint.
+  pub fn once() -> Self {
+    Self::new().with_pattern(ReadPattern::Once)
+  }
+
+  /// The bytes worth fetching from the start of a read. Random reads never read ahead.
+  pub fn read_ahead(&self) -> u64 {
+    match self.pattern {
+      ReadPattern::Random => 0,
+      ReadPattern::Sequential | ReadPattern::Once => self.read_ahead,
+    }
+  }
+
+  pub fn pattern(&self) -> ReadPattern {
+    self.pattern
+  }
+
+  pub fn priority(&self) -> ReadPriority {
+    self.priority
+  }
+
+  /// Whether the data of a read is worth caching at all.
+  pub fn should_cache(&self) -> bool {
+    self.pattern != ReadPattern::Once
   }
 }
@@ -0,0 +1,365 @@
+#[cfg(feature = "alloc")]
+use alloc::collections::VecDeque;
+
+use fileforge_macros::FileforgeError;
+
+use crate::{
+  error::render::builtin::number::formatted_unsigned::FormattedUnsigned,
+  stream::{
+    error::{
+      stream_exhausted::StreamExhaustedError, stream_read::StreamReadError, stream_restore::StreamRestoreError, stream_rewind::StreamRewindError,
+      stream_seek_out_of_bounds::StreamSeekOutOfBoundsError, stream_skip::StreamSkipError, user_restore::UserRestoreError, user_rewind::UserRewindError,
+    },
+    extensions::readable::error::{EitherError, SkipReadError},
+    ReadableStream, RestorableStream, RewindableStream, CLONED,
+  },
+};
+
+/// The last `CAP` elements read from the underlying stream, oldest first. With `alloc` it only
+/// grows as elements are read, and without it lives inline as a ring buffer.
+struct History<T, const CAP: usize> {
+  #[cfg(feature = "alloc")]
+  items: VecDeque<T>,
+  #[cfg(not(feature = "alloc"))]
+  items: [Option<T>; CAP],
+  #[cfg(not(feature = "alloc"))]
+  start: usize,
+  #[cfg(not(feature = "alloc"))]
+  length: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const CAP: usize> History<T, CAP> {
+  fn new() -> Self {
+    Self { items: VecDeque::new() }
+  }
+
+  fn len(&self) -> usize {
+    self.items.len()
+  }
+
+  fn get(&self, index: usize) -> &T {
+    &self.items[index]
+  }
+
+  fn push(&mut self, item: T) {
+    if CAP == 0 {
+      return;
+    }
+
+    if self.items.len() == CAP {
+      self.items.pop_front();
+    }
+
+    self.items.push_back(item);
+  }
+
+  fn clear(&mut self) {
+    self.items.clear();
+  }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<T, const CAP: usize> History<T, CAP> {
+  fn new() -> Self {
+    Self {
+      items: [const { None }; CAP],
+      start: 0,
+      length: 0,
+    }
+  }
+
+  fn len(&self) -> usize {
+    self.length
+  }
+
+  fn get(&self, index: usize) -> &T {
+    self.items[(self.start + index) % CAP].as_ref().expect("Index is within the history")
+  }
+
+  fn push(&mut self, item: T) {
+    if CAP == 0 {
+      return;
+    }
+
+    if self.length == CAP {
+      self.items[self.start] = Some(item);
+      self.start = (self.start + 1) % CAP;
+    } else {
+      self.items[(self.start + self.length) % CAP] = Some(item);
+      self.length += 1;
+    }
+  }
+
+  fn clear(&mut self) {
+    self.items = [const { None }; CAP];
+    self.start = 0;
+    self.length = 0;
+  }
+}
+
+#[derive(FileforgeError)]
+#[report(&"Buffered stream can't go back further than it remembers")]
+#[flag(
+  "Going back {distance} elements, but only the last {buffered} are buffered",
+  distance = FormattedUnsigned::new(self.distance as u128),
+  buffered = FormattedUnsigned::new(self.buffered as u128)
+)]
+pub struct BufferedHistoryError {
+  pub distance: u64,
+  pub buffered: u64,
+}
+
+impl UserRewindError for BufferedHistoryError {}
+impl UserRestoreError for BufferedHistoryError {}
+
+/// Remembers the last `CAP` elements read from a stream, so it can be rewound and restored over
+/// them even when the stream itself only goes forwards.
+///
+/// Rewound elements are read again from the history before anything new comes from the stream.
+/// Skipping reads through the elements it passes to keep the history whole, only skipping the
+/// stream itself for whatever would have fallen out of it anyway.
+pub struct Buffered<S: ReadableStream, const CAP: usize> {
+  stream: S,
+  history: History<S::Type, CAP>,
+  behind: usize,
+}
+
+impl<S: ReadableStream, const CAP: usize> Buffered<S, CAP> {
+  pub fn new(stream: S) -> Self {
+    Self {
+      stream,
+      history: History::new(),
+      behind: 0,
+    }
+  }
+
+  /// Gives back the underlying stream, which has already been read past any rewound elements.
+  pub fn into_inner(self) -> S {
+    self.stream
+  }
+
+  /// How far back the stream can currently go.
+  pub fn history(&self) -> u64 {
+    (self.history.len() - self.behind) as u64
+  }
+
+  fn go_back(&mut self, distance: u64) -> Result<(), BufferedHistoryError> {
+    if distance > self.history() {
+      return Err(BufferedHistoryError { distance, buffered: self.history() });
+    }
+
+    self.behind += distance as usize;
+    Ok(())
+  }
+}
+
+impl<S: ReadableStream, const CAP: usize> Buffered<S, CAP>
+where
+  S::Type: Clone,
+{
+  /// Reads the next element, from the history if the stream's been rewound.
+  async fn next_element(&mut self) -> Result<S::Type, StreamReadError<S::ReadError>> {
+    if self.behind > 0 {
+      let item = self.history.get(self.history.len() - self.behind).clone();
+
+      self.behind -= 1;
+      return Ok(item);
+    }
+
+    let item = self.stream.read(CLONED).await?;
+
+    self.history.push(item.clone());
+    Ok(item)
+  }
+}
+
+impl<S: ReadableStream, const CAP: usize> ReadableStream for Buffered<S, CAP>
+where
+  S::Type: Clone,
+{
+  type Type = S::Type;
+  type ReadError = S::ReadError;
+  type SkipError = EitherError<S::SkipError, SkipReadError<S::ReadError>>;
+
+  fn len(&self) -> Option<u64> {
+    self.stream.len()
+  }
+  fn offset(&self) -> u64 {
+    self.stream.offset() - self.behind as u64
+  }
+
+  async fn read<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[Self::Type; SIZE]) -> V) -> Result<V, StreamReadError<Self::ReadError>> {
+    if self.behind == 0 {
+      let history = &mut self.history;
+
+      return self
+        .stream
+        .read(async |items: &[S::Type; SIZE]| {
+          for item in items {
+            history.push(item.clone());
+          }
+
+          reader(items).await
+        })
+        .await;
+    }
+
+    let offset = self.offset();
+    let mut items: [Option<S::Type>; SIZE] = [const { None }; SIZE];
+
+    for (index, slot) in items.iter_mut().enumerate() {
+      match self.next_element().await {
+        Ok(item) => *slot = Some(item),
+        Err(error) => {
+          // everything read so far is in the history, unless there were more than it holds
+          self.behind = index.min(self.history.len());
+
+          return Err(match error {
+            StreamReadError::StreamExhausted(_) => StreamReadError::StreamExhausted(StreamExhaustedError {
+              stream_length: offset + index as u64,
+              read_length: SIZE as u64,
+              read_offset: offset,
+            }),
+            error => error,
+          });
+        }
+      }
+    }
+
+    Ok(reader(&items.map(|item| item.expect("Every element was read"))).await)
+  }
+
+  async fn skip(&mut self, size: u64) -> Result<(), StreamSkipError<Self::SkipError>> {
+    let seek_point = match self.len() {
+      Some(length) => StreamSkipError::assert_relative_forwards(length, self.offset(), size)?,
+      None => self.offset().saturating_add(size),
+    };
+
+    let replayed = size.min(self.behind as u64);
+    let mut rest = size - replayed;
+
+    self.behind -= replayed as usize;
+
+    // anything further back than the history holds would be pushed out of it by the rest
+    if rest > CAP as u64 {
+      self.stream.skip(rest - CAP as u64).await.map_err(|error| error.map_user(EitherError::First))?;
+      self.history.clear();
+      rest = CAP as u64;
+    }
+
+    for _ in 0..rest {
+      self.next_element().await.map_err(|error| match error {
+        StreamReadError::User(error) => StreamSkipError::User(EitherError::Second(SkipReadError::Read(error))),
+        StreamReadError::StreamExhausted(_) => StreamSkipError::OutOfBounds(StreamSeekOutOfBoundsError {
+          stream_length: self.offset(),
+          seek_point,
+        }),
+      })?;
+    }
+
+    Ok(())
+  }
+}
+
+impl<S: ReadableStream, const CAP: usize> RewindableStream for Buffered<S, CAP>
+where
+  S::Type: Clone,
+{
+  type RewindError = BufferedHistoryError;
+
+  async fn rewind(&mut self, size: u64) -> Result<(), StreamRewindError<Self::RewindError>> {
+    StreamRewindError::assert_relative_backwards(self.len().unwrap_or(self.offset()), self.offset(), size)?;
+
+    self.go_back(size).map_err(StreamRewindError::User)
+  }
+}
+
+impl<S: ReadableStream, const CAP: usize> RestorableStream for Buffered<S, CAP>
+where
+  S::Type: Clone,
+{
+  type Snapshot = u64;
+  type RestoreError = BufferedHistoryError;
+
+  fn snapshot(&self) -> Self::Snapshot {
+    self.offset()
+  }
+
+  async fn restore(&mut self, snapshot: Self::Snapshot) -> Result<(), StreamRestoreError<Self::RestoreError>> {
+    let distance = self.offset().checked_sub(snapshot).ok_or(StreamRestoreError::CannotRestoreForwards)?;
+
+    self.go_back(distance).map_err(StreamRestoreError::User)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::vec::Vec;
+
+  use crate::{
+    binary_reader::{endianness::Endianness, BinaryReader, PrimitiveReader},
+    provider::hint::ReadHint,
+    stream::{
+      builtin::provider::ProviderStream,
+      error::{stream_read::StreamReadError, stream_rewind::StreamRewindError},
+      extensions::readable::ReadableStreamExt,
+      ReadableStream, RewindableStream,
+    },
+  };
+
+  #[tokio::test]
+  async fn rewinds_a_forward_only_stream_within_its_history() {
+    let data = b"hello world\0rest".to_vec();
+    let mut stream = ProviderStream::new(data, ReadHint::new()).read_until(0).buffered::<8>();
+
+    assert_eq!(&stream.read(async |bytes: &[u8; 6]| *bytes).await.unwrap(), b"hello ");
+
+    stream.rewind(3).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.offset(), 3);
+    assert_eq!(&stream.read(async |bytes: &[u8; 5]| *bytes).await.unwrap(), b"lo wo");
+    assert_eq!(stream.next().await.unwrap(), b'r');
+
+    let error = stream
+      .rewind(9)
+      .await
+      .map_err(|error| matches!(error, StreamRewindError::User(history) if history.distance == 9 && history.buffered == 8));
+    assert!(matches!(error, Err(true)));
+
+    // replayed elements aren't lost when the stream runs out behind them
+    stream.rewind(3).await.map_err(|_| {}).unwrap();
+
+    let error = stream.read(async |bytes: &[u8; 6]| *bytes).await.unwrap_err();
+    assert!(matches!(error, StreamReadError::StreamExhausted(ref exhausted) if exhausted.stream_length == 11 && exhausted.read_offset == 6));
+    assert_eq!(&stream.read(async |bytes: &[u8; 5]| *bytes).await.unwrap(), b"world");
+  }
+
+  #[tokio::test]
+  async fn keeps_its_history_across_skips() {
+    let data: Vec<u8> = (0..32).collect();
+    let mut stream = ProviderStream::new(data, ReadHint::new()).buffered::<8>();
+
+    stream.skip(3).await.map_err(|_| {}).unwrap();
+    stream.rewind(3).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.next().await.unwrap(), 0);
+
+    stream.skip(20).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.history(), 8);
+
+    stream.rewind(8).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.next().await.unwrap(), 13);
+  }
+
+  #[tokio::test]
+  async fn lets_a_reader_restore_a_forward_only_stream() {
+    let data = b"\x01\x02\x03\x04\x05\x06\0".to_vec();
+    let stream = ProviderStream::new(data, ReadHint::new()).read_until(0).buffered::<16>();
+    let mut reader = BinaryReader::new(stream, Endianness::LittleEndian);
+
+    let snapshot = reader.snapshot();
+
+    assert_eq!(reader.get::<u32>().await.map_err(|_| {}).unwrap(), 0x04030201);
+    reader.restore(snapshot).await.map_err(|_| {}).unwrap();
+    assert_eq!(reader.get::<u16>().await.map_err(|_| {}).unwrap(), 0x0201);
+  }
+}
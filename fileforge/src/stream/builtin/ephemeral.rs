@@ -1 +1,298 @@
-pub struct EphemeralStream {}
+use alloc::vec::Vec;
+use core::convert::Infallible;
+
+use crate::{
+  control_flow::ControlFlow,
+  stream::{
+    error::{
+      stream_exhausted::StreamExhaustedError, stream_mutate::StreamMutateError, stream_overwrite::StreamOverwriteError, stream_partition::StreamPartitionError, stream_read::StreamReadError,
+      stream_restore::StreamRestoreError, stream_rewind::StreamRewindError, stream_seek::StreamSeekError, stream_seek_out_of_bounds::StreamSeekOutOfBoundsError, stream_skip::StreamSkipError,
+    },
+    DynamicPartitionableStream, MutableStream, ReadableStream, ResizableStream, RestorableStream, RewindableStream, SeekableStream, StaticPartitionableStream,
+  },
+};
+
+/// A stream over an owned buffer in memory, which grows and shrinks as it's overwritten.
+///
+/// It implements every stream trait directly, with nothing that can fail besides running off the
+/// end, so it's the simplest place to write encoder output to, and the stream the other streams'
+/// behaviour is checked against. Partitioning splits the buffer into two streams of their own.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EphemeralStream<T> {
+  data: Vec<T>,
+  offset: u64,
+}
+
+impl<T> EphemeralStream<T> {
+  pub fn new() -> Self {
+    Self::from(Vec::new())
+  }
+
+  pub fn as_slice(&self) -> &[T] {
+    &self.data
+  }
+
+  pub fn into_inner(self) -> Vec<T> {
+    self.data
+  }
+
+  fn length(&self) -> u64 {
+    self.data.len() as u64
+  }
+
+  fn split(mut self, at: u64, read_length: u64) -> Result<(Self, Self), StreamPartitionError<Infallible>> {
+    StreamExhaustedError::assert(self.length(), self.offset, read_length).map_err(StreamPartitionError::StreamExhausted)?;
+
+    let right = Self::from(self.data.split_off(at as usize));
+
+    Ok((self, right))
+  }
+}
+
+impl<T> Default for EphemeralStream<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T> From<Vec<T>> for EphemeralStream<T> {
+  fn from(data: Vec<T>) -> Self {
+    Self { data, offset: 0 }
+  }
+}
+
+impl<T> ReadableStream for EphemeralStream<T> {
+  type Type = T;
+  type ReadError = Infallible;
+  type SkipError = Infallible;
+
+  fn len(&self) -> Option<u64> {
+    Some(self.length())
+  }
+  fn offset(&self) -> u64 {
+    self.offset
+  }
+
+  async fn read<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[T; SIZE]) -> V) -> Result<V, StreamReadError<Self::ReadError>> {
+    StreamExhaustedError::assert(self.length(), self.offset, SIZE as u64)?;
+
+    let start = self.offset as usize;
+    let items: &[T; SIZE] = self.data[start..start + SIZE].try_into().expect("Slice is SIZE elements long");
+    let value = reader(items).await;
+
+    self.offset += SIZE as u64;
+    Ok(value)
+  }
+
+  async fn skip(&mut self, size: u64) -> Result<(), StreamSkipError<Self::SkipError>> {
+    self.offset = StreamSkipError::assert_relative_forwards(self.length(), self.offset, size)?;
+    Ok(())
+  }
+}
+
+impl<T> RewindableStream for EphemeralStream<T> {
+  type RewindError = Infallible;
+
+  async fn rewind(&mut self, size: u64) -> Result<(), StreamRewindError<Self::RewindError>> {
+    self.offset = StreamRewindError::assert_relative_backwards(self.length(), self.offset, size)?;
+    Ok(())
+  }
+}
+
+impl<T> SeekableStream for EphemeralStream<T> {
+  type SeekError = Infallible;
+
+  async fn seek(&mut self, offset: u64) -> Result<(), StreamSeekError<Self::SeekError>> {
+    StreamSeekOutOfBoundsError::assert(self.length(), offset)?;
+
+    self.offset = offset;
+    Ok(())
+  }
+}
+
+impl<T> MutableStream for EphemeralStream<T> {
+  type MutateError = Infallible;
+
+  async fn mutate<const SIZE: usize, V: ControlFlow>(&mut self, mutator: impl AsyncFnOnce(&mut [T; SIZE]) -> V) -> Result<V, StreamMutateError<Self::MutateError>> {
+    StreamExhaustedError::assert(self.length(), self.offset, SIZE as u64).map_err(StreamMutateError::StreamExhausted)?;
+
+    let start = self.offset as usize;
+    let items: &mut [T; SIZE] = (&mut self.data[start..start + SIZE]).try_into().expect("Slice is SIZE elements long");
+    let value = mutator(items).await;
+
+    self.offset += SIZE as u64;
+    Ok(value)
+  }
+}
+
+impl<T> ResizableStream for EphemeralStream<T> {
+  type OverwriteError = Infallible;
+
+  async fn overwrite<const SIZE: usize>(&mut self, length: u64, data: [T; SIZE]) -> Result<(), StreamOverwriteError<Self::OverwriteError>> {
+    StreamExhaustedError::assert(self.length(), self.offset, length).map_err(StreamOverwriteError::StreamExhausted)?;
+
+    let start = self.offset as usize;
+
+    self.data.splice(start..start + length as usize, data);
+    self.offset += SIZE as u64;
+    Ok(())
+  }
+}
+
+impl<T, const SIZE: usize> StaticPartitionableStream<SIZE> for EphemeralStream<T> {
+  type PartitionError = Infallible;
+  type PartitionLeft = Self;
+  type PartitionRight = Self;
+
+  async fn partition(self) -> Result<(Self::PartitionLeft, Self::PartitionRight), StreamPartitionError<Self::PartitionError>> {
+    let at = self.offset + SIZE as u64;
+
+    self.split(at, SIZE as u64)
+  }
+}
+
+impl<T> DynamicPartitionableStream for EphemeralStream<T> {
+  type PartitionError = Infallible;
+  type PartitionDynamicLeft = Self;
+  type PartitionDynamicRight = Self;
+
+  async fn partition_dynamic(self, size: u64) -> Result<(Self::PartitionDynamicLeft, Self::PartitionDynamicRight), StreamPartitionError<Self::PartitionError>> {
+    let at = self.offset.checked_add(size).ok_or(StreamPartitionError::StreamExhausted(StreamExhaustedError {
+      stream_length: self.length(),
+      read_length: size,
+      read_offset: self.offset,
+    }))?;
+
+    self.split(at, size)
+  }
+}
+
+impl<T> RestorableStream for EphemeralStream<T> {
+  type Snapshot = u64;
+  type RestoreError = Infallible;
+
+  fn snapshot(&self) -> Self::Snapshot {
+    self.offset
+  }
+
+  async fn restore(&mut self, snapshot: Self::Snapshot) -> Result<(), StreamRestoreError<Self::RestoreError>> {
+    if snapshot > self.offset {
+      return Err(StreamRestoreError::CannotRestoreForwards);
+    }
+
+    self.offset = snapshot;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::{string::String, vec, vec::Vec};
+
+  use super::EphemeralStream;
+  use crate::{
+    stream::{
+      error::{stream_partition::StreamPartitionError, stream_read::StreamReadError},
+      extensions::readable::ReadableStreamExt,
+      DynamicPartitionableStream, MutableStream, ReadableStream, ResizableStream, RestorableStream, RewindableStream, SeekableStream, StaticPartitionableStream,
+    },
+    testing,
+  };
+
+  #[tokio::test]
+  async fn reads_and_moves_around() {
+    let mut stream = EphemeralStream::from((0..8).collect::<Vec<u8>>());
+
+    assert_eq!(stream.read(async |values: &[u8; 3]| *values).await.unwrap(), [0, 1, 2]);
+    stream.skip(2).await.unwrap();
+    assert_eq!(stream.next().await.unwrap(), 5);
+
+    assert!(matches!(stream.read(async |values: &[u8; 3]| *values).await, Err(StreamReadError::StreamExhausted(_))));
+    assert!(stream.skip(3).await.is_err());
+
+    stream.rewind(6).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.next().await.unwrap(), 0);
+
+    stream.seek(7).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.next().await.unwrap(), 7);
+    assert!(stream.seek(9).await.is_err());
+
+    stream.seek(2).await.map_err(|_| {}).unwrap();
+
+    let snapshot = stream.snapshot();
+
+    stream.skip(3).await.unwrap();
+    stream.restore(snapshot).await.unwrap();
+    assert_eq!(stream.next().await.unwrap(), 2);
+    assert!(stream.restore(snapshot + 3).await.is_err());
+  }
+
+  #[tokio::test]
+  async fn holds_elements_that_arent_copy() {
+    let mut stream = EphemeralStream::from(vec![String::from("a"), String::from("b")]);
+
+    stream
+      .mutate(async |values: &mut [String; 1]| {
+        values[0].push('!');
+      })
+      .await
+      .unwrap();
+
+    stream.overwrite(1, [String::from("c"), String::from("d")]).await.unwrap();
+
+    assert_eq!(stream.offset(), 3);
+    assert_eq!(stream.into_inner(), ["a!", "c", "d"]);
+  }
+
+  #[tokio::test]
+  async fn grows_and_shrinks_when_overwritten() {
+    let mut stream = EphemeralStream::new();
+
+    stream.overwrite(0, *b"hello").await.unwrap();
+    stream.overwrite(0, *b" world").await.unwrap();
+    assert_eq!(stream.as_slice(), b"hello world");
+
+    stream.seek(0).await.map_err(|_| {}).unwrap();
+    stream.overwrite(5, *b"bye").await.unwrap();
+    assert_eq!(stream.as_slice(), b"bye world");
+    assert_eq!(stream.offset(), 3);
+
+    assert!(stream.overwrite(7, [0u8; 1]).await.is_err());
+  }
+
+  #[tokio::test]
+  async fn partitions_into_two_streams() {
+    let mut stream = EphemeralStream::from((0..8).collect::<Vec<u8>>());
+    stream.skip(2).await.unwrap();
+
+    let (mut left, mut right) = StaticPartitionableStream::<3>::partition(stream).await.map_err(|_| {}).unwrap();
+
+    assert_eq!(left.len(), Some(5));
+    assert_eq!(left.read(async |values: &[u8; 3]| *values).await.unwrap(), [2, 3, 4]);
+    assert_eq!(right.offset(), 0);
+    assert_eq!(right.as_slice(), [5, 6, 7]);
+
+    right.skip(1).await.unwrap();
+
+    let (left, right) = right.partition_dynamic(1).await.map_err(|_| {}).unwrap();
+
+    assert_eq!(left.as_slice(), [5, 6]);
+    assert_eq!(right.as_slice(), [7]);
+    assert!(matches!(right.partition_dynamic(2).await, Err(StreamPartitionError::StreamExhausted(_))));
+  }
+
+  #[tokio::test]
+  async fn conforms_to_the_stream_contracts() {
+    let data = (1..=8).collect::<Vec<u8>>();
+    let factory = async || EphemeralStream::from(data.clone());
+
+    testing::stream::readable(&factory, &data).await;
+    testing::stream::rewindable(&factory, &data).await;
+    testing::stream::seekable(&factory, &data).await;
+    testing::stream::restorable(&factory, &data).await;
+    testing::stream::mutable(&factory, &data).await;
+    testing::stream::resizable(&factory, &data).await;
+    testing::stream::static_partitionable::<3, _>(&factory, &data).await;
+    testing::stream::dynamic_partitionable(&factory, &data).await;
+  }
+}
@@ -0,0 +1,173 @@
+use core::{
+  future::Future,
+  mem,
+  pin::Pin,
+  task::{Context, Poll},
+};
+use std::{boxed::Box, io, vec::Vec};
+
+use crate::stream::ReadableStream;
+
+use super::{error::StreamUserError, reader::read_into};
+
+type ReadFuture<'s, S> = Pin<Box<dyn Future<Output = (S, Vec<u8>, usize, Option<<S as ReadableStream>::ReadError>)> + 's>>;
+
+enum State<'s, S: ReadableStream> {
+  Idle(S),
+  Reading(ReadFuture<'s, S>),
+}
+
+/// An async reader over a byte stream, implementing [`tokio::io::AsyncRead`] and
+/// [`futures_io::AsyncRead`] for whichever of those features are enabled.
+///
+/// The stream is moved into each read while it's waiting, and back out once it's done; bytes it
+/// read beyond what the caller had room for are kept for the next call. Errors work like they do
+/// for [`IoReader`](super::reader::IoReader): the stream's error waits in
+/// [`AsyncIoReader::take_error`], and reads fail with a [`StreamUserError`] until it's taken.
+pub struct AsyncIoReader<'s, S: ReadableStream> {
+  state: Option<State<'s, S>>,
+  buffer: Vec<u8>,
+  position: usize,
+  error: Option<S::ReadError>,
+}
+
+// the stream is only ever moved, never pinned
+impl<S: ReadableStream> Unpin for AsyncIoReader<'_, S> {}
+
+impl<'s, S: ReadableStream<Type = u8> + 's> AsyncIoReader<'s, S> {
+  pub fn new(stream: S) -> Self {
+    Self {
+      state: Some(State::Idle(stream)),
+      buffer: Vec::new(),
+      position: 0,
+      error: None,
+    }
+  }
+
+  pub fn take_error(&mut self) -> Option<S::ReadError> {
+    self.error.take()
+  }
+
+  /// Gives back the stream, unless a read of it was left waiting. Bytes it read that were never
+  /// returned are lost.
+  pub fn into_inner(self) -> Option<S> {
+    match self.state {
+      Some(State::Idle(stream)) => Some(stream),
+      _ => None,
+    }
+  }
+
+  fn poll_read_into(&mut self, context: &mut Context<'_>, out: &mut [u8]) -> Poll<io::Result<usize>> {
+    loop {
+      if self.position < self.buffer.len() {
+        let length = out.len().min(self.buffer.len() - self.position);
+
+        out[..length].copy_from_slice(&self.buffer[self.position..][..length]);
+        self.position += length;
+        return Poll::Ready(Ok(length));
+      }
+
+      if self.error.is_some() {
+        return Poll::Ready(Err(io::Error::other(StreamUserError)));
+      }
+
+      if out.is_empty() {
+        return Poll::Ready(Ok(0));
+      }
+
+      let mut future = match self.state.take().expect("Reads only leave the state empty while running") {
+        State::Reading(future) => future,
+        State::Idle(mut stream) => {
+          let mut buffer = mem::take(&mut self.buffer);
+          buffer.resize(out.len(), 0);
+
+          Box::pin(async move {
+            let (filled, error) = read_into(&mut stream, &mut buffer).await;
+            (stream, buffer, filled, error)
+          }) as ReadFuture<'s, S>
+        }
+      };
+
+      let Poll::Ready((stream, mut buffer, filled, error)) = future.as_mut().poll(context) else {
+        self.state = Some(State::Reading(future));
+        return Poll::Pending;
+      };
+
+      buffer.truncate(filled);
+      self.state = Some(State::Idle(stream));
+      self.buffer = buffer;
+      self.position = 0;
+      self.error = error;
+
+      if filled == 0 && self.error.is_none() {
+        return Poll::Ready(Ok(0));
+      }
+    }
+  }
+}
+
+#[cfg(feature = "tokio")]
+impl<'s, S: ReadableStream<Type = u8> + 's> tokio::io::AsyncRead for AsyncIoReader<'s, S> {
+  fn poll_read(self: Pin<&mut Self>, context: &mut Context<'_>, buffer: &mut tokio::io::ReadBuf<'_>) -> Poll<io::Result<()>> {
+    let filled = core::task::ready!(self.get_mut().poll_read_into(context, buffer.initialize_unfilled()))?;
+
+    buffer.advance(filled);
+    Poll::Ready(Ok(()))
+  }
+}
+
+#[cfg(feature = "futures-io")]
+impl<'s, S: ReadableStream<Type = u8> + 's> futures_io::AsyncRead for AsyncIoReader<'s, S> {
+  fn poll_read(self: Pin<&mut Self>, context: &mut Context<'_>, buffer: &mut [u8]) -> Poll<io::Result<usize>> {
+    self.get_mut().poll_read_into(context, buffer)
+  }
+}
+
+#[cfg(all(test, feature = "tokio"))]
+mod tests {
+  use std::vec::Vec;
+
+  use tokio::io::AsyncReadExt;
+
+  use crate::{
+    provider::hint::ReadHint,
+    stream::builtin::{io::tokio_stream::TokioIoStream, provider::ProviderStream},
+    stream::ReadableStream,
+  };
+
+  use super::AsyncIoReader;
+
+  #[tokio::test]
+  async fn round_trips_a_stream_through_tokio() {
+    let data: Vec<u8> = (0..0x2345u32).map(|index| (index ^ (index >> 8)) as u8).collect();
+    let mut reader = AsyncIoReader::new(ProviderStream::new(data.clone(), ReadHint::new()));
+
+    let mut head = [0; 3];
+    reader.read_exact(&mut head).await.unwrap();
+    assert_eq!(head, data[..3]);
+
+    let mut stream = TokioIoStream::new(reader);
+    let mut rest = Vec::new();
+
+    while let Ok(byte) = stream.read(async |byte: &[u8; 1]| byte[0]).await {
+      rest.push(byte);
+    }
+
+    assert_eq!(rest, data[3..]);
+  }
+
+  #[tokio::test]
+  async fn tokio_files_read_as_streams() {
+    let path = std::env::temp_dir().join(std::format!("fileforge-tokio-io-stream-{}", std::process::id()));
+    std::fs::write(&path, b"0123456789").unwrap();
+
+    let file = tokio::fs::File::open(&path).await.unwrap();
+    let mut stream = TokioIoStream::seekable(file).await.unwrap();
+
+    stream.skip(4).await.unwrap();
+    assert_eq!(stream.read(async |bytes: &[u8; 3]| *bytes).await.unwrap(), *b"456");
+    assert_eq!(stream.remaining(), Some(3));
+
+    std::fs::remove_file(path).unwrap();
+  }
+}
@@ -0,0 +1,162 @@
+use std::{io, vec::Vec};
+
+use crate::stream::error::{
+  stream_exhausted::StreamExhaustedError, stream_read::StreamReadError, stream_rewind::StreamRewindError, stream_seek::StreamSeekError, stream_seek_out_of_bounds::StreamSeekOutOfBoundsError,
+  stream_skip::StreamSkipError,
+};
+
+use super::error::IoStreamError;
+
+/// Smallest amount asked of the reader whenever the buffer runs dry.
+pub const BUFFER_SIZE: usize = 0x1000;
+
+/// The position and read-ahead buffer shared by the streams over readers, which only differ in
+/// how they call the reader.
+///
+/// Readers can return fewer bytes than asked for, and can't give bytes back, so everything read
+/// lands in the buffer and only leaves it once the stream has consumed it. A read that reaches
+/// the end of the reader partway through leaves what it got buffered, and reports the stream as
+/// exhausted rather than failed.
+pub(super) struct IoState {
+  buffer: Vec<u8>,
+  position: usize,
+  offset: u64,
+  length: Option<u64>,
+}
+
+impl IoState {
+  pub(super) fn new(offset: u64, length: Option<u64>) -> Self {
+    Self {
+      buffer: Vec::new(),
+      position: 0,
+      offset,
+      length,
+    }
+  }
+
+  pub(super) fn offset(&self) -> u64 {
+    self.offset
+  }
+
+  pub(super) fn length(&self) -> Option<u64> {
+    self.length
+  }
+
+  fn available(&self) -> usize {
+    self.buffer.len() - self.position
+  }
+
+  fn clear(&mut self) {
+    self.buffer.clear();
+    self.position = 0;
+  }
+
+  /// Reads until `size` bytes are buffered, returning `false` if the reader ends first.
+  async fn fill(&mut self, size: usize, mut read: impl AsyncFnMut(&mut [u8]) -> io::Result<usize>) -> io::Result<bool> {
+    if self.available() >= size {
+      return Ok(true);
+    }
+
+    self.buffer.drain(..self.position);
+    self.position = 0;
+
+    while self.buffer.len() < size {
+      let filled = self.buffer.len();
+      self.buffer.resize(filled + (size - filled).max(BUFFER_SIZE), 0);
+
+      let result = read(&mut self.buffer[filled..]).await;
+      self.buffer.truncate(filled + *result.as_ref().unwrap_or(&0));
+
+      match result {
+        Ok(0) => return Ok(false),
+        Ok(_) => {}
+        Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+        Err(error) => return Err(error),
+      }
+    }
+
+    Ok(true)
+  }
+
+  pub(super) async fn read<const SIZE: usize, V>(
+    &mut self,
+    read: impl AsyncFnMut(&mut [u8]) -> io::Result<usize>,
+    reader: impl AsyncFnOnce(&[u8; SIZE]) -> V,
+  ) -> Result<V, StreamReadError<IoStreamError>> {
+    if !self.fill(SIZE, read).await.map_err(|error| StreamReadError::User(IoStreamError::Read(error)))? {
+      return Err(StreamReadError::StreamExhausted(StreamExhaustedError {
+        stream_length: self.offset + self.available() as u64,
+        read_length: SIZE as u64,
+        read_offset: self.offset,
+      }));
+    }
+
+    let value = reader(self.buffer[self.position..][..SIZE].try_into().unwrap()).await;
+
+    self.position += SIZE;
+    self.offset += SIZE as u64;
+    Ok(value)
+  }
+
+  /// Skips by reading and dropping bytes, so a skip past the end of a reader of unknown length
+  /// still consumes everything up to it.
+  pub(super) async fn skip(&mut self, size: u64, mut read: impl AsyncFnMut(&mut [u8]) -> io::Result<usize>) -> Result<(), StreamSkipError<IoStreamError>> {
+    let seek_point = match self.length {
+      Some(length) => StreamSkipError::assert_relative_forwards(length, self.offset, size)?,
+      None => self.offset.checked_add(size).ok_or(StreamSkipError::SeekPointOverflowed {
+        stream_length: self.offset,
+        offset: self.offset,
+        seek_forwards_distance: size,
+      })?,
+    };
+
+    while self.offset < seek_point {
+      if !self.fill(1, &mut read).await.map_err(|error| StreamSkipError::User(IoStreamError::Read(error)))? {
+        return Err(StreamSkipError::OutOfBounds(StreamSeekOutOfBoundsError {
+          stream_length: self.offset,
+          seek_point,
+        }));
+      }
+
+      let step = (seek_point - self.offset).min(self.available() as u64);
+
+      self.position += step as usize;
+      self.offset += step;
+    }
+
+    Ok(())
+  }
+
+  /// Moves back within the buffer if the bytes are still there, and seeks the reader otherwise.
+  pub(super) async fn rewind(&mut self, size: u64, seek: impl AsyncFnOnce(u64) -> io::Result<u64>) -> Result<(), StreamRewindError<IoStreamError>> {
+    let seek_point = StreamRewindError::assert_relative_backwards(self.length.unwrap_or(self.offset), self.offset, size)?;
+
+    if size <= self.position as u64 {
+      self.position -= size as usize;
+    } else {
+      seek(seek_point).await.map_err(|error| StreamRewindError::User(IoStreamError::Seek(error)))?;
+      self.clear();
+    }
+
+    self.offset = seek_point;
+    Ok(())
+  }
+
+  pub(super) async fn seek(&mut self, offset: u64, seek: impl AsyncFnOnce(u64) -> io::Result<u64>) -> Result<(), StreamSeekError<IoStreamError>> {
+    if let Some(length) = self.length {
+      StreamSeekOutOfBoundsError::assert(length, offset)?;
+    }
+
+    if offset >= self.offset && offset - self.offset <= self.available() as u64 {
+      self.position += (offset - self.offset) as usize;
+    } else if offset < self.offset && self.offset - offset <= self.position as u64 {
+      self.position -= (self.offset - offset) as usize;
+    } else {
+      seek(offset).await.map_err(|error| StreamSeekError::User(IoStreamError::Seek(error)))?;
+      self.clear();
+    }
+
+    self.offset = offset;
+    Ok(())
+  }
+}
@@ -0,0 +1,36 @@
+use core::fmt::{self, Display};
+
+use fileforge_macros::FileforgeError;
+
+use crate::stream::error::{user_read::UserReadError, user_rewind::UserRewindError, user_seek::UserSeekError, user_skip::UserSkipError};
+
+/// An error from the reader under an [`IoStream`](super::stream::IoStream) or one of its async
+/// counterparts. Running out of bytes isn't one: that's reported as the stream being exhausted.
+#[derive(Debug, FileforgeError)]
+pub enum IoStreamError {
+  #[report(&"Failed to read from the reader")]
+  Read(std::io::Error),
+
+  #[report(&"Failed to seek the reader")]
+  Seek(std::io::Error),
+}
+
+impl UserReadError for IoStreamError {}
+impl UserSkipError for IoStreamError {}
+impl UserRewindError for IoStreamError {}
+impl UserSeekError for IoStreamError {}
+
+/// Carried by the [`std::io::Error`] an [`IoReader`](super::reader::IoReader) or
+/// [`AsyncIoReader`](super::async_reader::AsyncIoReader) returns when its stream fails, as stream
+/// errors needn't be `Send` or `'static`. The stream's own error is kept by the reader until it's
+/// taken with `take_error`, or `take_seek_error` for a failed seek.
+#[derive(Debug)]
+pub struct StreamUserError;
+
+impl Display for StreamUserError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("the stream failed; its error is held by the reader")
+  }
+}
+
+impl std::error::Error for StreamUserError {}
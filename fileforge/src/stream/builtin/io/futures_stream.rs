@@ -0,0 +1,122 @@
+use core::{future::poll_fn, pin::Pin};
+use std::io::{self, SeekFrom};
+
+use futures_io::{AsyncRead, AsyncSeek};
+
+use crate::stream::{
+  error::{stream_read::StreamReadError, stream_rewind::StreamRewindError, stream_seek::StreamSeekError, stream_skip::StreamSkipError},
+  ReadableStream, RewindableStream, SeekableStream,
+};
+
+use super::{buffer::IoState, error::IoStreamError};
+
+async fn read<R: AsyncRead + Unpin>(reader: &mut R, buffer: &mut [u8]) -> io::Result<usize> {
+  poll_fn(|context| Pin::new(&mut *reader).poll_read(context, buffer)).await
+}
+
+async fn seek<R: AsyncSeek + Unpin>(reader: &mut R, position: SeekFrom) -> io::Result<u64> {
+  poll_fn(|context| Pin::new(&mut *reader).poll_seek(context, position)).await
+}
+
+/// A stream of the bytes of a [`futures_io::AsyncRead`], which rewinds and seeks over an
+/// [`AsyncSeek`] reader made with [`FuturesIoStream::seekable`]. It works like
+/// [`IoStream`](super::stream::IoStream), but waits on the reader rather than blocking.
+pub struct FuturesIoStream<R> {
+  reader: R,
+  state: IoState,
+}
+
+impl<R: AsyncRead + Unpin> FuturesIoStream<R> {
+  /// A stream starting at offset 0, wherever the reader is, with no known length.
+  pub fn new(reader: R) -> Self {
+    Self { reader, state: IoState::new(0, None) }
+  }
+
+  /// Gives back the reader, which may be past [`offset`](ReadableStream::offset) by whatever
+  /// the stream had buffered.
+  pub fn into_inner(self) -> R {
+    self.reader
+  }
+}
+
+impl<R: AsyncRead + AsyncSeek + Unpin> FuturesIoStream<R> {
+  /// A stream whose offsets are positions in the reader, starting where the reader is.
+  pub async fn seekable(mut reader: R) -> io::Result<Self> {
+    let offset = seek(&mut reader, SeekFrom::Current(0)).await?;
+    let length = seek(&mut reader, SeekFrom::End(0)).await?;
+
+    seek(&mut reader, SeekFrom::Start(offset)).await?;
+
+    Ok(Self {
+      reader,
+      state: IoState::new(offset, Some(length)),
+    })
+  }
+}
+
+impl<R: AsyncRead + Unpin> ReadableStream for FuturesIoStream<R> {
+  type Type = u8;
+
+  type ReadError = IoStreamError;
+  type SkipError = IoStreamError;
+
+  fn len(&self) -> Option<u64> {
+    self.state.length()
+  }
+  fn offset(&self) -> u64 {
+    self.state.offset()
+  }
+
+  async fn read<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[u8; SIZE]) -> V) -> Result<V, StreamReadError<Self::ReadError>> {
+    self.state.read(async |buffer: &mut [u8]| read(&mut self.reader, buffer).await, reader).await
+  }
+
+  async fn skip(&mut self, size: u64) -> Result<(), StreamSkipError<Self::SkipError>> {
+    self.state.skip(size, async |buffer: &mut [u8]| read(&mut self.reader, buffer).await).await
+  }
+}
+
+impl<R: AsyncRead + AsyncSeek + Unpin> RewindableStream for FuturesIoStream<R> {
+  type RewindError = IoStreamError;
+
+  async fn rewind(&mut self, size: u64) -> Result<(), StreamRewindError<Self::RewindError>> {
+    self.state.rewind(size, async |offset| seek(&mut self.reader, SeekFrom::Start(offset)).await).await
+  }
+}
+
+impl<R: AsyncRead + AsyncSeek + Unpin> SeekableStream for FuturesIoStream<R> {
+  type SeekError = IoStreamError;
+
+  async fn seek(&mut self, offset: u64) -> Result<(), StreamSeekError<Self::SeekError>> {
+    self.state.seek(offset, async |offset| seek(&mut self.reader, SeekFrom::Start(offset)).await).await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::vec::Vec;
+
+  use crate::{
+    provider::hint::ReadHint,
+    stream::{
+      builtin::{io::async_reader::AsyncIoReader, provider::ProviderStream},
+      error::stream_read::StreamReadError,
+      ReadableStream,
+    },
+  };
+
+  use super::FuturesIoStream;
+
+  #[tokio::test]
+  async fn round_trips_a_stream_through_futures_io() {
+    let data: Vec<u8> = (0..0x1234u32).map(|index| (index * 5) as u8).collect();
+    let mut stream = FuturesIoStream::new(AsyncIoReader::new(ProviderStream::new(data.clone(), ReadHint::new())));
+
+    assert_eq!(stream.read(async |bytes: &[u8; 0x1000]| bytes[..].to_vec()).await.unwrap(), data[..0x1000]);
+
+    let error = stream.read(async |bytes: &[u8; 0x300]| bytes[..].to_vec()).await.unwrap_err();
+    assert!(matches!(error, StreamReadError::StreamExhausted(ref exhausted) if exhausted.stream_length == 0x1234));
+
+    assert_eq!(stream.read(async |bytes: &[u8; 0x234]| bytes[..].to_vec()).await.unwrap(), data[0x1000..]);
+  }
+}
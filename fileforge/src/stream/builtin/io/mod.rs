@@ -0,0 +1,17 @@
+//! Adapters between byte streams and the readers of [`std::io`], tokio and `futures-io`.
+//!
+//! [`IoStream`](stream::IoStream) and its async counterparts read a stream out of a reader;
+//! [`IoReader`](reader::IoReader) and [`AsyncIoReader`](async_reader::AsyncIoReader) go the
+//! other way. In both directions, the end of a reader and a stream running out of bytes are the
+//! same thing, and failures on one side are failures on the other.
+
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+pub mod async_reader;
+mod buffer;
+pub mod error;
+#[cfg(feature = "futures-io")]
+pub mod futures_stream;
+pub mod reader;
+pub mod stream;
+#[cfg(feature = "tokio")]
+pub mod tokio_stream;
@@ -0,0 +1,255 @@
+use core::{
+  convert::Infallible,
+  future::Future,
+  pin::pin,
+  task::{Context, Poll, Waker},
+};
+use std::{
+  io::{self, Read, Seek, SeekFrom},
+  sync::Arc,
+  task::Wake,
+  thread::{self, Thread},
+};
+
+use crate::stream::{error::stream_read::StreamReadError, error::stream_seek::StreamSeekError, ReadableStream, SeekableStream};
+
+use super::error::StreamUserError;
+
+/// Largest read made of the stream at once when filling a buffer.
+const CHUNK_SIZE: usize = 0x100;
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+  fn wake(self: Arc<Self>) {
+    self.0.unpark();
+  }
+}
+
+/// Runs `future` on the current thread, parking it whenever the future is waiting. Nothing else
+/// runs on the thread meanwhile, so this must not be called on an async runtime's thread.
+fn block_on<F: Future>(future: F) -> F::Output {
+  let mut future = pin!(future);
+  let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+  let mut context = Context::from_waker(&waker);
+
+  loop {
+    match future.as_mut().poll(&mut context) {
+      Poll::Ready(value) => return value,
+      Poll::Pending => thread::park(),
+    }
+  }
+}
+
+/// Reads from `stream` until `buffer` is full or the stream is exhausted, returning how much was
+/// read, and the error that stopped it early if there was one.
+pub(super) async fn read_into<S: ReadableStream<Type = u8>>(stream: &mut S, buffer: &mut [u8]) -> (usize, Option<S::ReadError>) {
+  let mut filled = 0;
+
+  while buffer.len() - filled >= CHUNK_SIZE {
+    match stream.read(async |chunk: &[u8; CHUNK_SIZE]| buffer[filled..][..CHUNK_SIZE].copy_from_slice(chunk)).await {
+      Ok(()) => filled += CHUNK_SIZE,
+      Err(StreamReadError::User(error)) => return (filled, Some(error)),
+      // less than a chunk is left, which is read a byte at a time below
+      Err(StreamReadError::StreamExhausted(_)) => break,
+    }
+  }
+
+  while filled < buffer.len() {
+    match stream.read(async |byte: &[u8; 1]| byte[0]).await {
+      Ok(byte) => {
+        buffer[filled] = byte;
+        filled += 1;
+      }
+      Err(StreamReadError::User(error)) => return (filled, Some(error)),
+      Err(StreamReadError::StreamExhausted(_)) => break,
+    }
+  }
+
+  (filled, None)
+}
+
+/// A [`std::io::Read`] over a byte stream, and [`Seek`] when made with [`IoReader::seekable`], for
+/// handing a stream's contents to code that doesn't know about fileforge.
+///
+/// The stream's futures are run to completion by parking the calling thread whenever they wait,
+/// so an `IoReader` must not be used on an async runtime's thread: a stream waiting on that
+/// runtime, such as one over a tokio file, would never be woken. Async code should use
+/// [`AsyncIoReader`](super::async_reader::AsyncIoReader) instead.
+///
+/// A stream running out of bytes is the end of the reader. When the stream fails instead, the
+/// read fails with a [`StreamUserError`], and the stream's error waits in [`IoReader::take_error`];
+/// every read and seek fails until it's taken. Bytes read before a failure are returned first.
+/// Seeks the stream fails work the same way, with the error waiting in
+/// [`IoReader::take_seek_error`].
+pub struct IoReader<S: ReadableStream, E = Infallible> {
+  stream: S,
+  error: Option<S::ReadError>,
+  seek_error: Option<E>,
+}
+
+impl<S: ReadableStream> IoReader<S> {
+  pub fn new(stream: S) -> Self {
+    Self {
+      stream,
+      error: None,
+      seek_error: None,
+    }
+  }
+}
+
+impl<S: SeekableStream> IoReader<S, S::SeekError> {
+  pub fn seekable(stream: S) -> Self {
+    Self {
+      stream,
+      error: None,
+      seek_error: None,
+    }
+  }
+
+  pub fn take_seek_error(&mut self) -> Option<S::SeekError> {
+    self.seek_error.take()
+  }
+}
+
+impl<S: ReadableStream, E> IoReader<S, E> {
+  pub fn take_error(&mut self) -> Option<S::ReadError> {
+    self.error.take()
+  }
+
+  fn assert_no_error(&self) -> io::Result<()> {
+    if self.error.is_some() || self.seek_error.is_some() {
+      return Err(io::Error::other(StreamUserError));
+    }
+
+    Ok(())
+  }
+
+  pub fn stream(&self) -> &S {
+    &self.stream
+  }
+
+  pub fn into_inner(self) -> S {
+    self.stream
+  }
+}
+
+impl<S: ReadableStream<Type = u8>, E> Read for IoReader<S, E> {
+  fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+    self.assert_no_error()?;
+
+    let (filled, error) = block_on(read_into(&mut self.stream, buffer));
+    self.error = error;
+
+    if filled == 0 && self.error.is_some() {
+      return Err(io::Error::other(StreamUserError));
+    }
+
+    Ok(filled)
+  }
+}
+
+impl<S: SeekableStream<Type = u8>> Seek for IoReader<S, S::SeekError> {
+  fn seek(&mut self, position: SeekFrom) -> io::Result<u64> {
+    self.assert_no_error()?;
+
+    let offset = match position {
+      SeekFrom::Start(offset) => Some(offset),
+      SeekFrom::Current(distance) => self.stream.offset().checked_add_signed(distance),
+      SeekFrom::End(distance) => self
+        .stream
+        .len()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Unsupported, "the stream's length is unknown"))?
+        .checked_add_signed(distance),
+    }
+    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "seek to a negative or overflowing position"))?;
+
+    match block_on(self.stream.seek(offset)) {
+      Ok(()) => {}
+      Err(StreamSeekError::OutOfBounds(_)) => return Err(io::Error::new(io::ErrorKind::InvalidInput, "seek past the end of the stream")),
+      Err(StreamSeekError::User(error)) => {
+        self.seek_error = Some(error);
+        return Err(io::Error::other(StreamUserError));
+      }
+    }
+
+    Ok(offset)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{
+    io::{self, Cursor, Read, Seek, SeekFrom},
+    vec,
+    vec::Vec,
+  };
+
+  use crate::{
+    provider::hint::ReadHint,
+    stream::builtin::{
+      io::{
+        error::{IoStreamError, StreamUserError},
+        stream::IoStream,
+      },
+      provider::ProviderStream,
+    },
+  };
+
+  use super::IoReader;
+
+  #[test]
+  fn reads_and_seeks_a_stream() {
+    let data: Vec<u8> = (0..0x321u32).map(|index| (index * 3) as u8).collect();
+    let mut reader = IoReader::seekable(ProviderStream::new(data.clone(), ReadHint::new()));
+    let mut all = Vec::new();
+
+    reader.read_to_end(&mut all).unwrap();
+    assert_eq!(all, data);
+
+    assert_eq!(reader.seek(SeekFrom::End(-0x21)).unwrap(), 0x300);
+
+    let mut tail = vec![0; 0x40];
+    assert_eq!(reader.read(&mut tail).unwrap(), 0x21);
+    assert_eq!(tail[..0x21], data[0x300..]);
+    assert_eq!(reader.read(&mut tail).unwrap(), 0);
+
+    assert_eq!(reader.seek(SeekFrom::Current(-0x321)).unwrap(), 0);
+    assert!(reader.seek(SeekFrom::Current(-1)).is_err());
+    assert!(reader.take_error().is_none());
+    assert!(reader.take_seek_error().is_none());
+  }
+
+  /// Refuses to seek anywhere but the start.
+  struct FailingSeeks(Cursor<Vec<u8>>);
+
+  impl Read for FailingSeeks {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+      self.0.read(buffer)
+    }
+  }
+
+  impl Seek for FailingSeeks {
+    fn seek(&mut self, position: SeekFrom) -> io::Result<u64> {
+      match position {
+        SeekFrom::Start(offset) if offset != 0 => Err(io::Error::other("refused")),
+        _ => self.0.seek(position),
+      }
+    }
+  }
+
+  #[test]
+  fn keeps_seek_errors() {
+    let stream = IoStream::seekable(FailingSeeks(Cursor::new((0..0x10).collect()))).unwrap();
+    let mut reader = IoReader::seekable(stream);
+    let error = reader.seek(SeekFrom::Start(4)).unwrap_err();
+
+    assert!(error.get_ref().is_some_and(|error| error.is::<StreamUserError>()));
+    assert!(reader.read(&mut [0; 4]).is_err());
+    assert!(matches!(reader.take_seek_error(), Some(IoStreamError::Seek(_))));
+
+    let mut head = [0; 4];
+    reader.read_exact(&mut head).unwrap();
+    assert_eq!(head, [0, 1, 2, 3]);
+  }
+}
@@ -0,0 +1,160 @@
+use std::io::{self, Read, Seek, SeekFrom};
+
+use crate::stream::{
+  error::{stream_read::StreamReadError, stream_rewind::StreamRewindError, stream_seek::StreamSeekError, stream_skip::StreamSkipError},
+  ReadableStream, RewindableStream, SeekableStream,
+};
+
+use super::{buffer::IoState, error::IoStreamError};
+
+/// A stream of the bytes of a [`std::io::Read`]. Over a reader that's also [`Seek`], made with
+/// [`IoStream::seekable`], it knows its length and can rewind and seek.
+///
+/// Reads are buffered, so the reader itself runs ahead of [`offset`](ReadableStream::offset).
+pub struct IoStream<R> {
+  reader: R,
+  state: IoState,
+}
+
+impl<R: Read> IoStream<R> {
+  /// A stream starting at offset 0, wherever the reader is, with no known length.
+  pub fn new(reader: R) -> Self {
+    Self { reader, state: IoState::new(0, None) }
+  }
+
+  /// Gives back the reader, which may be past [`offset`](ReadableStream::offset) by whatever
+  /// the stream had buffered.
+  pub fn into_inner(self) -> R {
+    self.reader
+  }
+}
+
+impl<R: Read + Seek> IoStream<R> {
+  /// A stream whose offsets are positions in the reader, starting where the reader is.
+  pub fn seekable(mut reader: R) -> io::Result<Self> {
+    let offset = reader.stream_position()?;
+    let length = reader.seek(SeekFrom::End(0))?;
+
+    reader.seek(SeekFrom::Start(offset))?;
+
+    Ok(Self {
+      reader,
+      state: IoState::new(offset, Some(length)),
+    })
+  }
+}
+
+impl<R: Read> ReadableStream for IoStream<R> {
+  type Type = u8;
+
+  type ReadError = IoStreamError;
+  type SkipError = IoStreamError;
+
+  fn len(&self) -> Option<u64> {
+    self.state.length()
+  }
+  fn offset(&self) -> u64 {
+    self.state.offset()
+  }
+
+  async fn read<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[u8; SIZE]) -> V) -> Result<V, StreamReadError<Self::ReadError>> {
+    self.state.read(async |buffer: &mut [u8]| self.reader.read(buffer), reader).await
+  }
+
+  async fn skip(&mut self, size: u64) -> Result<(), StreamSkipError<Self::SkipError>> {
+    self.state.skip(size, async |buffer: &mut [u8]| self.reader.read(buffer)).await
+  }
+}
+
+impl<R: Read + Seek> RewindableStream for IoStream<R> {
+  type RewindError = IoStreamError;
+
+  async fn rewind(&mut self, size: u64) -> Result<(), StreamRewindError<Self::RewindError>> {
+    self.state.rewind(size, async |offset| self.reader.seek(SeekFrom::Start(offset))).await
+  }
+}
+
+impl<R: Read + Seek> SeekableStream for IoStream<R> {
+  type SeekError = IoStreamError;
+
+  async fn seek(&mut self, offset: u64) -> Result<(), StreamSeekError<Self::SeekError>> {
+    self.state.seek(offset, async |offset| self.reader.seek(SeekFrom::Start(offset))).await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::io::Cursor;
+
+  use crate::stream::{error::stream_read::StreamReadError, ReadableStream, RewindableStream, SeekableStream, SINGLE};
+
+  use super::IoStream;
+
+  /// A reader handing out at most three bytes at a time.
+  struct Trickle<'a>(&'a [u8]);
+
+  impl std::io::Read for Trickle<'_> {
+    fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+      let length = buffer.len().min(self.0.len()).min(3);
+
+      buffer[..length].copy_from_slice(&self.0[..length]);
+      self.0 = &self.0[length..];
+      Ok(length)
+    }
+  }
+
+  #[tokio::test]
+  async fn reads_across_short_reads_and_reports_exhaustion() {
+    let data: [u8; 10] = core::array::from_fn(|index| index as u8);
+    let mut stream = IoStream::new(Trickle(&data));
+
+    assert_eq!(stream.read(async |bytes: &[u8; 4]| *bytes).await.unwrap(), [0, 1, 2, 3]);
+    stream.skip(2).await.unwrap();
+
+    let error = stream.read(async |bytes: &[u8; 5]| *bytes).await.unwrap_err();
+    assert!(matches!(error, StreamReadError::StreamExhausted(ref exhausted) if exhausted.stream_length == 10 && exhausted.read_offset == 6));
+
+    // the failed read left its bytes in place
+    assert_eq!(stream.read(async |bytes: &[u8; 4]| *bytes).await.unwrap(), [6, 7, 8, 9]);
+    assert_eq!(stream.len(), None);
+  }
+
+  #[tokio::test]
+  async fn surfaces_reader_errors_as_user_errors() {
+    struct Failing;
+
+    impl std::io::Read for Failing {
+      fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+        Err(std::io::Error::other("broken"))
+      }
+    }
+
+    let mut stream = IoStream::new(Failing);
+
+    assert!(matches!(stream.read(SINGLE).await, Err(StreamReadError::User(_))));
+  }
+
+  #[tokio::test]
+  async fn seeks_within_and_beyond_the_buffer() {
+    let data: std::vec::Vec<u8> = (0..0x3000u32).map(|index| (index / 7) as u8).collect();
+    let mut cursor = Cursor::new(data.clone());
+    cursor.set_position(0x10);
+
+    let mut stream = IoStream::seekable(cursor).unwrap();
+
+    assert_eq!(stream.offset(), 0x10);
+    assert_eq!(stream.remaining(), Some(0x2FF0));
+    assert_eq!(stream.read(SINGLE).await.unwrap(), data[0x10]);
+
+    stream.rewind(0x11).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.read(SINGLE).await.unwrap(), data[0]);
+
+    stream.seek(0x2800).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.read(SINGLE).await.unwrap(), data[0x2800]);
+
+    stream.seek(0x20).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.read(SINGLE).await.unwrap(), data[0x20]);
+
+    assert!(stream.seek(0x3001).await.is_err());
+  }
+}
@@ -0,0 +1,84 @@
+use std::io::{self, SeekFrom};
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
+
+use crate::stream::{
+  error::{stream_read::StreamReadError, stream_rewind::StreamRewindError, stream_seek::StreamSeekError, stream_skip::StreamSkipError},
+  ReadableStream, RewindableStream, SeekableStream,
+};
+
+use super::{buffer::IoState, error::IoStreamError};
+
+/// A stream of the bytes of a [`tokio::io::AsyncRead`], which rewinds and seeks over an
+/// [`AsyncSeek`] reader made with [`TokioIoStream::seekable`]. It works like
+/// [`IoStream`](super::stream::IoStream), but waits on the reader rather than blocking.
+pub struct TokioIoStream<R> {
+  reader: R,
+  state: IoState,
+}
+
+impl<R: AsyncRead + Unpin> TokioIoStream<R> {
+  /// A stream starting at offset 0, wherever the reader is, with no known length.
+  pub fn new(reader: R) -> Self {
+    Self { reader, state: IoState::new(0, None) }
+  }
+
+  /// Gives back the reader, which may be past [`offset`](ReadableStream::offset) by whatever
+  /// the stream had buffered.
+  pub fn into_inner(self) -> R {
+    self.reader
+  }
+}
+
+impl<R: AsyncRead + AsyncSeek + Unpin> TokioIoStream<R> {
+  /// A stream whose offsets are positions in the reader, starting where the reader is.
+  pub async fn seekable(mut reader: R) -> io::Result<Self> {
+    let offset = reader.stream_position().await?;
+    let length = reader.seek(SeekFrom::End(0)).await?;
+
+    reader.seek(SeekFrom::Start(offset)).await?;
+
+    Ok(Self {
+      reader,
+      state: IoState::new(offset, Some(length)),
+    })
+  }
+}
+
+impl<R: AsyncRead + Unpin> ReadableStream for TokioIoStream<R> {
+  type Type = u8;
+
+  type ReadError = IoStreamError;
+  type SkipError = IoStreamError;
+
+  fn len(&self) -> Option<u64> {
+    self.state.length()
+  }
+  fn offset(&self) -> u64 {
+    self.state.offset()
+  }
+
+  async fn read<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[u8; SIZE]) -> V) -> Result<V, StreamReadError<Self::ReadError>> {
+    self.state.read(async |buffer: &mut [u8]| self.reader.read(buffer).await, reader).await
+  }
+
+  async fn skip(&mut self, size: u64) -> Result<(), StreamSkipError<Self::SkipError>> {
+    self.state.skip(size, async |buffer: &mut [u8]| self.reader.read(buffer).await).await
+  }
+}
+
+impl<R: AsyncRead + AsyncSeek + Unpin> RewindableStream for TokioIoStream<R> {
+  type RewindError = IoStreamError;
+
+  async fn rewind(&mut self, size: u64) -> Result<(), StreamRewindError<Self::RewindError>> {
+    self.state.rewind(size, async |offset| self.reader.seek(SeekFrom::Start(offset)).await).await
+  }
+}
+
+impl<R: AsyncRead + AsyncSeek + Unpin> SeekableStream for TokioIoStream<R> {
+  type SeekError = IoStreamError;
+
+  async fn seek(&mut self, offset: u64) -> Result<(), StreamSeekError<Self::SeekError>> {
+    self.state.seek(offset, async |offset| self.reader.seek(SeekFrom::Start(offset)).await).await
+  }
+}
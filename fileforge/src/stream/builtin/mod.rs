@@ -1,4 +1,8 @@
+pub mod buffered;
 pub mod collector;
+#[cfg(feature = "alloc")]
 pub mod ephemeral;
+#[cfg(feature = "std")]
+pub mod io;
 pub mod provider;
 pub mod read_until;
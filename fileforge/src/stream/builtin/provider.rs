@@ -23,6 +23,7 @@ use crate::{
   },
 };
 
+#[derive(Clone)]
 pub struct ProviderStream<P: Provider> {
   poisoned: bool,
   hint: ReadHint,
@@ -40,10 +41,17 @@ impl<P: Provider> ProviderStream<P> {
     }
   }
 
+  /// Replaces the hint passed to the provider on every read from now on. Set a
+  /// [`ReadPattern::Random`](crate::provider::hint::ReadPattern::Random) hint before jumping
+  /// around a file, and go back to a sequential one before reading through it.
   pub fn set_read_hint(&mut self, hint: ReadHint) {
     self.hint = hint
   }
 
+  pub fn read_hint(&self) -> ReadHint {
+    self.hint
+  }
+
   fn assert_not_poisoned(&self) -> Result<(), ProviderStreamPoisonedError> {
     if self.poisoned {
       Err(ProviderStreamPoisonedError)
@@ -342,3 +350,33 @@ impl FileforgeError for ProviderStreamPoisonedError {
       .apply(callback);
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use alloc::{vec, vec::Vec};
+  use core::cell::RefCell;
+
+  use super::ProviderStream;
+  use crate::{provider::hint::ReadHint, testing};
+
+  #[tokio::test]
+  async fn conforms_to_the_stream_contracts() {
+    let data = (1..=8).collect::<Vec<u8>>();
+    let factory = async || ProviderStream::new(data.clone(), ReadHint::new());
+
+    testing::stream::readable(&factory, &data).await;
+    testing::stream::rewindable(&factory, &data).await;
+    testing::stream::seekable(&factory, &data).await;
+    testing::stream::restorable(&factory, &data).await;
+    testing::stream::mutable(&factory, &data).await;
+    testing::stream::resizable(&factory, &data).await;
+
+    // partitioning needs a provider the halves can borrow from, so each stream borrows its own copy
+    let mut copies = vec![data.clone(); 128];
+    let copies = RefCell::new(copies.iter_mut());
+    let factory = async || ProviderStream::new(copies.borrow_mut().next().expect("enough copies for every stream"), ReadHint::new());
+
+    testing::stream::static_partitionable::<3, _>(&factory, &data).await;
+    testing::stream::dynamic_partitionable(&factory, &data).await;
+  }
+}
@@ -130,3 +130,22 @@ where
     Ok(())
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use alloc::vec::Vec;
+
+  use crate::{
+    provider::hint::ReadHint,
+    stream::{builtin::provider::ProviderStream, extensions::readable::ReadableStreamExt},
+    testing,
+  };
+
+  #[tokio::test]
+  async fn conforms_to_the_stream_contracts() {
+    let data = b"fileforge\0rest".to_vec();
+    let factory = async || ProviderStream::new(data.clone(), ReadHint::new()).read_until(0);
+
+    testing::stream::readable(factory, b"fileforge").await;
+  }
+}
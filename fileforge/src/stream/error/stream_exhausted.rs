@@ -35,4 +35,14 @@ impl StreamExhaustedError {
       Ok(())
     }
   }
+
+  /// Moves the error's positions from a stream where another one starts at `start`, to that one's
+  /// own, which starts at `base`.
+  pub(crate) fn rebase(self, start: u64, base: u64) -> Self {
+    Self {
+      stream_length: self.stream_length.saturating_sub(start) + base,
+      read_length: self.read_length,
+      read_offset: self.read_offset.saturating_sub(start) + base,
+    }
+  }
 }
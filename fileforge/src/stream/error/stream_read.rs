@@ -27,3 +27,19 @@ impl<T, UserRead: UserReadError, I: From<UserRead>> super::MapExhausted<T, UserR
     }
   }
 }
+
+impl<UserRead: UserReadError> StreamReadError<UserRead> {
+  pub(crate) fn map_user<Other: UserReadError>(self, mapper: impl FnOnce(UserRead) -> Other) -> StreamReadError<Other> {
+    match self {
+      Self::User(error) => StreamReadError::User(mapper(error)),
+      Self::StreamExhausted(error) => StreamReadError::StreamExhausted(error),
+    }
+  }
+
+  pub(crate) fn rebase(self, start: u64, base: u64) -> Self {
+    match self {
+      Self::StreamExhausted(error) => Self::StreamExhausted(error.rebase(start, base)),
+      error => error,
+    }
+  }
+}
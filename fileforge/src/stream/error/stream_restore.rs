@@ -11,3 +11,12 @@ impl<UserRestore: UserRestoreError> From<UserRestore> for StreamRestoreError<Use
     Self::User(value)
   }
 }
+
+impl<UserRestore: UserRestoreError> StreamRestoreError<UserRestore> {
+  pub(crate) fn map_user<Other: UserRestoreError>(self, mapper: impl FnOnce(UserRestore) -> Other) -> StreamRestoreError<Other> {
+    match self {
+      Self::User(error) => StreamRestoreError::User(mapper(error)),
+      Self::CannotRestoreForwards => StreamRestoreError::CannotRestoreForwards,
+    }
+  }
+}
@@ -17,4 +17,19 @@ impl<UserRewind: UserRewindError> StreamRewindError<UserRewind> {
 
     Ok(seek_point)
   }
+
+  pub(crate) fn map_user<Other: UserRewindError>(self, mapper: impl FnOnce(UserRewind) -> Other) -> StreamRewindError<Other> {
+    match self {
+      Self::User(error) => StreamRewindError::User(mapper(error)),
+      Self::SeekPointUnderflowed {
+        stream_length,
+        offset,
+        seek_backwards_distance,
+      } => StreamRewindError::SeekPointUnderflowed {
+        stream_length,
+        offset,
+        seek_backwards_distance,
+      },
+    }
+  }
 }
@@ -12,3 +12,19 @@ impl<UserSeek: UserSeekError> From<StreamSeekOutOfBoundsError> for StreamSeekErr
 impl<UserSeek: UserSeekError> From<UserSeek> for StreamSeekError<UserSeek> {
   fn from(value: UserSeek) -> Self { Self::User(value) }
 }
+
+impl<UserSeek: UserSeekError> StreamSeekError<UserSeek> {
+  pub(crate) fn map_user<Other: UserSeekError>(self, mapper: impl FnOnce(UserSeek) -> Other) -> StreamSeekError<Other> {
+    match self {
+      Self::User(error) => StreamSeekError::User(mapper(error)),
+      Self::OutOfBounds(error) => StreamSeekError::OutOfBounds(error),
+    }
+  }
+
+  pub(crate) fn rebase(self, start: u64, base: u64) -> Self {
+    match self {
+      Self::User(error) => Self::User(error),
+      Self::OutOfBounds(error) => Self::OutOfBounds(error.rebase(start, base)),
+    }
+  }
+}
@@ -8,4 +8,12 @@ impl StreamSeekOutOfBoundsError {
   pub fn assert(stream_length: u64, seek_point: u64) -> Result<(), Self> {
     if seek_point > stream_length { Err(Self { seek_point, stream_length }) } else { Ok(()) }
   }
+
+  /// Moves the error's positions the same way as [`StreamExhaustedError::rebase`](super::stream_exhausted::StreamExhaustedError::rebase).
+  pub(crate) fn rebase(self, start: u64, base: u64) -> Self {
+    Self {
+      stream_length: self.stream_length.saturating_sub(start) + base,
+      seek_point: self.seek_point.saturating_sub(start) + base,
+    }
+  }
 }
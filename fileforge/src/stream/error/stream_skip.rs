@@ -33,4 +33,36 @@ impl<UserSkip: UserSkipError> StreamSkipError<UserSkip> {
 
     Ok(seek_point)
   }
+
+  pub(crate) fn map_user<Other: UserSkipError>(self, mapper: impl FnOnce(UserSkip) -> Other) -> StreamSkipError<Other> {
+    match self {
+      Self::User(error) => StreamSkipError::User(mapper(error)),
+      Self::OutOfBounds(error) => StreamSkipError::OutOfBounds(error),
+      Self::SeekPointOverflowed {
+        stream_length,
+        offset,
+        seek_forwards_distance,
+      } => StreamSkipError::SeekPointOverflowed {
+        stream_length,
+        offset,
+        seek_forwards_distance,
+      },
+    }
+  }
+
+  pub(crate) fn rebase(self, start: u64, base: u64) -> Self {
+    match self {
+      Self::User(error) => Self::User(error),
+      Self::OutOfBounds(error) => Self::OutOfBounds(error.rebase(start, base)),
+      Self::SeekPointOverflowed {
+        stream_length,
+        offset,
+        seek_forwards_distance,
+      } => Self::SeekPointOverflowed {
+        stream_length: stream_length.saturating_sub(start) + base,
+        offset: offset.saturating_sub(start) + base,
+        seek_forwards_distance,
+      },
+    }
+  }
 }
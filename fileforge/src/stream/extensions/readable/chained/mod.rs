@@ -0,0 +1,217 @@
+use crate::stream::{
+  error::{
+    stream_exhausted::StreamExhaustedError, stream_read::StreamReadError, stream_restore::StreamRestoreError, stream_rewind::StreamRewindError, stream_seek::StreamSeekError,
+    stream_skip::StreamSkipError,
+  },
+  extensions::readable::error::EitherError,
+  ReadableStream, RestorableStream, RewindableStream, SeekableStream, CLONED,
+};
+
+/// The elements of one stream followed by those of another, from wherever each was when chained.
+///
+/// Offsets carry on from the first stream's into the second. Reads that span both are read an
+/// element at a time, and unless both streams know their lengths, one that runs out partway has
+/// still consumed the elements it got.
+pub struct ChainedStream<A: ReadableStream, B: ReadableStream<Type = A::Type>> {
+  first: A,
+  second: B,
+  second_start: u64,
+}
+
+impl<A: ReadableStream, B: ReadableStream<Type = A::Type>> ChainedStream<A, B> {
+  pub(super) fn new(first: A, second: B) -> Self {
+    Self {
+      second_start: second.offset(),
+      first,
+      second,
+    }
+  }
+
+  pub fn into_inner(self) -> (A, B) {
+    (self.first, self.second)
+  }
+}
+
+impl<A: ReadableStream, B: ReadableStream<Type = A::Type>> ReadableStream for ChainedStream<A, B>
+where
+  A::Type: Clone,
+{
+  type Type = A::Type;
+  type ReadError = EitherError<A::ReadError, B::ReadError>;
+  type SkipError = EitherError<A::SkipError, B::SkipError>;
+
+  fn len(&self) -> Option<u64> {
+    Some(self.first.len()? + (self.second.len()? - self.second_start))
+  }
+  fn offset(&self) -> u64 {
+    self.first.offset() + (self.second.offset() - self.second_start)
+  }
+
+  async fn read<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[Self::Type; SIZE]) -> V) -> Result<V, StreamReadError<Self::ReadError>> {
+    match self.first.remaining() {
+      Some(remaining) if remaining >= SIZE as u64 => return self.first.read(reader).await.map_err(|error| error.map_user(EitherError::First)),
+      Some(0) => {
+        let base = self.first.offset();
+
+        return self.second.read(reader).await.map_err(|error| error.map_user(EitherError::Second).rebase(self.second_start, base));
+      }
+      _ => {}
+    }
+
+    let offset = self.offset();
+
+    if let Some(length) = self.len() {
+      StreamExhaustedError::assert(length, offset, SIZE as u64)?;
+    }
+
+    let mut items: [Option<A::Type>; SIZE] = [const { None }; SIZE];
+
+    for (index, slot) in items.iter_mut().enumerate() {
+      let item = match self.first.read(CLONED).await {
+        Ok(item) => item,
+        Err(StreamReadError::User(error)) => return Err(StreamReadError::User(EitherError::First(error))),
+        Err(StreamReadError::StreamExhausted(_)) => match self.second.read(CLONED).await {
+          Ok(item) => item,
+          Err(StreamReadError::User(error)) => return Err(StreamReadError::User(EitherError::Second(error))),
+          Err(StreamReadError::StreamExhausted(_)) => {
+            return Err(StreamReadError::StreamExhausted(StreamExhaustedError {
+              stream_length: offset + index as u64,
+              read_length: SIZE as u64,
+              read_offset: offset,
+            }))
+          }
+        },
+      };
+
+      *slot = Some(item);
+    }
+
+    Ok(reader(&items.map(|item| item.expect("Every element was read"))).await)
+  }
+
+  async fn skip(&mut self, size: u64) -> Result<(), StreamSkipError<Self::SkipError>> {
+    // only the first stream knows where it ends, so anything it can't skip comes out of the second
+    let rest = match self.first.skip(size).await {
+      Ok(()) => return Ok(()),
+      Err(StreamSkipError::OutOfBounds(bounds)) => {
+        self.first.skip(bounds.stream_length - self.first.offset()).await.map_err(|error| error.map_user(EitherError::First))?;
+
+        bounds.seek_point - bounds.stream_length
+      }
+      Err(error) => return Err(error.map_user(EitherError::First)),
+    };
+
+    let base = self.first.offset();
+
+    self.second.skip(rest).await.map_err(|error| error.map_user(EitherError::Second).rebase(self.second_start, base))
+  }
+}
+
+impl<A: RewindableStream, B: RewindableStream<Type = A::Type>> RewindableStream for ChainedStream<A, B>
+where
+  A::Type: Clone,
+{
+  type RewindError = EitherError<A::RewindError, B::RewindError>;
+
+  async fn rewind(&mut self, size: u64) -> Result<(), StreamRewindError<Self::RewindError>> {
+    StreamRewindError::assert_relative_backwards(self.len().unwrap_or(self.offset()), self.offset(), size)?;
+
+    let from_second = size.min(self.second.offset() - self.second_start);
+
+    self.second.rewind(from_second).await.map_err(|error| error.map_user(EitherError::Second))?;
+    self.first.rewind(size - from_second).await.map_err(|error| error.map_user(EitherError::First))
+  }
+}
+
+impl<A: SeekableStream, B: SeekableStream<Type = A::Type>> SeekableStream for ChainedStream<A, B>
+where
+  A::Type: Clone,
+{
+  type SeekError = EitherError<A::SeekError, B::SeekError>;
+
+  async fn seek(&mut self, offset: u64) -> Result<(), StreamSeekError<Self::SeekError>> {
+    match self.first.seek(offset).await {
+      Ok(()) => self.second.seek(self.second_start).await.map_err(|error| error.map_user(EitherError::Second)),
+      Err(StreamSeekError::OutOfBounds(bounds)) => {
+        let base = bounds.stream_length;
+
+        self.first.seek(base).await.map_err(|error| error.map_user(EitherError::First))?;
+        self
+          .second
+          .seek(self.second_start + (offset - base))
+          .await
+          .map_err(|error| error.map_user(EitherError::Second).rebase(self.second_start, base))
+      }
+      Err(error) => Err(error.map_user(EitherError::First)),
+    }
+  }
+}
+
+impl<A: RestorableStream, B: RestorableStream<Type = A::Type>> RestorableStream for ChainedStream<A, B>
+where
+  A::Type: Clone,
+{
+  type Snapshot = (A::Snapshot, B::Snapshot);
+  type RestoreError = EitherError<A::RestoreError, B::RestoreError>;
+
+  fn snapshot(&self) -> Self::Snapshot {
+    (self.first.snapshot(), self.second.snapshot())
+  }
+
+  async fn restore(&mut self, (first, second): Self::Snapshot) -> Result<(), StreamRestoreError<Self::RestoreError>> {
+    self.second.restore(second).await.map_err(|error| error.map_user(EitherError::Second))?;
+    self.first.restore(first).await.map_err(|error| error.map_user(EitherError::First))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::vec::Vec;
+
+  use crate::{
+    provider::hint::ReadHint,
+    stream::{builtin::provider::ProviderStream, error::stream_read::StreamReadError, extensions::readable::ReadableStreamExt, ReadableStream, RewindableStream, SeekableStream},
+  };
+
+  fn streams() -> (ProviderStream<Vec<u8>>, ProviderStream<Vec<u8>>) {
+    (ProviderStream::new((0..4).collect(), ReadHint::new()), ProviderStream::new((4..8).collect(), ReadHint::new()))
+  }
+
+  #[tokio::test]
+  async fn reads_across_the_join() {
+    let (first, second) = streams();
+    let mut stream = first.chain(second);
+
+    assert_eq!(stream.len(), Some(8));
+    assert_eq!(stream.read(async |values: &[u8; 3]| *values).await.map_err(|_| {}).unwrap(), [0, 1, 2]);
+    assert_eq!(stream.read(async |values: &[u8; 3]| *values).await.map_err(|_| {}).unwrap(), [3, 4, 5]);
+
+    let error = stream
+      .read(async |values: &[u8; 3]| *values)
+      .await
+      .map_err(|error| matches!(error, StreamReadError::StreamExhausted(exhausted) if exhausted.stream_length == 8 && exhausted.read_offset == 6));
+    assert!(matches!(error, Err(true)));
+
+    assert_eq!(stream.read(async |values: &[u8; 2]| *values).await.map_err(|_| {}).unwrap(), [6, 7]);
+  }
+
+  #[tokio::test]
+  async fn skips_rewinds_and_seeks_across_the_join() {
+    let (first, second) = streams();
+    let mut stream = first.chain(second);
+
+    stream.skip(5).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.next().await.map_err(|_| {}).unwrap(), 5);
+
+    stream.rewind(4).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.offset(), 2);
+    assert_eq!(stream.next().await.map_err(|_| {}).unwrap(), 2);
+
+    stream.seek(7).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.next().await.map_err(|_| {}).unwrap(), 7);
+
+    stream.seek(1).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.read(async |values: &[u8; 4]| *values).await.map_err(|_| {}).unwrap(), [1, 2, 3, 4]);
+    assert!(stream.seek(9).await.is_err());
+  }
+}
@@ -0,0 +1,27 @@
+use fileforge_macros::FileforgeError;
+
+use crate::{
+  error::FileforgeError,
+  stream::error::{user_read::UserReadError, user_restore::UserRestoreError, user_rewind::UserRewindError, user_seek::UserSeekError, user_skip::UserSkipError},
+};
+
+/// An error from one of the two streams a stream combines.
+#[derive(FileforgeError)]
+pub enum EitherError<First: FileforgeError, Second: FileforgeError> {
+  First(First),
+  Second(Second),
+}
+
+impl<First: UserReadError, Second: UserReadError> UserReadError for EitherError<First, Second> {}
+impl<First: UserSkipError, Second: UserSkipError> UserSkipError for EitherError<First, Second> {}
+impl<First: UserRewindError, Second: UserRewindError> UserRewindError for EitherError<First, Second> {}
+impl<First: UserSeekError, Second: UserSeekError> UserSeekError for EitherError<First, Second> {}
+impl<First: UserRestoreError, Second: UserRestoreError> UserRestoreError for EitherError<First, Second> {}
+
+/// Skipping through a stream that can only count its elements by reading them failed to read.
+#[derive(FileforgeError)]
+pub enum SkipReadError<E: UserReadError> {
+  Read(E),
+}
+
+impl<E: UserReadError> UserSkipError for SkipReadError<E> {}
@@ -0,0 +1,104 @@
+use crate::stream::{
+  error::{stream_read::StreamReadError, stream_restore::StreamRestoreError, stream_skip::StreamSkipError},
+  extensions::readable::{
+    error::SkipReadError,
+    pending::{Pending, PendingSnapshot},
+  },
+  ReadableStream, RestorableStream, CLONED,
+};
+
+/// A stream of what a mapper returns for each element of another, leaving out the elements it
+/// returns `None` for. Like [`FilteredStream`](super::filtered::FilteredStream), it has no length,
+/// counts offsets in its own elements, and skips by reading.
+pub struct FilteredMappedStream<S: ReadableStream, R, FilterMapper: AsyncFn(S::Type) -> Option<R>> {
+  stream: S,
+  filter_mapper: FilterMapper,
+  pending: Pending<R>,
+}
+
+impl<S: ReadableStream, R, FilterMapper: AsyncFn(S::Type) -> Option<R>> FilteredMappedStream<S, R, FilterMapper> {
+  pub(super) fn new(stream: S, filter_mapper: FilterMapper) -> Self {
+    Self {
+      stream,
+      filter_mapper,
+      pending: Pending::new(),
+    }
+  }
+
+  /// Gives back the underlying stream, which has already been read past any elements pulled
+  /// ahead of a read that ran out.
+  pub fn into_inner(self) -> S {
+    self.stream
+  }
+}
+
+impl<S: ReadableStream, R, FilterMapper: AsyncFn(S::Type) -> Option<R>> ReadableStream for FilteredMappedStream<S, R, FilterMapper>
+where
+  S::Type: Clone,
+{
+  type Type = R;
+  type ReadError = S::ReadError;
+  type SkipError = SkipReadError<S::ReadError>;
+
+  fn offset(&self) -> u64 {
+    self.pending.offset()
+  }
+
+  async fn read<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[R; SIZE]) -> V) -> Result<V, StreamReadError<Self::ReadError>> {
+    let (stream, filter_mapper) = (&mut self.stream, &self.filter_mapper);
+
+    self.pending.read(async || Ok(filter_mapper(stream.read(CLONED).await?).await), reader).await
+  }
+
+  async fn skip(&mut self, size: u64) -> Result<(), StreamSkipError<Self::SkipError>> {
+    let (stream, filter_mapper) = (&mut self.stream, &self.filter_mapper);
+
+    self.pending.skip(size, async || Ok(filter_mapper(stream.read(CLONED).await?).await)).await
+  }
+}
+
+impl<S: RestorableStream, R: Clone, FilterMapper: AsyncFn(S::Type) -> Option<R>> RestorableStream for FilteredMappedStream<S, R, FilterMapper>
+where
+  S::Type: Clone,
+{
+  type Snapshot = PendingSnapshot<S::Snapshot, R>;
+  type RestoreError = S::RestoreError;
+
+  fn snapshot(&self) -> Self::Snapshot {
+    PendingSnapshot {
+      snapshot: self.stream.snapshot(),
+      pending: self.pending.clone(),
+    }
+  }
+
+  async fn restore(&mut self, snapshot: Self::Snapshot) -> Result<(), StreamRestoreError<Self::RestoreError>> {
+    if snapshot.pending.offset() > self.pending.offset() {
+      return Err(StreamRestoreError::CannotRestoreForwards);
+    }
+
+    self.stream.restore(snapshot.snapshot).await?;
+    self.pending = snapshot.pending;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::{string::String, vec::Vec};
+
+  use crate::{
+    provider::hint::ReadHint,
+    stream::{builtin::provider::ProviderStream, extensions::readable::ReadableStreamExt, ReadableStream},
+  };
+
+  #[tokio::test]
+  async fn decodes_a_pipeline_into_a_string() {
+    let data: Vec<u8> = b"h3e-l_l0o".to_vec();
+    let mut stream = ProviderStream::new(data, ReadHint::new())
+      .filter_map(async |byte: u8| byte.is_ascii_alphabetic().then_some(byte as char))
+      .map(async |char: char| char.to_ascii_uppercase());
+
+    assert_eq!(stream.collect::<String>().await.unwrap(), "HELLO");
+    assert_eq!(stream.offset(), 5);
+  }
+}
@@ -1,6 +1,150 @@
-use crate::stream::ReadableStream;
+use crate::stream::{
+  error::{stream_read::StreamReadError, stream_restore::StreamRestoreError, stream_skip::StreamSkipError},
+  extensions::readable::{
+    error::SkipReadError,
+    pending::{Pending, PendingSnapshot},
+  },
+  ReadableStream, RestorableStream, CLONED,
+};
 
+/// A stream of the elements of another that pass a filter.
+///
+/// Offsets count the elements that passed. How many will is unknown until the underlying stream
+/// runs out, so the stream has no length, and skipping reads and filters what it skips.
 pub struct FilteredStream<S: ReadableStream, Filter: for<'a> AsyncFn(&'a S::Type) -> bool> {
-  pub(super) stream: S,
-  pub(super) filter: Filter,
+  stream: S,
+  filter: Filter,
+  pending: Pending<S::Type>,
+}
+
+impl<S: ReadableStream, Filter: for<'a> AsyncFn(&'a S::Type) -> bool> FilteredStream<S, Filter> {
+  pub(super) fn new(stream: S, filter: Filter) -> Self {
+    Self {
+      stream,
+      filter,
+      pending: Pending::new(),
+    }
+  }
+
+  /// Gives back the underlying stream, which has already been read past any elements pulled
+  /// ahead of a read that ran out.
+  pub fn into_inner(self) -> S {
+    self.stream
+  }
+}
+
+impl<S: ReadableStream, Filter: for<'a> AsyncFn(&'a S::Type) -> bool> ReadableStream for FilteredStream<S, Filter>
+where
+  S::Type: Clone,
+{
+  type Type = S::Type;
+  type ReadError = S::ReadError;
+  type SkipError = SkipReadError<S::ReadError>;
+
+  fn offset(&self) -> u64 {
+    self.pending.offset()
+  }
+
+  async fn read<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[Self::Type; SIZE]) -> V) -> Result<V, StreamReadError<Self::ReadError>> {
+    let (stream, filter) = (&mut self.stream, &self.filter);
+
+    self
+      .pending
+      .read(
+        async || {
+          let item = stream.read(CLONED).await?;
+          Ok(filter(&item).await.then_some(item))
+        },
+        reader,
+      )
+      .await
+  }
+
+  async fn skip(&mut self, size: u64) -> Result<(), StreamSkipError<Self::SkipError>> {
+    let (stream, filter) = (&mut self.stream, &self.filter);
+
+    self
+      .pending
+      .skip(size, async || {
+        let item = stream.read(CLONED).await?;
+        Ok(filter(&item).await.then_some(item))
+      })
+      .await
+  }
+}
+
+impl<S: RestorableStream, Filter: for<'a> AsyncFn(&'a S::Type) -> bool> RestorableStream for FilteredStream<S, Filter>
+where
+  S::Type: Clone,
+{
+  type Snapshot = PendingSnapshot<S::Snapshot, S::Type>;
+  type RestoreError = S::RestoreError;
+
+  fn snapshot(&self) -> Self::Snapshot {
+    PendingSnapshot {
+      snapshot: self.stream.snapshot(),
+      pending: self.pending.clone(),
+    }
+  }
+
+  async fn restore(&mut self, snapshot: Self::Snapshot) -> Result<(), StreamRestoreError<Self::RestoreError>> {
+    if snapshot.pending.offset() > self.pending.offset() {
+      return Err(StreamRestoreError::CannotRestoreForwards);
+    }
+
+    self.stream.restore(snapshot.snapshot).await?;
+    self.pending = snapshot.pending;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::vec::Vec;
+
+  use crate::{
+    provider::hint::ReadHint,
+    stream::{
+      builtin::provider::ProviderStream,
+      error::{stream_read::StreamReadError, stream_skip::StreamSkipError},
+      extensions::readable::ReadableStreamExt,
+      ReadableStream, RestorableStream,
+    },
+  };
+
+  #[tokio::test]
+  async fn yields_passing_elements_with_their_own_offsets() {
+    let data: Vec<u8> = (0..20).collect();
+    let mut stream = ProviderStream::new(data, ReadHint::new()).filter(async |value: &u8| value % 3 == 0);
+
+    assert_eq!(stream.len(), None);
+    assert_eq!(stream.read(async |values: &[u8; 2]| *values).await.unwrap(), [0, 3]);
+    assert_eq!(stream.offset(), 2);
+
+    stream.skip(2).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.next().await.unwrap(), 12);
+
+    // 15 and 18 are left, which a read of three runs out on without losing them
+    let error = stream.read(async |values: &[u8; 3]| *values).await.unwrap_err();
+    assert!(matches!(error, StreamReadError::StreamExhausted(ref exhausted) if exhausted.stream_length == 7 && exhausted.read_offset == 5));
+
+    assert_eq!(stream.read(async |values: &[u8; 2]| *values).await.unwrap(), [15, 18]);
+    assert!(matches!(stream.skip(1).await, Err(StreamSkipError::OutOfBounds(_))));
+  }
+
+  #[tokio::test]
+  async fn restores_pulled_elements() {
+    let data: Vec<u8> = (0..10).collect();
+    let mut stream = ProviderStream::new(data, ReadHint::new()).filter(async |value: &u8| value % 2 == 1);
+
+    assert_eq!(stream.next().await.unwrap(), 1);
+
+    let snapshot = stream.snapshot();
+
+    assert_eq!(stream.read(async |values: &[u8; 3]| *values).await.unwrap(), [3, 5, 7]);
+    stream.restore(snapshot).await.map_err(|_| {}).unwrap();
+
+    assert_eq!(stream.offset(), 1);
+    assert_eq!(stream.read(async |values: &[u8; 4]| *values).await.unwrap(), [3, 5, 7, 9]);
+  }
 }
@@ -0,0 +1,121 @@
+use fileforge_macros::FileforgeError;
+
+use crate::stream::{
+  error::{stream_read::StreamReadError, stream_skip::StreamSkipError, user_read::UserReadError},
+  extensions::readable::{error::SkipReadError, pending::Pending},
+  ReadableStream, CLONED,
+};
+
+#[derive(FileforgeError)]
+pub enum FlattenError<Outer: UserReadError, Inner: UserReadError> {
+  Outer(Outer),
+  Inner(Inner),
+}
+
+impl<Outer: UserReadError, Inner: UserReadError> UserReadError for FlattenError<Outer, Inner> {}
+
+/// A stream of the elements of each stream another yields, one after the other.
+///
+/// Each inner stream is read until it runs out, so reads can span several. Offsets count the
+/// flattened elements, the length is unknown, and skipping reads what it skips.
+pub struct FlattenedStream<S: ReadableStream>
+where
+  S::Type: ReadableStream,
+{
+  stream: S,
+  current: Option<S::Type>,
+  pending: Pending<<S::Type as ReadableStream>::Type>,
+}
+
+impl<S: ReadableStream> FlattenedStream<S>
+where
+  S::Type: ReadableStream,
+{
+  pub(super) fn new(stream: S) -> Self {
+    Self {
+      stream,
+      current: None,
+      pending: Pending::new(),
+    }
+  }
+
+  pub fn into_inner(self) -> S {
+    self.stream
+  }
+}
+
+type Element<S> = <<S as ReadableStream>::Type as ReadableStream>::Type;
+type Error<S> = FlattenError<<S as ReadableStream>::ReadError, <<S as ReadableStream>::Type as ReadableStream>::ReadError>;
+
+/// Reads the next element of the current inner stream, moving on to the next inner stream
+/// whenever one runs out.
+async fn pull<S: ReadableStream>(stream: &mut S, current: &mut Option<S::Type>) -> Result<Option<Element<S>>, StreamReadError<Error<S>>>
+where
+  S::Type: ReadableStream + Clone,
+  Element<S>: Clone,
+{
+  loop {
+    if let Some(inner) = current {
+      match inner.read(CLONED).await {
+        Ok(item) => return Ok(Some(item)),
+        Err(StreamReadError::StreamExhausted(_)) => *current = None,
+        Err(StreamReadError::User(error)) => return Err(StreamReadError::User(FlattenError::Inner(error))),
+      }
+    }
+
+    *current = Some(stream.read(CLONED).await.map_err(|error| match error {
+      StreamReadError::User(error) => StreamReadError::User(FlattenError::Outer(error)),
+      StreamReadError::StreamExhausted(error) => StreamReadError::StreamExhausted(error),
+    })?);
+  }
+}
+
+impl<S: ReadableStream> ReadableStream for FlattenedStream<S>
+where
+  S::Type: ReadableStream + Clone,
+  Element<S>: Clone,
+{
+  type Type = Element<S>;
+  type ReadError = Error<S>;
+  type SkipError = SkipReadError<Error<S>>;
+
+  fn offset(&self) -> u64 {
+    self.pending.offset()
+  }
+
+  async fn read<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[Self::Type; SIZE]) -> V) -> Result<V, StreamReadError<Self::ReadError>> {
+    let (stream, current) = (&mut self.stream, &mut self.current);
+
+    self.pending.read(async || pull(stream, current).await, reader).await
+  }
+
+  async fn skip(&mut self, size: u64) -> Result<(), StreamSkipError<Self::SkipError>> {
+    let (stream, current) = (&mut self.stream, &mut self.current);
+
+    self.pending.skip(size, async || pull(stream, current).await).await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::{vec, vec::Vec};
+
+  use crate::{
+    provider::hint::ReadHint,
+    stream::{builtin::provider::ProviderStream, extensions::readable::ReadableStreamExt, ReadableStream},
+  };
+
+  #[tokio::test]
+  async fn reads_across_inner_streams() {
+    let parts: Vec<&[u8]> = vec![b"ab", b"", b"cde", b"f"];
+    let streams = ProviderStream::new(parts, ReadHint::new()).map(async |part: &[u8]| ProviderStream::new(part, ReadHint::new()));
+    let mut stream = streams.flatten();
+
+    assert_eq!(stream.read(async |values: &[u8; 3]| *values).await.map_err(|_| {}).unwrap(), *b"abc");
+
+    stream.skip(1).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.read(async |values: &[u8; 2]| *values).await.map_err(|_| {}).unwrap(), *b"ef");
+    assert!(stream.next().await.is_err());
+    assert_eq!(stream.offset(), 6);
+  }
+}
@@ -1,6 +1,129 @@
-use crate::stream::ReadableStream;
+use crate::stream::{
+  error::{stream_read::StreamReadError, stream_restore::StreamRestoreError, stream_rewind::StreamRewindError, stream_seek::StreamSeekError, stream_skip::StreamSkipError},
+  ReadableStream, RestorableStream, RewindableStream, SeekableStream,
+};
 
+/// A stream of every element of another passed through a mapper, one to one, so offsets and
+/// lengths are the underlying stream's. Skipped elements aren't mapped.
 pub struct MappedStream<S: ReadableStream, R, Mapper: AsyncFn(S::Type) -> R> {
-  pub(super) stream: S,
-  pub(super) mapper: Mapper,
+  stream: S,
+  mapper: Mapper,
+}
+
+impl<S: ReadableStream, R, Mapper: AsyncFn(S::Type) -> R> MappedStream<S, R, Mapper> {
+  pub(super) fn new(stream: S, mapper: Mapper) -> Self {
+    Self { stream, mapper }
+  }
+
+  pub fn into_inner(self) -> S {
+    self.stream
+  }
+}
+
+impl<S: ReadableStream, R, Mapper: AsyncFn(S::Type) -> R> ReadableStream for MappedStream<S, R, Mapper>
+where
+  S::Type: Clone,
+{
+  type Type = R;
+  type ReadError = S::ReadError;
+  type SkipError = S::SkipError;
+
+  fn len(&self) -> Option<u64> {
+    self.stream.len()
+  }
+  fn remaining(&self) -> Option<u64> {
+    self.stream.remaining()
+  }
+  fn offset(&self) -> u64 {
+    self.stream.offset()
+  }
+
+  async fn read<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[R; SIZE]) -> V) -> Result<V, StreamReadError<Self::ReadError>> {
+    let mapper = &self.mapper;
+
+    self
+      .stream
+      .read(async |values: &[S::Type; SIZE]| {
+        let mut mapped: [Option<R>; SIZE] = [const { None }; SIZE];
+
+        for (slot, value) in mapped.iter_mut().zip(values) {
+          *slot = Some(mapper(value.clone()).await);
+        }
+
+        reader(&mapped.map(|value| value.expect("Every element was mapped"))).await
+      })
+      .await
+  }
+
+  async fn skip(&mut self, size: u64) -> Result<(), StreamSkipError<Self::SkipError>> {
+    self.stream.skip(size).await
+  }
+}
+
+impl<S: RewindableStream, R, Mapper: AsyncFn(S::Type) -> R> RewindableStream for MappedStream<S, R, Mapper>
+where
+  S::Type: Clone,
+{
+  type RewindError = S::RewindError;
+
+  async fn rewind(&mut self, size: u64) -> Result<(), StreamRewindError<Self::RewindError>> {
+    self.stream.rewind(size).await
+  }
+}
+
+impl<S: SeekableStream, R, Mapper: AsyncFn(S::Type) -> R> SeekableStream for MappedStream<S, R, Mapper>
+where
+  S::Type: Clone,
+{
+  type SeekError = S::SeekError;
+
+  async fn seek(&mut self, offset: u64) -> Result<(), StreamSeekError<Self::SeekError>> {
+    self.stream.seek(offset).await
+  }
+}
+
+impl<S: RestorableStream, R, Mapper: AsyncFn(S::Type) -> R> RestorableStream for MappedStream<S, R, Mapper>
+where
+  S::Type: Clone,
+{
+  type Snapshot = S::Snapshot;
+  type RestoreError = S::RestoreError;
+
+  fn snapshot(&self) -> Self::Snapshot {
+    self.stream.snapshot()
+  }
+
+  async fn restore(&mut self, snapshot: Self::Snapshot) -> Result<(), StreamRestoreError<Self::RestoreError>> {
+    self.stream.restore(snapshot).await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::vec::Vec;
+
+  use crate::{
+    provider::hint::ReadHint,
+    stream::{builtin::provider::ProviderStream, extensions::readable::ReadableStreamExt, ReadableStream, RewindableStream, SeekableStream},
+  };
+
+  #[tokio::test]
+  async fn maps_elements_and_keeps_positions() {
+    let data: Vec<u8> = (0..10).collect();
+    let mut stream = ProviderStream::new(data, ReadHint::new()).map(async |value: u8| value as u32 * 100);
+
+    assert_eq!(stream.len(), Some(10));
+    assert_eq!(stream.read(async |values: &[u32; 3]| *values).await.unwrap(), [0, 100, 200]);
+
+    stream.skip(2).await.unwrap();
+    assert_eq!(stream.next().await.unwrap(), 500);
+    assert_eq!(stream.remaining(), Some(4));
+
+    stream.rewind(4).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.next().await.unwrap(), 200);
+
+    stream.seek(9).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.next().await.unwrap(), 900);
+    assert!(stream.next().await.is_err());
+  }
 }
@@ -1,20 +1,50 @@
 pub mod byte;
+pub mod chained;
+pub mod error;
+#[cfg(feature = "alloc")]
+pub mod filter_mapped;
+#[cfg(feature = "alloc")]
 pub mod filtered;
+#[cfg(feature = "alloc")]
+pub mod flattened;
 pub mod mapped;
+pub mod peekable;
+#[cfg(feature = "alloc")]
+pub mod pending;
+pub mod taken;
+pub mod windowed;
+pub mod zipped;
 
 use core::future::Future;
 
-use crate::stream::{builtin::read_until::ReadUntil, collectable::Collectable, error::stream_read::StreamReadError, ReadableStream, SINGLE};
+#[cfg(feature = "alloc")]
+use crate::stream::extensions::readable::{filter_mapped::FilteredMappedStream, filtered::FilteredStream, flattened::FlattenedStream};
+use crate::stream::{
+  builtin::{buffered::Buffered, read_until::ReadUntil},
+  collectable::Collectable,
+  error::stream_read::StreamReadError,
+  extensions::readable::{chained::ChainedStream, mapped::MappedStream, peekable::PeekableStream, taken::TakenStream, windowed::WindowedStream, zipped::ZippedStream},
+  ReadableStream, SINGLE,
+};
 
 pub trait ReadableStreamExt: ReadableStream {
   // Transformation
-  // fn map<R, Mapper: AsyncFn(Self::Type) -> R>(self, mapper: Mapper) -> MappedStream<Self, R, Mapper>;
-  // fn filter<Filter: for<'a> AsyncFn(&'a Self::Type) -> bool>(self, filter: Filter) -> FilteredStream<Self, Filter>;
-  //   fn filter_map<R, FilterMapper: AsyncFn(Self::Type) -> Option<R>>(self, filter_mapper: FilterMapper) -> FilteredMappedStream<Self, R, FilterMapper>;
-  //   fn flatten<U>(self) -> FlattenedStream<Self, U>
-  //   where
-  //     Self::Type: ReadableStream<Type = U>;
+  fn map<R, Mapper: AsyncFn(Self::Type) -> R>(self, mapper: Mapper) -> MappedStream<Self, R, Mapper>;
+  #[cfg(feature = "alloc")]
+  fn filter<Filter: for<'a> AsyncFn(&'a Self::Type) -> bool>(self, filter: Filter) -> FilteredStream<Self, Filter>;
+  #[cfg(feature = "alloc")]
+  fn filter_map<R, FilterMapper: AsyncFn(Self::Type) -> Option<R>>(self, filter_mapper: FilterMapper) -> FilteredMappedStream<Self, R, FilterMapper>;
+  #[cfg(feature = "alloc")]
+  fn flatten(self) -> FlattenedStream<Self>
+  where
+    Self::Type: ReadableStream;
   fn read_until(self, value: Self::Type) -> ReadUntil<Self>;
+  fn take(self, limit: u64) -> TakenStream<Self>;
+  fn chain<Other: ReadableStream<Type = Self::Type>>(self, other: Other) -> ChainedStream<Self, Other>;
+  fn zip<Other: ReadableStream>(self, other: Other) -> ZippedStream<Self, Other>;
+  fn peekable<const N: usize>(self) -> PeekableStream<Self, N>;
+  fn windows<const N: usize>(self) -> WindowedStream<Self, N>;
+  fn buffered<const CAP: usize>(self) -> Buffered<Self, CAP>;
 
   // Consumption
   fn next(&mut self) -> impl Future<Output = Result<Self::Type, StreamReadError<Self::ReadError>>>
@@ -33,18 +63,56 @@ impl<S: ReadableStream> ReadableStreamExt for S {
     self.read(SINGLE).await
   }
 
-  // fn map<R, Mapper: AsyncFn(Self::Type) -> R>(self, mapper: Mapper) -> MappedStream<Self, R, Mapper> {
-  //   MappedStream { stream: self, mapper }
-  // }
+  fn map<R, Mapper: AsyncFn(Self::Type) -> R>(self, mapper: Mapper) -> MappedStream<Self, R, Mapper> {
+    MappedStream::new(self, mapper)
+  }
+
+  #[cfg(feature = "alloc")]
+  fn filter<Filter: for<'a> AsyncFn(&'a Self::Type) -> bool>(self, filter: Filter) -> FilteredStream<Self, Filter> {
+    FilteredStream::new(self, filter)
+  }
 
-  // fn filter<Filter: for<'a> AsyncFn(&'a Self::Type) -> bool>(self, filter: Filter) -> FilteredStream<Self, Filter> {
-  //   FilteredStream { stream: self, filter }
-  // }
+  #[cfg(feature = "alloc")]
+  fn filter_map<R, FilterMapper: AsyncFn(Self::Type) -> Option<R>>(self, filter_mapper: FilterMapper) -> FilteredMappedStream<Self, R, FilterMapper> {
+    FilteredMappedStream::new(self, filter_mapper)
+  }
+
+  #[cfg(feature = "alloc")]
+  fn flatten(self) -> FlattenedStream<Self>
+  where
+    Self::Type: ReadableStream,
+  {
+    FlattenedStream::new(self)
+  }
 
   fn read_until(self, value: Self::Type) -> ReadUntil<Self> {
     ReadUntil::new(self, value)
   }
 
+  fn take(self, limit: u64) -> TakenStream<Self> {
+    TakenStream::new(self, limit)
+  }
+
+  fn chain<Other: ReadableStream<Type = Self::Type>>(self, other: Other) -> ChainedStream<Self, Other> {
+    ChainedStream::new(self, other)
+  }
+
+  fn zip<Other: ReadableStream>(self, other: Other) -> ZippedStream<Self, Other> {
+    ZippedStream::new(self, other)
+  }
+
+  fn peekable<const N: usize>(self) -> PeekableStream<Self, N> {
+    PeekableStream::new(self)
+  }
+
+  fn windows<const N: usize>(self) -> WindowedStream<Self, N> {
+    WindowedStream::new(self)
+  }
+
+  fn buffered<const CAP: usize>(self) -> Buffered<Self, CAP> {
+    Buffered::new(self)
+  }
+
   async fn collect<C: Collectable<Self> + Default>(&mut self) -> Result<C, C::Error> {
     let mut collector = C::default();
 
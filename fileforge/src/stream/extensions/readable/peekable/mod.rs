@@ -0,0 +1,245 @@
+use crate::stream::{
+  error::{
+    stream_exhausted::StreamExhaustedError, stream_read::StreamReadError, stream_restore::StreamRestoreError, stream_rewind::StreamRewindError, stream_seek::StreamSeekError,
+    stream_skip::StreamSkipError,
+  },
+  ReadableStream, RestorableStream, RewindableStream, SeekableStream, CLONED,
+};
+
+/// A stream that can look at up to `N` of its next elements without reading past them.
+///
+/// Peeked elements are held until they're read, so the stream's offset stays where reading left
+/// off, and a peek that runs out of elements keeps the ones it got.
+pub struct PeekableStream<S: ReadableStream, const N: usize> {
+  stream: S,
+  peeked: [Option<S::Type>; N],
+  count: usize,
+}
+
+impl<S: ReadableStream, const N: usize> PeekableStream<S, N> {
+  pub(super) fn new(stream: S) -> Self {
+    Self {
+      stream,
+      peeked: [const { None }; N],
+      count: 0,
+    }
+  }
+
+  /// Gives back the underlying stream, which has already been read past any peeked elements.
+  pub fn into_inner(self) -> S {
+    self.stream
+  }
+
+  fn take_front(&mut self) -> S::Type {
+    let item = self.peeked[0].take().expect("There's a peeked element");
+
+    self.peeked.rotate_left(1);
+    self.count -= 1;
+    item
+  }
+}
+
+impl<S: ReadableStream, const N: usize> PeekableStream<S, N>
+where
+  S::Type: Clone,
+{
+  /// Reads the next `SIZE` elements without moving past them. `SIZE` can be at most `N`.
+  pub async fn peek<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[S::Type; SIZE]) -> V) -> Result<V, StreamReadError<S::ReadError>> {
+    const { assert!(SIZE <= N, "Can't peek further ahead than the stream holds") };
+
+    self.fill(SIZE).await?;
+
+    let items: [S::Type; SIZE] = core::array::from_fn(|index| self.peeked[index].clone().expect("Enough elements were peeked"));
+
+    Ok(reader(&items).await)
+  }
+
+  async fn fill(&mut self, size: usize) -> Result<(), StreamReadError<S::ReadError>> {
+    while self.count < size {
+      match self.stream.read(CLONED).await {
+        Ok(item) => {
+          self.peeked[self.count] = Some(item);
+          self.count += 1;
+        }
+        Err(StreamReadError::StreamExhausted(_)) => {
+          return Err(StreamReadError::StreamExhausted(StreamExhaustedError {
+            stream_length: self.stream.offset(),
+            read_length: size as u64,
+            read_offset: self.offset(),
+          }))
+        }
+        Err(error) => return Err(error),
+      }
+    }
+
+    Ok(())
+  }
+}
+
+impl<S: ReadableStream, const N: usize> ReadableStream for PeekableStream<S, N>
+where
+  S::Type: Clone,
+{
+  type Type = S::Type;
+  type ReadError = S::ReadError;
+  type SkipError = S::SkipError;
+
+  fn len(&self) -> Option<u64> {
+    self.stream.len()
+  }
+  fn offset(&self) -> u64 {
+    self.stream.offset() - self.count as u64
+  }
+
+  async fn read<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[Self::Type; SIZE]) -> V) -> Result<V, StreamReadError<Self::ReadError>> {
+    if self.count == 0 {
+      return self.stream.read(reader).await;
+    }
+
+    if SIZE <= N {
+      self.fill(SIZE).await?;
+
+      let items: [S::Type; SIZE] = core::array::from_fn(|_| self.take_front());
+
+      return Ok(reader(&items).await);
+    }
+
+    // too many to peek, so whatever isn't peeked already comes straight from the stream
+    let offset = self.offset();
+
+    if let Some(length) = self.len() {
+      StreamExhaustedError::assert(length, offset, SIZE as u64)?;
+    }
+
+    let mut items: [Option<S::Type>; SIZE] = [const { None }; SIZE];
+
+    for (index, slot) in items.iter_mut().enumerate() {
+      *slot = Some(match self.count {
+        0 => self.stream.read(CLONED).await.map_err(|error| match error {
+          StreamReadError::StreamExhausted(_) => StreamReadError::StreamExhausted(StreamExhaustedError {
+            stream_length: offset + index as u64,
+            read_length: SIZE as u64,
+            read_offset: offset,
+          }),
+          error => error,
+        })?,
+        _ => self.take_front(),
+      });
+    }
+
+    Ok(reader(&items.map(|item| item.expect("Every element was read"))).await)
+  }
+
+  async fn skip(&mut self, size: u64) -> Result<(), StreamSkipError<Self::SkipError>> {
+    if let Some(length) = self.len() {
+      StreamSkipError::assert_relative_forwards(length, self.offset(), size)?;
+    }
+
+    let dropped = size.min(self.count as u64);
+
+    self.stream.skip(size - dropped).await?;
+
+    for _ in 0..dropped {
+      self.take_front();
+    }
+
+    Ok(())
+  }
+}
+
+impl<S: RewindableStream, const N: usize> RewindableStream for PeekableStream<S, N>
+where
+  S::Type: Clone,
+{
+  type RewindError = S::RewindError;
+
+  async fn rewind(&mut self, size: u64) -> Result<(), StreamRewindError<Self::RewindError>> {
+    StreamRewindError::assert_relative_backwards(self.len().unwrap_or(self.offset()), self.offset(), size)?;
+
+    self.stream.rewind(size + self.count as u64).await?;
+    self.peeked = [const { None }; N];
+    self.count = 0;
+    Ok(())
+  }
+}
+
+impl<S: SeekableStream, const N: usize> SeekableStream for PeekableStream<S, N>
+where
+  S::Type: Clone,
+{
+  type SeekError = S::SeekError;
+
+  async fn seek(&mut self, offset: u64) -> Result<(), StreamSeekError<Self::SeekError>> {
+    self.stream.seek(offset).await?;
+    self.peeked = [const { None }; N];
+    self.count = 0;
+    Ok(())
+  }
+}
+
+impl<S: RestorableStream, const N: usize> RestorableStream for PeekableStream<S, N>
+where
+  S::Type: Clone,
+{
+  type Snapshot = (S::Snapshot, [Option<S::Type>; N], usize);
+  type RestoreError = S::RestoreError;
+
+  fn snapshot(&self) -> Self::Snapshot {
+    (self.stream.snapshot(), self.peeked.clone(), self.count)
+  }
+
+  async fn restore(&mut self, (snapshot, peeked, count): Self::Snapshot) -> Result<(), StreamRestoreError<Self::RestoreError>> {
+    self.stream.restore(snapshot).await?;
+    self.peeked = peeked;
+    self.count = count;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::vec::Vec;
+
+  use crate::{
+    provider::hint::ReadHint,
+    stream::{builtin::provider::ProviderStream, extensions::readable::ReadableStreamExt, ReadableStream, RestorableStream, RewindableStream, SINGLE},
+  };
+
+  #[tokio::test]
+  async fn peeks_without_moving() {
+    let data: Vec<u8> = (0..8).collect();
+    let mut stream = ProviderStream::new(data, ReadHint::new()).peekable::<3>();
+
+    assert_eq!(stream.peek(async |values: &[u8; 2]| *values).await.unwrap(), [0, 1]);
+    assert_eq!(stream.offset(), 0);
+    assert_eq!(stream.next().await.unwrap(), 0);
+
+    assert_eq!(stream.peek(async |values: &[u8; 3]| *values).await.unwrap(), [1, 2, 3]);
+    assert_eq!(stream.read(async |values: &[u8; 4]| *values).await.unwrap(), [1, 2, 3, 4]);
+
+    assert_eq!(stream.peek(SINGLE).await.unwrap(), 5);
+    stream.skip(2).await.unwrap();
+    assert_eq!(stream.offset(), 7);
+
+    stream.rewind(3).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.next().await.unwrap(), 4);
+  }
+
+  #[tokio::test]
+  async fn keeps_peeked_elements_when_exhausted() {
+    let data: Vec<u8> = (0..4).collect();
+    let mut stream = ProviderStream::new(data, ReadHint::new()).peekable::<4>();
+
+    stream.skip(2).await.unwrap();
+    assert_eq!(stream.peek(SINGLE).await.unwrap(), 2);
+
+    let snapshot = stream.snapshot();
+
+    assert!(stream.peek(async |values: &[u8; 3]| *values).await.is_err());
+    assert_eq!(stream.read(async |values: &[u8; 2]| *values).await.unwrap(), [2, 3]);
+
+    stream.restore(snapshot).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.offset(), 2);
+    assert_eq!(stream.read(async |values: &[u8; 2]| *values).await.unwrap(), [2, 3]);
+  }
+}
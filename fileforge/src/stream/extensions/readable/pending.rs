@@ -0,0 +1,92 @@
+use alloc::collections::VecDeque;
+
+use crate::stream::{
+  error::{stream_exhausted::StreamExhaustedError, stream_read::StreamReadError, stream_seek_out_of_bounds::StreamSeekOutOfBoundsError, stream_skip::StreamSkipError, user_read::UserReadError},
+  extensions::readable::error::SkipReadError,
+};
+
+/// Elements pulled from an underlying stream that haven't been read yet, for streams that yield
+/// an unknown number of elements per element underneath.
+///
+/// Elements are pulled one at a time until a read has enough, so one that runs out of elements
+/// partway leaves them here for a smaller read to pick up.
+#[derive(Clone)]
+pub(crate) struct Pending<T> {
+  items: VecDeque<T>,
+  offset: u64,
+}
+
+impl<T> Pending<T> {
+  pub(crate) fn new() -> Self {
+    Self { items: VecDeque::new(), offset: 0 }
+  }
+
+  pub(crate) fn offset(&self) -> u64 {
+    self.offset
+  }
+
+  /// Reads `SIZE` elements, calling `pull` for the next element until there are enough. `pull`
+  /// returns `None` for elements that don't yield anything.
+  pub(crate) async fn read<const SIZE: usize, V, E: UserReadError>(
+    &mut self,
+    mut pull: impl AsyncFnMut() -> Result<Option<T>, StreamReadError<E>>,
+    reader: impl AsyncFnOnce(&[T; SIZE]) -> V,
+  ) -> Result<V, StreamReadError<E>> {
+    while self.items.len() < SIZE {
+      match pull().await {
+        Ok(Some(item)) => self.items.push_back(item),
+        Ok(None) => {}
+        Err(StreamReadError::StreamExhausted(_)) => {
+          return Err(StreamReadError::StreamExhausted(StreamExhaustedError {
+            stream_length: self.offset + self.items.len() as u64,
+            read_length: SIZE as u64,
+            read_offset: self.offset,
+          }))
+        }
+        Err(StreamReadError::User(error)) => return Err(StreamReadError::User(error)),
+      }
+    }
+
+    let items: [T; SIZE] = core::array::from_fn(|_| self.items.pop_front().expect("Enough items were pulled"));
+
+    self.offset += SIZE as u64;
+    Ok(reader(&items).await)
+  }
+
+  pub(crate) async fn skip<E: UserReadError>(&mut self, size: u64, mut pull: impl AsyncFnMut() -> Result<Option<T>, StreamReadError<E>>) -> Result<(), StreamSkipError<SkipReadError<E>>> {
+    let seek_point = self.offset.checked_add(size).ok_or(StreamSkipError::SeekPointOverflowed {
+      stream_length: self.offset,
+      offset: self.offset,
+      seek_forwards_distance: size,
+    })?;
+
+    while self.offset < seek_point {
+      if self.items.pop_front().is_some() {
+        self.offset += 1;
+        continue;
+      }
+
+      match pull().await {
+        Ok(Some(item)) => self.items.push_back(item),
+        Ok(None) => {}
+        Err(StreamReadError::StreamExhausted(_)) => {
+          return Err(StreamSkipError::OutOfBounds(StreamSeekOutOfBoundsError {
+            stream_length: self.offset,
+            seek_point,
+          }))
+        }
+        Err(StreamReadError::User(error)) => return Err(StreamSkipError::User(SkipReadError::Read(error))),
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// A snapshot of a stream built on [`Pending`] elements: the underlying stream's snapshot, and the
+/// elements pulled from it but not yet read.
+#[derive(Clone)]
+pub struct PendingSnapshot<Snapshot, T> {
+  pub(crate) snapshot: Snapshot,
+  pub(crate) pending: Pending<T>,
+}
@@ -0,0 +1,146 @@
+use crate::stream::{
+  error::{
+    stream_exhausted::StreamExhaustedError, stream_read::StreamReadError, stream_restore::StreamRestoreError, stream_rewind::StreamRewindError, stream_seek::StreamSeekError,
+    stream_seek_out_of_bounds::StreamSeekOutOfBoundsError, stream_skip::StreamSkipError,
+  },
+  ReadableStream, RestorableStream, RewindableStream, SeekableStream,
+};
+
+/// At most `limit` elements of another stream, starting where it was when taken. Offsets start
+/// at zero, and the stream is as long as the limit, or what's left of the underlying stream if
+/// that's shorter.
+pub struct TakenStream<S: ReadableStream> {
+  stream: S,
+  start: u64,
+  limit: u64,
+}
+
+impl<S: ReadableStream> TakenStream<S> {
+  pub(super) fn new(stream: S, limit: u64) -> Self {
+    Self {
+      start: stream.offset(),
+      stream,
+      limit,
+    }
+  }
+
+  pub fn into_inner(self) -> S {
+    self.stream
+  }
+
+  /// The furthest the stream can go, which is the limit when the underlying stream's length is
+  /// unknown.
+  fn bound(&self) -> u64 {
+    self.len().unwrap_or(self.limit)
+  }
+}
+
+impl<S: ReadableStream> ReadableStream for TakenStream<S> {
+  type Type = S::Type;
+  type ReadError = S::ReadError;
+  type SkipError = S::SkipError;
+
+  fn len(&self) -> Option<u64> {
+    Some(self.stream.len()?.saturating_sub(self.start).min(self.limit))
+  }
+  fn offset(&self) -> u64 {
+    self.stream.offset() - self.start
+  }
+
+  async fn read<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[Self::Type; SIZE]) -> V) -> Result<V, StreamReadError<Self::ReadError>> {
+    StreamExhaustedError::assert(self.bound(), self.offset(), SIZE as u64)?;
+
+    self.stream.read(reader).await.map_err(|error| error.rebase(self.start, 0))
+  }
+
+  async fn skip(&mut self, size: u64) -> Result<(), StreamSkipError<Self::SkipError>> {
+    StreamSkipError::assert_relative_forwards(self.bound(), self.offset(), size)?;
+
+    self.stream.skip(size).await.map_err(|error| error.rebase(self.start, 0))
+  }
+}
+
+impl<S: RewindableStream> RewindableStream for TakenStream<S> {
+  type RewindError = S::RewindError;
+
+  async fn rewind(&mut self, size: u64) -> Result<(), StreamRewindError<Self::RewindError>> {
+    StreamRewindError::assert_relative_backwards(self.bound(), self.offset(), size)?;
+
+    self.stream.rewind(size).await
+  }
+}
+
+impl<S: SeekableStream> SeekableStream for TakenStream<S> {
+  type SeekError = S::SeekError;
+
+  async fn seek(&mut self, offset: u64) -> Result<(), StreamSeekError<Self::SeekError>> {
+    StreamSeekOutOfBoundsError::assert(self.bound(), offset)?;
+
+    self.stream.seek(self.start + offset).await.map_err(|error| error.rebase(self.start, 0))
+  }
+}
+
+impl<S: RestorableStream> RestorableStream for TakenStream<S> {
+  type Snapshot = S::Snapshot;
+  type RestoreError = S::RestoreError;
+
+  fn snapshot(&self) -> Self::Snapshot {
+    self.stream.snapshot()
+  }
+
+  async fn restore(&mut self, snapshot: Self::Snapshot) -> Result<(), StreamRestoreError<Self::RestoreError>> {
+    self.stream.restore(snapshot).await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::vec::Vec;
+
+  use crate::{
+    provider::hint::ReadHint,
+    stream::{
+      builtin::provider::ProviderStream,
+      error::{stream_read::StreamReadError, stream_seek::StreamSeekError},
+      extensions::readable::ReadableStreamExt,
+      ReadableStream, RewindableStream, SeekableStream,
+    },
+  };
+
+  #[tokio::test]
+  async fn limits_reads_to_the_taken_elements() {
+    let data: Vec<u8> = (0..10).collect();
+    let mut stream = ProviderStream::new(data, ReadHint::new());
+
+    stream.skip(2).await.unwrap();
+
+    let mut taken = (&mut stream).take(5);
+
+    assert_eq!(taken.len(), Some(5));
+    assert_eq!(taken.read(async |values: &[u8; 2]| *values).await.unwrap(), [2, 3]);
+    assert_eq!(taken.offset(), 2);
+
+    let error = taken.read(async |values: &[u8; 4]| *values).await.unwrap_err();
+    assert!(matches!(error, StreamReadError::StreamExhausted(ref exhausted) if exhausted.stream_length == 5 && exhausted.read_offset == 2));
+
+    taken.rewind(2).await.map_err(|_| {}).unwrap();
+    assert!(taken.rewind(1).await.is_err());
+    assert!(matches!(taken.seek(6).await, Err(StreamSeekError::OutOfBounds(_))));
+
+    taken.seek(4).await.map_err(|_| {}).unwrap();
+    assert_eq!(taken.next().await.unwrap(), 6);
+    assert!(taken.next().await.is_err());
+
+    assert_eq!(stream.next().await.unwrap(), 7);
+  }
+
+  #[tokio::test]
+  async fn is_only_as_long_as_the_underlying_stream() {
+    let data: Vec<u8> = (0..4).collect();
+    let mut taken = ProviderStream::new(data, ReadHint::new()).take(10);
+
+    assert_eq!(taken.len(), Some(4));
+    assert!(taken.skip(5).await.is_err());
+    assert_eq!(taken.read(async |values: &[u8; 4]| *values).await.unwrap(), [0, 1, 2, 3]);
+  }
+}
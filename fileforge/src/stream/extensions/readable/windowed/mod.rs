@@ -0,0 +1,243 @@
+use crate::stream::{
+  error::{
+    stream_exhausted::StreamExhaustedError, stream_read::StreamReadError, stream_restore::StreamRestoreError, stream_rewind::StreamRewindError, stream_seek::StreamSeekError,
+    stream_seek_out_of_bounds::StreamSeekOutOfBoundsError, stream_skip::StreamSkipError,
+  },
+  extensions::readable::error::{EitherError, SkipReadError},
+  ReadableStream, RestorableStream, RewindableStream, SeekableStream, CLONED,
+};
+
+/// Every run of `N` consecutive elements of another stream, each starting one element after the
+/// last, from wherever the stream was when windowed.
+///
+/// Each window after the first reads one new element. Skipping fewer windows than one is wide
+/// reads the elements it passes, and further skips the underlying stream. A read of several
+/// windows from a stream of unknown length that runs out partway has still moved past the ones it
+/// got.
+pub struct WindowedStream<S: ReadableStream, const N: usize> {
+  stream: S,
+  start: u64,
+  offset: u64,
+  window: Option<[S::Type; N]>,
+}
+
+impl<S: ReadableStream, const N: usize> WindowedStream<S, N> {
+  pub(super) fn new(stream: S) -> Self {
+    const { assert!(N > 0, "Windows have to hold at least one element") };
+
+    Self {
+      start: stream.offset(),
+      offset: 0,
+      window: None,
+      stream,
+    }
+  }
+
+  pub fn into_inner(self) -> S {
+    self.stream
+  }
+
+  /// The number of windows in `length` elements of the underlying stream.
+  fn windows(&self, length: u64) -> u64 {
+    (length.saturating_sub(self.start) + 1).saturating_sub(N as u64)
+  }
+}
+
+impl<S: ReadableStream, const N: usize> WindowedStream<S, N>
+where
+  S::Type: Clone,
+{
+  /// Reads the next window, which is the last one with a new element on the end.
+  async fn advance(&mut self) -> Result<[S::Type; N], StreamReadError<S::ReadError>> {
+    let window = match &mut self.window {
+      Some(window) => {
+        let item = self.stream.read(CLONED).await?;
+
+        window.rotate_left(1);
+        window[N - 1] = item;
+        window.clone()
+      }
+      None => {
+        let window = self.stream.read(async |items: &[S::Type; N]| items.clone()).await?;
+
+        self.window = Some(window.clone());
+        window
+      }
+    };
+
+    self.offset += 1;
+    Ok(window)
+  }
+}
+
+impl<S: ReadableStream, const N: usize> ReadableStream for WindowedStream<S, N>
+where
+  S::Type: Clone,
+{
+  type Type = [S::Type; N];
+  type ReadError = S::ReadError;
+  type SkipError = EitherError<S::SkipError, SkipReadError<S::ReadError>>;
+
+  fn len(&self) -> Option<u64> {
+    Some(self.windows(self.stream.len()?))
+  }
+  fn offset(&self) -> u64 {
+    self.offset
+  }
+
+  async fn read<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[Self::Type; SIZE]) -> V) -> Result<V, StreamReadError<Self::ReadError>> {
+    let offset = self.offset;
+
+    if let Some(length) = self.len() {
+      StreamExhaustedError::assert(length, offset, SIZE as u64)?;
+    }
+
+    let mut windows: [Option<[S::Type; N]>; SIZE] = [const { None }; SIZE];
+
+    for slot in windows.iter_mut() {
+      *slot = Some(self.advance().await.map_err(|error| match error {
+        StreamReadError::StreamExhausted(_) => StreamReadError::StreamExhausted(StreamExhaustedError {
+          stream_length: self.offset,
+          read_length: SIZE as u64,
+          read_offset: offset,
+        }),
+        error => error,
+      })?);
+    }
+
+    Ok(reader(&windows.map(|window| window.expect("Every window was read"))).await)
+  }
+
+  async fn skip(&mut self, size: u64) -> Result<(), StreamSkipError<Self::SkipError>> {
+    let seek_point = match self.len() {
+      Some(length) => StreamSkipError::assert_relative_forwards(length, self.offset, size)?,
+      None => self.offset.saturating_add(size),
+    };
+
+    // the current window already holds the next N - 1 elements
+    let held = if self.window.is_some() { N as u64 - 1 } else { 0 };
+
+    if size < held {
+      for _ in 0..size {
+        self.advance().await.map_err(|error| match error {
+          StreamReadError::User(error) => StreamSkipError::User(EitherError::Second(SkipReadError::Read(error))),
+          StreamReadError::StreamExhausted(_) => StreamSkipError::OutOfBounds(StreamSeekOutOfBoundsError {
+            stream_length: self.offset,
+            seek_point,
+          }),
+        })?;
+      }
+
+      return Ok(());
+    }
+
+    self.stream.skip(size - held).await.map_err(|error| match error.map_user(EitherError::First) {
+      StreamSkipError::OutOfBounds(bounds) => StreamSkipError::OutOfBounds(StreamSeekOutOfBoundsError {
+        stream_length: self.windows(bounds.stream_length),
+        seek_point,
+      }),
+      error => error,
+    })?;
+
+    self.window = None;
+    self.offset = seek_point;
+    Ok(())
+  }
+}
+
+impl<S: RewindableStream, const N: usize> RewindableStream for WindowedStream<S, N>
+where
+  S::Type: Clone,
+{
+  type RewindError = S::RewindError;
+
+  async fn rewind(&mut self, size: u64) -> Result<(), StreamRewindError<Self::RewindError>> {
+    let offset = StreamRewindError::assert_relative_backwards(self.len().unwrap_or(self.offset), self.offset, size)?;
+
+    self.stream.rewind(self.stream.offset() - (self.start + offset)).await?;
+    self.window = None;
+    self.offset = offset;
+    Ok(())
+  }
+}
+
+impl<S: SeekableStream, const N: usize> SeekableStream for WindowedStream<S, N>
+where
+  S::Type: Clone,
+{
+  type SeekError = S::SeekError;
+
+  async fn seek(&mut self, offset: u64) -> Result<(), StreamSeekError<Self::SeekError>> {
+    if let Some(length) = self.len() {
+      StreamSeekOutOfBoundsError::assert(length, offset)?;
+    }
+
+    self.stream.seek(self.start + offset).await.map_err(|error| match error {
+      StreamSeekError::OutOfBounds(bounds) => StreamSeekError::OutOfBounds(StreamSeekOutOfBoundsError {
+        stream_length: self.windows(bounds.stream_length),
+        seek_point: offset,
+      }),
+      error => error,
+    })?;
+
+    self.window = None;
+    self.offset = offset;
+    Ok(())
+  }
+}
+
+impl<S: RestorableStream, const N: usize> RestorableStream for WindowedStream<S, N>
+where
+  S::Type: Clone,
+{
+  type Snapshot = (S::Snapshot, Option<[S::Type; N]>, u64);
+  type RestoreError = S::RestoreError;
+
+  fn snapshot(&self) -> Self::Snapshot {
+    (self.stream.snapshot(), self.window.clone(), self.offset)
+  }
+
+  async fn restore(&mut self, (snapshot, window, offset): Self::Snapshot) -> Result<(), StreamRestoreError<Self::RestoreError>> {
+    if offset > self.offset {
+      return Err(StreamRestoreError::CannotRestoreForwards);
+    }
+
+    self.stream.restore(snapshot).await?;
+    self.window = window;
+    self.offset = offset;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::vec::Vec;
+
+  use crate::{
+    provider::hint::ReadHint,
+    stream::{builtin::provider::ProviderStream, extensions::readable::ReadableStreamExt, ReadableStream, RewindableStream, SeekableStream},
+  };
+
+  #[tokio::test]
+  async fn slides_one_element_at_a_time() {
+    let data: Vec<u8> = (0..6).collect();
+    let mut stream = ProviderStream::new(data, ReadHint::new()).windows::<3>();
+
+    assert_eq!(stream.len(), Some(4));
+    assert_eq!(stream.read(async |windows: &[[u8; 3]; 2]| *windows).await.unwrap(), [[0, 1, 2], [1, 2, 3]]);
+
+    stream.skip(1).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.next().await.unwrap(), [3, 4, 5]);
+    assert!(stream.next().await.is_err());
+
+    stream.rewind(3).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.next().await.unwrap(), [1, 2, 3]);
+
+    stream.skip(2).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.offset(), 4);
+    assert!(stream.skip(1).await.is_err());
+
+    stream.seek(0).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.next().await.unwrap(), [0, 1, 2]);
+  }
+}
@@ -0,0 +1,187 @@
+use crate::stream::{
+  error::{
+    stream_exhausted::StreamExhaustedError, stream_read::StreamReadError, stream_restore::StreamRestoreError, stream_rewind::StreamRewindError, stream_seek::StreamSeekError,
+    stream_seek_out_of_bounds::StreamSeekOutOfBoundsError, stream_skip::StreamSkipError,
+  },
+  extensions::readable::error::EitherError,
+  ReadableStream, RestorableStream, RewindableStream, SeekableStream,
+};
+
+/// Pairs of elements from two streams read side by side, from wherever each was when zipped, as
+/// long as the shorter one lasts.
+///
+/// Both streams are checked for enough elements before a read or skip when they know their
+/// lengths. Otherwise the first stream has already moved on if the second runs out.
+pub struct ZippedStream<A: ReadableStream, B: ReadableStream> {
+  first: A,
+  second: B,
+  first_start: u64,
+  second_start: u64,
+}
+
+impl<A: ReadableStream, B: ReadableStream> ZippedStream<A, B> {
+  pub(super) fn new(first: A, second: B) -> Self {
+    Self {
+      first_start: first.offset(),
+      second_start: second.offset(),
+      first,
+      second,
+    }
+  }
+
+  pub fn into_inner(self) -> (A, B) {
+    (self.first, self.second)
+  }
+
+  /// Checks that both streams have `size` more elements, as far as they know.
+  fn assert_remaining(&self, size: u64) -> Result<(), StreamExhaustedError> {
+    let offset = self.first.offset() - self.first_start;
+
+    match self.first.remaining().into_iter().chain(self.second.remaining()).min() {
+      Some(remaining) => StreamExhaustedError::assert(offset + remaining, offset, size),
+      None => Ok(()),
+    }
+  }
+}
+
+impl<A: ReadableStream, B: ReadableStream> ReadableStream for ZippedStream<A, B>
+where
+  A::Type: Clone,
+  B::Type: Clone,
+{
+  type Type = (A::Type, B::Type);
+  type ReadError = EitherError<A::ReadError, B::ReadError>;
+  type SkipError = EitherError<A::SkipError, B::SkipError>;
+
+  fn len(&self) -> Option<u64> {
+    Some((self.first.len()? - self.first_start).min(self.second.len()? - self.second_start))
+  }
+  fn offset(&self) -> u64 {
+    self.first.offset() - self.first_start
+  }
+
+  async fn read<const SIZE: usize, V>(&mut self, reader: impl AsyncFnOnce(&[Self::Type; SIZE]) -> V) -> Result<V, StreamReadError<Self::ReadError>> {
+    self.assert_remaining(SIZE as u64)?;
+
+    let (first_start, second_start) = (self.first_start, self.second_start);
+    let second = &mut self.second;
+
+    let result = self
+      .first
+      .read(async |firsts: &[A::Type; SIZE]| {
+        second
+          .read(async |seconds: &[B::Type; SIZE]| {
+            let pairs: [(A::Type, B::Type); SIZE] = core::array::from_fn(|index| (firsts[index].clone(), seconds[index].clone()));
+
+            reader(&pairs).await
+          })
+          .await
+      })
+      .await;
+
+    match result {
+      Ok(Ok(value)) => Ok(value),
+      Ok(Err(error)) => Err(error.map_user(EitherError::Second).rebase(second_start, 0)),
+      Err(error) => Err(error.map_user(EitherError::First).rebase(first_start, 0)),
+    }
+  }
+
+  async fn skip(&mut self, size: u64) -> Result<(), StreamSkipError<Self::SkipError>> {
+    if let Err(exhausted) = self.assert_remaining(size) {
+      return Err(StreamSkipError::OutOfBounds(StreamSeekOutOfBoundsError {
+        stream_length: exhausted.stream_length,
+        seek_point: exhausted.read_offset + size,
+      }));
+    }
+
+    let start = self.first_start;
+    self.first.skip(size).await.map_err(|error| error.map_user(EitherError::First).rebase(start, 0))?;
+
+    let start = self.second_start;
+    self.second.skip(size).await.map_err(|error| error.map_user(EitherError::Second).rebase(start, 0))
+  }
+}
+
+impl<A: RewindableStream, B: RewindableStream> RewindableStream for ZippedStream<A, B>
+where
+  A::Type: Clone,
+  B::Type: Clone,
+{
+  type RewindError = EitherError<A::RewindError, B::RewindError>;
+
+  async fn rewind(&mut self, size: u64) -> Result<(), StreamRewindError<Self::RewindError>> {
+    StreamRewindError::assert_relative_backwards(self.len().unwrap_or(self.offset()), self.offset(), size)?;
+
+    self.first.rewind(size).await.map_err(|error| error.map_user(EitherError::First))?;
+    self.second.rewind(size).await.map_err(|error| error.map_user(EitherError::Second))
+  }
+}
+
+impl<A: SeekableStream, B: SeekableStream> SeekableStream for ZippedStream<A, B>
+where
+  A::Type: Clone,
+  B::Type: Clone,
+{
+  type SeekError = EitherError<A::SeekError, B::SeekError>;
+
+  async fn seek(&mut self, offset: u64) -> Result<(), StreamSeekError<Self::SeekError>> {
+    if let Some(length) = self.len() {
+      StreamSeekOutOfBoundsError::assert(length, offset)?;
+    }
+
+    let start = self.first_start;
+    self.first.seek(start + offset).await.map_err(|error| error.map_user(EitherError::First).rebase(start, 0))?;
+
+    let start = self.second_start;
+    self.second.seek(start + offset).await.map_err(|error| error.map_user(EitherError::Second).rebase(start, 0))
+  }
+}
+
+impl<A: RestorableStream, B: RestorableStream> RestorableStream for ZippedStream<A, B>
+where
+  A::Type: Clone,
+  B::Type: Clone,
+{
+  type Snapshot = (A::Snapshot, B::Snapshot);
+  type RestoreError = EitherError<A::RestoreError, B::RestoreError>;
+
+  fn snapshot(&self) -> Self::Snapshot {
+    (self.first.snapshot(), self.second.snapshot())
+  }
+
+  async fn restore(&mut self, (first, second): Self::Snapshot) -> Result<(), StreamRestoreError<Self::RestoreError>> {
+    self.first.restore(first).await.map_err(|error| error.map_user(EitherError::First))?;
+    self.second.restore(second).await.map_err(|error| error.map_user(EitherError::Second))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::{vec, vec::Vec};
+
+  use crate::{
+    provider::hint::ReadHint,
+    stream::{builtin::provider::ProviderStream, extensions::readable::ReadableStreamExt, ReadableStream, RewindableStream, SeekableStream},
+  };
+
+  #[tokio::test]
+  async fn pairs_elements_until_the_shorter_stream_ends() {
+    let numbers: Vec<u8> = (0..6).collect();
+    let letters: Vec<char> = vec!['a', 'b', 'c', 'd'];
+    let mut stream = ProviderStream::new(numbers, ReadHint::new()).zip(ProviderStream::new(letters, ReadHint::new()));
+
+    assert_eq!(stream.len(), Some(4));
+    assert_eq!(stream.read(async |pairs: &[(u8, char); 2]| *pairs).await.map_err(|_| {}).unwrap(), [(0, 'a'), (1, 'b')]);
+
+    assert!(stream.read(async |pairs: &[(u8, char); 3]| *pairs).await.is_err());
+    assert!(stream.skip(3).await.is_err());
+    assert_eq!(stream.offset(), 2);
+
+    stream.rewind(1).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.next().await.map_err(|_| {}).unwrap(), (1, 'b'));
+
+    stream.seek(3).await.map_err(|_| {}).unwrap();
+    assert_eq!(stream.next().await.map_err(|_| {}).unwrap(), (3, 'd'));
+    assert!(stream.next().await.is_err());
+  }
+}
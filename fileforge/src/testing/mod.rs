@@ -0,0 +1,72 @@
+//! Conformance checks for stream and provider implementations, enabled by the `testing` feature.
+//!
+//! Each check takes a factory making a fresh instance and the elements it should hold, then
+//! exercises one trait's contract over every position, panicking with a description of the first
+//! thing it gets wrong. Stream checks that start over at each position only try both ends and a
+//! spread of positions between them once there are more than a few hundred elements. Streams have to start at offset 0. Streams that don't know their length
+//! are only required to fail past the end, but the errors they do give are still checked.
+//!
+//! Checks that mutate swap neighbouring elements, so the expected elements should differ from
+//! their neighbours for those to notice anything.
+
+pub mod provider;
+pub mod stream;
+
+use alloc::vec::Vec;
+
+use crate::{
+  provider::{hint::ReadHint, Provider},
+  stream::{ReadableStream, CLONED},
+};
+
+/// Inputs up to this long are checked at every position.
+const ALL_POSITIONS_UP_TO: u64 = 256;
+
+/// Positions checked at each end of longer inputs, and roughly how many between them.
+const EDGE_POSITIONS: u64 = 16;
+const SPREAD_POSITIONS: u64 = 48;
+
+/// The positions in `0..=length` worth checking, which is all of them unless `length` is long
+/// enough that checking each would be slow.
+fn positions(length: u64) -> impl Iterator<Item = u64> {
+  // odd, so the spread doesn't only land on even positions
+  let step = (length / SPREAD_POSITIONS) | 1;
+
+  (0..=length).filter(move |&position| length <= ALL_POSITIONS_UP_TO || position < EDGE_POSITIONS || length - position < EDGE_POSITIONS || position % step == 0)
+}
+
+/// Reads `count` elements from `stream` one at a time.
+async fn read_elements<S: ReadableStream>(stream: &mut S, count: u64, context: &str) -> Vec<S::Type>
+where
+  S::Type: Clone,
+{
+  let mut elements = Vec::new();
+
+  for _ in 0..count {
+    let offset = stream.offset();
+    let element = stream.read(CLONED).await.unwrap_or_else(|_| panic!("{context}: reading the element at {offset} failed"));
+
+    elements.push(element);
+  }
+
+  elements
+}
+
+/// Reads every element of `provider` one at a time.
+async fn provider_elements<P: Provider>(provider: &P, context: &str) -> Vec<P::Type>
+where
+  P::Type: Clone,
+{
+  let mut elements = Vec::new();
+
+  for offset in 0..provider.len() {
+    let element = provider
+      .read(offset, ReadHint::new(), async |elements: &[P::Type; 1]| elements[0].clone())
+      .await
+      .unwrap_or_else(|_| panic!("{context}: reading the element at {offset} failed"));
+
+    elements.push(element);
+  }
+
+  elements
+}
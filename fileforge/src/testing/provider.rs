@@ -0,0 +1,245 @@
+use core::fmt::Debug;
+
+use crate::{
+  provider::{
+    error::{
+      out_of_bounds::OutOfBoundsError, provider_mutate::ProviderMutateError, provider_partition::ProviderPartitionError, provider_read::ProviderReadError, provider_resize::ProviderResizeError,
+      provider_slice::ProviderSliceError,
+    },
+    hint::ReadHint,
+    MutProvider, PartitionableProvider, Provider, ResizableProvider,
+  },
+  stream::{builtin::provider::ProviderStream, UserMutateError, UserPartitionError, UserReadError},
+  testing::{provider_elements, stream},
+};
+
+const CHUNK: usize = 3;
+const SLICE: usize = 2;
+
+/// Checks an out of bounds error describes `(provider_size, read_offset, read_length)`.
+fn assert_out_of_bounds(error: Option<OutOfBoundsError>, expected: (u64, u64, Option<u64>), context: &str) {
+  let error = error.unwrap_or_else(|| panic!("{context} should fail with OutOfBounds"));
+
+  assert_eq!(
+    (error.provider_size, error.read_offset, error.read_length),
+    expected,
+    "{context}: the out of bounds error reports the wrong (provider size, read offset, read length)"
+  );
+}
+
+/// Checks a provider reads `expected` at every offset, alone and in chunks, slices it, and fails
+/// past its end, then checks the readable stream traits over it through a [`ProviderStream`].
+pub async fn readable<P: Provider>(factory: impl Fn() -> P, expected: &[P::Type])
+where
+  P::Type: Clone + PartialEq + Debug,
+  P::ReadError: UserReadError,
+{
+  let length = expected.len() as u64;
+  let provider = factory();
+
+  assert_eq!(provider.len(), length, "The provider reports the wrong length");
+  assert_eq!(provider_elements(&provider, "Reading the provider").await, expected, "The provider was read wrong");
+
+  for offset in 0..=length.saturating_sub(CHUNK as u64) {
+    let read = provider
+      .read(offset, ReadHint::new(), async |elements: &[P::Type; CHUNK]| elements.clone())
+      .await
+      .unwrap_or_else(|_| panic!("Reading a chunk at {offset} failed"));
+
+    assert_eq!(read.as_slice(), &expected[offset as usize..offset as usize + CHUNK], "The chunk at {offset} was read wrong");
+  }
+
+  for offset in length.saturating_sub(CHUNK as u64 - 1)..=length {
+    let error = match provider.read(offset, ReadHint::new(), async |_: &[P::Type; CHUNK]| {}).await {
+      Ok(()) => panic!("Reading a chunk running past the end at {offset} should fail"),
+      Err(ProviderReadError::OutOfBounds(error)) => Some(error),
+      Err(ProviderReadError::User(_)) => None,
+    };
+    assert_out_of_bounds(error, (length, offset, Some(CHUNK as u64)), "Reading a chunk running past the end");
+  }
+
+  for start in 0..=length.saturating_sub(SLICE as u64) {
+    let slice = provider.slice::<SLICE>(start).unwrap_or_else(|_| panic!("Slicing {SLICE} elements at {start} failed"));
+
+    assert_eq!(slice.len(), SLICE as u64, "A slice of {SLICE} elements reports the wrong length");
+    assert_eq!(
+      provider_elements(&slice, "Reading a slice").await,
+      expected[start as usize..start as usize + SLICE],
+      "The slice of {SLICE} elements at {start} holds the wrong elements"
+    );
+  }
+
+  for start in length.saturating_sub(SLICE as u64 - 1)..=length {
+    let error = match provider.slice::<SLICE>(start) {
+      Ok(_) => panic!("Slicing past the end at {start} should fail"),
+      Err(ProviderSliceError::OutOfBounds(error)) => Some(error),
+      Err(ProviderSliceError::User(_)) => None,
+    };
+    assert_out_of_bounds(error, (length, start, Some(SLICE as u64)), "Slicing past the end");
+  }
+
+  for start in 0..=length {
+    for size in [None, Some(length - start)] {
+      let slice = provider.slice_dynamic(start, size).unwrap_or_else(|_| panic!("Slicing {size:?} elements at {start} failed"));
+
+      assert_eq!(slice.len(), length - start, "A slice of {size:?} elements at {start} reports the wrong length");
+      assert_eq!(
+        provider_elements(&slice, "Reading a slice").await,
+        expected[start as usize..],
+        "The slice of {size:?} elements at {start} holds the wrong elements"
+      );
+    }
+
+    let error = match provider.slice_dynamic(start, Some(length - start + 1)) {
+      Ok(_) => panic!("Slicing past the end at {start} should fail"),
+      Err(ProviderSliceError::OutOfBounds(error)) => Some(error),
+      Err(ProviderSliceError::User(_)) => None,
+    };
+    assert_out_of_bounds(error, (length, start, Some(length - start + 1)), "Slicing past the end");
+  }
+
+  let error = match provider.slice_dynamic(length + 1, None) {
+    Ok(_) => panic!("Slicing from past the end should fail"),
+    Err(ProviderSliceError::OutOfBounds(error)) => Some(error),
+    Err(ProviderSliceError::User(_)) => None,
+  };
+  assert_out_of_bounds(error, (length, length + 1, None), "Slicing from past the end");
+
+  let factory = async || ProviderStream::new(factory(), ReadHint::new());
+
+  stream::readable(&factory, expected).await;
+  stream::rewindable(&factory, expected).await;
+  stream::seekable(&factory, expected).await;
+  stream::restorable(&factory, expected).await;
+}
+
+/// Checks mutating every pair of neighbouring elements changes them in place, and that mutating
+/// past the end fails, then checks the same through a [`ProviderStream`].
+pub async fn mutable<P: MutProvider>(factory: impl Fn() -> P, expected: &[P::Type])
+where
+  P::Type: Clone + PartialEq + Debug,
+  P::ReadError: UserReadError,
+  P::MutateError: UserMutateError,
+{
+  let length = expected.len() as u64;
+
+  for offset in 0..length.saturating_sub(1) {
+    let mut provider = factory();
+
+    provider
+      .mutate(offset, async |elements: &mut [P::Type; 2]| elements.swap(0, 1))
+      .await
+      .unwrap_or_else(|_| panic!("Mutating the elements at {offset} failed"));
+
+    let mut swapped = expected.to_vec();
+    swapped.swap(offset as usize, offset as usize + 1);
+
+    assert_eq!(provider.len(), length, "Mutating changed the provider's length");
+    assert_eq!(
+      provider_elements(&provider, "Reading after mutating").await,
+      swapped,
+      "Mutating the elements at {offset} changed the wrong elements"
+    );
+  }
+
+  let offset = length.saturating_sub(1);
+  let error = match factory().mutate(offset, async |_: &mut [P::Type; 2]| {}).await {
+    Ok(()) => panic!("Mutating past the end should fail"),
+    Err(ProviderMutateError::OutOfBounds(error)) => Some(error),
+    Err(ProviderMutateError::User(_)) => None,
+  };
+  assert_out_of_bounds(error, (length, offset, Some(2)), "Mutating past the end");
+
+  stream::mutable(async || ProviderStream::new(factory(), ReadHint::new()), expected).await;
+}
+
+/// Checks resizing every run of elements keeps everything around it, and that resizing past the
+/// end fails, then checks overwriting through a [`ProviderStream`].
+pub async fn resizable<P: ResizableProvider>(factory: impl Fn() -> P, expected: &[P::Type])
+where
+  P::Type: Clone + PartialEq + Debug,
+  P::ReadError: UserReadError,
+{
+  let length = expected.len() as u64;
+
+  for offset in 0..=length {
+    for old_length in 0..=length - offset {
+      for new_length in [0, old_length, old_length + 2] {
+        let mut provider = factory();
+
+        provider
+          .resize_at(offset, old_length, new_length)
+          .await
+          .unwrap_or_else(|_| panic!("Resizing {old_length} elements at {offset} to {new_length} failed"));
+        assert_eq!(
+          provider.len(),
+          length - old_length + new_length,
+          "Resizing {old_length} elements at {offset} to {new_length} left the wrong length"
+        );
+
+        let elements = provider_elements(&provider, "Reading after resizing").await;
+        let (head, tail) = (offset as usize, (offset + new_length) as usize);
+
+        assert_eq!(elements[..head], expected[..head], "Resizing elements at {offset} changed the ones before them");
+        assert_eq!(
+          elements[tail..],
+          expected[(offset + old_length) as usize..],
+          "Resizing elements at {offset} didn't move the ones after them"
+        );
+      }
+    }
+
+    let old_length = length - offset + 1;
+    let error = match factory().resize_at(offset, old_length, 0).await {
+      Ok(()) => panic!("Resizing past the end should fail"),
+      Err(ProviderResizeError::OutOfBounds(error)) => Some(error),
+      Err(ProviderResizeError::User(_)) => None,
+    };
+    assert_out_of_bounds(error, (length, offset, Some(old_length)), "Resizing past the end");
+  }
+
+  let factory = async || ProviderStream::new(factory(), ReadHint::new());
+
+  stream::resizable(&factory, expected).await;
+}
+
+/// Checks partitioning at every offset splits the elements between the halves, and that
+/// partitioning past the end fails, then checks partitioning through a [`ProviderStream`].
+pub async fn partitionable<P: PartitionableProvider>(factory: impl Fn() -> P, expected: &[P::Type])
+where
+  P::Type: Copy + PartialEq + Debug,
+  P::ReadError: UserReadError,
+  P::PartitionError: UserPartitionError,
+  <P::PartitionLeftProvider as Provider>::ReadError: UserReadError,
+  <P::PartitionRightProvider as Provider>::ReadError: UserReadError,
+{
+  let length = expected.len() as u64;
+
+  for at in 0..=length {
+    let (left, right) = factory().partition(at).unwrap_or_else(|_| panic!("Partitioning at {at} failed"));
+
+    assert_eq!(left.len(), at, "Partitioning at {at}: the left half reports the wrong length");
+    assert_eq!(
+      provider_elements(&left, "Reading the left half").await,
+      expected[..at as usize],
+      "Partitioning at {at}: the left half holds the wrong elements"
+    );
+    assert_eq!(right.len(), length - at, "Partitioning at {at}: the right half reports the wrong length");
+    assert_eq!(
+      provider_elements(&right, "Reading the right half").await,
+      expected[at as usize..],
+      "Partitioning at {at}: the right half holds the wrong elements"
+    );
+  }
+
+  match factory().partition(length + 1) {
+    Ok(_) => panic!("Partitioning past the end should fail"),
+    Err(ProviderPartitionError::OutOfBounds(error)) => assert_eq!(error.provider_size, length, "Partitioning past the end: the out of bounds error reports the wrong provider size"),
+    Err(ProviderPartitionError::User(_)) => panic!("Partitioning past the end should fail with OutOfBounds"),
+  }
+
+  let factory = async || ProviderStream::new(factory(), ReadHint::new());
+
+  stream::static_partitionable::<SLICE, _>(&factory, expected).await;
+  stream::dynamic_partitionable(&factory, expected).await;
+}
@@ -0,0 +1,424 @@
+use core::fmt::Debug;
+
+use crate::{
+  stream::{
+    error::{stream_exhausted::StreamExhaustedError, stream_restore::StreamRestoreError},
+    DynamicPartitionableStream, MutableStream, ReadableStream, ResizableStream, RestorableStream, RewindableStream, SeekableStream, StaticPartitionableStream, StreamMutateError, StreamOverwriteError,
+    StreamPartitionError, StreamReadError, StreamRewindError, StreamSeekError, StreamSkipError, CLONED,
+  },
+  testing::{positions, read_elements},
+};
+
+const CHUNK: usize = 3;
+
+/// Checks the stream reports `length` and what remains of it, if it knows its length at all.
+fn assert_length<S: ReadableStream>(stream: &S, length: u64, context: &str) {
+  if let Some(reported) = stream.len() {
+    assert_eq!(reported, length, "{context}: the stream reports the wrong length");
+    assert_eq!(stream.remaining(), Some(length - stream.offset()), "{context}: the remaining length isn't the length minus the offset");
+  }
+}
+
+/// Checks an error from running off the end describes `(stream_length, read_offset, read_length)`.
+/// Streams that knew their length beforehand have to report it as exhaustion.
+fn assert_exhausted(error: Option<StreamExhaustedError>, required: bool, expected: (u64, u64, u64), context: &str) {
+  match error {
+    Some(error) => assert_eq!(
+      (error.stream_length, error.read_offset, error.read_length),
+      expected,
+      "{context}: the exhausted error reports the wrong (stream length, read offset, read length)"
+    ),
+    None => assert!(!required, "{context}: a stream that knows its length should fail with StreamExhausted"),
+  }
+}
+
+/// Checks a stream reads `expected` in order, alone and in chunks, skips forwards over it, and
+/// fails past its end.
+pub async fn readable<S: ReadableStream>(factory: impl AsyncFn() -> S, expected: &[S::Type])
+where
+  S::Type: Clone + PartialEq + Debug,
+{
+  let length = expected.len() as u64;
+  let mut stream = factory().await;
+
+  assert_eq!(stream.offset(), 0, "A fresh stream should start at offset 0");
+  assert_length(&stream, length, "A fresh stream");
+
+  for (index, element) in expected.iter().enumerate() {
+    let read = stream.read(CLONED).await.unwrap_or_else(|_| panic!("Reading the element at {index} failed"));
+
+    assert_eq!(&read, element, "The element at {index} was read wrong");
+    assert_eq!(stream.offset(), index as u64 + 1, "Reading the element at {index} should move the stream forwards by one");
+    assert_length(&stream, length, "Reading one element at a time");
+  }
+
+  let required = stream.len().is_some();
+  let error = match stream.read(async |_: &[S::Type; 1]| {}).await {
+    Ok(()) => panic!("Reading past the end should fail"),
+    Err(StreamReadError::StreamExhausted(error)) => Some(error),
+    Err(StreamReadError::User(_)) => None,
+  };
+  assert_exhausted(error, required, (length, length, 1), "Reading past the end");
+
+  let mut stream = factory().await;
+
+  for (index, chunk) in expected.chunks_exact(CHUNK).enumerate() {
+    let read = stream
+      .read(async |elements: &[S::Type; CHUNK]| elements.clone())
+      .await
+      .unwrap_or_else(|_| panic!("Reading chunk {index} failed"));
+
+    assert_eq!(read.as_slice(), chunk, "Chunk {index} was read wrong");
+  }
+
+  if !length.is_multiple_of(CHUNK as u64) {
+    let offset = stream.offset();
+    let required = stream.len().is_some();
+    let error = match stream.read(async |_: &[S::Type; CHUNK]| {}).await {
+      Ok(()) => panic!("Reading a chunk running past the end should fail"),
+      Err(StreamReadError::StreamExhausted(error)) => Some(error),
+      Err(StreamReadError::User(_)) => None,
+    };
+    assert_exhausted(error, required, (length, offset, CHUNK as u64), "Reading a chunk running past the end");
+  }
+
+  for skipped in positions(length) {
+    let mut stream = factory().await;
+
+    stream.skip(skipped).await.unwrap_or_else(|_| panic!("Skipping {skipped} elements failed"));
+    assert_eq!(stream.offset(), skipped, "Skipping {skipped} elements moved the stream to the wrong offset");
+    assert_length(&stream, length, "Skipping");
+
+    if let Some(element) = expected.get(skipped as usize) {
+      let read = stream.read(CLONED).await.unwrap_or_else(|_| panic!("Reading after skipping {skipped} elements failed"));
+
+      assert_eq!(&read, element, "Reading after skipping {skipped} elements gave the wrong element");
+    }
+  }
+
+  let mut stream = factory().await;
+  let required = stream.len().is_some();
+
+  match stream.skip(length + 1).await {
+    Ok(()) => panic!("Skipping past the end should fail"),
+    Err(StreamSkipError::OutOfBounds(error)) => assert_eq!(
+      (error.stream_length, error.seek_point),
+      (length, length + 1),
+      "Skipping past the end: the out of bounds error reports the wrong (stream length, seek point)"
+    ),
+    Err(_) => assert!(!required, "Skipping past the end of a stream that knows its length should fail with OutOfBounds"),
+  }
+}
+
+/// Checks a stream rewinds from its end to each offset, and can't rewind past its start.
+pub async fn rewindable<S: RewindableStream>(factory: impl AsyncFn() -> S, expected: &[S::Type])
+where
+  S::Type: Clone + PartialEq + Debug,
+{
+  let length = expected.len() as u64;
+
+  for distance in positions(length) {
+    let mut stream = factory().await;
+
+    stream.skip(length).await.unwrap_or_else(|_| panic!("Skipping to the end failed"));
+    stream.rewind(distance).await.unwrap_or_else(|_| panic!("Rewinding {distance} elements from the end failed"));
+    assert_eq!(stream.offset(), length - distance, "Rewinding {distance} elements from the end moved the stream to the wrong offset");
+
+    let read = read_elements(&mut stream, distance, "Reading after rewinding").await;
+    assert_eq!(read, expected[(length - distance) as usize..], "Reading after rewinding {distance} elements gave the wrong elements");
+  }
+
+  let mut stream = factory().await;
+  stream.skip(length).await.unwrap_or_else(|_| panic!("Skipping to the end failed"));
+
+  match stream.rewind(length + 1).await {
+    Ok(()) => panic!("Rewinding past the start should fail"),
+    Err(StreamRewindError::SeekPointUnderflowed {
+      stream_length,
+      offset,
+      seek_backwards_distance,
+    }) => {
+      assert_eq!(
+        (offset, seek_backwards_distance),
+        (length, length + 1),
+        "Rewinding past the start: the underflow error reports the wrong (offset, distance)"
+      );
+
+      if stream.len().is_some() {
+        assert_eq!(stream_length, length, "Rewinding past the start: the underflow error reports the wrong stream length");
+      }
+    }
+    Err(StreamRewindError::User(_)) => panic!("Rewinding past the start should fail with SeekPointUnderflowed"),
+  }
+}
+
+/// Checks a stream seeks forwards to each offset and back to its start, and can't seek past its
+/// end.
+pub async fn seekable<S: SeekableStream>(factory: impl AsyncFn() -> S, expected: &[S::Type])
+where
+  S::Type: Clone + PartialEq + Debug,
+{
+  let length = expected.len() as u64;
+
+  for offset in positions(length) {
+    let mut stream = factory().await;
+
+    stream.seek(offset).await.unwrap_or_else(|_| panic!("Seeking to {offset} failed"));
+    assert_eq!(stream.offset(), offset, "Seeking to {offset} moved the stream to the wrong offset");
+
+    let read = read_elements(&mut stream, length - offset, "Reading after seeking").await;
+    assert_eq!(read, expected[offset as usize..], "Reading after seeking to {offset} gave the wrong elements");
+
+    stream.seek(0).await.unwrap_or_else(|_| panic!("Seeking back to the start failed"));
+    assert_eq!(stream.offset(), 0, "Seeking back to the start moved the stream to the wrong offset");
+
+    let read = read_elements(&mut stream, length.min(1), "Reading after seeking back").await;
+    assert_eq!(read, expected[..length.min(1) as usize], "Reading after seeking back to the start gave the wrong element");
+  }
+
+  let mut stream = factory().await;
+  let required = stream.len().is_some();
+
+  match stream.seek(length + 1).await {
+    Ok(()) => panic!("Seeking past the end should fail"),
+    Err(StreamSeekError::OutOfBounds(error)) => assert_eq!(
+      (error.stream_length, error.seek_point),
+      (length, length + 1),
+      "Seeking past the end: the out of bounds error reports the wrong (stream length, seek point)"
+    ),
+    Err(StreamSeekError::User(_)) => assert!(!required, "Seeking past the end of a stream that knows its length should fail with OutOfBounds"),
+  }
+}
+
+/// Checks a stream restores backwards to snapshots taken at each offset, and refuses to restore
+/// forwards.
+pub async fn restorable<S: RestorableStream>(factory: impl AsyncFn() -> S, expected: &[S::Type])
+where
+  S::Type: Clone + PartialEq + Debug,
+{
+  let length = expected.len() as u64;
+
+  for offset in positions(length) {
+    let mut stream = factory().await;
+
+    stream.skip(offset).await.unwrap_or_else(|_| panic!("Skipping to {offset} failed"));
+
+    let snapshot = stream.snapshot();
+
+    stream.skip(length - offset).await.unwrap_or_else(|_| panic!("Skipping to the end failed"));
+    stream.restore(snapshot).await.unwrap_or_else(|_| panic!("Restoring a snapshot from {offset} failed"));
+    assert_eq!(stream.offset(), offset, "Restoring a snapshot from {offset} moved the stream to the wrong offset");
+
+    let read = read_elements(&mut stream, length - offset, "Reading after restoring").await;
+    assert_eq!(read, expected[offset as usize..], "Reading after restoring a snapshot from {offset} gave the wrong elements");
+  }
+
+  if length > 0 {
+    let mut stream = factory().await;
+    let start = stream.snapshot();
+
+    stream.skip(length).await.unwrap_or_else(|_| panic!("Skipping to the end failed"));
+
+    let end = stream.snapshot();
+
+    stream.restore(start).await.unwrap_or_else(|_| panic!("Restoring a snapshot from the start failed"));
+
+    match stream.restore(end).await {
+      Ok(()) => panic!("Restoring forwards should fail"),
+      Err(StreamRestoreError::CannotRestoreForwards) => {}
+      Err(StreamRestoreError::User(_)) => panic!("Restoring forwards should fail with CannotRestoreForwards"),
+    }
+  }
+}
+
+/// Checks mutating every pair of neighbouring elements changes them in place, and that mutating
+/// past the end fails.
+pub async fn mutable<S: MutableStream + RewindableStream>(factory: impl AsyncFn() -> S, expected: &[S::Type])
+where
+  S::Type: Clone + PartialEq + Debug,
+{
+  let length = expected.len() as u64;
+
+  for offset in 0..length.saturating_sub(1) {
+    let mut stream = factory().await;
+
+    stream.skip(offset).await.unwrap_or_else(|_| panic!("Skipping to {offset} failed"));
+    stream
+      .mutate(async |elements: &mut [S::Type; 2]| elements.swap(0, 1))
+      .await
+      .unwrap_or_else(|_| panic!("Mutating the elements at {offset} failed"));
+    assert_eq!(stream.offset(), offset + 2, "Mutating two elements at {offset} should move the stream forwards by two");
+    assert_length(&stream, length, "Mutating");
+
+    stream.rewind(offset + 2).await.unwrap_or_else(|_| panic!("Rewinding to the start failed"));
+
+    let mut swapped = expected.to_vec();
+    swapped.swap(offset as usize, offset as usize + 1);
+
+    assert_eq!(
+      read_elements(&mut stream, length, "Reading after mutating").await,
+      swapped,
+      "Mutating the elements at {offset} changed the wrong elements"
+    );
+  }
+
+  let offset = length.saturating_sub(1);
+  let mut stream = factory().await;
+
+  stream.skip(offset).await.unwrap_or_else(|_| panic!("Skipping to {offset} failed"));
+
+  let required = stream.len().is_some();
+  let error = match stream.mutate(async |_: &mut [S::Type; 2]| {}).await {
+    Ok(()) => panic!("Mutating past the end should fail"),
+    Err(StreamMutateError::StreamExhausted(error)) => Some(error),
+    Err(StreamMutateError::User(_)) => None,
+  };
+  assert_exhausted(error, required, (length, offset, 2), "Mutating past the end");
+}
+
+/// Checks overwriting every run of elements with two others resizes the stream around them, and
+/// that overwriting past the end fails. `expected` can't be empty, since the replacements are
+/// taken from it.
+pub async fn resizable<S: ResizableStream + RewindableStream>(factory: impl AsyncFn() -> S, expected: &[S::Type])
+where
+  S::Type: Clone + PartialEq + Debug,
+{
+  let (Some(first), Some(last)) = (expected.first(), expected.last()) else {
+    panic!("Resizing can only be checked over a stream with elements in it");
+  };
+  let data = [last.clone(), first.clone()];
+  let length = expected.len() as u64;
+
+  for offset in 0..=length {
+    for replaced in 0..=length - offset {
+      let mut stream = factory().await;
+
+      stream.skip(offset).await.unwrap_or_else(|_| panic!("Skipping to {offset} failed"));
+      stream
+        .overwrite(replaced, data.clone())
+        .await
+        .unwrap_or_else(|_| panic!("Overwriting {replaced} elements at {offset} failed"));
+
+      let mut spliced = expected.to_vec();
+      spliced.splice(offset as usize..(offset + replaced) as usize, data.clone());
+
+      assert_eq!(stream.offset(), offset + 2, "Overwriting with two elements at {offset} should move the stream forwards by two");
+      assert_length(&stream, spliced.len() as u64, "Overwriting");
+
+      stream.rewind(offset + 2).await.unwrap_or_else(|_| panic!("Rewinding to the start failed"));
+
+      let read = read_elements(&mut stream, spliced.len() as u64, "Reading after overwriting").await;
+      assert_eq!(read, spliced, "Overwriting {replaced} elements at {offset} left the wrong elements");
+    }
+  }
+
+  for offset in 0..=length {
+    let mut stream = factory().await;
+
+    stream.skip(offset).await.unwrap_or_else(|_| panic!("Skipping to {offset} failed"));
+
+    let replaced = length - offset + 1;
+    let required = stream.len().is_some();
+    let error = match stream.overwrite(replaced, data.clone()).await {
+      Ok(()) => panic!("Overwriting past the end should fail"),
+      Err(StreamOverwriteError::StreamExhausted(error)) => Some(error),
+      Err(StreamOverwriteError::User(_)) => None,
+    };
+    assert_exhausted(error, required, (length, offset, replaced), "Overwriting past the end");
+  }
+}
+
+/// Checks the halves of a partition made at `offset`, which should split the elements at `at`.
+async fn assert_partition<L: ReadableStream, R: ReadableStream<Type = L::Type>>(left: L, right: R, expected: &[L::Type], offset: u64, at: u64)
+where
+  L::Type: Clone + PartialEq + Debug,
+{
+  let (mut left, mut right) = (left, right);
+  let length = expected.len() as u64;
+
+  assert_eq!(left.offset(), offset, "Partitioning at {offset}: the left half should keep the stream's offset");
+  assert_length(&left, at, "Partitioning: the left half");
+
+  let read = read_elements(&mut left, at - offset, "Reading the left half of a partition").await;
+  assert_eq!(read, expected[offset as usize..at as usize], "Partitioning {offset}..{at}: the left half holds the wrong elements");
+  assert!(left.read(async |_: &[L::Type; 1]| {}).await.is_err(), "Partitioning {offset}..{at}: the left half should end at {at}");
+
+  assert_eq!(right.offset(), 0, "Partitioning at {at}: the right half should start at offset 0");
+  assert_length(&right, length - at, "Partitioning: the right half");
+
+  let read = read_elements(&mut right, length - at, "Reading the right half of a partition").await;
+  assert_eq!(read, expected[at as usize..], "Partitioning at {at}: the right half holds the wrong elements");
+  assert!(
+    right.read(async |_: &[L::Type; 1]| {}).await.is_err(),
+    "Partitioning at {at}: the right half should end with the stream"
+  );
+}
+
+/// Checks partitioning running past the end fails.
+fn assert_partition_exhausted<E>(result: Result<(), StreamPartitionError<E>>, required: bool, length: u64, end: u64, context: &str)
+where
+  E: crate::stream::UserPartitionError,
+{
+  match result {
+    Ok(()) => panic!("{context} should fail"),
+    Err(StreamPartitionError::StreamExhausted(error)) => {
+      assert_eq!(error.stream_length, length, "{context}: the exhausted error reports the wrong stream length");
+      assert_eq!(error.read_offset + error.read_length, end, "{context}: the exhausted error should end where the partition would have");
+    }
+    Err(StreamPartitionError::User(_)) => assert!(!required, "{context}: a stream that knows its length should fail with StreamExhausted"),
+  }
+}
+
+/// Checks partitioning off the next `SIZE` elements from every offset.
+pub async fn static_partitionable<const SIZE: usize, S: StaticPartitionableStream<SIZE>>(factory: impl AsyncFn() -> S, expected: &[S::Type])
+where
+  S::Type: Clone + PartialEq + Debug,
+{
+  let length = expected.len() as u64;
+
+  for offset in 0..=length {
+    let mut stream = factory().await;
+
+    stream.skip(offset).await.unwrap_or_else(|_| panic!("Skipping to {offset} failed"));
+
+    let at = offset + SIZE as u64;
+    let required = stream.len().is_some();
+    let result = StaticPartitionableStream::<SIZE>::partition(stream).await;
+
+    if at <= length {
+      let (left, right) = result.unwrap_or_else(|_| panic!("Partitioning {offset}..{at} failed"));
+
+      assert_partition(left, right, expected, offset, at).await;
+    } else {
+      assert_partition_exhausted(result.map(|_| ()), required, length, at, "Partitioning past the end");
+    }
+  }
+}
+
+/// Checks partitioning off every run of elements from every offset.
+pub async fn dynamic_partitionable<S: DynamicPartitionableStream>(factory: impl AsyncFn() -> S, expected: &[S::Type])
+where
+  S::Type: Clone + PartialEq + Debug,
+{
+  let length = expected.len() as u64;
+
+  for offset in 0..=length {
+    for size in 0..=length - offset + 1 {
+      let mut stream = factory().await;
+
+      stream.skip(offset).await.unwrap_or_else(|_| panic!("Skipping to {offset} failed"));
+
+      let at = offset + size;
+      let required = stream.len().is_some();
+      let result = stream.partition_dynamic(size).await;
+
+      if at <= length {
+        let (left, right) = result.unwrap_or_else(|_| panic!("Partitioning {offset}..{at} failed"));
+
+        assert_partition(left, right, expected, offset, at).await;
+      } else {
+        assert_partition_exhausted(result.map(|_| ()), required, length, at, "Partitioning past the end");
+      }
+    }
+  }
+}